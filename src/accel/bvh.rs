@@ -0,0 +1,551 @@
+use std::cmp::Ordering;
+
+use crate::accel::Primitive;
+use crate::util::Float;
+use crate::util::bounds::Bounds3;
+use crate::util::rays::Ray;
+use crate::util::vector::{Point3f, Vector3f};
+
+/// Number of centroid buckets the binned SAH evaluates per split.
+const SAH_BUCKETS: usize = 12;
+
+/// Capacity of the stack of nodes still to visit during traversal. Nodes
+/// deeper than half of it are split into equal halves whatever the split
+/// method, so that no tree over `u32` primitives outgrows it.
+const TRAVERSAL_STACK_SIZE: usize = 64;
+
+/// How the builder chooses where to split the primitives of a node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SplitMethod {
+    /// Binned surface area heuristic: bucket the centroids along the widest
+    /// axis and split where the estimated traversal cost is lowest. Nodes
+    /// that are cheaper to intersect directly become leaves.
+    #[default]
+    Sah,
+    /// Split at the midpoint of the centroid bounds along the widest axis.
+    Middle,
+    /// Split the centroids along the widest axis into two equal halves.
+    EqualCounts,
+}
+
+/// Closest intersection found by `Bvh::intersect`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BvhHit {
+    /// Index into `Bvh::primitives`.
+    pub primitive: usize,
+    pub t: Float,
+}
+
+/// A node of the flattened tree. Nodes are stored in depth-first order, so
+/// the first child of an interior node always directly follows it.
+#[derive(Debug, Clone, Copy)]
+struct LinearNode {
+    bounds: Bounds3,
+    /// First primitive of a leaf, or the second child of an interior node.
+    offset: u32,
+    /// Zero for interior nodes.
+    n_primitives: u32,
+    /// Axis an interior node was split along.
+    axis: u8,
+}
+
+struct BuildPrimitive {
+    index: usize,
+    bounds: Bounds3,
+    centroid: Point3f,
+}
+
+/// Bounding volume hierarchy over a set of primitives.
+///
+/// The primitives are reordered during the build so that every leaf refers to
+/// a contiguous range of them; hits report indices into that order.
+pub struct Bvh<P> {
+    primitives: Vec<P>,
+    nodes: Vec<LinearNode>,
+}
+
+impl<P: Primitive> Bvh<P> {
+    /// Builds the hierarchy. Leaves hold at most `max_prims_in_node`
+    /// primitives unless their centroids coincide and cannot be separated.
+    pub fn new(primitives: Vec<P>, max_prims_in_node: usize, split_method: SplitMethod) -> Self {
+        let max_prims_in_node = max_prims_in_node.max(1);
+        let mut build: Vec<BuildPrimitive> = primitives
+            .iter()
+            .enumerate()
+            .map(|(index, p)| {
+                let bounds = p.bounds();
+                BuildPrimitive {
+                    index,
+                    bounds,
+                    centroid: bounds.lerp(Point3f::new(0.5, 0.5, 0.5)),
+                }
+            })
+            .collect();
+
+        let mut nodes = Vec::with_capacity(2 * build.len());
+        if !build.is_empty() {
+            Self::build_recursive(
+                &mut build,
+                0,
+                0,
+                &mut nodes,
+                max_prims_in_node,
+                split_method,
+            );
+        }
+
+        let mut slots: Vec<Option<P>> = primitives.into_iter().map(Some).collect();
+        let primitives = build
+            .iter()
+            .map(|b| slots[b.index].take().expect("primitive used twice"))
+            .collect();
+        Self { primitives, nodes }
+    }
+
+    /// Emits the subtree for `prims`, whose first element is primitive
+    /// `start` of the final order, at `depth` below the root, and returns
+    /// the index of its root node.
+    fn build_recursive(
+        prims: &mut [BuildPrimitive],
+        start: usize,
+        depth: usize,
+        nodes: &mut Vec<LinearNode>,
+        max_prims_in_node: usize,
+        split_method: SplitMethod,
+    ) -> usize {
+        let node_index = nodes.len();
+        let n = prims.len();
+        let bounds = prims
+            .iter()
+            .fold(Bounds3::new(), |b, p| b.union_bounds(&p.bounds));
+        let centroid_bounds = prims
+            .iter()
+            .fold(Bounds3::new(), |b, p| b.union_point(&p.centroid));
+        let axis = centroid_bounds.maximum_extent();
+
+        let split = if n == 1
+            || bounds.surface_area() == 0.0
+            || centroid_bounds.pMax[axis] == centroid_bounds.pMin[axis]
+        {
+            None
+        } else if depth >= TRAVERSAL_STACK_SIZE / 2 {
+            Some(split_equal_counts(prims, axis))
+        } else {
+            match split_method {
+                SplitMethod::Middle => Some(split_middle(prims, &centroid_bounds, axis)),
+                SplitMethod::EqualCounts => Some(split_equal_counts(prims, axis)),
+                SplitMethod::Sah => {
+                    split_sah(prims, &bounds, &centroid_bounds, axis, max_prims_in_node)
+                }
+            }
+        };
+
+        match split {
+            None => nodes.push(LinearNode {
+                bounds,
+                offset: start as u32,
+                n_primitives: n as u32,
+                axis: 0,
+            }),
+            Some(mid) => {
+                nodes.push(LinearNode {
+                    bounds,
+                    offset: 0,
+                    n_primitives: 0,
+                    axis: axis as u8,
+                });
+                let (left, right) = prims.split_at_mut(mid);
+                Self::build_recursive(
+                    left,
+                    start,
+                    depth + 1,
+                    nodes,
+                    max_prims_in_node,
+                    split_method,
+                );
+                let second = Self::build_recursive(
+                    right,
+                    start + mid,
+                    depth + 1,
+                    nodes,
+                    max_prims_in_node,
+                    split_method,
+                );
+                nodes[node_index].offset = second as u32;
+            }
+        }
+        node_index
+    }
+
+    /// The primitives in hierarchy order.
+    pub fn primitives(&self) -> &[P] {
+        &self.primitives
+    }
+
+    /// Bounds of everything in the hierarchy; empty if there are no
+    /// primitives.
+    pub fn bounds(&self) -> Bounds3 {
+        self.nodes.first().map_or_else(Bounds3::new, |n| n.bounds)
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Closest primitive hit by `ray` in `(0, ray.t_max())`.
+    pub fn intersect(&self, ray: &Ray) -> Option<BvhHit> {
+        self.intersect_with(ray, |p, ray| p.intersect(ray).map(|t| (t, ())))
+            .map(|(hit, ())| hit)
+    }
+
+    /// Like `intersect`, but with `hit` testing each candidate primitive so
+    /// that whatever it computes along with the distance, such as a surface
+    /// record, comes back for the closest hit without intersecting again.
+    pub fn intersect_with<H>(
+        &self,
+        ray: &Ray,
+        mut hit: impl FnMut(&P, &Ray) -> Option<(Float, H)>,
+    ) -> Option<(BvhHit, H)> {
+        let mut closest = None;
+        self.traverse(ray, |primitive, t_max| {
+            if let Some((t, h)) = hit(&self.primitives[primitive], &ray.with_t_max(*t_max)) {
+                *t_max = t;
+                closest = Some((BvhHit { primitive, t }, h));
+            }
+            false
+        });
+        closest
+    }
+
+    /// Whether `ray` hits any primitive in `(0, ray.t_max())`. Stops at the
+    /// first hit found, so it is the query to use for shadow rays.
+    pub fn intersect_p(&self, ray: &Ray) -> bool {
+        let mut hit = false;
+        self.traverse(ray, |primitive, _| {
+            hit = self.primitives[primitive].intersect_p(ray);
+            hit
+        });
+        hit
+    }
+
+    /// Visits, front to back, the primitives of every leaf whose bounds `ray`
+    /// overlaps within `(0, t_max)`, starting from `ray.t_max()`. `visit` may
+    /// shrink `t_max` to prune the rest of the search, and returns true to
+    /// stop it altogether.
+    fn traverse(&self, ray: &Ray, mut visit: impl FnMut(usize, &mut Float) -> bool) {
+        if self.nodes.is_empty() {
+            return;
+        }
+        let mut t_max = ray.t_max();
+        let d = ray.direction();
+        let inv_dir = Vector3f::new(1.0 / d.x, 1.0 / d.y, 1.0 / d.z);
+        let dir_is_neg = [
+            (inv_dir.x < 0.0) as usize,
+            (inv_dir.y < 0.0) as usize,
+            (inv_dir.z < 0.0) as usize,
+        ];
+
+        let mut to_visit = [0; TRAVERSAL_STACK_SIZE];
+        let mut to_visit_len = 0;
+        let mut current = 0;
+        loop {
+            let node = &self.nodes[current];
+            if node
+                .bounds
+                .intersect_p_fast(ray, t_max, &inv_dir, dir_is_neg)
+            {
+                if node.n_primitives > 0 {
+                    let first = node.offset as usize;
+                    for i in first..first + node.n_primitives as usize {
+                        if visit(i, &mut t_max) {
+                            return;
+                        }
+                    }
+                } else {
+                    // Descend into the child nearer the ray origin first.
+                    let second = node.offset as usize;
+                    if dir_is_neg[node.axis as usize] == 1 {
+                        to_visit[to_visit_len] = current + 1;
+                        current = second;
+                    } else {
+                        to_visit[to_visit_len] = second;
+                        current += 1;
+                    }
+                    to_visit_len += 1;
+                    continue;
+                }
+            }
+            if to_visit_len == 0 {
+                return;
+            }
+            to_visit_len -= 1;
+            current = to_visit[to_visit_len];
+        }
+    }
+}
+
+/// Reorders `items` so that those matching `pred` come first and returns how
+/// many there are.
+fn partition<T>(items: &mut [T], pred: impl Fn(&T) -> bool) -> usize {
+    let mut first = 0;
+    for i in 0..items.len() {
+        if pred(&items[i]) {
+            items.swap(first, i);
+            first += 1;
+        }
+    }
+    first
+}
+
+fn split_equal_counts(prims: &mut [BuildPrimitive], axis: usize) -> usize {
+    let mid = prims.len() / 2;
+    prims.select_nth_unstable_by(mid, |a, b| {
+        a.centroid[axis]
+            .partial_cmp(&b.centroid[axis])
+            .unwrap_or(Ordering::Equal)
+    });
+    mid
+}
+
+fn split_middle(prims: &mut [BuildPrimitive], centroid_bounds: &Bounds3, axis: usize) -> usize {
+    let pmid = (centroid_bounds.pMin[axis] + centroid_bounds.pMax[axis]) / 2.0;
+    let mid = partition(prims, |p| p.centroid[axis] < pmid);
+    // Clustered centroids can all land on one side of the midpoint.
+    if mid == 0 || mid == prims.len() {
+        split_equal_counts(prims, axis)
+    } else {
+        mid
+    }
+}
+
+/// Returns the split position, or `None` if a leaf is cheaper than any split.
+fn split_sah(
+    prims: &mut [BuildPrimitive],
+    bounds: &Bounds3,
+    centroid_bounds: &Bounds3,
+    axis: usize,
+    max_prims_in_node: usize,
+) -> Option<usize> {
+    let n = prims.len();
+    if n <= 2 {
+        return Some(split_equal_counts(prims, axis));
+    }
+
+    let bucket_of = |p: &BuildPrimitive| {
+        let b = (SAH_BUCKETS as Float * centroid_bounds.offset(&p.centroid)[axis]) as usize;
+        b.min(SAH_BUCKETS - 1)
+    };
+    let mut counts = [0usize; SAH_BUCKETS];
+    let mut bucket_bounds = [Bounds3::new(); SAH_BUCKETS];
+    for p in prims.iter() {
+        let b = bucket_of(p);
+        counts[b] += 1;
+        bucket_bounds[b] = bucket_bounds[b].union_bounds(&p.bounds);
+    }
+
+    // costs[i] is the summed count * area of both sides when splitting after
+    // bucket i, accumulated by one sweep from each end.
+    let mut costs = [0.0 as Float; SAH_BUCKETS - 1];
+    let mut count_below = 0;
+    let mut bound_below = Bounds3::new();
+    for i in 0..SAH_BUCKETS - 1 {
+        count_below += counts[i];
+        bound_below = bound_below.union_bounds(&bucket_bounds[i]);
+        if count_below > 0 {
+            costs[i] += count_below as Float * bound_below.surface_area();
+        }
+    }
+    let mut count_above = 0;
+    let mut bound_above = Bounds3::new();
+    for i in (1..SAH_BUCKETS).rev() {
+        count_above += counts[i];
+        bound_above = bound_above.union_bounds(&bucket_bounds[i]);
+        if count_above > 0 {
+            costs[i - 1] += count_above as Float * bound_above.surface_area();
+        }
+    }
+
+    let (min_bucket, min_cost) =
+        costs
+            .iter()
+            .copied()
+            .enumerate()
+            .fold(
+                (0, Float::INFINITY),
+                |best, (i, c)| if c < best.1 { (i, c) } else { best },
+            );
+
+    // Costs are relative to intersecting one primitive, with traversing a
+    // node taken to cost half of that.
+    let leaf_cost = n as Float;
+    let split_cost = 0.5 + min_cost / bounds.surface_area();
+    if n > max_prims_in_node || split_cost < leaf_cost {
+        Some(partition(prims, |p| bucket_of(p) <= min_bucket))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Sphere {
+        center: Point3f,
+        radius: Float,
+    }
+
+    impl Primitive for Sphere {
+        fn bounds(&self) -> Bounds3 {
+            let r = Vector3f::new(self.radius, self.radius, self.radius);
+            Bounds3::from_points(self.center - r, self.center + r)
+        }
+        fn intersect(&self, ray: &Ray) -> Option<Float> {
+            let oc = ray.origin() - self.center;
+            let d = ray.direction();
+            let a = d.length_squared();
+            let b = oc.dot(&d);
+            let c = oc.length_squared() - self.radius * self.radius;
+            let disc = b * b - a * c;
+            if disc < 0.0 {
+                return None;
+            }
+            let root = disc.sqrt();
+            [(-b - root) / a, (-b + root) / a]
+                .into_iter()
+                .find(|&t| t > 0.0 && t < ray.t_max())
+        }
+    }
+
+    /// Small deterministic generator so the tests do not need a dependency.
+    struct Lcg(u32);
+    impl Lcg {
+        fn next(&mut self) -> Float {
+            self.0 = self.0.wrapping_mul(1664525).wrapping_add(1013904223);
+            (self.0 >> 8) as Float / (1u32 << 24) as Float
+        }
+    }
+
+    fn random_spheres(n: usize, rng: &mut Lcg) -> Vec<Sphere> {
+        (0..n)
+            .map(|_| Sphere {
+                center: Point3f::new(
+                    rng.next() * 20.0 - 10.0,
+                    rng.next() * 20.0 - 10.0,
+                    rng.next() * 20.0 - 10.0,
+                ),
+                radius: 0.1 + rng.next() * 0.5,
+            })
+            .collect()
+    }
+
+    fn brute_force(spheres: &[Sphere], ray: &Ray) -> Option<Float> {
+        spheres
+            .iter()
+            .filter_map(|s| s.intersect(ray))
+            .min_by(|a, b| a.partial_cmp(b).unwrap())
+    }
+
+    #[test]
+    fn lopsided_trees_fit_the_traversal_stack() {
+        // Midpoint splits peel off two spheres per level here, which would
+        // nest 100 deep.
+        let spheres = || {
+            (0..200).map(|k| Sphere {
+                center: Point3f::new((1.5 as Float).powi(k), 0.0, 0.0),
+                radius: 0.25,
+            })
+        };
+        let bvh = Bvh::new(spheres().collect(), 1, SplitMethod::Middle);
+        let ray = Ray::new(
+            Point3f::new(-1.0, 0.0, 0.0),
+            Vector3f::new(1.0, 0.0, 0.0),
+            0.0,
+        );
+        assert_eq!(bvh.intersect(&ray).unwrap().t, 1.75);
+        // Straight down onto the sphere at the bottom of the tree.
+        let ray = Ray::new(
+            Point3f::new((1.5 as Float).powi(199), 1.0, 0.0),
+            Vector3f::new(0.0, -1.0, 0.0),
+            0.0,
+        );
+        assert!(bvh.intersect_p(&ray));
+        assert_eq!(bvh.primitives().len(), spheres().count());
+    }
+
+    #[test]
+    fn closest_hit_matches_brute_force_for_every_split_method() {
+        for method in [
+            SplitMethod::Sah,
+            SplitMethod::Middle,
+            SplitMethod::EqualCounts,
+        ] {
+            let mut rng = Lcg(7);
+            let reference = random_spheres(300, &mut rng);
+            let bvh = Bvh::new(random_spheres(300, &mut Lcg(7)), 4, method);
+            for _ in 0..500 {
+                let origin = Point3f::new(
+                    rng.next() * 30.0 - 15.0,
+                    rng.next() * 30.0 - 15.0,
+                    rng.next() * 30.0 - 15.0,
+                );
+                let dir = Vector3f::new(rng.next() - 0.5, rng.next() - 0.5, rng.next() - 0.5);
+                let ray = Ray::new(origin, dir, 0.0);
+
+                let expected = brute_force(&reference, &ray);
+                let hit = bvh.intersect(&ray);
+                assert_eq!(hit.map(|h| h.t), expected, "{method:?}");
+                if let Some(h) = hit {
+                    let p = &bvh.primitives()[h.primitive];
+                    assert_eq!(p.intersect(&ray), Some(h.t));
+                }
+                assert_eq!(bvh.intersect_p(&ray), expected.is_some());
+            }
+        }
+    }
+
+    #[test]
+    fn t_max_limits_both_queries() {
+        let spheres = vec![Sphere {
+            center: Point3f::new(0.0, 0.0, 5.0),
+            radius: 1.0,
+        }];
+        let bvh = Bvh::new(spheres, 1, SplitMethod::Sah);
+        let ray = Ray::new(
+            Point3f::new(0.0, 0.0, 0.0),
+            Vector3f::new(0.0, 0.0, 1.0),
+            0.0,
+        );
+        let (near, far) = (ray.with_t_max(3.0), ray.with_t_max(10.0));
+        assert_eq!(bvh.intersect(&far).map(|h| h.t), Some(4.0));
+        assert!(bvh.intersect(&near).is_none());
+        assert!(bvh.intersect_p(&far));
+        assert!(!bvh.intersect_p(&near));
+    }
+
+    #[test]
+    fn leaves_respect_max_prims_in_node() {
+        let bvh = Bvh::new(random_spheres(100, &mut Lcg(3)), 2, SplitMethod::Middle);
+        assert_eq!(bvh.primitives().len(), 100);
+        let mut covered = 0;
+        for node in &bvh.nodes {
+            assert!(node.n_primitives <= 2);
+            covered += node.n_primitives as usize;
+        }
+        assert_eq!(covered, 100);
+        assert_eq!(bvh.node_count(), bvh.nodes.len());
+    }
+
+    #[test]
+    fn empty_bvh_misses_everything() {
+        let bvh: Bvh<Sphere> = Bvh::new(Vec::new(), 4, SplitMethod::Sah);
+        let ray = Ray::new(
+            Point3f::new(0.0, 0.0, 0.0),
+            Vector3f::new(1.0, 0.0, 0.0),
+            0.0,
+        );
+        assert!(bvh.intersect(&ray).is_none());
+        assert!(!bvh.intersect_p(&ray));
+        assert!(bvh.bounds().is_degenerate());
+    }
+}
@@ -0,0 +1,4 @@
+mod bvh;
+mod primitive;
+pub use bvh::{Bvh, BvhHit, SplitMethod};
+pub use primitive::Primitive;
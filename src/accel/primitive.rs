@@ -0,0 +1,20 @@
+use crate::util::Float;
+use crate::util::bounds::Bounds3;
+use crate::util::rays::Ray;
+
+/// Anything an acceleration structure can be built over.
+pub trait Primitive {
+    /// World-space bounds of the primitive.
+    fn bounds(&self) -> Bounds3;
+
+    /// Parametric distance of the closest intersection with `ray` in
+    /// `(0, ray.t_max())`, if there is one.
+    fn intersect(&self, ray: &Ray) -> Option<Float>;
+
+    /// Whether `ray` hits the primitive anywhere in `(0, ray.t_max())`.
+    /// Shadow rays only need this, so implementors can override it with a
+    /// cheaper test.
+    fn intersect_p(&self, ray: &Ray) -> bool {
+        self.intersect(ray).is_some()
+    }
+}
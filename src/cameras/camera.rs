@@ -0,0 +1,264 @@
+use crate::filters::Filter;
+use crate::util::Float;
+use crate::util::bounds::Bounds2f;
+use crate::util::math::AnimatedTransform;
+use crate::util::math::transformations::Transform;
+use crate::util::rays::{Ray, RayDifferential};
+use crate::util::sampling::sample_uniform_disk_concentric;
+use crate::util::vector::{Point2f, Point2i, Point3f, Vector3f};
+
+/// Where on the film and lens, and when during the shutter interval, a
+/// camera ray should start.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CameraSample {
+    /// Position in raster space, in pixels.
+    pub p_film: Point2f,
+    /// Sample in `[0, 1)^2` for choosing a point on the lens.
+    pub p_lens: Point2f,
+    /// Sample in `[0, 1)` for choosing a time between shutter open and close.
+    pub time: Float,
+    /// Weight of the sample from the pixel reconstruction filter.
+    pub filter_weight: Float,
+}
+
+/// The camera sample for pixel `p_pixel`, with the film position offset
+/// from the pixel center by importance sampling `filter` with `u_filter`,
+/// so that the sample only needs to be weighted by the filter sample's
+/// weight.
+pub fn get_camera_sample<F: Filter + ?Sized>(
+    filter: &F,
+    p_pixel: Point2i,
+    u_filter: Point2f,
+    u_lens: Point2f,
+    u_time: Float,
+) -> CameraSample {
+    let fs = filter.sample(u_filter);
+    CameraSample {
+        p_film: Point2f::new(
+            p_pixel.x as Float + 0.5 + fs.p.x,
+            p_pixel.y as Float + 0.5 + fs.p.y,
+        ),
+        p_lens: u_lens,
+        time: u_time,
+        filter_weight: fs.weight,
+    }
+}
+
+/// A ray leaving the camera, with the factor by which the radiance it
+/// carries back contributes to the image.
+#[derive(Debug, Clone, Copy)]
+pub struct CameraRay {
+    pub ray: Ray,
+    pub weight: Float,
+}
+
+/// A `CameraRay` with offset rays for the neighbouring pixels.
+#[derive(Debug, Clone, Copy)]
+pub struct CameraRayDifferential {
+    pub ray: RayDifferential,
+    pub weight: Float,
+}
+
+/// A model of how light reaching the film relates to rays in the scene.
+///
+/// Integrators only see this trait, so every camera is interchangeable.
+pub trait Camera {
+    fn base(&self) -> &CameraBase;
+
+    /// The render-space ray for a film sample, or `None` if no light can
+    /// reach that point of the film, e.g. because the lens system blocks it.
+    fn generate_ray(&self, sample: &CameraSample) -> Option<CameraRay>;
+
+    /// Like `generate_ray`, with offset rays for a one-pixel step in x and
+    /// y. The default estimates them by finite differences of
+    /// `generate_ray`, stepping the other way if the first step is blocked,
+    /// and leaves them out if both are.
+    fn generate_ray_differential(&self, sample: &CameraSample) -> Option<CameraRayDifferential> {
+        let central = self.generate_ray(sample)?;
+        let mut rd = RayDifferential::new(central.ray);
+        let (o, d) = (central.ray.origin(), central.ray.direction());
+        let offset = |dx: Float, dy: Float| {
+            let mut shifted = *sample;
+            shifted.p_film = Point2f::new(sample.p_film.x + dx, sample.p_film.y + dy);
+            self.generate_ray(&shifted).map(|cr| {
+                // Scale the difference up to a whole pixel.
+                let s = 1.0 / (dx + dy);
+                (
+                    o + (cr.ray.origin() - o) * s,
+                    d + (cr.ray.direction() - d) * s,
+                )
+            })
+        };
+        const EPS: Float = 0.05;
+        let rx = offset(EPS, 0.0).or_else(|| offset(-EPS, 0.0));
+        let ry = offset(0.0, EPS).or_else(|| offset(0.0, -EPS));
+        if let (Some((rx_origin, rx_direction)), Some((ry_origin, ry_direction))) = (rx, ry) {
+            rd.has_differentials = true;
+            rd.rx_origin = rx_origin;
+            rd.rx_direction = rx_direction;
+            rd.ry_origin = ry_origin;
+            rd.ry_direction = ry_direction;
+        }
+        Some(CameraRayDifferential {
+            ray: rd,
+            weight: central.weight,
+        })
+    }
+}
+
+/// State shared by every camera: where it is, when its shutter is open and
+/// the resolution of the image it makes.
+#[derive(Debug, Clone, Copy)]
+pub struct CameraBase {
+    pub render_from_camera: AnimatedTransform,
+    pub shutter_open: Float,
+    pub shutter_close: Float,
+    pub resolution: Point2i,
+}
+
+impl CameraBase {
+    pub fn new(
+        render_from_camera: AnimatedTransform,
+        shutter_open: Float,
+        shutter_close: Float,
+        resolution: Point2i,
+    ) -> Self {
+        Self {
+            render_from_camera,
+            shutter_open,
+            shutter_close,
+            resolution,
+        }
+    }
+
+    /// Time within the shutter interval for a sample `u` in `[0, 1)`.
+    pub fn sample_time(&self, u: Float) -> Float {
+        self.shutter_open + u * (self.shutter_close - self.shutter_open)
+    }
+
+    /// Moves a camera-space ray into render space at the ray's time.
+    pub fn render_from_camera_ray(&self, r: &Ray) -> Ray {
+        self.render_from_camera.transform_ray(r)
+    }
+    pub fn render_from_camera_ray_differential(&self, r: &RayDifferential) -> RayDifferential {
+        self.render_from_camera.transform_ray_differential(r)
+    }
+}
+
+/// The chain of transforms from raster space to camera space shared by
+/// cameras that project the scene onto a plane, along with their thin-lens
+/// parameters.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Projection {
+    pub camera_from_raster: Transform,
+    pub lens_radius: Float,
+    pub focal_distance: Float,
+}
+
+impl Projection {
+    /// `screen_window` is the extent of the image on the screen plane;
+    /// `None` gives `[-1, 1]` along the shorter image axis and matches the
+    /// aspect ratio along the longer one.
+    pub fn new(
+        screen_from_camera: Transform,
+        screen_window: Option<Bounds2f>,
+        resolution: Point2i,
+        lens_radius: Float,
+        focal_distance: Float,
+    ) -> Self {
+        let screen = screen_window.unwrap_or_else(|| default_screen_window(resolution));
+        // Screen y points up and raster y down, so flip on the way.
+        let ndc_from_screen =
+            Transform::scale(
+                1.0 / (screen.pMax.x - screen.pMin.x),
+                1.0 / (screen.pMax.y - screen.pMin.y),
+                1.0,
+            ) * Transform::translate(Vector3f::new(-screen.pMin.x, -screen.pMax.y, 0.0));
+        let raster_from_ndc =
+            Transform::scale(resolution.x as Float, -(resolution.y as Float), 1.0);
+        let screen_from_raster = (raster_from_ndc * ndc_from_screen).inverse();
+        Self {
+            camera_from_raster: screen_from_camera.inverse() * screen_from_raster,
+            lens_radius,
+            focal_distance,
+        }
+    }
+
+    /// Moves a camera-space pinhole ray from `o` along `d` onto the lens,
+    /// at the point picked by `u_lens`, and bends it toward the plane of
+    /// focus. Every ray through the lens for one film point meets the
+    /// pinhole ray there.
+    pub fn thin_lens(&self, u_lens: Point2f, o: Point3f, d: Vector3f) -> (Point3f, Vector3f) {
+        if self.lens_radius <= 0.0 {
+            return (o, d);
+        }
+        let p_lens = sample_uniform_disk_concentric(u_lens);
+        let p_focus = o + d * (self.focal_distance / d.z);
+        let origin = Point3f::new(
+            o.x + p_lens.x * self.lens_radius,
+            o.y + p_lens.y * self.lens_radius,
+            0.0,
+        );
+        (origin, (p_focus - origin).normalize())
+    }
+
+    /// Camera-space point on the near plane for a raster position.
+    pub fn camera_point(&self, p_film: Point2f) -> Point3f {
+        self.camera_from_raster
+            .transform_point(&Point3f::new(p_film.x, p_film.y, 0.0))
+    }
+
+    /// Camera-space offset of the near-plane point per raster step in x and
+    /// in y.
+    pub fn raster_steps(&self) -> (Vector3f, Vector3f) {
+        let origin = self.camera_point(Point2f::new(0.0, 0.0));
+        (
+            self.camera_point(Point2f::new(1.0, 0.0)) - origin,
+            self.camera_point(Point2f::new(0.0, 1.0)) - origin,
+        )
+    }
+}
+
+fn default_screen_window(resolution: Point2i) -> Bounds2f {
+    let aspect = resolution.x as Float / resolution.y as Float;
+    let (x, y) = if aspect > 1.0 {
+        (aspect, 1.0)
+    } else {
+        (1.0, 1.0 / aspect)
+    };
+    Bounds2f::from_points(Point2f::new(-x, -y), Point2f::new(x, y))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filters::{BoxFilter, MitchellFilter};
+
+    #[test]
+    fn camera_samples_are_jittered_by_the_filter() {
+        let p_pixel = Point2i::new(3, 7);
+        let s = get_camera_sample(
+            &BoxFilter::default(),
+            p_pixel,
+            Point2f::new(0.0, 0.75),
+            Point2f::new(0.2, 0.4),
+            0.5,
+        );
+        assert_eq!(s.p_film, Point2f::new(3.0, 7.75));
+        assert_eq!(
+            (s.p_lens, s.time, s.filter_weight),
+            (Point2f::new(0.2, 0.4), 0.5, 1.0)
+        );
+
+        let mitchell = MitchellFilter::default();
+        let s = get_camera_sample(
+            &mitchell,
+            p_pixel,
+            Point2f::new(0.5, 0.5),
+            Point2f::default(),
+            0.0,
+        );
+        assert!((s.p_film.x - 3.5).abs() < 0.1 && (s.p_film.y - 7.5).abs() < 0.1);
+        assert!(s.filter_weight > 0.0);
+    }
+}
@@ -0,0 +1,12 @@
+mod camera;
+mod orthographic;
+mod perspective;
+mod realistic;
+mod spherical;
+pub use camera::{
+    Camera, CameraBase, CameraRay, CameraRayDifferential, CameraSample, get_camera_sample,
+};
+pub use orthographic::OrthographicCamera;
+pub use perspective::PerspectiveCamera;
+pub use realistic::{LensElement, RealisticCamera, parse_lens_description};
+pub use spherical::{SphericalCamera, SphericalMapping};
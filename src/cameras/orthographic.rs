@@ -0,0 +1,131 @@
+use crate::cameras::camera::{
+    Camera, CameraBase, CameraRay, CameraRayDifferential, CameraSample, Projection,
+};
+use crate::util::Float;
+use crate::util::bounds::Bounds2f;
+use crate::util::math::transformations::Transform;
+use crate::util::rays::{Ray, RayDifferential};
+use crate::util::vector::{Point3f, Vector3f};
+
+/// A camera whose rays are all parallel to +z in camera space, so that
+/// objects keep their size regardless of distance.
+///
+/// The screen window is measured in camera-space units. A thin lens works as
+/// for `PerspectiveCamera`.
+#[derive(Debug, Clone, Copy)]
+pub struct OrthographicCamera {
+    base: CameraBase,
+    projection: Projection,
+    dx_camera: Vector3f,
+    dy_camera: Vector3f,
+}
+
+impl OrthographicCamera {
+    pub fn new(
+        base: CameraBase,
+        screen_window: Option<Bounds2f>,
+        lens_radius: Float,
+        focal_distance: Float,
+    ) -> Self {
+        let projection = Projection::new(
+            Transform::orthographic(0.0, 1.0),
+            screen_window,
+            base.resolution,
+            lens_radius,
+            focal_distance,
+        );
+        let (dx_camera, dy_camera) = projection.raster_steps();
+        Self {
+            base,
+            projection,
+            dx_camera,
+            dy_camera,
+        }
+    }
+
+    fn camera_ray(&self, sample: &CameraSample, p_camera: Point3f) -> Ray {
+        let (o, d) =
+            self.projection
+                .thin_lens(sample.p_lens, p_camera, Vector3f::new(0.0, 0.0, 1.0));
+        Ray::new(o, d, self.base.sample_time(sample.time))
+    }
+}
+
+impl Camera for OrthographicCamera {
+    fn base(&self) -> &CameraBase {
+        &self.base
+    }
+
+    fn generate_ray(&self, sample: &CameraSample) -> Option<CameraRay> {
+        let ray = self.camera_ray(sample, self.projection.camera_point(sample.p_film));
+        Some(CameraRay {
+            ray: self.base.render_from_camera_ray(&ray),
+            weight: 1.0,
+        })
+    }
+
+    fn generate_ray_differential(&self, sample: &CameraSample) -> Option<CameraRayDifferential> {
+        let p_camera = self.projection.camera_point(sample.p_film);
+        let ray = RayDifferential::with_differentials(
+            self.camera_ray(sample, p_camera),
+            &self.camera_ray(sample, p_camera + self.dx_camera),
+            &self.camera_ray(sample, p_camera + self.dy_camera),
+        );
+        Some(CameraRayDifferential {
+            ray: self.base.render_from_camera_ray_differential(&ray),
+            weight: 1.0,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::math::AnimatedTransform;
+    use crate::util::vector::{Point2f, Point2i};
+
+    fn camera(lens_radius: Float) -> OrthographicCamera {
+        let base = CameraBase::new(
+            AnimatedTransform::fixed(Transform::identity()),
+            0.0,
+            1.0,
+            Point2i::new(100, 50),
+        );
+        let window = Bounds2f::from_points(Point2f::new(-4.0, -2.0), Point2f::new(4.0, 2.0));
+        OrthographicCamera::new(base, Some(window), lens_radius, 3.0)
+    }
+
+    fn sample(x: Float, y: Float) -> CameraSample {
+        CameraSample {
+            p_film: Point2f::new(x, y),
+            p_lens: Point2f::new(0.2, 0.7),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn rays_are_parallel_and_span_the_window() {
+        let cam = camera(0.0);
+        let corner = cam.generate_ray(&sample(0.0, 0.0)).unwrap().ray;
+        assert!((corner.origin() - Point3f::new(-4.0, 2.0, 0.0)).length() < 1e-4);
+        assert_eq!(corner.direction(), Vector3f::new(0.0, 0.0, 1.0));
+        let rd = cam
+            .generate_ray_differential(&sample(50.0, 25.0))
+            .unwrap()
+            .ray;
+        assert!(Vector3f::from(rd.origin()).length() < 1e-4);
+        assert!((rd.rx_origin - rd.origin() - Vector3f::new(0.08, 0.0, 0.0)).length() < 1e-4);
+        assert!((rd.ry_origin - rd.origin() - Vector3f::new(0.0, -0.08, 0.0)).length() < 1e-4);
+        assert_eq!(rd.rx_direction, rd.direction());
+    }
+
+    #[test]
+    fn thin_lens_focuses_on_the_plane() {
+        let pinhole = camera(0.0).generate_ray(&sample(20.0, 10.0)).unwrap().ray;
+        let focus = pinhole.get(3.0);
+        let r = camera(0.5).generate_ray(&sample(20.0, 10.0)).unwrap().ray;
+        assert!((r.origin() - pinhole.origin()).length() > 0.01);
+        let t = (3.0 - r.origin().z) / r.direction().z;
+        assert!((r.get(t) - focus).length() < 1e-4);
+    }
+}
@@ -0,0 +1,190 @@
+use crate::cameras::camera::{
+    Camera, CameraBase, CameraRay, CameraRayDifferential, CameraSample, Projection,
+};
+use crate::util::Float;
+use crate::util::bounds::Bounds2f;
+use crate::util::math::transformations::Transform;
+use crate::util::rays::{Ray, RayDifferential};
+use crate::util::vector::{Point3f, Vector3f};
+
+/// A camera that projects through a single point, or through a thin lens
+/// when `lens_radius` is positive, looking down +z in camera space.
+///
+/// With a thin lens only points at `focal_distance` are in focus and rays
+/// start on the lens rather than at the origin.
+#[derive(Debug, Clone, Copy)]
+pub struct PerspectiveCamera {
+    base: CameraBase,
+    projection: Projection,
+    dx_camera: Vector3f,
+    dy_camera: Vector3f,
+}
+
+impl PerspectiveCamera {
+    /// `fov` is in degrees and spans the shorter image axis, or the
+    /// `screen_window` if one is given.
+    pub fn new(
+        base: CameraBase,
+        fov: Float,
+        screen_window: Option<Bounds2f>,
+        lens_radius: Float,
+        focal_distance: Float,
+    ) -> Self {
+        let projection = Projection::new(
+            Transform::perspective(fov, 1e-2, 1000.0),
+            screen_window,
+            base.resolution,
+            lens_radius,
+            focal_distance,
+        );
+        let (dx_camera, dy_camera) = projection.raster_steps();
+        Self {
+            base,
+            projection,
+            dx_camera,
+            dy_camera,
+        }
+    }
+
+    /// Origin and direction of the camera-space ray through the film point
+    /// `p_camera` on the near plane.
+    fn camera_ray(&self, sample: &CameraSample, p_camera: Vector3f) -> Ray {
+        let (o, d) =
+            self.projection
+                .thin_lens(sample.p_lens, Point3f::default(), p_camera.normalize());
+        Ray::new(o, d, self.base.sample_time(sample.time))
+    }
+}
+
+impl Camera for PerspectiveCamera {
+    fn base(&self) -> &CameraBase {
+        &self.base
+    }
+
+    fn generate_ray(&self, sample: &CameraSample) -> Option<CameraRay> {
+        let p_camera = Vector3f::from(self.projection.camera_point(sample.p_film));
+        let ray = self.camera_ray(sample, p_camera);
+        Some(CameraRay {
+            ray: self.base.render_from_camera_ray(&ray),
+            weight: 1.0,
+        })
+    }
+
+    /// The offset rays are computed exactly rather than by finite
+    /// differences.
+    fn generate_ray_differential(&self, sample: &CameraSample) -> Option<CameraRayDifferential> {
+        let p_camera = Vector3f::from(self.projection.camera_point(sample.p_film));
+        let ray = RayDifferential::with_differentials(
+            self.camera_ray(sample, p_camera),
+            &self.camera_ray(sample, p_camera + self.dx_camera),
+            &self.camera_ray(sample, p_camera + self.dy_camera),
+        );
+        Some(CameraRayDifferential {
+            ray: self.base.render_from_camera_ray_differential(&ray),
+            weight: 1.0,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::math::AnimatedTransform;
+    use crate::util::vector::{Point2f, Point2i};
+
+    fn camera(lens_radius: Float) -> PerspectiveCamera {
+        let base = CameraBase::new(
+            AnimatedTransform::fixed(Transform::identity()),
+            0.5,
+            1.5,
+            Point2i::new(200, 100),
+        );
+        PerspectiveCamera::new(base, 90.0, None, lens_radius, 5.0)
+    }
+
+    fn sample(x: Float, y: Float) -> CameraSample {
+        CameraSample {
+            p_film: Point2f::new(x, y),
+            p_lens: Point2f::new(0.9, 0.2),
+            time: 0.25,
+            filter_weight: 1.0,
+        }
+    }
+
+    #[test]
+    fn pinhole_rays_span_the_field_of_view() {
+        let cam = camera(0.0);
+        let center = cam.generate_ray(&sample(100.0, 50.0)).unwrap().ray;
+        assert_eq!(center.origin(), Point3f::default());
+        assert!((center.direction() - Vector3f::new(0.0, 0.0, 1.0)).length() < 1e-5);
+        assert_eq!(center.time(), 0.75);
+
+        // The fov spans the shorter (vertical) axis; raster y grows
+        // downward.
+        let top = cam
+            .generate_ray(&sample(100.0, 0.0))
+            .unwrap()
+            .ray
+            .direction();
+        assert!((top.y - top.z).abs() < 1e-4 && top.y > 0.0);
+        let left = cam
+            .generate_ray(&sample(0.0, 50.0))
+            .unwrap()
+            .ray
+            .direction();
+        assert!((left.x / left.z + 2.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn thin_lens_rays_meet_on_the_focal_plane() {
+        let pinhole = camera(0.0).generate_ray(&sample(30.0, 70.0)).unwrap().ray;
+        let focus = pinhole.get(5.0 / pinhole.direction().z);
+        let cam = camera(0.5);
+        for (u, v) in [(0.1, 0.1), (0.9, 0.4), (0.5, 0.8)] {
+            let mut s = sample(30.0, 70.0);
+            s.p_lens = Point2f::new(u, v);
+            let r = cam.generate_ray(&s).unwrap().ray;
+            assert!(r.origin().z.abs() < 1e-5);
+            assert!(Vector3f::from(r.origin()).length() <= 0.5 + 1e-5);
+            let t = (5.0 - r.origin().z) / r.direction().z;
+            assert!((r.get(t) - focus).length() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn differentials_match_neighbouring_pixels() {
+        for lens_radius in [0.0, 0.3] {
+            let cam = camera(lens_radius);
+            let rd = cam
+                .generate_ray_differential(&sample(40.0, 60.0))
+                .unwrap()
+                .ray;
+            assert!(rd.has_differentials);
+            let rx = cam.generate_ray(&sample(41.0, 60.0)).unwrap().ray;
+            let ry = cam.generate_ray(&sample(40.0, 61.0)).unwrap().ray;
+            assert!((rd.rx_direction - rx.direction()).length() < 1e-5);
+            assert!((rd.ry_direction - ry.direction()).length() < 1e-5);
+            assert!((rd.rx_origin - rx.origin()).length() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn rays_follow_the_camera_transform() {
+        let camera_from_world = Transform::look_at(
+            Point3f::new(0.0, 2.0, -4.0),
+            Point3f::new(0.0, 2.0, 0.0),
+            Vector3f::new(0.0, 1.0, 0.0),
+        )
+        .unwrap();
+        let base = CameraBase::new(
+            AnimatedTransform::fixed(camera_from_world.inverse()),
+            0.0,
+            1.0,
+            Point2i::new(64, 64),
+        );
+        let cam = PerspectiveCamera::new(base, 60.0, None, 0.0, 1.0);
+        let r = cam.generate_ray(&sample(32.0, 32.0)).unwrap().ray;
+        assert!((r.origin() - Point3f::new(0.0, 2.0, -4.0)).length() < 1e-4);
+        assert!((r.direction() - Vector3f::new(0.0, 0.0, 1.0)).length() < 1e-4);
+    }
+}
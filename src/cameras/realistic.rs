@@ -0,0 +1,547 @@
+use std::io;
+use std::path::Path;
+
+use crate::cameras::camera::{Camera, CameraBase, CameraRay, CameraSample};
+use crate::util::Float;
+use crate::util::bounds::Bounds2f;
+use crate::util::low_discrepancy::radical_inverse;
+use crate::util::math::quadratic;
+use crate::util::rays::Ray;
+use crate::util::vector::{Normal3f, Point2f, Point3f, Vector3f};
+
+/// Number of radial segments of the film the exit pupil is bounded over.
+const EXIT_PUPIL_SEGMENTS: usize = 64;
+/// Number of film and rear-element point pairs tried per segment.
+const EXIT_PUPIL_SAMPLES: usize = 128 * 128;
+
+/// One spherical interface, or the aperture stop, of a lens system.
+///
+/// Lengths are in meters. Elements are listed from the scene side to the
+/// film side, and `thickness` is the distance to the next element along the
+/// optical axis, or to the film for the last one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LensElement {
+    /// Signed radius of the spherical surface, or `0` for the aperture stop.
+    pub curvature_radius: Float,
+    pub thickness: Float,
+    /// Index of refraction on the film side, or `0` for air.
+    pub eta: Float,
+    pub aperture_radius: Float,
+}
+
+impl LensElement {
+    fn is_stop(&self) -> bool {
+        self.curvature_radius == 0.0
+    }
+}
+
+/// Parses a lens description in the tabular format used by pbrt's lens
+/// files: one element per line as curvature radius, thickness, index of
+/// refraction and aperture diameter, all lengths in millimeters. `#` starts
+/// a comment.
+pub fn parse_lens_description(text: &str) -> io::Result<Vec<LensElement>> {
+    let invalid = |msg: String| io::Error::new(io::ErrorKind::InvalidData, msg);
+    let mut elements = Vec::new();
+    for (line_no, line) in text.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+        let values = line
+            .split_whitespace()
+            .map(|v| v.parse::<Float>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| invalid(format!("line {}: {e}", line_no + 1)))?;
+        let [radius, thickness, eta, diameter] = values[..] else {
+            return Err(invalid(format!(
+                "line {}: expected 4 values, found {}",
+                line_no + 1,
+                values.len()
+            )));
+        };
+        elements.push(LensElement {
+            curvature_radius: radius * 0.001,
+            thickness: thickness * 0.001,
+            eta,
+            aperture_radius: diameter * 0.001 / 2.0,
+        });
+    }
+    if elements.is_empty() {
+        return Err(invalid("lens description has no elements".into()));
+    }
+    Ok(elements)
+}
+
+/// A camera that simulates a real lens system by tracing rays from the film
+/// through each of its elements.
+///
+/// Rays are aimed at bounds on the exit pupil, the region of the rear
+/// element that light from a given film point can pass through, so few
+/// of them are blocked inside the lens. Rays still blocked produce no
+/// camera ray.
+#[derive(Debug, Clone)]
+pub struct RealisticCamera {
+    base: CameraBase,
+    elements: Vec<LensElement>,
+    /// Film extent in camera space, in meters.
+    physical_extent: Bounds2f,
+    film_diagonal: Float,
+    /// Exit pupil bounds for film points at increasing distance from the
+    /// optical axis, in a frame where the point lies on +x.
+    exit_pupil_bounds: Vec<Bounds2f>,
+}
+
+impl RealisticCamera {
+    /// Builds the camera from lens `elements`, narrowing the aperture stop
+    /// to `aperture_diameter` and moving the film to focus at
+    /// `focus_distance`. Lengths are in meters, including the diagonal of
+    /// the film.
+    ///
+    /// Returns `None` if the lens system does not pass light along its axis
+    /// or cannot focus at that distance.
+    pub fn new(
+        base: CameraBase,
+        mut elements: Vec<LensElement>,
+        aperture_diameter: Float,
+        focus_distance: Float,
+        film_diagonal: Float,
+    ) -> Option<Self> {
+        for e in elements.iter_mut().filter(|e| e.is_stop()) {
+            e.aperture_radius = e.aperture_radius.min(aperture_diameter / 2.0);
+        }
+        let res = base.resolution;
+        let aspect = res.y as Float / res.x as Float;
+        let x = (film_diagonal * film_diagonal / (1.0 + aspect * aspect)).sqrt();
+        let y = aspect * x;
+        let mut camera = Self {
+            base,
+            elements,
+            physical_extent: Bounds2f::from_points(
+                Point2f::new(-x / 2.0, -y / 2.0),
+                Point2f::new(x / 2.0, y / 2.0),
+            ),
+            film_diagonal,
+            exit_pupil_bounds: Vec::new(),
+        };
+        let film_distance = camera.focus_thick_lens(focus_distance)?;
+        camera.elements.last_mut()?.thickness = film_distance;
+
+        camera.exit_pupil_bounds = (0..EXIT_PUPIL_SEGMENTS)
+            .map(|i| {
+                let r = |i: usize| i as Float / EXIT_PUPIL_SEGMENTS as Float * film_diagonal / 2.0;
+                camera.bound_exit_pupil(r(i), r(i + 1))
+            })
+            .collect();
+        Some(camera)
+    }
+
+    /// Reads the lens elements from a lens description file, see
+    /// [`parse_lens_description`].
+    pub fn load_lens_file(path: impl AsRef<Path>) -> io::Result<Vec<LensElement>> {
+        parse_lens_description(&std::fs::read_to_string(path)?)
+    }
+
+    pub fn elements(&self) -> &[LensElement] {
+        &self.elements
+    }
+
+    /// Distance from the film to the rear element.
+    fn lens_rear_z(&self) -> Float {
+        self.elements.last().map_or(0.0, |e| e.thickness)
+    }
+    /// Distance from the film to the front element.
+    fn lens_front_z(&self) -> Float {
+        self.elements.iter().map(|e| e.thickness).sum()
+    }
+    fn rear_element_radius(&self) -> Float {
+        self.elements.last().map_or(0.0, |e| e.aperture_radius)
+    }
+
+    /// Traces a camera-space ray leaving the film through the elements,
+    /// returning it as it leaves the front element, or `None` if it is
+    /// blocked or totally internally reflected.
+    fn trace_lenses_from_film(&self, r_camera: &Ray) -> Option<Ray> {
+        // The lens system looks down -z.
+        let mut o = flip_z(r_camera.origin());
+        let mut d = flip_z_vector(r_camera.direction());
+        let mut element_z = 0.0;
+        for (i, element) in self.elements.iter().enumerate().rev() {
+            element_z -= element.thickness;
+            let (t, n) = if element.is_stop() {
+                if d.z >= 0.0 {
+                    return None;
+                }
+                ((element_z - o.z) / d.z, None)
+            } else {
+                let z_center = element_z + element.curvature_radius;
+                let (t, n) = intersect_spherical_element(element.curvature_radius, z_center, o, d)?;
+                (t, Some(n))
+            };
+            let p_hit = o + d * t;
+            if p_hit.x * p_hit.x + p_hit.y * p_hit.y
+                > element.aperture_radius * element.aperture_radius
+            {
+                return None;
+            }
+            o = p_hit;
+            if let Some(n) = n {
+                let eta_i = element.eta;
+                let eta_t = match i.checked_sub(1).map(|j| self.elements[j].eta) {
+                    Some(eta) if eta != 0.0 => eta,
+                    _ => 1.0,
+                };
+                d = refract(&(-d).normalize(), &n, eta_i / eta_t)?;
+            }
+        }
+        Some(Ray::new(flip_z(o), flip_z_vector(d), r_camera.time()))
+    }
+
+    /// The reverse of `trace_lenses_from_film`, for a ray entering the front
+    /// element from the scene.
+    fn trace_lenses_from_scene(&self, r_camera: &Ray) -> Option<Ray> {
+        let mut o = flip_z(r_camera.origin());
+        let mut d = flip_z_vector(r_camera.direction());
+        let mut element_z = -self.lens_front_z();
+        for (i, element) in self.elements.iter().enumerate() {
+            let (t, n) = if element.is_stop() {
+                ((element_z - o.z) / d.z, None)
+            } else {
+                let z_center = element_z + element.curvature_radius;
+                let (t, n) = intersect_spherical_element(element.curvature_radius, z_center, o, d)?;
+                (t, Some(n))
+            };
+            let p_hit = o + d * t;
+            if p_hit.x * p_hit.x + p_hit.y * p_hit.y
+                > element.aperture_radius * element.aperture_radius
+            {
+                return None;
+            }
+            o = p_hit;
+            if let Some(n) = n {
+                let eta_i = match i.checked_sub(1).map(|j| self.elements[j].eta) {
+                    Some(eta) if eta != 0.0 => eta,
+                    _ => 1.0,
+                };
+                let eta_t = if element.eta != 0.0 { element.eta } else { 1.0 };
+                d = refract(&(-d).normalize(), &n, eta_i / eta_t)?;
+            }
+            element_z += element.thickness;
+        }
+        Some(Ray::new(flip_z(o), flip_z_vector(d), r_camera.time()))
+    }
+
+    /// Principal plane and focal point along z of the thick-lens
+    /// approximation, for light entering from the scene (`[0]`) and from the
+    /// film (`[1]`).
+    fn thick_lens_approximation(&self) -> Option<([Float; 2], [Float; 2])> {
+        // A ray parallel to the axis, close enough to it for the paraxial
+        // approximation.
+        let x = 0.001 * self.film_diagonal;
+        let r_scene = Ray::new(
+            Point3f::new(x, 0.0, self.lens_front_z() + 1.0),
+            Vector3f::new(0.0, 0.0, -1.0),
+            0.0,
+        );
+        let r_film = self.trace_lenses_from_scene(&r_scene)?;
+        let (pz0, fz0) = cardinal_points(&r_scene, &r_film);
+
+        let r_film = Ray::new(
+            Point3f::new(x, 0.0, self.lens_rear_z() - 1.0),
+            Vector3f::new(0.0, 0.0, 1.0),
+            0.0,
+        );
+        let r_scene = self.trace_lenses_from_film(&r_film)?;
+        let (pz1, fz1) = cardinal_points(&r_film, &r_scene);
+        Some(([pz0, pz1], [fz0, fz1]))
+    }
+
+    /// Distance from the film to the rear element that brings objects at
+    /// `focus_distance` from the film into focus.
+    fn focus_thick_lens(&self, focus_distance: Float) -> Option<Float> {
+        let (pz, fz) = self.thick_lens_approximation()?;
+        let f = fz[0] - pz[0];
+        let z = -focus_distance;
+        let c = (pz[1] - z - pz[0]) * (pz[1] - z - 4.0 * f - pz[0]);
+        if c <= 0.0 {
+            return None;
+        }
+        let delta = 0.5 * (pz[1] - z + pz[0] - c.sqrt());
+        Some(self.lens_rear_z() + delta)
+    }
+
+    /// Bounds on the rear element of the points that rays from film points
+    /// between `r0` and `r1` along +x can pass through the whole system via.
+    fn bound_exit_pupil(&self, r0: Float, r1: Float) -> Bounds2f {
+        let rear_radius = 1.5 * self.rear_element_radius();
+        let proj_rear_bounds = Bounds2f::from_points(
+            Point2f::new(-rear_radius, -rear_radius),
+            Point2f::new(rear_radius, rear_radius),
+        );
+        let n = EXIT_PUPIL_SAMPLES;
+        let mut pupil_bounds = Bounds2f::new();
+        for i in 0..n {
+            let p_film = Point3f::new(r0 + (i as Float + 0.5) / n as Float * (r1 - r0), 0.0, 0.0);
+            // A Hammersley set, so that every part of the film segment is
+            // paired with points all over the rear element.
+            let u = Point2f::new(radical_inverse(0, i as u64), radical_inverse(1, i as u64));
+            let p_rear = proj_rear_bounds.lerp(u);
+            // Points already inside the bounds need no tracing.
+            if pupil_bounds.is_point_inside(&p_rear)
+                || self
+                    .trace_lenses_from_film(&Ray::new(
+                        p_film,
+                        Point3f::new(p_rear.x, p_rear.y, self.lens_rear_z()) - p_film,
+                        0.0,
+                    ))
+                    .is_some()
+            {
+                pupil_bounds = pupil_bounds.union_point(&p_rear);
+            }
+        }
+        if pupil_bounds.is_empty() {
+            return proj_rear_bounds;
+        }
+        // Pad by the sample spacing to catch what fell between the samples.
+        Bounds2f::expand(
+            pupil_bounds,
+            2.0 * proj_rear_bounds.diagonal().length() / (n as Float).sqrt(),
+        )
+    }
+
+    /// Point on the rear element for the film point `p_film`, picked within
+    /// its exit pupil bounds by `u_lens`, and the area of those bounds.
+    fn sample_exit_pupil(&self, p_film: Point2f, u_lens: Point2f) -> (Point3f, Float) {
+        let r_film = (p_film.x * p_film.x + p_film.y * p_film.y).sqrt();
+        let index = (r_film / (self.film_diagonal / 2.0) * EXIT_PUPIL_SEGMENTS as Float) as usize;
+        let bounds = self.exit_pupil_bounds[index.min(EXIT_PUPIL_SEGMENTS - 1)];
+        let p_lens = bounds.lerp(u_lens);
+        // Rotate from the frame where the film point lies on +x.
+        let (sin_theta, cos_theta) = if r_film != 0.0 {
+            (p_film.y / r_film, p_film.x / r_film)
+        } else {
+            (0.0, 1.0)
+        };
+        (
+            Point3f::new(
+                cos_theta * p_lens.x - sin_theta * p_lens.y,
+                sin_theta * p_lens.x + cos_theta * p_lens.y,
+                self.lens_rear_z(),
+            ),
+            bounds.area(),
+        )
+    }
+}
+
+impl Camera for RealisticCamera {
+    fn base(&self) -> &CameraBase {
+        &self.base
+    }
+
+    /// The weight accounts for the `cos^4` falloff and the area of the
+    /// exit pupil bounds, normalized to `1` for a ray along the axis.
+    fn generate_ray(&self, sample: &CameraSample) -> Option<CameraRay> {
+        let res = self.base.resolution;
+        let s = Point2f::new(
+            sample.p_film.x / res.x as Float,
+            sample.p_film.y / res.y as Float,
+        );
+        // The image on the film is inverted, so flip x; y is already flipped
+        // by raster space pointing down.
+        let p_film2 = self.physical_extent.lerp(s);
+        let p_film = Point3f::new(-p_film2.x, p_film2.y, 0.0);
+        let (p_rear, pupil_area) =
+            self.sample_exit_pupil(Point2f::new(p_film.x, p_film.y), sample.p_lens);
+        let r_film = Ray::new(p_film, p_rear - p_film, self.base.sample_time(sample.time));
+        let r = self.trace_lenses_from_film(&r_film)?;
+        let r = Ray::new(r.origin(), r.direction().normalize(), r.time());
+
+        let cos_theta = r_film.direction().normalize().z;
+        let cos4_theta = (cos_theta * cos_theta) * (cos_theta * cos_theta);
+        Some(CameraRay {
+            ray: self.base.render_from_camera_ray(&r),
+            weight: cos4_theta * pupil_area / self.exit_pupil_bounds[0].area(),
+        })
+    }
+}
+
+fn flip_z(p: Point3f) -> Point3f {
+    Point3f::new(p.x, p.y, -p.z)
+}
+fn flip_z_vector(v: Vector3f) -> Vector3f {
+    Vector3f::new(v.x, v.y, -v.z)
+}
+
+/// Intersects a ray with the sphere of `radius` centered at `z_center` on
+/// the axis, choosing the hit on the side of the sphere the element occupies.
+/// Returns the distance and the normal facing back along the ray.
+fn intersect_spherical_element(
+    radius: Float,
+    z_center: Float,
+    o: Point3f,
+    d: Vector3f,
+) -> Option<(Float, Vector3f)> {
+    let o = o - Vector3f::new(0.0, 0.0, z_center);
+    let ov = Vector3f::from(o);
+    let (t0, t1) = quadratic(
+        d.length_squared(),
+        2.0 * d.dot(&ov),
+        ov.length_squared() - radius * radius,
+    )?;
+    let use_closer_t = (d.z > 0.0) ^ (radius < 0.0);
+    let t = if use_closer_t { t0.min(t1) } else { t0.max(t1) };
+    if t < 0.0 {
+        return None;
+    }
+    let n = Normal3f::from((ov + d * t).normalize()).face_forward(&-d);
+    Some((t, Vector3f::from(n)))
+}
+
+/// Where the ray `r_out` that entered parallel to the axis as `r_in`
+/// crosses the axis (the focal point) and the plane where it would have had
+/// to bend in a single refraction (the principal plane), as z positions
+/// `(principal, focal)`.
+fn cardinal_points(r_in: &Ray, r_out: &Ray) -> (Float, Float) {
+    let tf = -r_out.origin().x / r_out.direction().x;
+    let fz = -r_out.get(tf).z;
+    let tp = (r_in.origin().x - r_out.origin().x) / r_out.direction().x;
+    let pz = -r_out.get(tp).z;
+    (pz, fz)
+}
+
+/// Refracts the unit vector `wi` through an interface with normal `n` on
+/// its side, for the relative index of refraction `eta_i / eta_t`. Returns
+/// `None` on total internal reflection.
+fn refract(wi: &Vector3f, n: &Vector3f, eta: Float) -> Option<Vector3f> {
+    let cos_theta_i = n.dot(wi);
+    let sin2_theta_i = (1.0 - cos_theta_i * cos_theta_i).max(0.0);
+    let sin2_theta_t = eta * eta * sin2_theta_i;
+    if sin2_theta_t >= 1.0 {
+        return None;
+    }
+    let cos_theta_t = (1.0 - sin2_theta_t).sqrt();
+    Some(-*wi * eta + *n * (eta * cos_theta_i - cos_theta_t))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::math::AnimatedTransform;
+    use crate::util::math::transformations::Transform;
+    use crate::util::vector::Point2i;
+
+    /// Double-Gauss 50mm lens, from pbrt's `dgauss.50mm.dat`.
+    const DGAUSS_50MM: &str = "
+        # D-GAUSS F/2 22deg HFOV
+        # radius  axpos  N      aperture
+        29.475    3.76   1.67   25.2
+        84.83     0.12   1      25.2
+        19.275    4.025  1.67   23
+        40.77     3.275  1.699  23
+        12.75     5.705  1      18
+        0         4.5    0      17.1
+        -14.495   1.18   1.603  17
+        40.77     6.065  1.658  20
+        -20.385   0.19   1      20
+        437.065   3.22   1.717  20
+        -39.73    5.0    1      20
+    ";
+
+    fn camera(focus_distance: Float) -> RealisticCamera {
+        let base = CameraBase::new(
+            AnimatedTransform::fixed(Transform::identity()),
+            0.0,
+            1.0,
+            Point2i::new(64, 48),
+        );
+        let elements = parse_lens_description(DGAUSS_50MM).unwrap();
+        RealisticCamera::new(base, elements, 0.01, focus_distance, 0.035).unwrap()
+    }
+
+    #[test]
+    fn parses_lens_files() {
+        let elements = parse_lens_description(DGAUSS_50MM).unwrap();
+        assert_eq!(elements.len(), 11);
+        assert!((elements[0].curvature_radius - 0.029475).abs() < 1e-7);
+        assert!((elements[0].aperture_radius - 0.0126).abs() < 1e-7);
+        assert!(elements[5].is_stop());
+        assert!(parse_lens_description("1 2 3").is_err());
+        assert!(parse_lens_description("# nothing\n").is_err());
+        assert!(parse_lens_description("1 2 x 4").is_err());
+    }
+
+    #[test]
+    fn center_rays_converge_at_the_focus_distance() {
+        let focus = 2.0;
+        let cam = camera(focus);
+        // The stop was narrowed to the requested aperture.
+        assert!((cam.elements()[5].aperture_radius - 0.005).abs() < 1e-7);
+
+        let mut hits = 0;
+        for i in 0..16 {
+            let sample = CameraSample {
+                p_film: Point2f::new(32.0, 24.0),
+                p_lens: Point2f::new(
+                    (i % 4) as Float / 4.0 + 0.125,
+                    (i / 4) as Float / 4.0 + 0.125,
+                ),
+                ..Default::default()
+            };
+            let Some(cr) = cam.generate_ray(&sample) else {
+                continue;
+            };
+            hits += 1;
+            let r = cr.ray;
+            assert!(r.direction().z > 0.9);
+            assert!(cr.weight > 0.0);
+            // Closest approach to the optical axis.
+            let (o, d) = (r.origin(), r.direction());
+            let dxy = d.x * d.x + d.y * d.y;
+            if dxy > 1e-12 {
+                let t = -(o.x * d.x + o.y * d.y) / dxy;
+                let z = r.get(t).z;
+                assert!((z - focus).abs() < 0.1 * focus, "crossed the axis at {z}");
+            }
+        }
+        assert!(hits >= 8);
+    }
+
+    #[test]
+    fn exit_pupil_bounds_admit_center_rays() {
+        let cam = camera(1.0);
+        let b = cam.exit_pupil_bounds[0];
+        assert!(!b.is_empty() && b.area() > 0.0);
+        assert!(b.is_point_inside(&Point2f::new(0.0, 0.0)));
+        // Off-axis bounds shrink from vignetting.
+        let edge = cam.exit_pupil_bounds[EXIT_PUPIL_SEGMENTS - 1];
+        assert!(edge.area() <= b.area() * 1.5);
+    }
+
+    #[test]
+    fn exit_pupil_bounds_hold_every_ray_through_the_lens() {
+        let cam = camera(1.0);
+        let rear_radius = 1.5 * cam.rear_element_radius();
+        let segment = cam.film_diagonal / 2.0 / EXIT_PUPIL_SEGMENTS as Float;
+        for s in [0, EXIT_PUPIL_SEGMENTS / 2, EXIT_PUPIL_SEGMENTS - 1] {
+            let bounds = cam.exit_pupil_bounds[s];
+            // Both ends of the segment, where the film sees the rear
+            // element at its most different.
+            for x in [s as Float, s as Float + 0.999].map(|f| f * segment) {
+                let p_film = Point3f::new(x, 0.0, 0.0);
+                for i in 0..64 * 64 {
+                    let p_rear = Point2f::new(
+                        ((i % 64) as Float + 0.5) / 32.0 - 1.0,
+                        ((i / 64) as Float + 0.5) / 32.0 - 1.0,
+                    ) * rear_radius;
+                    let ray = Ray::new(
+                        p_film,
+                        Point3f::new(p_rear.x, p_rear.y, cam.lens_rear_z()) - p_film,
+                        0.0,
+                    );
+                    if cam.trace_lenses_from_film(&ray).is_some() {
+                        assert!(bounds.is_point_inside(&p_rear), "{s} {x} {p_rear:?}");
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use crate::cameras::camera::{Camera, CameraBase, CameraRay, CameraSample};
+use crate::util::Float;
+use crate::util::math::{PI, equal_area_square_to_sphere, spherical_direction};
+use crate::util::rays::Ray;
+use crate::util::vector::{Point2f, Point3f, Vector3f};
+
+/// How a `SphericalCamera` lays the sphere of directions out on the film.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SphericalMapping {
+    /// Latitude-longitude: `phi` along x and `theta` along y.
+    #[default]
+    EquiRectangular,
+    /// The octahedral equal-area mapping, where every pixel covers the same
+    /// solid angle. Expects a square film.
+    EqualArea,
+}
+
+/// A camera that sees in every direction from a single point, producing
+/// environment-map panoramas.
+///
+/// Camera-space +y is the pole of the equirectangular mapping and the
+/// center of the equal-area one.
+#[derive(Debug, Clone, Copy)]
+pub struct SphericalCamera {
+    base: CameraBase,
+    mapping: SphericalMapping,
+}
+
+impl SphericalCamera {
+    pub fn new(base: CameraBase, mapping: SphericalMapping) -> Self {
+        Self { base, mapping }
+    }
+
+    pub fn mapping(&self) -> SphericalMapping {
+        self.mapping
+    }
+}
+
+impl Camera for SphericalCamera {
+    fn base(&self) -> &CameraBase {
+        &self.base
+    }
+
+    fn generate_ray(&self, sample: &CameraSample) -> Option<CameraRay> {
+        let res = self.base.resolution;
+        let uv = Point2f::new(
+            sample.p_film.x / res.x as Float,
+            sample.p_film.y / res.y as Float,
+        );
+        let d = match self.mapping {
+            SphericalMapping::EquiRectangular => {
+                let (sin_theta, cos_theta) = (PI * uv.y).sin_cos();
+                spherical_direction(sin_theta, cos_theta, 2.0 * PI * uv.x)
+            }
+            SphericalMapping::EqualArea => equal_area_square_to_sphere(Point2f::new(
+                uv.x.clamp(0.0, 1.0),
+                uv.y.clamp(0.0, 1.0),
+            )),
+        };
+        // Put the mapping's +z pole on +y, the usual up direction.
+        let d = Vector3f::new(d.x, d.z, d.y);
+        let ray = Ray::new(Point3f::default(), d, self.base.sample_time(sample.time));
+        Some(CameraRay {
+            ray: self.base.render_from_camera_ray(&ray),
+            weight: 1.0,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::math::AnimatedTransform;
+    use crate::util::math::transformations::Transform;
+    use crate::util::vector::Point2i;
+
+    fn camera(mapping: SphericalMapping, res: Point2i) -> SphericalCamera {
+        let base = CameraBase::new(
+            AnimatedTransform::fixed(Transform::identity()),
+            0.0,
+            1.0,
+            res,
+        );
+        SphericalCamera::new(base, mapping)
+    }
+
+    fn direction(cam: &SphericalCamera, x: Float, y: Float) -> Vector3f {
+        let sample = CameraSample {
+            p_film: Point2f::new(x, y),
+            ..Default::default()
+        };
+        cam.generate_ray(&sample).unwrap().ray.direction()
+    }
+
+    #[test]
+    fn equirectangular_rows_are_latitudes() {
+        let cam = camera(SphericalMapping::EquiRectangular, Point2i::new(360, 180));
+        assert!((direction(&cam, 17.0, 0.0) - Vector3f::new(0.0, 1.0, 0.0)).length() < 1e-5);
+        assert!((direction(&cam, 0.0, 90.0) - Vector3f::new(1.0, 0.0, 0.0)).length() < 1e-5);
+        assert!((direction(&cam, 90.0, 90.0) - Vector3f::new(0.0, 0.0, 1.0)).length() < 1e-5);
+        assert!((direction(&cam, 200.0, 180.0) - Vector3f::new(0.0, -1.0, 0.0)).length() < 1e-5);
+    }
+
+    #[test]
+    fn equal_area_center_looks_up() {
+        let cam = camera(SphericalMapping::EqualArea, Point2i::new(64, 64));
+        assert!((direction(&cam, 32.0, 32.0) - Vector3f::new(0.0, 1.0, 0.0)).length() < 1e-5);
+        assert!((direction(&cam, 0.0, 0.0) - Vector3f::new(0.0, -1.0, 0.0)).length() < 1e-5);
+
+        // The default finite-difference differentials exist everywhere.
+        let sample = CameraSample {
+            p_film: Point2f::new(10.5, 40.5),
+            ..Default::default()
+        };
+        let rd = cam.generate_ray_differential(&sample).unwrap().ray;
+        assert!(rd.has_differentials);
+        let step = (rd.rx_direction - rd.direction()).length();
+        assert!(step > 0.0 && step < 0.1);
+    }
+}
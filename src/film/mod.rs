@@ -0,0 +1,2 @@
+mod rgb_film;
+pub use rgb_film::RGBFilm;
@@ -0,0 +1,325 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::filters::Filter;
+use crate::images::{Image, PixelFormat};
+use crate::util::Float;
+use crate::util::bounds::{Bounds2f, Bounds2i};
+use crate::util::color::RGB;
+use crate::util::vector::{Point2f, Point2i};
+
+/// An `f64` that can be added to concurrently.
+#[derive(Debug, Default)]
+struct AtomicF64(AtomicU64);
+
+impl AtomicF64 {
+    fn load(&self) -> f64 {
+        f64::from_bits(self.0.load(Ordering::Relaxed))
+    }
+
+    // The conversion is a no-op when `Float` is `f64`.
+    #[allow(clippy::useless_conversion)]
+    fn add(&self, v: Float) {
+        let v = f64::from(v);
+        // The closure always returns `Some`, so the update cannot fail.
+        let _ = self
+            .0
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |bits| {
+                Some((f64::from_bits(bits) + v).to_bits())
+            });
+    }
+}
+
+/// Running sums for one pixel. Kept in double precision since a pixel may
+/// accumulate many thousands of samples.
+#[derive(Debug, Default)]
+struct Pixel {
+    rgb_sum: [AtomicF64; 3],
+    weight_sum: AtomicF64,
+    rgb_splat: [AtomicF64; 3],
+}
+
+/// A film that records RGB radiance estimates for the pixels of a crop
+/// window of the full image.
+///
+/// Camera samples are accumulated as a weighted average per pixel, while
+/// splats from light-tracing strategies are spread over nearby pixels by the
+/// reconstruction filter, summed separately and scaled when the image is
+/// produced. Both can be added through a shared reference from any number of
+/// render threads.
+#[derive(Debug)]
+pub struct RGBFilm<F: Filter> {
+    full_resolution: Point2i,
+    pixel_bounds: Bounds2i,
+    filter: F,
+    max_component_value: Float,
+    pixels: Vec<Pixel>,
+}
+
+impl<F: Filter> RGBFilm<F> {
+    /// A film for the part of a `full_resolution` image inside
+    /// `pixel_bounds`, with `pMax` exclusive.
+    pub fn new(full_resolution: Point2i, pixel_bounds: Bounds2i, filter: F) -> Self {
+        let pixel_bounds = Bounds2i::intersect(
+            pixel_bounds,
+            Bounds2i::from_points(Point2i::new(0, 0), full_resolution),
+        );
+        assert!(
+            !pixel_bounds.is_degenerate(),
+            "crop window {pixel_bounds:?} does not overlap the image"
+        );
+        let n = pixel_bounds.iter().len();
+        Self {
+            full_resolution,
+            pixel_bounds,
+            filter,
+            max_component_value: Float::INFINITY,
+            pixels: (0..n).map(|_| Pixel::default()).collect(),
+        }
+    }
+
+    /// Clamps every added value so its largest component is at most `max`,
+    /// trading bias for less noise from rare, very bright samples.
+    pub fn with_max_component_value(mut self, max: Float) -> Self {
+        self.max_component_value = max;
+        self
+    }
+
+    pub fn full_resolution(&self) -> Point2i {
+        self.full_resolution
+    }
+
+    /// The pixels this film records, with `pMax` exclusive.
+    pub fn pixel_bounds(&self) -> Bounds2i {
+        self.pixel_bounds
+    }
+
+    pub fn filter(&self) -> &F {
+        &self.filter
+    }
+
+    /// The region of the image plane that camera samples must cover: the
+    /// pixel bounds grown by the filter radius, less the half pixel to
+    /// pixel centers.
+    pub fn sample_bounds(&self) -> Bounds2f {
+        let radius = self.filter.radius();
+        let b = Bounds2f::from(self.pixel_bounds);
+        Bounds2f::from_points(
+            Point2f::new(b.pMin.x - radius.x + 0.5, b.pMin.y - radius.y + 0.5),
+            Point2f::new(b.pMax.x + radius.x - 0.5, b.pMax.y + radius.y - 0.5),
+        )
+    }
+
+    /// Adds the radiance estimate `rgb` of a camera sample in pixel
+    /// `p_pixel`, with the weight the reconstruction filter gave it.
+    pub fn add_sample(&self, p_pixel: Point2i, rgb: RGB, weight: Float) {
+        let Some(pixel) = self.pixel(p_pixel) else {
+            panic!(
+                "sample for pixel {p_pixel:?} outside of {:?}",
+                self.pixel_bounds
+            );
+        };
+        let rgb = self.clamp(rgb);
+        for c in 0..3 {
+            pixel.rgb_sum[c].add(weight * rgb[c]);
+        }
+        pixel.weight_sum.add(weight);
+    }
+
+    /// Adds `rgb` at the continuous raster position `p_film` to every pixel
+    /// whose center is within the filter radius, weighted by the filter.
+    /// Unlike camera samples, splats may land anywhere, and the parts
+    /// outside the film are dropped.
+    pub fn add_splat(&self, p_film: Point2f, rgb: RGB) {
+        if rgb.has_nans() || !(p_film.x.is_finite() && p_film.y.is_finite()) {
+            return;
+        }
+        let rgb = self.clamp(rgb);
+        let radius = self.filter.radius();
+        let (x, y) = (p_film.x - 0.5, p_film.y - 0.5);
+        // Clip to the film before converting so that far-off points cannot
+        // overflow `i32`.
+        let (p_min, p_max) = (self.pixel_bounds.pMin, self.pixel_bounds.pMax);
+        let to_x = |v: Float| v.clamp(p_min.x as Float, p_max.x as Float) as i32;
+        let to_y = |v: Float| v.clamp(p_min.y as Float, p_max.y as Float) as i32;
+        let splat_bounds = Bounds2i {
+            pMin: Point2i::new(to_x((x - radius.x).ceil()), to_y((y - radius.y).ceil())),
+            pMax: Point2i::new(
+                to_x((x + radius.x).floor() + 1.0),
+                to_y((y + radius.y).floor() + 1.0),
+            ),
+        };
+        for p in &splat_bounds {
+            let weight = self
+                .filter
+                .evaluate(Point2f::new(x - p.x as Float, y - p.y as Float));
+            if weight != 0.0 {
+                let pixel = self.pixel(p).expect("splat bounds are clipped to the film");
+                for c in 0..3 {
+                    pixel.rgb_splat[c].add(weight * rgb[c]);
+                }
+            }
+        }
+    }
+
+    /// The final value of pixel `p_pixel`: the weighted average of its
+    /// samples plus its splats times `splat_scale`, which is typically one
+    /// over the number of samples taken per pixel.
+    pub fn get_pixel_rgb(&self, p_pixel: Point2i, splat_scale: Float) -> RGB {
+        let pixel = self
+            .pixel(p_pixel)
+            .unwrap_or_else(|| panic!("pixel {p_pixel:?} outside of {:?}", self.pixel_bounds));
+        let weight_sum = pixel.weight_sum.load();
+        let mut rgb = [0.0; 3];
+        for (c, v) in rgb.iter_mut().enumerate() {
+            if weight_sum != 0.0 {
+                *v = (pixel.rgb_sum[c].load() / weight_sum) as Float;
+            }
+            *v += splat_scale * pixel.rgb_splat[c].load() as Float;
+        }
+        RGB::new(rgb[0], rgb[1], rgb[2])
+    }
+
+    /// The `Float` RGB image of the crop window, with pixel `(0, 0)` at its
+    /// `pMin`.
+    pub fn get_image(&self, splat_scale: Float) -> Image {
+        let p_min = self.pixel_bounds.pMin;
+        let extent = self.pixel_bounds.diagonal();
+        let mut image = Image::new(
+            PixelFormat::Float,
+            Point2i::new(extent.x, extent.y),
+            &["R", "G", "B"],
+        );
+        for p in &self.pixel_bounds {
+            let rgb = self.get_pixel_rgb(p, splat_scale);
+            image.set_channels(
+                Point2i::new(p.x - p_min.x, p.y - p_min.y),
+                &[rgb.r, rgb.g, rgb.b],
+            );
+        }
+        image
+    }
+
+    fn pixel(&self, p: Point2i) -> Option<&Pixel> {
+        let b = &self.pixel_bounds;
+        if !((b.pMin.x..b.pMax.x).contains(&p.x) && (b.pMin.y..b.pMax.y).contains(&p.y)) {
+            return None;
+        }
+        let width = b.pMax.x - b.pMin.x;
+        Some(&self.pixels[((p.y - b.pMin.y) * width + (p.x - b.pMin.x)) as usize])
+    }
+
+    fn clamp(&self, rgb: RGB) -> RGB {
+        let m = rgb.max_component_value();
+        if m > self.max_component_value {
+            rgb * (self.max_component_value / m)
+        } else {
+            rgb
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filters::{BoxFilter, TriangleFilter};
+    use crate::util::vector::Vector2f;
+
+    fn film() -> RGBFilm<BoxFilter> {
+        RGBFilm::new(
+            Point2i::new(8, 6),
+            Bounds2i::from_points(Point2i::new(2, 1), Point2i::new(10, 4)),
+            BoxFilter::default(),
+        )
+    }
+
+    #[test]
+    fn crop_window_is_clipped_to_the_image() {
+        let f = film();
+        assert_eq!(
+            f.pixel_bounds(),
+            Bounds2i::from_points(Point2i::new(2, 1), Point2i::new(8, 4))
+        );
+        assert_eq!(f.get_image(1.0).resolution(), Point2i::new(6, 3));
+    }
+
+    #[test]
+    fn samples_are_a_weighted_average_and_splats_a_scaled_sum() {
+        let f = film().with_max_component_value(10.0);
+        let p = Point2i::new(3, 2);
+        f.add_sample(p, RGB::new(1.0, 2.0, 3.0), 1.0);
+        f.add_sample(p, RGB::new(3.0, 2.0, 1.0), 3.0);
+        assert_eq!(f.get_pixel_rgb(p, 1.0), RGB::new(2.5, 2.0, 1.5));
+        // Clamped to a maximum component of 10.
+        f.add_splat(Point2f::new(3.5, 2.9), RGB::new(40.0, 0.0, 20.0));
+        f.add_splat(Point2f::new(-3.0, 2.0), RGB::new(1.0, 1.0, 1.0));
+        assert_eq!(f.get_pixel_rgb(p, 0.5), RGB::new(7.5, 2.0, 4.0));
+        let image = f.get_image(0.5);
+        assert_eq!(image.get_channels(Point2i::new(1, 1)), [7.5, 2.0, 4.0]);
+        assert_eq!(image.get_channels(Point2i::new(0, 0)), [0.0; 3]);
+    }
+
+    #[test]
+    fn splats_are_spread_by_the_filter() {
+        let f = RGBFilm::new(
+            Point2i::new(8, 6),
+            Bounds2i::from_points(Point2i::new(0, 0), Point2i::new(8, 6)),
+            TriangleFilter::new(Vector2f::new(1.0, 1.0)),
+        );
+        let b = f.sample_bounds();
+        assert_eq!(
+            (b.pMin, b.pMax),
+            (Point2f::new(-0.5, -0.5), Point2f::new(8.5, 6.5))
+        );
+        // Halfway between the centers of pixels (2, 3) and (3, 3).
+        f.add_splat(Point2f::new(3.0, 3.5), RGB::new(1.0, 1.0, 1.0));
+        assert_eq!(
+            f.get_pixel_rgb(Point2i::new(2, 3), 1.0),
+            RGB::new(0.5, 0.5, 0.5)
+        );
+        assert_eq!(
+            f.get_pixel_rgb(Point2i::new(3, 3), 1.0),
+            RGB::new(0.5, 0.5, 0.5)
+        );
+        assert_eq!(f.get_pixel_rgb(Point2i::new(3, 2), 1.0), RGB::default());
+        assert_eq!(f.get_pixel_rgb(Point2i::new(1, 3), 1.0), RGB::default());
+    }
+
+    #[test]
+    fn far_off_splats_are_ignored() {
+        let f = film();
+        for p in [
+            Point2f::new(3.0e9, 2.0),
+            Point2f::new(3.0, -3.0e9),
+            Point2f::new(Float::MAX, Float::MAX),
+            Point2f::new(-Float::MAX, 2.0),
+        ] {
+            f.add_splat(p, RGB::new(1.0, 1.0, 1.0));
+        }
+        for p in &f.pixel_bounds() {
+            assert_eq!(f.get_pixel_rgb(p, 1.0), RGB::default());
+        }
+    }
+
+    #[test]
+    fn concurrent_adds_are_not_lost() {
+        let f = film();
+        std::thread::scope(|s| {
+            for _ in 0..4 {
+                s.spawn(|| {
+                    for p in &f.pixel_bounds() {
+                        for _ in 0..100 {
+                            f.add_sample(p, RGB::new(1.0, 1.0, 1.0), 0.5);
+                            f.add_splat(
+                                Point2f::new(p.x as Float + 0.5, p.y as Float + 0.5),
+                                RGB::new(1.0, 0.0, 0.0),
+                            );
+                        }
+                    }
+                });
+            }
+        });
+        for p in &f.pixel_bounds() {
+            assert_eq!(f.get_pixel_rgb(p, 1.0 / 400.0), RGB::new(2.0, 1.0, 1.0));
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use crate::filters::{Filter, FilterSample};
+use crate::util::Float;
+use crate::util::vector::{Point2f, Vector2f};
+
+/// Weights every sample within the radius equally. Cheap, but lets
+/// frequencies above the pixel rate alias.
+#[derive(Debug, Clone, Copy)]
+pub struct BoxFilter {
+    radius: Vector2f,
+}
+
+impl BoxFilter {
+    pub fn new(radius: Vector2f) -> Self {
+        Self { radius }
+    }
+}
+
+impl Default for BoxFilter {
+    /// A box covering exactly one pixel.
+    fn default() -> Self {
+        Self::new(Vector2f::new(0.5, 0.5))
+    }
+}
+
+impl Filter for BoxFilter {
+    fn radius(&self) -> Vector2f {
+        self.radius
+    }
+
+    fn evaluate(&self, p: Point2f) -> Float {
+        if p.x.abs() <= self.radius.x && p.y.abs() <= self.radius.y {
+            1.0
+        } else {
+            0.0
+        }
+    }
+
+    fn integral(&self) -> Float {
+        4.0 * self.radius.x * self.radius.y
+    }
+
+    fn sample(&self, u: Point2f) -> FilterSample {
+        let p = Point2f::new(
+            (2.0 * u.x - 1.0) * self.radius.x,
+            (2.0 * u.y - 1.0) * self.radius.y,
+        );
+        FilterSample { p, weight: 1.0 }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn samples_cover_the_box() {
+        let f = BoxFilter::new(Vector2f::new(1.0, 0.5));
+        let s = f.sample(Point2f::new(0.0, 0.75));
+        assert_eq!((s.p, s.weight), (Point2f::new(-1.0, 0.25), 1.0));
+        assert_eq!(f.evaluate(s.p), 1.0);
+        assert_eq!(f.evaluate(Point2f::new(0.0, 0.6)), 0.0);
+        assert_eq!(f.integral(), 2.0);
+    }
+}
@@ -0,0 +1,109 @@
+use crate::util::Float;
+use crate::util::bounds::Bounds2f;
+use crate::util::sampling::PiecewiseConstant2D;
+use crate::util::vector::{Point2f, Vector2f};
+
+/// An offset from the pixel center at which to take a sample, and the
+/// factor its radiance estimate should be weighted by.
+#[derive(Debug, Clone, Copy)]
+pub struct FilterSample {
+    pub p: Point2f,
+    pub weight: Float,
+}
+
+/// A pixel reconstruction filter.
+///
+/// Rather than weighting uniformly placed samples by `evaluate`, cameras
+/// place samples according to `sample`, so the filter only contributes a
+/// weight that is constant up to sign for filters that can be sampled
+/// exactly.
+pub trait Filter {
+    /// Half the width and height of the filter's support, centered on the
+    /// origin.
+    fn radius(&self) -> Vector2f;
+
+    fn evaluate(&self, p: Point2f) -> Float;
+
+    /// Integral of `evaluate` over the support.
+    fn integral(&self) -> Float;
+
+    /// An offset distributed like the filter, or its absolute value for
+    /// filters with negative lobes, for `u` in `[0, 1)^2`.
+    fn sample(&self, u: Point2f) -> FilterSample;
+}
+
+/// Lets a film hold a filter chosen at run time, such as one named in a
+/// scene file.
+impl<F: Filter + ?Sized> Filter for Box<F> {
+    fn radius(&self) -> Vector2f {
+        (**self).radius()
+    }
+    fn evaluate(&self, p: Point2f) -> Float {
+        (**self).evaluate(p)
+    }
+    fn integral(&self) -> Float {
+        (**self).integral()
+    }
+    fn sample(&self, u: Point2f) -> FilterSample {
+        (**self).sample(u)
+    }
+}
+
+/// Importance sampling for filters without an analytic inverse, from a
+/// tabulation of the filter at 32 points per unit of radius.
+#[derive(Debug, Clone, Default)]
+pub struct FilterSampler {
+    f: Vec<Float>,
+    nx: usize,
+    distrib: PiecewiseConstant2D,
+}
+
+impl FilterSampler {
+    pub fn new(radius: Vector2f, evaluate: impl Fn(Point2f) -> Float) -> Self {
+        let domain = Bounds2f::from_points(
+            Point2f::new(-radius.x, -radius.y),
+            Point2f::new(radius.x, radius.y),
+        );
+        let nx = ((32.0 * radius.x) as usize).max(1);
+        let ny = ((32.0 * radius.y) as usize).max(1);
+        let f: Vec<Float> = (0..nx * ny)
+            .map(|i| {
+                evaluate(domain.lerp(Point2f::new(
+                    ((i % nx) as Float + 0.5) / nx as Float,
+                    ((i / nx) as Float + 0.5) / ny as Float,
+                )))
+            })
+            .collect();
+        let distrib = PiecewiseConstant2D::new(&f, nx, ny, domain);
+        Self { f, nx, distrib }
+    }
+
+    /// Samples the absolute value of the tabulated filter. The weight
+    /// carries the sign of the filter where the sample landed.
+    pub fn sample(&self, u: Point2f) -> FilterSample {
+        let (p, pdf, offset) = self.distrib.sample(u);
+        let f = self.f[offset.y as usize * self.nx + offset.x as usize];
+        FilterSample { p, weight: f / pdf }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sampled_weights_are_constant_up_to_sign() {
+        let sampler =
+            FilterSampler::new(
+                Vector2f::new(1.0, 0.5),
+                |p| {
+                    if p.x < 0.0 { -1.0 } else { 3.0 }
+                },
+            );
+        // The tabulated |f| integrates to 2 * 2 * 1 over the domain.
+        let left = sampler.sample(Point2f::new(0.1, 0.5));
+        assert!(left.p.x < 0.0 && (left.weight + 4.0).abs() < 1e-4);
+        let right = sampler.sample(Point2f::new(0.9, 0.5));
+        assert!(right.p.x > 0.0 && (right.weight - 4.0).abs() < 1e-4);
+    }
+}
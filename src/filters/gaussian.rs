@@ -0,0 +1,75 @@
+use crate::filters::{Filter, FilterSample, FilterSampler};
+use crate::util::Float;
+use crate::util::math::{gaussian, gaussian_integral};
+use crate::util::vector::{Point2f, Vector2f};
+
+/// A Gaussian, shifted down so it reaches zero at the radius instead of
+/// being cut off with a discontinuity.
+#[derive(Debug, Clone)]
+pub struct GaussianFilter {
+    radius: Vector2f,
+    sigma: Float,
+    exp_x: Float,
+    exp_y: Float,
+    sampler: FilterSampler,
+}
+
+impl GaussianFilter {
+    pub fn new(radius: Vector2f, sigma: Float) -> Self {
+        let mut filter = Self {
+            radius,
+            sigma,
+            exp_x: gaussian(radius.x, 0.0, sigma),
+            exp_y: gaussian(radius.y, 0.0, sigma),
+            sampler: FilterSampler::default(),
+        };
+        filter.sampler = FilterSampler::new(radius, |p| filter.evaluate(p));
+        filter
+    }
+}
+
+impl Default for GaussianFilter {
+    fn default() -> Self {
+        Self::new(Vector2f::new(1.5, 1.5), 0.5)
+    }
+}
+
+impl Filter for GaussianFilter {
+    fn radius(&self) -> Vector2f {
+        self.radius
+    }
+
+    fn evaluate(&self, p: Point2f) -> Float {
+        (gaussian(p.x, 0.0, self.sigma) - self.exp_x).max(0.0)
+            * (gaussian(p.y, 0.0, self.sigma) - self.exp_y).max(0.0)
+    }
+
+    fn integral(&self) -> Float {
+        let (rx, ry) = (self.radius.x, self.radius.y);
+        (gaussian_integral(-rx, rx, 0.0, self.sigma) - 2.0 * rx * self.exp_x)
+            * (gaussian_integral(-ry, ry, 0.0, self.sigma) - 2.0 * ry * self.exp_y)
+    }
+
+    fn sample(&self, u: Point2f) -> FilterSample {
+        self.sampler.sample(u)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vanishes_at_the_radius_and_weights_match_the_integral() {
+        let f = GaussianFilter::default();
+        assert_eq!(f.evaluate(Point2f::new(1.5, 0.0)), 0.0);
+        assert!(f.evaluate(Point2f::new(0.0, 0.0)) > f.evaluate(Point2f::new(0.5, 0.5)));
+        // A positive filter is sampled exactly up to tabulation, so every
+        // weight is close to its integral.
+        for u in [Point2f::new(0.5, 0.5), Point2f::new(0.1, 0.8)] {
+            let s = f.sample(u);
+            assert!(s.p.x.abs() <= 1.5 && s.p.y.abs() <= 1.5);
+            assert!((s.weight - f.integral()).abs() < 0.01 * f.integral());
+        }
+    }
+}
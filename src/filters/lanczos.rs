@@ -0,0 +1,93 @@
+use crate::filters::{Filter, FilterSample, FilterSampler};
+use crate::util::Float;
+use crate::util::math::PI;
+use crate::util::vector::{Point2f, Vector2f};
+
+/// A sinc windowed by a wider sinc, approximating the ideal low-pass filter
+/// within a finite radius. `tau` is the number of sinc lobes the window
+/// spans.
+#[derive(Debug, Clone)]
+pub struct LanczosSincFilter {
+    radius: Vector2f,
+    tau: Float,
+    integral: Float,
+    sampler: FilterSampler,
+}
+
+impl LanczosSincFilter {
+    pub fn new(radius: Vector2f, tau: Float) -> Self {
+        let mut filter = Self {
+            radius,
+            tau,
+            integral: 0.0,
+            sampler: FilterSampler::default(),
+        };
+        filter.sampler = FilterSampler::new(radius, |p| filter.evaluate(p));
+        // No closed form; use the midpoint rule on a 64 x 64 grid.
+        let n = 64;
+        let sum: Float = (0..n * n)
+            .map(|i| {
+                filter.evaluate(Point2f::new(
+                    (2.0 * ((i % n) as Float + 0.5) / n as Float - 1.0) * radius.x,
+                    (2.0 * ((i / n) as Float + 0.5) / n as Float - 1.0) * radius.y,
+                ))
+            })
+            .sum();
+        filter.integral = sum / (n * n) as Float * 4.0 * radius.x * radius.y;
+        filter
+    }
+}
+
+impl Default for LanczosSincFilter {
+    fn default() -> Self {
+        Self::new(Vector2f::new(0.5, 0.5), 3.0)
+    }
+}
+
+fn sinc(x: Float) -> Float {
+    let x = PI * x;
+    if 1.0 - x * x == 1.0 { 1.0 } else { x.sin() / x }
+}
+
+fn windowed_sinc(x: Float, radius: Float, tau: Float) -> Float {
+    if x.abs() > radius {
+        return 0.0;
+    }
+    sinc(x) * sinc(x / tau)
+}
+
+impl Filter for LanczosSincFilter {
+    fn radius(&self) -> Vector2f {
+        self.radius
+    }
+
+    fn evaluate(&self, p: Point2f) -> Float {
+        windowed_sinc(p.x, self.radius.x, self.tau) * windowed_sinc(p.y, self.radius.y, self.tau)
+    }
+
+    fn integral(&self) -> Float {
+        self.integral
+    }
+
+    fn sample(&self, u: Point2f) -> FilterSample {
+        self.sampler.sample(u)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sinc_zeros_fall_on_integers() {
+        assert_eq!(sinc(0.0), 1.0);
+        assert!(sinc(1.0).abs() < 1e-6 && sinc(2.0).abs() < 1e-6);
+        let f = LanczosSincFilter::new(Vector2f::new(2.0, 2.0), 2.0);
+        assert_eq!(f.evaluate(Point2f::new(0.0, 0.0)), 1.0);
+        assert_eq!(f.evaluate(Point2f::new(2.5, 0.0)), 0.0);
+        assert!(f.evaluate(Point2f::new(1.5, 0.0)) < 0.0);
+        // The central lobe dominates.
+        assert!(f.integral() > 0.5 && f.integral() < 1.5);
+        assert!(f.sample(Point2f::new(0.5, 0.5)).weight > 0.0);
+    }
+}
@@ -0,0 +1,90 @@
+use crate::filters::{Filter, FilterSample, FilterSampler};
+use crate::util::Float;
+use crate::util::vector::{Point2f, Vector2f};
+
+/// The Mitchell-Netravali family of cubic filters. Its negative lobes
+/// sharpen edges; `b` and `c` trade ringing against blurring, with
+/// `b + 2c = 1` recommended.
+#[derive(Debug, Clone)]
+pub struct MitchellFilter {
+    radius: Vector2f,
+    b: Float,
+    c: Float,
+    sampler: FilterSampler,
+}
+
+impl MitchellFilter {
+    pub fn new(radius: Vector2f, b: Float, c: Float) -> Self {
+        let mut filter = Self {
+            radius,
+            b,
+            c,
+            sampler: FilterSampler::default(),
+        };
+        filter.sampler = FilterSampler::new(radius, |p| filter.evaluate(p));
+        filter
+    }
+
+    /// The 1D cubic over `[-2, 2]`.
+    fn mitchell_1d(&self, x: Float) -> Float {
+        let (b, c) = (self.b, self.c);
+        let x = x.abs();
+        if x <= 1.0 {
+            ((12.0 - 9.0 * b - 6.0 * c) * x * x * x
+                + (-18.0 + 12.0 * b + 6.0 * c) * x * x
+                + (6.0 - 2.0 * b))
+                / 6.0
+        } else if x <= 2.0 {
+            ((-b - 6.0 * c) * x * x * x
+                + (6.0 * b + 30.0 * c) * x * x
+                + (-12.0 * b - 48.0 * c) * x
+                + (8.0 * b + 24.0 * c))
+                / 6.0
+        } else {
+            0.0
+        }
+    }
+}
+
+impl Default for MitchellFilter {
+    fn default() -> Self {
+        Self::new(Vector2f::new(2.0, 2.0), 1.0 / 3.0, 1.0 / 3.0)
+    }
+}
+
+impl Filter for MitchellFilter {
+    fn radius(&self) -> Vector2f {
+        self.radius
+    }
+
+    fn evaluate(&self, p: Point2f) -> Float {
+        self.mitchell_1d(2.0 * p.x / self.radius.x) * self.mitchell_1d(2.0 * p.y / self.radius.y)
+    }
+
+    /// The cubic integrates to one over `[-2, 2]` for any `b` and `c`.
+    fn integral(&self) -> Float {
+        self.radius.x * self.radius.y / 4.0
+    }
+
+    fn sample(&self, u: Point2f) -> FilterSample {
+        self.sampler.sample(u)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn negative_lobes_give_negative_weights() {
+        let f = MitchellFilter::default();
+        assert!((f.mitchell_1d(0.0) - 8.0 / 9.0).abs() < 1e-6);
+        assert!(f.evaluate(Point2f::new(2.0, 0.0)).abs() < 1e-6);
+        assert!(f.evaluate(Point2f::new(1.5, 0.0)) < 0.0);
+        let center = f.sample(Point2f::new(0.5, 0.5));
+        assert!(center.weight > 0.0);
+        // The marginal puts the lobes at the ends of the sample range.
+        let lobe = f.sample(Point2f::new(0.5, 0.001));
+        assert!(lobe.p.y < -1.0 && lobe.weight < 0.0);
+    }
+}
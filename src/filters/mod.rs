@@ -0,0 +1,12 @@
+mod box_filter;
+mod filter;
+mod gaussian;
+mod lanczos;
+mod mitchell;
+mod triangle;
+pub use box_filter::BoxFilter;
+pub use filter::{Filter, FilterSample, FilterSampler};
+pub use gaussian::GaussianFilter;
+pub use lanczos::LanczosSincFilter;
+pub use mitchell::MitchellFilter;
+pub use triangle::TriangleFilter;
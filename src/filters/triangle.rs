@@ -0,0 +1,61 @@
+use crate::filters::{Filter, FilterSample};
+use crate::util::Float;
+use crate::util::sampling::sample_tent;
+use crate::util::vector::{Point2f, Vector2f};
+
+/// A separable tent falling linearly from the center to zero at the radius.
+#[derive(Debug, Clone, Copy)]
+pub struct TriangleFilter {
+    radius: Vector2f,
+}
+
+impl TriangleFilter {
+    pub fn new(radius: Vector2f) -> Self {
+        Self { radius }
+    }
+}
+
+impl Default for TriangleFilter {
+    fn default() -> Self {
+        Self::new(Vector2f::new(2.0, 2.0))
+    }
+}
+
+impl Filter for TriangleFilter {
+    fn radius(&self) -> Vector2f {
+        self.radius
+    }
+
+    fn evaluate(&self, p: Point2f) -> Float {
+        (self.radius.x - p.x.abs()).max(0.0) * (self.radius.y - p.y.abs()).max(0.0)
+    }
+
+    fn integral(&self) -> Float {
+        self.radius.x * self.radius.x * self.radius.y * self.radius.y
+    }
+
+    fn sample(&self, u: Point2f) -> FilterSample {
+        FilterSample {
+            p: Point2f::new(
+                sample_tent(u.x, self.radius.x),
+                sample_tent(u.y, self.radius.y),
+            ),
+            weight: 1.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn center_sample_hits_the_peak() {
+        let f = TriangleFilter::default();
+        let s = f.sample(Point2f::new(0.5, 0.5));
+        assert_eq!(s.p, Point2f::new(0.0, 0.0));
+        assert_eq!(f.evaluate(s.p), 4.0);
+        assert_eq!(f.evaluate(Point2f::new(1.0, -2.5)), 0.0);
+        assert_eq!(f.integral(), 16.0);
+    }
+}
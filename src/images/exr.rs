@@ -0,0 +1,335 @@
+use std::io::{self, Write};
+
+use crate::images::image::{invalid_data, to_f32};
+use crate::images::{Image, PixelFormat};
+use crate::util::Float;
+use crate::util::half::Half;
+use crate::util::vector::Point2i;
+
+const MAGIC: [u8; 4] = [0x76, 0x2f, 0x31, 0x01];
+
+/// OpenEXR pixel types.
+const UINT: i32 = 0;
+const HALF: i32 = 1;
+const FLOAT: i32 = 2;
+
+impl Image {
+    /// Reads a single-part, scanline OpenEXR file without compression, the
+    /// subset `write_exr` produces. The image is `Half` unless a channel is
+    /// stored with more precision.
+    pub fn read_exr(bytes: &[u8]) -> io::Result<Image> {
+        let mut r = Reader { bytes, pos: 0 };
+        if r.take(4)? != MAGIC {
+            return Err(invalid_data("not an OpenEXR file"));
+        }
+        let version = r.i32()?;
+        if version & 0xff != 2 || version & !0xff != 0 {
+            return Err(invalid_data(
+                "only single-part scanline OpenEXR files are supported",
+            ));
+        }
+
+        let mut channels = Vec::new();
+        let mut compression = None;
+        let mut data_window = None;
+        loop {
+            let name = r.string()?;
+            if name.is_empty() {
+                break;
+            }
+            let _kind = r.string()?;
+            let size = usize::try_from(r.i32()?)
+                .map_err(|_| invalid_data("negative OpenEXR attribute size"))?;
+            let mut value = Reader {
+                bytes: r.take(size)?,
+                pos: 0,
+            };
+            match name {
+                "channels" => loop {
+                    let channel = value.string()?;
+                    if channel.is_empty() {
+                        break;
+                    }
+                    let pixel_type = value.i32()?;
+                    value.take(4)?;
+                    if (value.i32()?, value.i32()?) != (1, 1) {
+                        return Err(invalid_data(
+                            "subsampled OpenEXR channels are not supported",
+                        ));
+                    }
+                    if !(UINT..=FLOAT).contains(&pixel_type) {
+                        return Err(invalid_data("invalid OpenEXR pixel type"));
+                    }
+                    channels.push((channel, pixel_type));
+                },
+                "compression" => compression = Some(value.take(1)?[0]),
+                "dataWindow" => {
+                    data_window = Some([value.i32()?, value.i32()?, value.i32()?, value.i32()?])
+                }
+                _ => {}
+            }
+        }
+        if compression != Some(0) {
+            return Err(invalid_data("compressed OpenEXR files are not supported"));
+        }
+        let [x_min, y_min, x_max, y_max] =
+            data_window.ok_or_else(|| invalid_data("OpenEXR file has no data window"))?;
+        // Widened so that a hostile window cannot overflow.
+        let extent = |min: i32, max: i32| {
+            i32::try_from(max as i64 - min as i64 + 1)
+                .ok()
+                .filter(|&n| n >= 0)
+                .ok_or_else(|| invalid_data("invalid OpenEXR data window"))
+        };
+        let resolution = Point2i::new(extent(x_min, x_max)?, extent(y_min, y_max)?);
+        // Uncompressed pixels take at least this much of the file, which
+        // bounds the allocation below.
+        let pixel_size: i64 = channels
+            .iter()
+            .map(|&(_, t)| if t == HALF { 2 } else { 4 })
+            .sum();
+        let data_size = (resolution.x as i64 * resolution.y as i64).checked_mul(pixel_size);
+        if data_size.is_none_or(|n| n > bytes.len() as i64) {
+            return Err(invalid_data("truncated OpenEXR file"));
+        }
+
+        let format = if channels.iter().all(|&(_, t)| t == HALF) {
+            PixelFormat::Half
+        } else {
+            PixelFormat::Float
+        };
+        let names: Vec<&str> = channels.iter().map(|&(n, _)| n).collect();
+        let mut image = Image::new(format, resolution, &names);
+        let offsets = (0..resolution.y)
+            .map(|_| r.u64())
+            .collect::<io::Result<Vec<_>>>()?;
+        for offset in offsets {
+            let mut chunk = Reader {
+                bytes,
+                pos: offset as usize,
+            };
+            let y = chunk.i32()? as i64 - y_min as i64;
+            chunk.i32()?;
+            if !(0..resolution.y as i64).contains(&y) {
+                return Err(invalid_data("OpenEXR scanline outside the data window"));
+            }
+            let y = y as i32;
+            for (c, &(_, pixel_type)) in channels.iter().enumerate() {
+                for x in 0..resolution.x {
+                    let v = match pixel_type {
+                        UINT => chunk.u32()? as Float,
+                        HALF => {
+                            Half::from_bits(u16::from_le_bytes(chunk.take(2)?.try_into().unwrap()))
+                                .to_f32() as Float
+                        }
+                        _ => f32::from_le_bytes(chunk.take(4)?.try_into().unwrap()) as Float,
+                    };
+                    image.set_channel(Point2i::new(x, y), c, v);
+                }
+            }
+        }
+        Ok(image)
+    }
+
+    /// Writes an uncompressed scanline OpenEXR file. `Float` images are
+    /// stored as 32-bit floats and the others as halfs, with 8-bit values
+    /// decoded to linear.
+    pub fn write_exr(&self, w: &mut impl Write) -> io::Result<()> {
+        let pixel_type = if self.format() == PixelFormat::Float {
+            FLOAT
+        } else {
+            HALF
+        };
+        let value_size = if pixel_type == FLOAT { 4 } else { 2 };
+        // Channels must be listed in alphabetical order.
+        let mut order: Vec<usize> = (0..self.n_channels()).collect();
+        order.sort_by_key(|&c| &self.channel_names()[c]);
+
+        let mut channels = Vec::new();
+        for &c in &order {
+            channels.extend_from_slice(self.channel_names()[c].as_bytes());
+            channels.push(0);
+            channels.extend_from_slice(&pixel_type.to_le_bytes());
+            // Not perceptually linear, then reserved bytes and sampling.
+            channels.extend_from_slice(&[0, 0, 0, 0]);
+            channels.extend_from_slice(&1i32.to_le_bytes());
+            channels.extend_from_slice(&1i32.to_le_bytes());
+        }
+        channels.push(0);
+        let res = self.resolution();
+        let window: Vec<u8> = [0, 0, res.x - 1, res.y - 1]
+            .iter()
+            .flat_map(|v: &i32| v.to_le_bytes())
+            .collect();
+        let one = 1.0f32.to_le_bytes();
+
+        let mut header = MAGIC.to_vec();
+        header.extend_from_slice(&2i32.to_le_bytes());
+        for (name, kind, value) in [
+            ("channels", "chlist", &channels[..]),
+            ("compression", "compression", &[0][..]),
+            ("dataWindow", "box2i", &window),
+            ("displayWindow", "box2i", &window),
+            ("lineOrder", "lineOrder", &[0][..]),
+            ("pixelAspectRatio", "float", &one),
+            ("screenWindowCenter", "v2f", &[0; 8][..]),
+            ("screenWindowWidth", "float", &one),
+        ] {
+            header.extend_from_slice(name.as_bytes());
+            header.push(0);
+            header.extend_from_slice(kind.as_bytes());
+            header.push(0);
+            header.extend_from_slice(&(value.len() as i32).to_le_bytes());
+            header.extend_from_slice(value);
+        }
+        header.push(0);
+
+        // Each scanline is its own chunk: its y, its size and then each
+        // channel's values for the row.
+        let line_size = res.x as usize * self.n_channels() * value_size;
+        let first_chunk = header.len() + 8 * res.y as usize;
+        for y in 0..res.y as usize {
+            let offset = (first_chunk + y * (8 + line_size)) as u64;
+            header.extend_from_slice(&offset.to_le_bytes());
+        }
+        w.write_all(&header)?;
+        let mut line = Vec::with_capacity(8 + line_size);
+        for y in 0..res.y {
+            line.clear();
+            line.extend_from_slice(&y.to_le_bytes());
+            line.extend_from_slice(&(line_size as i32).to_le_bytes());
+            for &c in &order {
+                for x in 0..res.x {
+                    let v = to_f32(self.get_channel(Point2i::new(x, y), c));
+                    if pixel_type == FLOAT {
+                        line.extend_from_slice(&v.to_le_bytes());
+                    } else {
+                        line.extend_from_slice(&Half::from_f32(v).to_bits().to_le_bytes());
+                    }
+                }
+            }
+            w.write_all(&line)?;
+        }
+        Ok(())
+    }
+}
+
+/// Little-endian reads from a byte slice, failing on truncation.
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> io::Result<&'a [u8]> {
+        let v = self
+            .pos
+            .checked_add(n)
+            .and_then(|end| self.bytes.get(self.pos..end))
+            .ok_or_else(|| invalid_data("truncated OpenEXR file"))?;
+        self.pos += n;
+        Ok(v)
+    }
+
+    fn i32(&mut self) -> io::Result<i32> {
+        Ok(i32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u32(&mut self) -> io::Result<u32> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> io::Result<u64> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    /// A null-terminated string.
+    fn string(&mut self) -> io::Result<&'a str> {
+        let len = self.bytes[self.pos.min(self.bytes.len())..]
+            .iter()
+            .position(|&b| b == 0)
+            .ok_or_else(|| invalid_data("truncated OpenEXR file"))?;
+        let s = std::str::from_utf8(self.take(len)?)
+            .map_err(|_| invalid_data("invalid OpenEXR string"))?;
+        self.pos += 1;
+        Ok(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_named_channels() {
+        for format in [PixelFormat::Half, PixelFormat::Float] {
+            let mut image = Image::new(format, Point2i::new(3, 2), &["R", "G", "B", "Z"]);
+            image.set_channels(Point2i::new(0, 0), &[1.5, 0.25, 1000.0, -4.0]);
+            image.set_channels(Point2i::new(2, 1), &[0.0, 2.0, 0.125, 1e-3]);
+            let mut bytes = Vec::new();
+            image.write_exr(&mut bytes).unwrap();
+            let read = Image::read_exr(&bytes).unwrap();
+            assert_eq!(read.format(), format);
+            // Channels come back in alphabetical order.
+            assert_eq!(read.channel_names(), ["B", "G", "R", "Z"]);
+            for p in [Point2i::new(0, 0), Point2i::new(2, 1), Point2i::new(1, 1)] {
+                for name in ["R", "G", "B", "Z"] {
+                    let c = image.channel_index(name).unwrap();
+                    let rc = read.channel_index(name).unwrap();
+                    assert_eq!(read.get_channel(p, rc), image.get_channel(p, c));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn eight_bit_images_are_written_as_linear_halfs() {
+        let mut image = Image::new(PixelFormat::U256, Point2i::new(1, 1), &["Y"]);
+        image.set_channel(Point2i::new(0, 0), 0, 0.5);
+        let mut bytes = Vec::new();
+        image.write_exr(&mut bytes).unwrap();
+        let read = Image::read_exr(&bytes).unwrap();
+        assert_eq!(read.format(), PixelFormat::Half);
+        let v = read.get_channel(Point2i::new(0, 0), 0);
+        assert!((v - image.get_channel(Point2i::new(0, 0), 0)).abs() < 1e-3);
+        assert!(Image::read_exr(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    /// Overwrites the size and value of the header attribute `name`.
+    fn patch_attribute(bytes: &mut [u8], name: &str, value: &[u8]) {
+        let name = [name.as_bytes(), &[0]].concat();
+        let at = bytes.windows(name.len()).position(|w| w == name).unwrap();
+        let kind_len = bytes[at + name.len()..]
+            .iter()
+            .position(|&b| b == 0)
+            .unwrap();
+        let start = at + name.len() + kind_len + 1;
+        bytes[start..start + value.len()].copy_from_slice(value);
+    }
+
+    #[test]
+    fn rejects_hostile_headers() {
+        let image = Image::new(PixelFormat::Half, Point2i::new(2, 2), &["Y"]);
+        let mut bytes = Vec::new();
+        image.write_exr(&mut bytes).unwrap();
+
+        let mut negative = bytes.clone();
+        patch_attribute(&mut negative, "lineOrder", &(-1i32).to_le_bytes());
+        assert!(Image::read_exr(&negative).is_err());
+
+        for window in [
+            [i32::MIN, 0, i32::MAX, 1],
+            [0, 0, 65535, 65535],
+            [0, 0, 0, -5],
+        ] {
+            let mut huge = bytes.clone();
+            let window: Vec<u8> = window.iter().flat_map(|v| v.to_le_bytes()).collect();
+            patch_attribute(
+                &mut huge,
+                "dataWindow",
+                &[&16i32.to_le_bytes()[..], &window].concat(),
+            );
+            assert!(Image::read_exr(&huge).is_err());
+        }
+    }
+}
@@ -0,0 +1,337 @@
+use std::io;
+use std::path::Path;
+
+use crate::util::Float;
+use crate::util::color::{linear_to_srgb, srgb_to_linear};
+use crate::util::half::Half;
+use crate::util::vector::Point2i;
+
+/// How the channels of an image are stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PixelFormat {
+    /// 8 bits per channel, interpreted through a `ColorEncoding`.
+    U256,
+    Half,
+    Float,
+}
+
+/// The transfer curve mapping the 8-bit values of `U256` images to linear
+/// values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorEncoding {
+    Linear,
+    #[default]
+    SRGB,
+}
+
+impl ColorEncoding {
+    pub fn to_linear(self, v: u8) -> Float {
+        let v = v as Float / 255.0;
+        match self {
+            ColorEncoding::Linear => v,
+            ColorEncoding::SRGB => srgb_to_linear(v),
+        }
+    }
+
+    /// The nearest 8-bit encoding of `v`, clamped to `[0, 1]`.
+    pub fn from_linear(self, v: Float) -> u8 {
+        let v = v.clamp(0.0, 1.0);
+        let v = match self {
+            ColorEncoding::Linear => v,
+            ColorEncoding::SRGB => linear_to_srgb(v),
+        };
+        (v * 255.0 + 0.5) as u8
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum PixelData {
+    U256(Vec<u8>),
+    Half(Vec<Half>),
+    Float(Vec<f32>),
+}
+
+/// A rectangular grid of pixels with any number of named channels, stored
+/// interleaved in scanline order from the top.
+///
+/// Channel values are read and written as linear `Float`s whatever the
+/// storage format.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Image {
+    resolution: Point2i,
+    channel_names: Vec<String>,
+    encoding: ColorEncoding,
+    data: PixelData,
+}
+
+impl Image {
+    /// An image of the given size with all channels zero.
+    pub fn new(format: PixelFormat, resolution: Point2i, channel_names: &[&str]) -> Self {
+        assert!(
+            resolution.x >= 0 && resolution.y >= 0,
+            "negative image resolution"
+        );
+        let n = (resolution.x as i64 * resolution.y as i64) as usize * channel_names.len();
+        let data = match format {
+            PixelFormat::U256 => PixelData::U256(vec![0; n]),
+            PixelFormat::Half => PixelData::Half(vec![Half::default(); n]),
+            PixelFormat::Float => PixelData::Float(vec![0.0; n]),
+        };
+        Self {
+            resolution,
+            channel_names: channel_names.iter().map(|s| s.to_string()).collect(),
+            encoding: ColorEncoding::default(),
+            data,
+        }
+    }
+
+    /// Sets the encoding of 8-bit values, which is sRGB by default. It has
+    /// no effect on other formats, other than being kept by conversions.
+    pub fn with_encoding(mut self, encoding: ColorEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    pub fn format(&self) -> PixelFormat {
+        match self.data {
+            PixelData::U256(_) => PixelFormat::U256,
+            PixelData::Half(_) => PixelFormat::Half,
+            PixelData::Float(_) => PixelFormat::Float,
+        }
+    }
+
+    pub fn resolution(&self) -> Point2i {
+        self.resolution
+    }
+
+    pub fn encoding(&self) -> ColorEncoding {
+        self.encoding
+    }
+
+    pub fn channel_names(&self) -> &[String] {
+        &self.channel_names
+    }
+
+    pub fn n_channels(&self) -> usize {
+        self.channel_names.len()
+    }
+
+    pub fn channel_index(&self, name: &str) -> Option<usize> {
+        self.channel_names.iter().position(|n| n == name)
+    }
+
+    pub fn get_channel(&self, p: Point2i, c: usize) -> Float {
+        let i = self.offset(p, c);
+        match &self.data {
+            PixelData::U256(d) => self.encoding.to_linear(d[i]),
+            PixelData::Half(d) => d[i].to_f32() as Float,
+            PixelData::Float(d) => d[i] as Float,
+        }
+    }
+
+    pub fn set_channel(&mut self, p: Point2i, c: usize, v: Float) {
+        let i = self.offset(p, c);
+        match &mut self.data {
+            PixelData::U256(d) => d[i] = self.encoding.from_linear(v),
+            PixelData::Half(d) => d[i] = Half::from_f32(to_f32(v)),
+            PixelData::Float(d) => d[i] = to_f32(v),
+        }
+    }
+
+    /// All channels of pixel `p`, in order.
+    pub fn get_channels(&self, p: Point2i) -> Vec<Float> {
+        (0..self.n_channels())
+            .map(|c| self.get_channel(p, c))
+            .collect()
+    }
+
+    pub fn set_channels(&mut self, p: Point2i, values: &[Float]) {
+        assert_eq!(values.len(), self.n_channels(), "wrong number of channels");
+        for (c, &v) in values.iter().enumerate() {
+            self.set_channel(p, c, v);
+        }
+    }
+
+    /// A copy stored in `format`, keeping the channel names and encoding.
+    pub fn convert_to_format(&self, format: PixelFormat) -> Image {
+        if format == self.format() {
+            return self.clone();
+        }
+        let names: Vec<&str> = self.channel_names.iter().map(String::as_str).collect();
+        let mut image = Image::new(format, self.resolution, &names).with_encoding(self.encoding);
+        let nc = self.n_channels();
+        for i in 0..self.len() {
+            let p = Point2i::new(
+                (i / nc) as i32 % self.resolution.x,
+                (i / nc) as i32 / self.resolution.x,
+            );
+            image.set_channel(p, i % nc, self.get_channel(p, i % nc));
+        }
+        image
+    }
+
+    /// Reads an image, choosing the format from the file extension: `png`,
+    /// `pfm`, `ppm` or `exr`.
+    pub fn read(path: impl AsRef<Path>) -> io::Result<Image> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path)?;
+        match extension(path).as_str() {
+            "png" => Image::read_png(&bytes),
+            "pfm" => Image::read_pfm(&bytes),
+            "ppm" => Image::read_ppm(&bytes),
+            "exr" => Image::read_exr(&bytes),
+            _ => Err(unsupported_extension(path)),
+        }
+    }
+
+    /// Writes the image, choosing the format from the file extension as
+    /// for `read`. Channels are converted as the format requires.
+    pub fn write(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        let mut bytes = Vec::new();
+        match extension(path).as_str() {
+            "png" => self.write_png(&mut bytes)?,
+            "pfm" => self.write_pfm(&mut bytes)?,
+            "ppm" => self.write_ppm(&mut bytes)?,
+            "exr" => self.write_exr(&mut bytes)?,
+            _ => return Err(unsupported_extension(path)),
+        }
+        std::fs::write(path, bytes)
+    }
+
+    /// Builds an image from interleaved 8-bit values.
+    pub(crate) fn from_u256(
+        resolution: Point2i,
+        channel_names: &[&str],
+        encoding: ColorEncoding,
+        data: Vec<u8>,
+    ) -> Self {
+        assert_eq!(
+            data.len(),
+            (resolution.x as i64 * resolution.y as i64) as usize * channel_names.len()
+        );
+        Self {
+            resolution,
+            channel_names: channel_names.iter().map(|s| s.to_string()).collect(),
+            encoding,
+            data: PixelData::U256(data),
+        }
+    }
+
+    /// The raw 8-bit values of a `U256` image.
+    pub(crate) fn u256_data(&self) -> Option<&[u8]> {
+        match &self.data {
+            PixelData::U256(d) => Some(d),
+            _ => None,
+        }
+    }
+
+    /// Number of values stored, pixels times channels.
+    fn len(&self) -> usize {
+        (self.resolution.x * self.resolution.y) as usize * self.n_channels()
+    }
+
+    fn offset(&self, p: Point2i, c: usize) -> usize {
+        assert!(
+            (0..self.resolution.x).contains(&p.x) && (0..self.resolution.y).contains(&p.y),
+            "pixel {p:?} outside of a {:?} image",
+            self.resolution
+        );
+        assert!(c < self.n_channels(), "no channel {c}");
+        (p.y * self.resolution.x + p.x) as usize * self.n_channels() + c
+    }
+}
+
+// A no-op unless `Float` is `f64`.
+#[allow(clippy::unnecessary_cast)]
+pub(crate) fn to_f32(v: Float) -> f32 {
+    v as f32
+}
+
+fn extension(path: &Path) -> String {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_ascii_lowercase()
+}
+
+fn unsupported_extension(path: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("{}: unsupported image file extension", path.display()),
+    )
+}
+
+/// Shorthand for the error returned for malformed files.
+pub(crate) fn invalid_data(msg: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn channels_read_back_linear_in_every_format() {
+        let res = Point2i::new(3, 2);
+        for format in [PixelFormat::U256, PixelFormat::Half, PixelFormat::Float] {
+            let mut image = Image::new(format, res, &["R", "G", "B", "A"]);
+            image.set_channels(Point2i::new(2, 1), &[0.5, 0.25, 1.0, 0.75]);
+            let tolerance = if format == PixelFormat::U256 {
+                0.01
+            } else {
+                1e-3
+            };
+            let values = image.get_channels(Point2i::new(2, 1));
+            for (v, expected) in values.iter().zip([0.5, 0.25, 1.0, 0.75]) {
+                assert!((v - expected).abs() < tolerance, "{format:?}: {v}");
+            }
+            assert_eq!(image.get_channel(Point2i::new(1, 1), 0), 0.0);
+            assert_eq!(image.channel_index("A"), Some(3));
+        }
+    }
+
+    #[test]
+    fn conversion_keeps_values_and_encoding() {
+        let mut image = Image::new(PixelFormat::Float, Point2i::new(2, 2), &["Y"]);
+        image.set_channel(Point2i::new(1, 0), 0, 0.25);
+        image.set_channel(Point2i::new(0, 1), 0, 2.0);
+        let srgb = image.convert_to_format(PixelFormat::U256);
+        // 0.25 encodes to sRGB 137; out of range values clamp.
+        assert_eq!(srgb.u256_data(), Some(&[0, 137, 255, 0][..]));
+        let linear = image
+            .clone()
+            .with_encoding(ColorEncoding::Linear)
+            .convert_to_format(PixelFormat::U256);
+        assert_eq!(linear.u256_data(), Some(&[0, 64, 255, 0][..]));
+        let half = image.convert_to_format(PixelFormat::Half);
+        assert_eq!(half.get_channel(Point2i::new(0, 1), 0), 2.0);
+        assert_eq!(half.convert_to_format(PixelFormat::Float), image);
+    }
+
+    #[test]
+    fn files_are_dispatched_by_extension() {
+        let dir = std::env::temp_dir().join(format!("pbrt-image-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut image = Image::new(PixelFormat::Float, Point2i::new(2, 3), &["R", "G", "B"]);
+        image.set_channels(Point2i::new(1, 2), &[0.5, 1.0, 0.0]);
+        for ext in ["png", "PFM", "ppm", "exr"] {
+            let path = dir.join(format!("image.{ext}"));
+            image.write(&path).unwrap();
+            let read = Image::read(&path).unwrap();
+            assert_eq!(read.resolution(), image.resolution());
+            // OpenEXR reorders the channels, so look them up by name.
+            let v: Vec<Float> = ["R", "G", "B"]
+                .iter()
+                .map(|n| read.get_channel(Point2i::new(1, 2), read.channel_index(n).unwrap()))
+                .collect();
+            assert!(
+                (v[0] - 0.5).abs() < 0.01 && v[1] == 1.0 && v[2] == 0.0,
+                "{ext}"
+            );
+        }
+        let err = image.write(dir.join("image.tga")).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,7 @@
+mod exr;
+mod image;
+mod pfm;
+mod png;
+mod ppm;
+mod zlib;
+pub use image::{ColorEncoding, Image, PixelFormat};
@@ -0,0 +1,105 @@
+use std::io::{self, Write};
+
+use crate::images::image::{invalid_data, to_f32};
+use crate::images::ppm::header_tokens;
+use crate::images::{Image, PixelFormat};
+use crate::util::Float;
+use crate::util::vector::Point2i;
+
+impl Image {
+    /// Reads a Portable FloatMap: `PF` for RGB or `Pf` for a single
+    /// channel, with rows stored bottom to top and the sign of the scale
+    /// giving the byte order.
+    pub fn read_pfm(bytes: &[u8]) -> io::Result<Image> {
+        let (tokens, data_start) = header_tokens(bytes, 4)?;
+        let names: &[&str] = match tokens[0] {
+            "PF" => &["R", "G", "B"],
+            "Pf" => &["Y"],
+            magic => return Err(invalid_data(format!("unsupported PFM type {magic:?}"))),
+        };
+        let invalid = |s: &str| invalid_data(format!("invalid PFM header value {s:?}"));
+        let width: i32 = tokens[1].parse().map_err(|_| invalid(tokens[1]))?;
+        let height: i32 = tokens[2].parse().map_err(|_| invalid(tokens[2]))?;
+        let scale: Float = tokens[3].parse().map_err(|_| invalid(tokens[3]))?;
+        if width < 0 || height < 0 || scale == 0.0 || !scale.is_finite() {
+            return Err(invalid_data("invalid PFM header"));
+        }
+        let nc = names.len();
+        let row_len = width as usize * nc * 4;
+        let data = row_len
+            .checked_mul(height as usize)
+            .and_then(|n| bytes.get(data_start..data_start.checked_add(n)?))
+            .ok_or_else(|| invalid_data("truncated PFM data"))?;
+        let mut image = Image::new(PixelFormat::Float, Point2i::new(width, height), names);
+        for (row, line) in data.chunks(row_len.max(1)).enumerate() {
+            let y = height - 1 - row as i32;
+            for (i, v) in line.chunks(4).enumerate() {
+                let v: [u8; 4] = v.try_into().unwrap();
+                let v = if scale < 0.0 {
+                    f32::from_le_bytes(v)
+                } else {
+                    f32::from_be_bytes(v)
+                };
+                let p = Point2i::new((i / nc) as i32, y);
+                image.set_channel(p, i % nc, v as Float * scale.abs());
+            }
+        }
+        Ok(image)
+    }
+
+    /// Writes a little-endian Portable FloatMap from a 1- or 3-channel
+    /// image.
+    pub fn write_pfm(&self, w: &mut impl Write) -> io::Result<()> {
+        let magic = match self.n_channels() {
+            1 => "Pf",
+            3 => "PF",
+            n => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("PFM needs 1 or 3 channels, not {n}"),
+                ));
+            }
+        };
+        let res = self.resolution();
+        write!(w, "{magic}\n{} {}\n-1\n", res.x, res.y)?;
+        let mut data = Vec::with_capacity((res.x * res.y) as usize * self.n_channels() * 4);
+        for y in (0..res.y).rev() {
+            for x in 0..res.x {
+                for v in self.get_channels(Point2i::new(x, y)) {
+                    data.extend_from_slice(&to_f32(v).to_le_bytes());
+                }
+            }
+        }
+        w.write_all(&data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_and_reads_big_endian() {
+        let mut image = Image::new(PixelFormat::Float, Point2i::new(2, 2), &["R", "G", "B"]);
+        image.set_channels(Point2i::new(0, 0), &[1.5, -2.0, 1e4]);
+        image.set_channels(Point2i::new(1, 1), &[0.25, 0.0, 3.0]);
+        let mut bytes = Vec::new();
+        image.write_pfm(&mut bytes).unwrap();
+        assert!(bytes.starts_with(b"PF\n2 2\n-1\n"));
+        // The bottom row comes first.
+        assert_eq!(&bytes[10 + 12..10 + 16], &0.25f32.to_le_bytes());
+        assert_eq!(Image::read_pfm(&bytes).unwrap(), image);
+
+        let mut big = b"Pf\n1 1\n2.0\n".to_vec();
+        big.extend_from_slice(&1.25f32.to_be_bytes());
+        let gray = Image::read_pfm(&big).unwrap();
+        assert_eq!(gray.get_channel(Point2i::new(0, 0), 0), 2.5);
+    }
+
+    #[test]
+    fn reads_empty_images_and_rejects_oversized_ones() {
+        let empty = Image::read_pfm(b"PF\n0 3\n-1\n").unwrap();
+        assert_eq!(empty.resolution(), Point2i::new(0, 3));
+        assert!(Image::read_pfm(b"PF\n2147483647 2147483647\n-1\n").is_err());
+    }
+}
@@ -0,0 +1,334 @@
+use std::io::{self, Write};
+
+use crate::images::image::invalid_data;
+use crate::images::zlib;
+use crate::images::{ColorEncoding, Image, PixelFormat};
+use crate::util::Float;
+use crate::util::vector::Point2i;
+
+const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+/// Channel names for gray, gray and alpha, RGB and RGBA images.
+fn channel_names(n: usize) -> &'static [&'static str] {
+    match n {
+        1 => &["Y"],
+        2 => &["Y", "A"],
+        3 => &["R", "G", "B"],
+        _ => &["R", "G", "B", "A"],
+    }
+}
+
+impl Image {
+    /// Reads a non-interlaced PNG with 8 or 16 bits per channel, or an
+    /// 8-bit palette. 8-bit images are kept as `U256`; 16-bit ones are
+    /// decoded to linear `Half`. Values are taken as linear if a `gAMA`
+    /// chunk says so and as sRGB otherwise.
+    pub fn read_png(bytes: &[u8]) -> io::Result<Image> {
+        if !bytes.starts_with(&SIGNATURE) {
+            return Err(invalid_data("not a PNG file"));
+        }
+        let mut header = None;
+        let mut palette: &[u8] = &[];
+        let (mut srgb, mut gamma) = (false, None);
+        let mut compressed = Vec::new();
+        let mut pos = SIGNATURE.len();
+        loop {
+            let chunk = bytes
+                .get(pos..pos + 8)
+                .ok_or_else(|| invalid_data("truncated PNG chunk"))?;
+            let len = u32::from_be_bytes(chunk[..4].try_into().unwrap()) as usize;
+            let kind = &bytes[pos + 4..pos + 8];
+            let body = bytes
+                .get(pos + 8..pos + 12 + len)
+                .ok_or_else(|| invalid_data("truncated PNG chunk"))?;
+            let (data, crc) = body.split_at(len);
+            if zlib::crc32(zlib::crc32(0, kind), data)
+                != u32::from_be_bytes(crc.try_into().unwrap())
+            {
+                return Err(invalid_data("PNG chunk checksum mismatch"));
+            }
+            match kind {
+                b"IHDR" => header = Some(data),
+                b"PLTE" => palette = data,
+                b"sRGB" => srgb = true,
+                b"gAMA" => gamma = data.try_into().ok().map(u32::from_be_bytes),
+                b"IDAT" => compressed.extend_from_slice(data),
+                b"IEND" => break,
+                _ => {}
+            }
+            pos += 12 + len;
+        }
+
+        let header = header
+            .filter(|h| h.len() == 13)
+            .ok_or_else(|| invalid_data("missing PNG header"))?;
+        let width = u32::from_be_bytes(header[0..4].try_into().unwrap());
+        let height = u32::from_be_bytes(header[4..8].try_into().unwrap());
+        if !(1..=i32::MAX as u32).contains(&width) || !(1..=i32::MAX as u32).contains(&height) {
+            return Err(invalid_data(format!("invalid PNG size {width}x{height}")));
+        }
+        let (width, height) = (width as usize, height as usize);
+        let (bit_depth, color_type, interlace) = (header[8], header[9], header[12]);
+        if interlace != 0 {
+            return Err(invalid_data("interlaced PNGs are not supported"));
+        }
+        let n_channels = match (color_type, bit_depth) {
+            (0, 8 | 16) => 1,
+            (2, 8 | 16) => 3,
+            (3, 8) => 1,
+            (4, 8 | 16) => 2,
+            (6, 8 | 16) => 4,
+            _ => {
+                return Err(invalid_data(format!(
+                    "unsupported PNG color type {color_type} with bit depth {bit_depth}"
+                )));
+            }
+        };
+        // An sRGB chunk takes precedence over gAMA, whose gamma of 1.0 is
+        // stored times 100000. Other gammas are close enough to sRGB.
+        let encoding = if !srgb && gamma == Some(100_000) {
+            ColorEncoding::Linear
+        } else {
+            ColorEncoding::SRGB
+        };
+        let bytes_per_pixel = n_channels * bit_depth as usize / 8;
+        let raw = zlib::decompress(&compressed)?;
+        let row_len = width
+            .checked_mul(bytes_per_pixel)
+            .ok_or_else(|| invalid_data("PNG image is too large"))?;
+        let samples = unfilter(&raw, row_len, height, bytes_per_pixel)?;
+
+        let resolution = Point2i::new(width as i32, height as i32);
+        if color_type == 3 {
+            let data = samples
+                .iter()
+                .map(|&i| {
+                    palette
+                        .get(3 * i as usize..3 * i as usize + 3)
+                        .ok_or_else(|| invalid_data("PNG palette index out of range"))
+                })
+                .collect::<io::Result<Vec<_>>>()?
+                .concat();
+            return Ok(Image::from_u256(
+                resolution,
+                channel_names(3),
+                encoding,
+                data,
+            ));
+        }
+        let names = channel_names(n_channels);
+        if bit_depth == 8 {
+            return Ok(Image::from_u256(resolution, names, encoding, samples));
+        }
+        let mut image = Image::new(PixelFormat::Half, resolution, names);
+        for (i, v) in samples.chunks(2).enumerate() {
+            let v = u16::from_be_bytes([v[0], v[1]]) as Float / 65535.0;
+            let c = i % n_channels;
+            // Alpha is never gamma encoded.
+            let v = if names[c] == "A" || encoding == ColorEncoding::Linear {
+                v
+            } else {
+                crate::util::color::srgb_to_linear(v)
+            };
+            let p = i / n_channels;
+            image.set_channel(Point2i::new((p % width) as i32, (p / width) as i32), c, v);
+        }
+        Ok(image)
+    }
+
+    /// Writes an 8-bit PNG from an image with 1 to 4 channels, taken as
+    /// gray, gray and alpha, RGB or RGBA. Other formats are encoded with
+    /// the image's color encoding, sRGB by default. The pixel data is
+    /// stored uncompressed.
+    pub fn write_png(&self, w: &mut impl Write) -> io::Result<()> {
+        let color_type = match self.n_channels() {
+            1 => 0,
+            2 => 4,
+            3 => 2,
+            4 => 6,
+            n => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("PNG needs 1 to 4 channels, not {n}"),
+                ));
+            }
+        };
+        let image = self.convert_to_format(PixelFormat::U256);
+        let data = image.u256_data().expect("converted to U256");
+        let res = self.resolution();
+        let row_len = res.x as usize * self.n_channels();
+        let mut raw = Vec::with_capacity((row_len + 1) * res.y as usize);
+        for row in data.chunks(row_len.max(1)).take(res.y as usize) {
+            raw.push(0);
+            raw.extend_from_slice(row);
+        }
+
+        let mut ihdr = Vec::with_capacity(13);
+        ihdr.extend_from_slice(&(res.x as u32).to_be_bytes());
+        ihdr.extend_from_slice(&(res.y as u32).to_be_bytes());
+        ihdr.extend_from_slice(&[8, color_type, 0, 0, 0]);
+        w.write_all(&SIGNATURE)?;
+        write_chunk(w, b"IHDR", &ihdr)?;
+        match self.encoding() {
+            // Perceptual rendering intent.
+            ColorEncoding::SRGB => write_chunk(w, b"sRGB", &[0])?,
+            // A gamma of 1.0, times 100000.
+            ColorEncoding::Linear => write_chunk(w, b"gAMA", &100_000u32.to_be_bytes())?,
+        }
+        write_chunk(w, b"IDAT", &zlib::compress(&raw))?;
+        write_chunk(w, b"IEND", &[])
+    }
+}
+
+fn write_chunk(w: &mut impl Write, kind: &[u8; 4], data: &[u8]) -> io::Result<()> {
+    w.write_all(&(data.len() as u32).to_be_bytes())?;
+    w.write_all(kind)?;
+    w.write_all(data)?;
+    w.write_all(&zlib::crc32(zlib::crc32(0, kind), data).to_be_bytes())
+}
+
+/// Undoes the per-row prediction filters of `height` rows of `row_len`
+/// bytes each.
+fn unfilter(raw: &[u8], row_len: usize, height: usize, bpp: usize) -> io::Result<Vec<u8>> {
+    let too_large = || invalid_data("PNG image is too large");
+    let out_len = row_len.checked_mul(height).ok_or_else(too_large)?;
+    let raw_len = (row_len + 1).checked_mul(height).ok_or_else(too_large)?;
+    if raw.len() < raw_len {
+        return Err(invalid_data("truncated PNG image data"));
+    }
+    let mut out = vec![0u8; out_len];
+    for y in 0..height {
+        let filter = raw[y * (row_len + 1)];
+        let line = &raw[y * (row_len + 1) + 1..(y + 1) * (row_len + 1)];
+        let (done, rest) = out.split_at_mut(y * row_len);
+        let prev = if y > 0 {
+            &done[(y - 1) * row_len..]
+        } else {
+            &[][..]
+        };
+        let cur = &mut rest[..row_len];
+        for i in 0..row_len {
+            let a = if i >= bpp { cur[i - bpp] as i16 } else { 0 };
+            let b = prev.get(i).map_or(0, |&v| v as i16);
+            let c = if i >= bpp {
+                prev.get(i - bpp).map_or(0, |&v| v as i16)
+            } else {
+                0
+            };
+            let predicted = match filter {
+                0 => 0,
+                1 => a,
+                2 => b,
+                3 => (a + b) / 2,
+                4 => {
+                    // Paeth: whichever neighbour is closest to a + b - c.
+                    let p = a + b - c;
+                    let (pa, pb, pc) = ((p - a).abs(), (p - b).abs(), (p - c).abs());
+                    if pa <= pb && pa <= pc {
+                        a
+                    } else if pb <= pc {
+                        b
+                    } else {
+                        c
+                    }
+                }
+                _ => return Err(invalid_data(format!("invalid PNG filter type {filter}"))),
+            };
+            cur[i] = line[i].wrapping_add(predicted as u8);
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_through_srgb() {
+        let mut image = Image::new(
+            PixelFormat::Float,
+            Point2i::new(3, 2),
+            &["R", "G", "B", "A"],
+        );
+        image.set_channels(Point2i::new(0, 0), &[1.0, 0.5, 0.0, 1.0]);
+        image.set_channels(Point2i::new(2, 1), &[0.2, 0.04, 0.9, 0.5]);
+        let mut bytes = Vec::new();
+        image.write_png(&mut bytes).unwrap();
+        let read = Image::read_png(&bytes).unwrap();
+        assert_eq!(read.format(), PixelFormat::U256);
+        assert_eq!(read.channel_names(), image.channel_names());
+        assert_eq!(read, image.convert_to_format(PixelFormat::U256));
+        let mut corrupt = bytes.clone();
+        corrupt[20] ^= 1;
+        assert!(Image::read_png(&corrupt).is_err());
+    }
+
+    #[test]
+    fn round_trips_linear_images() {
+        let mut image = Image::new(PixelFormat::Float, Point2i::new(2, 1), &["Y"])
+            .with_encoding(ColorEncoding::Linear);
+        image.set_channel(Point2i::new(0, 0), 0, 0.25);
+        image.set_channel(Point2i::new(1, 0), 0, 0.75);
+        let mut bytes = Vec::new();
+        image.write_png(&mut bytes).unwrap();
+        let read = Image::read_png(&bytes).unwrap();
+        assert_eq!(read.encoding(), ColorEncoding::Linear);
+        assert_eq!(read, image.convert_to_format(PixelFormat::U256));
+        assert!((read.get_channel(Point2i::new(0, 0), 0) - 0.25).abs() < 1.0 / 255.0);
+    }
+
+    /// A PNG with the given IHDR size and bit depth and no image data.
+    fn empty_png(width: u32, height: u32, bit_depth: u8) -> Vec<u8> {
+        let mut bytes = SIGNATURE.to_vec();
+        let mut ihdr = Vec::new();
+        ihdr.extend_from_slice(&width.to_be_bytes());
+        ihdr.extend_from_slice(&height.to_be_bytes());
+        ihdr.extend_from_slice(&[bit_depth, 0, 0, 0, 0]);
+        write_chunk(&mut bytes, b"IHDR", &ihdr).unwrap();
+        write_chunk(&mut bytes, b"IDAT", &zlib::compress(&[])).unwrap();
+        write_chunk(&mut bytes, b"IEND", &[]).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn rejects_hostile_headers() {
+        for (width, height, bit_depth) in [
+            (0x8000_0000, 1, 16),
+            (u32::MAX, u32::MAX, 8),
+            (0x8000_0000, 0, 8),
+            (0, 1, 8),
+            (1, 0, 8),
+        ] {
+            let err = Image::read_png(&empty_png(width, height, bit_depth)).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData, "{width}x{height}");
+        }
+        // Sizes that fit but whose data is missing.
+        let err = Image::read_png(&empty_png(i32::MAX as u32, i32::MAX as u32, 16)).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn reads_filtered_16_bit_rows() {
+        // 2x2 16-bit gray, first row with the Sub filter, second with Paeth.
+        let rows = [
+            1, 0x80, 0x00, 0x00, 0x10, // 0x8000, 0x8010
+            4, 0x7f, 0xff, 0x81, 0x11, // 0xffff, 0x8010
+        ];
+        let mut bytes = SIGNATURE.to_vec();
+        let mut ihdr = Vec::new();
+        ihdr.extend_from_slice(&2u32.to_be_bytes());
+        ihdr.extend_from_slice(&2u32.to_be_bytes());
+        ihdr.extend_from_slice(&[16, 0, 0, 0, 0]);
+        write_chunk(&mut bytes, b"IHDR", &ihdr).unwrap();
+        write_chunk(&mut bytes, b"IDAT", &zlib::compress(&rows)).unwrap();
+        write_chunk(&mut bytes, b"IEND", &[]).unwrap();
+        let image = Image::read_png(&bytes).unwrap();
+        assert_eq!(image.format(), PixelFormat::Half);
+        assert!((image.get_channel(Point2i::new(0, 1), 0) - 1.0).abs() < 1e-3);
+        let expected = crate::util::color::srgb_to_linear(0x8010 as Float / 65535.0);
+        assert!((image.get_channel(Point2i::new(1, 1), 0) - expected).abs() < 1e-3);
+        let expected = crate::util::color::srgb_to_linear(0x8000 as Float / 65535.0);
+        assert!((image.get_channel(Point2i::new(0, 0), 0) - expected).abs() < 1e-3);
+    }
+}
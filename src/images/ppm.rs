@@ -0,0 +1,153 @@
+use std::io::{self, Write};
+
+use crate::images::image::invalid_data;
+use crate::images::{ColorEncoding, Image, PixelFormat};
+use crate::util::vector::Point2i;
+
+impl Image {
+    /// Reads a binary (`P5`, `P6`) or ASCII (`P2`, `P3`) PGM/PPM image as
+    /// 8-bit sRGB, rescaling other maximum values to 255.
+    pub fn read_ppm(bytes: &[u8]) -> io::Result<Image> {
+        let (tokens, data_start) = header_tokens(bytes, 4)?;
+        let (n_channels, binary) = match tokens[0] {
+            "P2" => (1, false),
+            "P3" => (3, false),
+            "P5" => (1, true),
+            "P6" => (3, true),
+            magic => return Err(invalid_data(format!("unsupported PPM type {magic:?}"))),
+        };
+        let number = |s: &str| {
+            s.parse::<u32>()
+                .map_err(|_| invalid_data(format!("invalid PPM header value {s:?}")))
+        };
+        let (width, height, max_value) =
+            (number(tokens[1])?, number(tokens[2])?, number(tokens[3])?);
+        if !(1..=65535).contains(&max_value) {
+            return Err(invalid_data(format!(
+                "invalid PPM maximum value {max_value}"
+            )));
+        }
+        if width > i32::MAX as u32 || height > i32::MAX as u32 {
+            return Err(invalid_data(format!(
+                "PPM size {width}x{height} is too large"
+            )));
+        }
+        let n = width as usize * height as usize * n_channels;
+        let truncated = || invalid_data("truncated PPM data");
+        let samples: Vec<u32> = if binary {
+            let size = if max_value > 255 { 2 } else { 1 };
+            let data = n
+                .checked_mul(size)
+                .and_then(|len| bytes.get(data_start..data_start.checked_add(len)?))
+                .ok_or_else(truncated)?;
+            data.chunks(size)
+                .map(|s| s.iter().fold(0, |v, &b| (v << 8) | b as u32))
+                .collect()
+        } else {
+            let text = bytes.get(data_start..).ok_or_else(truncated)?;
+            let text =
+                std::str::from_utf8(text).map_err(|_| invalid_data("invalid ASCII PPM data"))?;
+            let samples = text
+                .split_ascii_whitespace()
+                .take(n)
+                .map(number)
+                .collect::<io::Result<Vec<_>>>()?;
+            if samples.len() < n {
+                return Err(truncated());
+            }
+            samples
+        };
+        let data = samples
+            .iter()
+            .map(|&v| ((v.min(max_value) * 255 + max_value / 2) / max_value) as u8)
+            .collect();
+        let names: &[&str] = if n_channels == 1 {
+            &["Y"]
+        } else {
+            &["R", "G", "B"]
+        };
+        Ok(Image::from_u256(
+            Point2i::new(width as i32, height as i32),
+            names,
+            ColorEncoding::SRGB,
+            data,
+        ))
+    }
+
+    /// Writes a binary PPM, or a PGM for single-channel images, encoding
+    /// other formats to 8 bits.
+    pub fn write_ppm(&self, w: &mut impl Write) -> io::Result<()> {
+        let magic = match self.n_channels() {
+            1 => "P5",
+            3 => "P6",
+            n => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("PPM needs 1 or 3 channels, not {n}"),
+                ));
+            }
+        };
+        let res = self.resolution();
+        write!(w, "{magic}\n{} {}\n255\n", res.x, res.y)?;
+        let image = self.convert_to_format(PixelFormat::U256);
+        w.write_all(image.u256_data().expect("converted to U256"))
+    }
+}
+
+/// The first `n` whitespace-separated tokens of a Netpbm-style header,
+/// skipping `#` comments, and the offset of the data after the single
+/// whitespace character that ends the header.
+pub(crate) fn header_tokens(bytes: &[u8], n: usize) -> io::Result<(Vec<&str>, usize)> {
+    let mut tokens = Vec::with_capacity(n);
+    let mut i = 0;
+    while tokens.len() < n {
+        match bytes.get(i) {
+            None => return Err(invalid_data("truncated image header")),
+            Some(b'#') => {
+                while bytes.get(i).is_some_and(|&b| b != b'\n') {
+                    i += 1;
+                }
+            }
+            Some(b) if b.is_ascii_whitespace() => i += 1,
+            Some(_) => {
+                let start = i;
+                while bytes.get(i).is_some_and(|b| !b.is_ascii_whitespace()) {
+                    i += 1;
+                }
+                let token = std::str::from_utf8(&bytes[start..i])
+                    .map_err(|_| invalid_data("invalid image header"))?;
+                tokens.push(token);
+            }
+        }
+    }
+    Ok((tokens, i + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn binary_round_trip_and_ascii_with_comments() {
+        let mut image = Image::new(PixelFormat::U256, Point2i::new(2, 1), &["R", "G", "B"]);
+        image.set_channels(Point2i::new(1, 0), &[1.0, 0.0, 0.5]);
+        let mut bytes = Vec::new();
+        image.write_ppm(&mut bytes).unwrap();
+        assert!(bytes.starts_with(b"P6\n2 1\n255\n"));
+        assert_eq!(Image::read_ppm(&bytes).unwrap(), image);
+
+        let ascii = b"P2 # gray\n# size\n3 1\n# max\n15\n0 15\n 5";
+        let gray = Image::read_ppm(ascii).unwrap();
+        assert_eq!(gray.channel_names(), ["Y"]);
+        assert_eq!(gray.u256_data(), Some(&[0, 255, 85][..]));
+        assert!(Image::read_ppm(b"P6\n2 2\n255\n\x01\x02").is_err());
+        assert!(Image::read_ppm(b"P4\n1 1\n").is_err());
+    }
+
+    #[test]
+    fn rejects_headers_that_end_the_file() {
+        assert!(Image::read_ppm(b"P3 1 1 255").is_err());
+        assert!(Image::read_ppm(b"P6 1 1 255").is_err());
+        assert!(Image::read_ppm(b"P6 4294967295 4294967295 255\n").is_err());
+    }
+}
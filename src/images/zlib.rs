@@ -0,0 +1,353 @@
+//! Just enough of zlib (RFC 1950) and DEFLATE (RFC 1951) for PNG: a complete
+//! decompressor, and a compressor that only emits stored blocks.
+
+use std::io;
+
+use crate::images::image::invalid_data;
+
+const CRC_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut n = 0;
+    while n < 256 {
+        let mut c = n as u32;
+        let mut k = 0;
+        while k < 8 {
+            c = if c & 1 != 0 {
+                0xedb8_8320 ^ (c >> 1)
+            } else {
+                c >> 1
+            };
+            k += 1;
+        }
+        table[n] = c;
+        n += 1;
+    }
+    table
+};
+
+/// The CRC-32 used by PNG chunks, continuing from `crc` (0 to start).
+pub(crate) fn crc32(crc: u32, bytes: &[u8]) -> u32 {
+    let mut c = !crc;
+    for &b in bytes {
+        c = CRC_TABLE[((c ^ b as u32) & 0xff) as usize] ^ (c >> 8);
+    }
+    !c
+}
+
+fn adler32(bytes: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    // 5552 is the most bytes that can be summed before `b` could overflow.
+    for chunk in bytes.chunks(5552) {
+        for &byte in chunk {
+            a += byte as u32;
+            b += a;
+        }
+        a %= 65521;
+        b %= 65521;
+    }
+    (b << 16) | a
+}
+
+/// Wraps `data` in a zlib stream of uncompressed blocks.
+pub(crate) fn compress(data: &[u8]) -> Vec<u8> {
+    let mut out = vec![0x78, 0x01];
+    let mut blocks = data.chunks(0xffff).peekable();
+    if blocks.peek().is_none() {
+        out.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        out.push(blocks.peek().is_none() as u8);
+        let len = block.len() as u16;
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(&(!len).to_le_bytes());
+        out.extend_from_slice(block);
+    }
+    out.extend_from_slice(&adler32(data).to_be_bytes());
+    out
+}
+
+/// Decompresses a zlib stream, checking its checksum.
+pub(crate) fn decompress(data: &[u8]) -> io::Result<Vec<u8>> {
+    let [cmf, flg, ..] = *data else {
+        return Err(invalid_data("truncated zlib stream"));
+    };
+    if cmf & 0x0f != 8 || !(cmf as u16 * 256 + flg as u16).is_multiple_of(31) || flg & 0x20 != 0 {
+        return Err(invalid_data("unsupported zlib stream header"));
+    }
+    let mut input = BitReader {
+        data,
+        pos: 2,
+        bit_buf: 0,
+        bit_count: 0,
+    };
+    let out = inflate(&mut input)?;
+    let pos = input.pos;
+    let checksum = data
+        .get(pos..pos + 4)
+        .ok_or_else(|| invalid_data("missing zlib checksum"))?;
+    if u32::from_be_bytes(checksum.try_into().unwrap()) != adler32(&out) {
+        return Err(invalid_data("zlib checksum mismatch"));
+    }
+    Ok(out)
+}
+
+struct BitReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+    bit_buf: u32,
+    bit_count: u32,
+}
+
+impl BitReader<'_> {
+    /// The next `n` bits, least significant first.
+    fn bits(&mut self, n: u32) -> io::Result<u32> {
+        while self.bit_count < n {
+            let byte = *self
+                .data
+                .get(self.pos)
+                .ok_or_else(|| invalid_data("truncated deflate stream"))?;
+            self.pos += 1;
+            self.bit_buf |= (byte as u32) << self.bit_count;
+            self.bit_count += 8;
+        }
+        let v = self.bit_buf & ((1u32 << n) - 1);
+        self.bit_buf >>= n;
+        self.bit_count -= n;
+        Ok(v)
+    }
+
+    /// Drops the bits left in the current byte.
+    fn align(&mut self) {
+        self.bit_buf = 0;
+        self.bit_count = 0;
+    }
+}
+
+/// A canonical Huffman code, decoded one bit at a time.
+struct Huffman {
+    /// Number of codes of each length.
+    counts: [u16; 16],
+    /// Symbols ordered by code.
+    symbols: Vec<u16>,
+}
+
+impl Huffman {
+    fn new(lengths: &[u8]) -> Self {
+        let mut counts = [0u16; 16];
+        for &l in lengths {
+            counts[l as usize] += 1;
+        }
+        counts[0] = 0;
+        let mut offsets = [0u16; 16];
+        for len in 1..15 {
+            offsets[len + 1] = offsets[len] + counts[len];
+        }
+        let mut symbols = vec![0; lengths.len()];
+        for (symbol, &l) in lengths.iter().enumerate() {
+            if l != 0 {
+                symbols[offsets[l as usize] as usize] = symbol as u16;
+                offsets[l as usize] += 1;
+            }
+        }
+        Self { counts, symbols }
+    }
+
+    fn decode(&self, input: &mut BitReader) -> io::Result<u16> {
+        // Codes of each length are consecutive, starting at `first`.
+        let (mut code, mut first, mut index) = (0i32, 0i32, 0i32);
+        for len in 1..16 {
+            code |= input.bits(1)? as i32;
+            let count = self.counts[len] as i32;
+            if code - first < count {
+                return Ok(self.symbols[(index + code - first) as usize]);
+            }
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+        Err(invalid_data("invalid Huffman code"))
+    }
+}
+
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+const DIST_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DIST_EXTRA: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+/// Order in which code length code lengths are stored.
+const CODE_LENGTH_ORDER: [usize; 19] = [
+    16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15,
+];
+
+fn inflate(input: &mut BitReader) -> io::Result<Vec<u8>> {
+    let mut out = Vec::new();
+    loop {
+        let last = input.bits(1)? == 1;
+        match input.bits(2)? {
+            0 => {
+                input.align();
+                let header = input
+                    .data
+                    .get(input.pos..input.pos + 4)
+                    .ok_or_else(|| invalid_data("truncated stored block"))?;
+                let len = u16::from_le_bytes([header[0], header[1]]);
+                if len != !u16::from_le_bytes([header[2], header[3]]) {
+                    return Err(invalid_data("corrupt stored block length"));
+                }
+                let start = input.pos + 4;
+                let block = input
+                    .data
+                    .get(start..start + len as usize)
+                    .ok_or_else(|| invalid_data("truncated stored block"))?;
+                out.extend_from_slice(block);
+                input.pos = start + len as usize;
+            }
+            1 => {
+                let mut lengths = [0u8; 288];
+                lengths[..144].fill(8);
+                lengths[144..256].fill(9);
+                lengths[256..280].fill(7);
+                lengths[280..].fill(8);
+                let lit = Huffman::new(&lengths);
+                let dist = Huffman::new(&[5; 30]);
+                inflate_block(input, &mut out, &lit, &dist)?;
+            }
+            2 => {
+                let (lit, dist) = read_dynamic_codes(input)?;
+                inflate_block(input, &mut out, &lit, &dist)?;
+            }
+            _ => return Err(invalid_data("invalid deflate block type")),
+        }
+        if last {
+            // The checksum starts at the next byte boundary.
+            input.align();
+            return Ok(out);
+        }
+    }
+}
+
+fn read_dynamic_codes(input: &mut BitReader) -> io::Result<(Huffman, Huffman)> {
+    let n_lit = input.bits(5)? as usize + 257;
+    let n_dist = input.bits(5)? as usize + 1;
+    let n_code = input.bits(4)? as usize + 4;
+    let mut code_lengths = [0u8; 19];
+    for &i in &CODE_LENGTH_ORDER[..n_code] {
+        code_lengths[i] = input.bits(3)? as u8;
+    }
+    let code_lengths = Huffman::new(&code_lengths);
+
+    // Literal/length and distance code lengths share one run-length coded
+    // sequence.
+    let mut lengths = Vec::with_capacity(n_lit + n_dist);
+    while lengths.len() < n_lit + n_dist {
+        let (value, repeat) = match code_lengths.decode(input)? {
+            l @ 0..=15 => (l as u8, 1),
+            16 => {
+                let prev = *lengths
+                    .last()
+                    .ok_or_else(|| invalid_data("repeat with no previous length"))?;
+                (prev, 3 + input.bits(2)?)
+            }
+            17 => (0, 3 + input.bits(3)?),
+            _ => (0, 11 + input.bits(7)?),
+        };
+        lengths.extend(std::iter::repeat_n(value, repeat as usize));
+    }
+    if lengths.len() > n_lit + n_dist {
+        return Err(invalid_data("code lengths overrun"));
+    }
+    Ok((
+        Huffman::new(&lengths[..n_lit]),
+        Huffman::new(&lengths[n_lit..]),
+    ))
+}
+
+fn inflate_block(
+    input: &mut BitReader,
+    out: &mut Vec<u8>,
+    lit: &Huffman,
+    dist: &Huffman,
+) -> io::Result<()> {
+    loop {
+        let symbol = lit.decode(input)? as usize;
+        match symbol {
+            0..=255 => out.push(symbol as u8),
+            256 => return Ok(()),
+            257..=285 => {
+                let i = symbol - 257;
+                let len = LENGTH_BASE[i] as usize + input.bits(LENGTH_EXTRA[i] as u32)? as usize;
+                let d = dist.decode(input)? as usize;
+                if d >= 30 {
+                    return Err(invalid_data("invalid distance symbol"));
+                }
+                let distance = DIST_BASE[d] as usize + input.bits(DIST_EXTRA[d] as u32)? as usize;
+                if distance > out.len() {
+                    return Err(invalid_data("distance before start of output"));
+                }
+                // Copies may overlap the bytes they produce.
+                let start = out.len() - distance;
+                for k in 0..len {
+                    out.push(out[start + k]);
+                }
+            }
+            _ => return Err(invalid_data("invalid literal/length symbol")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stored_streams_round_trip() {
+        let data: Vec<u8> = (0..200_000u32).map(|i| (i * 7 % 251) as u8).collect();
+        assert_eq!(decompress(&compress(&data)).unwrap(), data);
+        assert_eq!(decompress(&compress(&[])).unwrap(), Vec::<u8>::new());
+        let mut corrupt = compress(b"abc");
+        *corrupt.last_mut().unwrap() ^= 1;
+        assert!(decompress(&corrupt).is_err());
+    }
+
+    #[test]
+    fn decodes_huffman_blocks() {
+        // zlib.compress(b"hello hello hello hello"): fixed Huffman codes with
+        // an overlapping back-reference.
+        let fixed = [
+            0x78, 0x9c, 0xcb, 0x48, 0xcd, 0xc9, 0xc9, 0x57, 0xc8, 0x40, 0x27, 0x01, 0x68, 0x03,
+            0x08, 0xb1,
+        ];
+        assert_eq!(decompress(&fixed).unwrap(), b"hello hello hello hello");
+        // zlib.compress(b"0 squared is 0; 1 squared is 1; ... 9 squared is 81; ", 9):
+        // dynamic codes.
+        let dynamic = [
+            0x78, 0xda, 0x55, 0x8e, 0xbb, 0x0d, 0xc0, 0x20, 0x10, 0x43, 0x57, 0xf1, 0x08, 0x7c,
+            0x0f, 0x90, 0xa7, 0x89, 0x94, 0x14, 0x94, 0x09, 0x62, 0xff, 0x5c, 0x69, 0xca, 0xf7,
+            0x64, 0xd9, 0x0e, 0x58, 0xef, 0xbe, 0xbe, 0xe7, 0xc6, 0x5c, 0x08, 0x44, 0x54, 0x8e,
+            0x44, 0x52, 0x2e, 0x44, 0x56, 0x1e, 0x44, 0x39, 0xf2, 0x46, 0x54, 0x15, 0xa9, 0x12,
+            0xa6, 0x22, 0x7b, 0xa2, 0x1d, 0x95, 0xde, 0xd1, 0x55, 0x98, 0x8f, 0x0c, 0x15, 0xdd,
+            0x5f, 0xfc, 0xc3, 0x28, 0x32, 0xcb,
+        ];
+        let expected: String = (0..10)
+            .map(|i| format!("{i} squared is {}; ", i * i))
+            .collect();
+        assert_eq!(decompress(&dynamic).unwrap(), expected.as_bytes());
+    }
+
+    #[test]
+    fn crc_matches_reference() {
+        assert_eq!(crc32(0, b"IEND"), 0xae42_6082);
+        assert_eq!(crc32(crc32(0, b"IE"), b"ND"), 0xae42_6082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+}
@@ -0,0 +1,151 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::cameras::get_camera_sample;
+use crate::samplers::Sampler;
+use crate::scene::Scene;
+use crate::util::bounds::Bounds2i;
+use crate::util::color::RGB;
+use crate::util::rays::Ray;
+use crate::util::vector::Point2i;
+
+/// Side of the square tiles that threads take turns rendering, in pixels.
+const TILE_SIZE: i32 = 16;
+
+/// Shades every surface by how squarely it faces the ray that found it, as
+/// if lit by a light at the eye.
+///
+/// It needs neither lights nor materials, so it can preview the geometry of
+/// any scene. Samples only depend on the pixel and sample index, so an image
+/// does not depend on the number of threads.
+#[derive(Debug, Clone, Copy)]
+pub struct EyeLightIntegrator {
+    n_threads: usize,
+}
+
+impl EyeLightIntegrator {
+    /// Renders with `n_threads` threads, or one per core for `0`.
+    pub fn new(n_threads: usize) -> Self {
+        let n_threads = if n_threads == 0 {
+            std::thread::available_parallelism().map_or(1, |n| n.get())
+        } else {
+            n_threads
+        };
+        Self { n_threads }
+    }
+
+    /// Radiance arriving along `ray`.
+    pub fn li(&self, scene: &Scene, ray: &Ray) -> RGB {
+        let Some((_, si)) = scene
+            .aggregate
+            .intersect_with(ray, |shape, ray| shape.intersect_interaction(ray))
+        else {
+            return RGB::default();
+        };
+        let v = si
+            .interaction
+            .n
+            .abs_dot_vector(&ray.direction().normalize());
+        RGB::new(v, v, v)
+    }
+
+    /// Adds as many samples as the scene's sampler asks for to every pixel
+    /// of the film.
+    pub fn render(&self, scene: &Scene) {
+        let tiles = tiles(scene.film.pixel_bounds());
+        let next = AtomicUsize::new(0);
+        std::thread::scope(|s| {
+            for _ in 0..self.n_threads.min(tiles.len()) {
+                s.spawn(|| {
+                    let mut sampler = scene.sampler.clone();
+                    while let Some(tile) = tiles.get(next.fetch_add(1, Ordering::Relaxed)) {
+                        for p in tile {
+                            self.render_pixel(scene, sampler.as_mut(), p);
+                        }
+                    }
+                });
+            }
+        });
+    }
+
+    fn render_pixel(&self, scene: &Scene, sampler: &mut dyn Sampler, p: Point2i) {
+        for index in 0..sampler.samples_per_pixel() {
+            sampler.start_pixel_sample(p, index, 0);
+            let u_filter = sampler.get_pixel_2d();
+            let u_time = sampler.get_1d();
+            let u_lens = sampler.get_2d();
+            let sample = get_camera_sample(scene.film.filter(), p, u_filter, u_lens, u_time);
+            let l = match scene.camera.generate_ray(&sample) {
+                Some(cr) => self.li(scene, &cr.ray) * cr.weight,
+                None => RGB::default(),
+            };
+            scene.film.add_sample(p, l, sample.filter_weight);
+        }
+    }
+}
+
+/// Splits `bounds` into tiles of at most `TILE_SIZE` pixels on a side, in
+/// scanline order.
+fn tiles(bounds: Bounds2i) -> Vec<Bounds2i> {
+    let mut tiles = Vec::new();
+    for y in (bounds.pMin.y..bounds.pMax.y).step_by(TILE_SIZE as usize) {
+        for x in (bounds.pMin.x..bounds.pMax.x).step_by(TILE_SIZE as usize) {
+            tiles.push(Bounds2i::from_points(
+                Point2i::new(x, y),
+                Point2i::new(
+                    (x + TILE_SIZE).min(bounds.pMax.x),
+                    (y + TILE_SIZE).min(bounds.pMax.y),
+                ),
+            ));
+        }
+    }
+    tiles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scene::parse_str;
+    use std::path::Path;
+
+    const SCENE: &str = r#"
+        LookAt 0 0 -5  0 0 0  0 1 0
+        Camera "perspective" "float fov" 30
+        Film "rgb" "integer xresolution" 40 "integer yresolution" 30
+        PixelFilter "box"
+        Sampler "independent" "integer pixelsamples" 4
+        WorldBegin
+        Shape "sphere" "float radius" 1
+    "#;
+
+    fn render(n_threads: usize) -> Scene {
+        let scene = Scene::new(parse_str(SCENE, Path::new(".")).unwrap()).unwrap();
+        EyeLightIntegrator::new(n_threads).render(&scene);
+        scene
+    }
+
+    #[test]
+    fn sees_the_sphere_in_the_middle_of_the_image() {
+        let scene = render(2);
+        let center = scene.film.get_pixel_rgb(Point2i::new(20, 15), 1.0);
+        assert!(center.g > 0.95, "{center:?}");
+        let corner = scene.film.get_pixel_rgb(Point2i::new(0, 0), 1.0);
+        assert!(corner.is_black(), "{corner:?}");
+    }
+
+    #[test]
+    fn images_do_not_depend_on_the_thread_count() {
+        let (a, b) = (render(1), render(3));
+        for p in &a.film.pixel_bounds() {
+            assert_eq!(a.film.get_pixel_rgb(p, 1.0), b.film.get_pixel_rgb(p, 1.0));
+        }
+    }
+
+    #[test]
+    fn tiles_cover_the_bounds_once() {
+        let bounds = Bounds2i::from_points(Point2i::new(3, 5), Point2i::new(40, 30));
+        let tiles = tiles(bounds);
+        assert_eq!(tiles.len(), 3 * 2);
+        let pixels: usize = tiles.iter().map(|t| t.iter().len()).sum();
+        assert_eq!(pixels, 37 * 25);
+    }
+}
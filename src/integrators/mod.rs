@@ -0,0 +1,2 @@
+mod eye_light;
+pub use eye_light::EyeLightIntegrator;
@@ -0,0 +1,13 @@
+// Bounds and transforms keep the `pMin`/`pMax`/`mInv` names from pbrt.
+#![allow(non_snake_case)]
+
+pub mod accel;
+pub mod cameras;
+pub mod film;
+pub mod filters;
+pub mod images;
+pub mod integrators;
+pub mod samplers;
+pub mod scene;
+pub mod shapes;
+pub mod util;
@@ -0,0 +1,204 @@
+use std::sync::Arc;
+
+use crate::samplers::{RandomizeStrategy, Sampler};
+use crate::util::Float;
+use crate::util::hash::mix_bits;
+use crate::util::low_discrepancy::{
+    DigitPermutation, PRIME_TABLE_SIZE, compute_radical_inverse_permutations,
+    inverse_radical_inverse, owen_scrambled_radical_inverse, radical_inverse,
+    scrambled_radical_inverse,
+};
+use crate::util::vector::{Point2f, Point2i};
+
+/// Largest image extent the first two Halton dimensions are scaled to; the
+/// pattern repeats beyond it.
+const MAX_HALTON_RESOLUTION: i32 = 128;
+
+/// Samples from the Halton sequence, whose dimension `i` is the radical
+/// inverse in the `i`-th prime base.
+///
+/// The first two dimensions are scaled over the image so that each pixel
+/// takes its samples from the points that fall inside it, which keeps the
+/// pixel samples well distributed across the whole image. The remaining
+/// dimensions are randomized by `RandomizeStrategy`.
+#[derive(Debug, Clone)]
+pub struct HaltonSampler {
+    samples_per_pixel: usize,
+    randomize: RandomizeStrategy,
+    /// Shared between the clones for each render thread.
+    digit_permutations: Arc<Vec<DigitPermutation>>,
+    /// Powers of 2 and 3 that cover the image in x and y.
+    base_scales: [u64; 2],
+    base_exponents: [u32; 2],
+    /// Inverses of the other scale modulo each base scale.
+    mult_inverse: [u64; 2],
+    halton_index: u64,
+    dimension: usize,
+}
+
+impl HaltonSampler {
+    pub fn new(
+        samples_per_pixel: usize,
+        full_resolution: Point2i,
+        randomize: RandomizeStrategy,
+        seed: u64,
+    ) -> Self {
+        let digit_permutations = if randomize == RandomizeStrategy::PermuteDigits {
+            compute_radical_inverse_permutations(seed)
+        } else {
+            Vec::new()
+        };
+        let mut base_scales = [1; 2];
+        let mut base_exponents = [0; 2];
+        for (i, base) in [2, 3].into_iter().enumerate() {
+            let extent = full_resolution[i].min(MAX_HALTON_RESOLUTION) as u64;
+            while base_scales[i] < extent {
+                base_scales[i] *= base;
+                base_exponents[i] += 1;
+            }
+        }
+        let mult_inverse = [
+            multiplicative_inverse(base_scales[1], base_scales[0]),
+            multiplicative_inverse(base_scales[0], base_scales[1]),
+        ];
+        Self {
+            samples_per_pixel,
+            randomize,
+            digit_permutations: Arc::new(digit_permutations),
+            base_scales,
+            base_exponents,
+            mult_inverse,
+            halton_index: 0,
+            dimension: 0,
+        }
+    }
+
+    fn sample_dimension(&self, dimension: usize) -> Float {
+        match self.randomize {
+            RandomizeStrategy::None => radical_inverse(dimension, self.halton_index),
+            RandomizeStrategy::PermuteDigits => scrambled_radical_inverse(
+                dimension,
+                self.halton_index,
+                &self.digit_permutations[dimension],
+            ),
+            RandomizeStrategy::FastOwen | RandomizeStrategy::Owen => {
+                owen_scrambled_radical_inverse(
+                    dimension,
+                    self.halton_index,
+                    mix_bits(1 + ((dimension as u64) << 4)) as u32,
+                )
+            }
+        }
+    }
+}
+
+/// `a^-1` modulo `n`, for coprime `a` and `n`.
+fn multiplicative_inverse(a: u64, n: u64) -> u64 {
+    let (_, x, _) = extended_gcd(a as i64, n as i64);
+    x.rem_euclid(n as i64) as u64
+}
+
+fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    if b == 0 {
+        return (a, 1, 0);
+    }
+    let (g, x, y) = extended_gcd(b, a % b);
+    (g, y, x - (a / b) * y)
+}
+
+impl Sampler for HaltonSampler {
+    fn samples_per_pixel(&self) -> usize {
+        self.samples_per_pixel
+    }
+
+    fn start_pixel_sample(&mut self, p: Point2i, sample_index: usize, dimension: usize) {
+        // Find the first index whose scaled first two dimensions fall in
+        // the pixel, by the Chinese remainder theorem; the pixel's later
+        // points are every `sample_stride` after it.
+        let sample_stride = self.base_scales[0] * self.base_scales[1];
+        let mut index = 0;
+        if sample_stride > 1 {
+            for i in 0..2 {
+                let pm = p[i].rem_euclid(MAX_HALTON_RESOLUTION) as u64;
+                let base = [2, 3][i];
+                let offset = inverse_radical_inverse(pm, base, self.base_exponents[i]);
+                index += offset * (sample_stride / self.base_scales[i]) * self.mult_inverse[i];
+            }
+            index %= sample_stride;
+        }
+        self.halton_index = index + sample_index as u64 * sample_stride;
+        self.dimension = dimension.max(2);
+    }
+
+    fn get_1d(&mut self) -> Float {
+        if self.dimension >= PRIME_TABLE_SIZE {
+            self.dimension = 2;
+        }
+        self.dimension += 1;
+        self.sample_dimension(self.dimension - 1)
+    }
+
+    fn get_2d(&mut self) -> Point2f {
+        if self.dimension + 1 >= PRIME_TABLE_SIZE {
+            self.dimension = 2;
+        }
+        let dim = self.dimension;
+        self.dimension += 2;
+        Point2f::new(self.sample_dimension(dim), self.sample_dimension(dim + 1))
+    }
+
+    fn get_pixel_2d(&mut self) -> Point2f {
+        // The digits that picked the pixel are dropped, leaving the offset
+        // within it.
+        Point2f::new(
+            radical_inverse(0, self.halton_index >> self.base_exponents[0]),
+            radical_inverse(1, self.halton_index / self.base_scales[1]),
+        )
+    }
+
+    fn clone_box(&self) -> Box<dyn Sampler> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::samplers::sampler::assert_deterministic;
+
+    #[test]
+    fn samples_are_reproducible() {
+        let res = Point2i::new(20, 10);
+        for randomize in [RandomizeStrategy::PermuteDigits, RandomizeStrategy::Owen] {
+            assert_deterministic(&mut HaltonSampler::new(8, res, randomize, 0));
+        }
+    }
+
+    #[test]
+    fn pixel_samples_come_from_points_inside_the_pixel() {
+        let res = Point2i::new(20, 10);
+        let mut s = HaltonSampler::new(4, res, RandomizeStrategy::None, 0);
+        assert_eq!((s.base_scales, s.base_exponents), ([32, 27], [5, 3]));
+        for p in [Point2i::new(0, 0), Point2i::new(13, 7), Point2i::new(19, 9)] {
+            for i in 0..4 {
+                s.start_pixel_sample(p, i, 0);
+                let index = s.halton_index;
+                let x = radical_inverse(0, index) * s.base_scales[0] as Float;
+                let y = radical_inverse(1, index) * s.base_scales[1] as Float;
+                assert_eq!((x as i32, y as i32), (p.x, p.y), "sample {i}");
+                let u = s.get_pixel_2d();
+                assert!((x.fract() - u.x).abs() < 1e-4 && (y.fract() - u.y).abs() < 1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn dimensions_past_the_pixel_use_the_following_primes() {
+        let mut s = HaltonSampler::new(1, Point2i::new(1, 1), RandomizeStrategy::None, 0);
+        s.start_pixel_sample(Point2i::new(0, 0), 5, 0);
+        assert_eq!(s.get_1d(), radical_inverse(2, 5));
+        let u = s.get_2d();
+        assert_eq!((u.x, u.y), (radical_inverse(3, 5), radical_inverse(4, 5)));
+        assert_eq!(multiplicative_inverse(27, 32), 19);
+    }
+}
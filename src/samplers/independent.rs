@@ -0,0 +1,90 @@
+use crate::samplers::Sampler;
+use crate::util::Float;
+use crate::util::hash::hash;
+use crate::util::rng::Rng;
+use crate::util::vector::{Point2f, Point2i};
+
+/// Uniform random values with no stratification at all. Mostly a baseline
+/// to compare the other samplers against.
+#[derive(Debug, Clone)]
+pub struct IndependentSampler {
+    samples_per_pixel: usize,
+    seed: u64,
+    rng: Rng,
+}
+
+impl IndependentSampler {
+    pub fn new(samples_per_pixel: usize, seed: u64) -> Self {
+        Self {
+            samples_per_pixel,
+            seed,
+            rng: Rng::default(),
+        }
+    }
+}
+
+/// Positions `rng` at the start of a pixel sample: one random sequence per
+/// pixel, with room for 65536 dimensions per sample.
+pub(crate) fn seek_pixel_sample(
+    rng: &mut Rng,
+    p: Point2i,
+    seed: u64,
+    sample_index: usize,
+    dimension: usize,
+) {
+    *rng = Rng::new(hash(&[p.x as u64, p.y as u64, seed]));
+    rng.advance((sample_index as i64) * 65536 + dimension as i64);
+}
+
+impl Sampler for IndependentSampler {
+    fn samples_per_pixel(&self) -> usize {
+        self.samples_per_pixel
+    }
+
+    fn start_pixel_sample(&mut self, p: Point2i, sample_index: usize, dimension: usize) {
+        seek_pixel_sample(&mut self.rng, p, self.seed, sample_index, dimension);
+    }
+
+    fn get_1d(&mut self) -> Float {
+        self.rng.uniform_float()
+    }
+
+    fn get_2d(&mut self) -> Point2f {
+        Point2f::new(self.rng.uniform_float(), self.rng.uniform_float())
+    }
+
+    fn get_pixel_2d(&mut self) -> Point2f {
+        self.get_2d()
+    }
+
+    fn clone_box(&self) -> Box<dyn Sampler> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::samplers::sampler::assert_deterministic;
+
+    #[test]
+    fn samples_are_reproducible() {
+        assert_deterministic(&mut IndependentSampler::new(4, 0));
+        let mut a = IndependentSampler::new(4, 0);
+        let mut b = IndependentSampler::new(4, 1);
+        a.start_pixel_sample(Point2i::new(0, 0), 0, 0);
+        b.start_pixel_sample(Point2i::new(0, 0), 0, 0);
+        assert_ne!(a.get_1d(), b.get_1d());
+    }
+
+    #[test]
+    fn dimensions_continue_one_stream() {
+        let mut s = IndependentSampler::new(1, 0);
+        s.start_pixel_sample(Point2i::new(1, 2), 3, 0);
+        let u = s.get_2d();
+        let third = s.get_1d();
+        s.start_pixel_sample(Point2i::new(1, 2), 3, 1);
+        assert_eq!(s.get_1d(), u.y);
+        assert_eq!(s.get_1d(), third);
+    }
+}
@@ -0,0 +1,14 @@
+mod halton;
+mod independent;
+mod padded_sobol;
+mod sampler;
+mod sobol;
+mod stratified;
+mod zsobol;
+pub use halton::HaltonSampler;
+pub use independent::IndependentSampler;
+pub use padded_sobol::PaddedSobolSampler;
+pub use sampler::{RandomizeStrategy, Sampler};
+pub use sobol::SobolSampler;
+pub use stratified::StratifiedSampler;
+pub use zsobol::ZSobolSampler;
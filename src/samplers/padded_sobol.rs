@@ -0,0 +1,115 @@
+use crate::samplers::{RandomizeStrategy, Sampler};
+use crate::util::Float;
+use crate::util::hash::{hash, permutation_element};
+use crate::util::low_discrepancy::sobol_sample;
+use crate::util::vector::{Point2f, Point2i};
+
+/// Takes every 1D and 2D dimension from the first dimensions of the Sobol
+/// sequence, with the order of the pixel's samples shuffled independently
+/// for each dimension so that dimensions are not correlated.
+///
+/// Each dimension stays well stratified on its own. The number of samples
+/// per pixel should be a power of two.
+#[derive(Debug, Clone)]
+pub struct PaddedSobolSampler {
+    samples_per_pixel: usize,
+    randomize: RandomizeStrategy,
+    seed: u64,
+    pixel: Point2i,
+    sample_index: usize,
+    dimension: usize,
+}
+
+impl PaddedSobolSampler {
+    pub fn new(samples_per_pixel: usize, randomize: RandomizeStrategy, seed: u64) -> Self {
+        Self {
+            samples_per_pixel,
+            randomize,
+            seed,
+            pixel: Point2i::default(),
+            sample_index: 0,
+            dimension: 0,
+        }
+    }
+
+    /// The hash for the next dimension and the shuffled index of the current
+    /// sample in it.
+    fn next_dimension(&mut self, n_dimensions: usize) -> (u64, u64) {
+        let h = hash(&[
+            self.pixel.x as u64,
+            self.pixel.y as u64,
+            self.dimension as u64,
+            self.seed,
+        ]);
+        self.dimension += n_dimensions;
+        let index = permutation_element(
+            self.sample_index as u32,
+            self.samples_per_pixel as u32,
+            h as u32,
+        );
+        (h, index as u64)
+    }
+
+    fn sample_dimension(&self, dimension: usize, index: u64, seed: u32) -> Float {
+        sobol_sample(index, dimension, self.randomize.sobol_scrambler(seed))
+    }
+}
+
+impl Sampler for PaddedSobolSampler {
+    fn samples_per_pixel(&self) -> usize {
+        self.samples_per_pixel
+    }
+
+    fn start_pixel_sample(&mut self, p: Point2i, sample_index: usize, dimension: usize) {
+        self.pixel = p;
+        self.sample_index = sample_index;
+        self.dimension = dimension;
+    }
+
+    fn get_1d(&mut self) -> Float {
+        let (h, index) = self.next_dimension(1);
+        self.sample_dimension(0, index, (h >> 32) as u32)
+    }
+
+    fn get_2d(&mut self) -> Point2f {
+        let (h, index) = self.next_dimension(2);
+        Point2f::new(
+            self.sample_dimension(0, index, h as u32),
+            self.sample_dimension(1, index, (h >> 32) as u32),
+        )
+    }
+
+    fn get_pixel_2d(&mut self) -> Point2f {
+        self.get_2d()
+    }
+
+    fn clone_box(&self) -> Box<dyn Sampler> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::samplers::sampler::{assert_deterministic, assert_stratified_2d};
+
+    #[test]
+    fn every_dimension_is_stratified() {
+        for randomize in [
+            RandomizeStrategy::None,
+            RandomizeStrategy::PermuteDigits,
+            RandomizeStrategy::FastOwen,
+            RandomizeStrategy::Owen,
+        ] {
+            assert_deterministic(&mut PaddedSobolSampler::new(16, randomize, 0));
+            assert_stratified_2d(&mut PaddedSobolSampler::new(16, randomize, 5), 4);
+        }
+        let mut s = PaddedSobolSampler::new(8, RandomizeStrategy::FastOwen, 0);
+        let mut strata = [0; 8];
+        for i in 0..8 {
+            s.start_pixel_sample(Point2i::new(2, 9), i, 4);
+            strata[(s.get_1d() * 8.0) as usize] += 1;
+        }
+        assert_eq!(strata, [1; 8]);
+    }
+}
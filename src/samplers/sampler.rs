@@ -0,0 +1,108 @@
+use crate::util::Float;
+use crate::util::low_discrepancy::SobolScrambler;
+use crate::util::vector::{Point2f, Point2i};
+
+/// How the samplers built on low-discrepancy sequences randomize them, so
+/// that pixels and dimensions get decorrelated points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RandomizeStrategy {
+    /// The unrandomized sequence. Every pixel sees the same points.
+    None,
+    /// A random permutation of the digit values, or a random XOR for base
+    /// 2.
+    PermuteDigits,
+    /// A hash-based approximation of Owen scrambling. The same as `Owen`
+    /// for the Halton sampler.
+    #[default]
+    FastOwen,
+    /// Owen scrambling, where each digit's permutation depends on the
+    /// digits before it.
+    Owen,
+}
+
+impl RandomizeStrategy {
+    /// The scrambler that applies this strategy to base-2 samples.
+    pub(crate) fn sobol_scrambler(self, seed: u32) -> SobolScrambler {
+        match self {
+            RandomizeStrategy::None => SobolScrambler::None,
+            RandomizeStrategy::PermuteDigits => SobolScrambler::BinaryPermute(seed),
+            RandomizeStrategy::FastOwen => SobolScrambler::FastOwen(seed),
+            RandomizeStrategy::Owen => SobolScrambler::Owen(seed),
+        }
+    }
+}
+
+/// A source of sample values in `[0, 1)` for each dimension of the
+/// integrand of a pixel sample.
+///
+/// After `start_pixel_sample` the `get_*` calls consume consecutive
+/// dimensions. The values only depend on the pixel, the sample index and
+/// the dimension, never on what was sampled before, so any sample can be
+/// regenerated on its own and images do not depend on how pixels are
+/// spread over threads.
+pub trait Sampler: Send + Sync {
+    fn samples_per_pixel(&self) -> usize;
+
+    /// Moves to sample `sample_index` of pixel `p`, starting at
+    /// `dimension`.
+    fn start_pixel_sample(&mut self, p: Point2i, sample_index: usize, dimension: usize);
+
+    fn get_1d(&mut self) -> Float;
+
+    fn get_2d(&mut self) -> Point2f;
+
+    /// The sample for the position within the pixel, which some samplers
+    /// take from dimensions of their own so that it is well distributed
+    /// over the image.
+    fn get_pixel_2d(&mut self) -> Point2f;
+
+    /// A copy with its own state, for another render thread.
+    fn clone_box(&self) -> Box<dyn Sampler>;
+}
+
+impl Clone for Box<dyn Sampler> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+/// Checks that a sampler gives the same values when a sample is
+/// regenerated, whatever was sampled in between.
+#[cfg(test)]
+pub(crate) fn assert_deterministic(sampler: &mut dyn Sampler) {
+    let draw = |s: &mut dyn Sampler, p: Point2i, index: usize| {
+        s.start_pixel_sample(p, index, 0);
+        let pixel = s.get_pixel_2d();
+        let (a, b, c) = (s.get_1d(), s.get_2d(), s.get_1d());
+        [pixel.x, pixel.y, a, b.x, b.y, c]
+    };
+    let first = draw(sampler, Point2i::new(3, 5), 2);
+    for v in first {
+        assert!((0.0..1.0).contains(&v), "{v}");
+    }
+    draw(sampler, Point2i::new(4, 5), 2);
+    draw(sampler, Point2i::new(3, 5), 1);
+    assert_eq!(draw(sampler, Point2i::new(3, 5), 2), first);
+    assert_eq!(
+        draw(&mut *sampler.clone_box(), Point2i::new(3, 5), 2),
+        first
+    );
+    assert_ne!(draw(sampler, Point2i::new(3, 5), 3), first);
+    assert_ne!(draw(sampler, Point2i::new(4, 5), 2), first);
+}
+
+/// Checks that the samples of a pixel cover `[0, 1)^2` with one point per
+/// cell of an `n` by `n` grid in the first 2D dimension after the pixel
+/// sample.
+#[cfg(test)]
+pub(crate) fn assert_stratified_2d(sampler: &mut dyn Sampler, n: usize) {
+    assert_eq!(sampler.samples_per_pixel(), n * n);
+    let mut cells = vec![0; n * n];
+    for i in 0..n * n {
+        sampler.start_pixel_sample(Point2i::new(7, 2), i, 0);
+        sampler.get_pixel_2d();
+        let u = sampler.get_2d();
+        cells[(u.y * n as Float) as usize * n + (u.x * n as Float) as usize] += 1;
+    }
+    assert!(cells.iter().all(|&c| c == 1), "{cells:?}");
+}
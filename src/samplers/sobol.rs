@@ -0,0 +1,224 @@
+use crate::samplers::{RandomizeStrategy, Sampler};
+use crate::util::Float;
+use crate::util::hash::hash;
+use crate::util::low_discrepancy::{
+    N_SOBOL_DIMENSIONS, SOBOL_MATRIX_SIZE, SobolScrambler, sobol_matrix_column, sobol_sample,
+};
+use crate::util::math::ONE_MINUS_EPSILON;
+use crate::util::vector::{Point2f, Point2i};
+
+/// Samples from a single Sobol sequence spread over the whole image: the
+/// first two dimensions, scaled to the image, decide which pixel a point
+/// belongs to, and each pixel takes the points that fall inside it.
+///
+/// The sequence is a (0, 2)-sequence in those dimensions, so pixel samples
+/// are well stratified over the image as well as within each pixel. The
+/// number of samples per pixel should be a power of two.
+#[derive(Debug, Clone)]
+pub struct SobolSampler {
+    samples_per_pixel: usize,
+    randomize: RandomizeStrategy,
+    seed: u64,
+    /// Resolution the first two dimensions are scaled to: a power of two
+    /// at least as large as the image.
+    scale: u32,
+    log2_scale: u32,
+    /// Rows of the inverse of the matrix that maps the low `2 *
+    /// log2_scale` bits of an index to the pixel it falls in.
+    index_from_pixel: Vec<u64>,
+    pixel: Point2i,
+    sobol_index: u64,
+    dimension: usize,
+}
+
+impl SobolSampler {
+    pub fn new(
+        samples_per_pixel: usize,
+        full_resolution: Point2i,
+        randomize: RandomizeStrategy,
+        seed: u64,
+    ) -> Self {
+        let scale = (full_resolution.x.max(full_resolution.y).max(1) as u32).next_power_of_two();
+        let log2_scale = scale.trailing_zeros();
+        assert!(
+            2 * log2_scale as usize <= SOBOL_MATRIX_SIZE.min(32),
+            "image too large for the Sobol sampler"
+        );
+        Self {
+            samples_per_pixel,
+            randomize,
+            seed,
+            scale,
+            log2_scale,
+            index_from_pixel: invert_gf2(&pixel_from_index_columns(log2_scale)),
+            pixel: Point2i::default(),
+            sobol_index: 0,
+            dimension: 0,
+        }
+    }
+
+    /// Index of sample `frame` among the points that fall in `p`.
+    fn interval_to_index(&self, frame: u64, p: Point2i) -> u64 {
+        let m = self.log2_scale;
+        if m == 0 {
+            return frame;
+        }
+        let mask = (1u64 << m) - 1;
+        // Where the frame's high index bits alone would put the point, to
+        // be cancelled out by the low bits.
+        let delta = pixel_bits(frame << (2 * m), m);
+        let target = (((p.x as u64 & mask) << m) | (p.y as u64 & mask)) ^ delta;
+        let low = self
+            .index_from_pixel
+            .iter()
+            .enumerate()
+            .fold(0, |low, (bit, row)| {
+                low | (((row & target).count_ones() as u64 & 1) << bit)
+            });
+        (frame << (2 * m)) | low
+    }
+
+    fn sample_dimension(&self, dimension: usize) -> Float {
+        let scrambler = self
+            .randomize
+            .sobol_scrambler(hash(&[dimension as u64, self.seed]) as u32);
+        sobol_sample(self.sobol_index, dimension, scrambler)
+    }
+}
+
+/// The top `m` bits of the first two dimensions of the unscrambled Sobol
+/// point `index`, packed with x above y.
+fn pixel_bits(mut index: u64, m: u32) -> u64 {
+    let (mut x, mut y) = (0u32, 0u32);
+    let mut c = 0;
+    while index != 0 {
+        if index & 1 != 0 {
+            x ^= sobol_matrix_column(0, c);
+            y ^= sobol_matrix_column(1, c);
+        }
+        index >>= 1;
+        c += 1;
+    }
+    ((x >> (32 - m)) as u64) << m | (y >> (32 - m)) as u64
+}
+
+/// The columns of the GF(2) matrix from the low `2 * m` index bits to
+/// `pixel_bits`.
+fn pixel_from_index_columns(m: u32) -> Vec<u64> {
+    (0..2 * m).map(|c| pixel_bits(1 << c, m)).collect()
+}
+
+/// Inverts the square GF(2) matrix with the given columns, returning the
+/// rows of the inverse. The first two Sobol dimensions form a (0,
+/// 2)-sequence, so the matrix is always invertible.
+fn invert_gf2(columns: &[u64]) -> Vec<u64> {
+    let n = columns.len();
+    // Row `r` of the matrix, with the identity alongside in the high bits.
+    let mut rows: Vec<u64> = (0..n)
+        .map(|r| {
+            let row = columns
+                .iter()
+                .enumerate()
+                .fold(0, |row, (c, col)| row | (((col >> r) & 1) << c));
+            row | (1 << (n + r))
+        })
+        .collect();
+    for c in 0..n {
+        let pivot = (c..n)
+            .find(|&r| (rows[r] >> c) & 1 != 0)
+            .expect("singular Sobol pixel matrix");
+        rows.swap(c, pivot);
+        for r in 0..n {
+            if r != c && (rows[r] >> c) & 1 != 0 {
+                rows[r] ^= rows[c];
+            }
+        }
+    }
+    rows.into_iter().map(|row| row >> n).collect()
+}
+
+impl Sampler for SobolSampler {
+    fn samples_per_pixel(&self) -> usize {
+        self.samples_per_pixel
+    }
+
+    fn start_pixel_sample(&mut self, p: Point2i, sample_index: usize, dimension: usize) {
+        self.pixel = p;
+        self.dimension = dimension.max(2);
+        self.sobol_index = self.interval_to_index(sample_index as u64, p);
+    }
+
+    fn get_1d(&mut self) -> Float {
+        if self.dimension >= N_SOBOL_DIMENSIONS {
+            self.dimension = 2;
+        }
+        self.dimension += 1;
+        self.sample_dimension(self.dimension - 1)
+    }
+
+    fn get_2d(&mut self) -> Point2f {
+        if self.dimension + 1 >= N_SOBOL_DIMENSIONS {
+            self.dimension = 2;
+        }
+        let dim = self.dimension;
+        self.dimension += 2;
+        Point2f::new(self.sample_dimension(dim), self.sample_dimension(dim + 1))
+    }
+
+    fn get_pixel_2d(&mut self) -> Point2f {
+        let offset = |dim: usize, pixel: i32| {
+            let u = sobol_sample(self.sobol_index, dim, SobolScrambler::None);
+            (u * self.scale as Float - pixel as Float).clamp(0.0, ONE_MINUS_EPSILON)
+        };
+        Point2f::new(offset(0, self.pixel.x), offset(1, self.pixel.y))
+    }
+
+    fn clone_box(&self) -> Box<dyn Sampler> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::samplers::sampler::assert_deterministic;
+
+    #[test]
+    fn samples_are_reproducible() {
+        let mut s = SobolSampler::new(16, Point2i::new(30, 20), RandomizeStrategy::Owen, 0);
+        assert_deterministic(&mut s);
+    }
+
+    #[test]
+    fn pixel_samples_come_from_points_inside_the_pixel() {
+        let s = SobolSampler::new(8, Point2i::new(30, 20), RandomizeStrategy::None, 0);
+        assert_eq!(s.scale, 32);
+        let mut s = s;
+        for p in [
+            Point2i::new(0, 0),
+            Point2i::new(17, 3),
+            Point2i::new(29, 19),
+        ] {
+            let mut indices = Vec::new();
+            for i in 0..8 {
+                s.start_pixel_sample(p, i, 0);
+                let x = sobol_sample(s.sobol_index, 0, SobolScrambler::None) * 32.0;
+                let y = sobol_sample(s.sobol_index, 1, SobolScrambler::None) * 32.0;
+                assert_eq!((x as i32, y as i32), (p.x, p.y), "sample {i}");
+                let u = s.get_pixel_2d();
+                assert!((x.fract() - u.x).abs() < 1e-4 && (y.fract() - u.y).abs() < 1e-4);
+                indices.push(s.sobol_index);
+            }
+            indices.dedup();
+            assert_eq!(indices.len(), 8);
+        }
+    }
+
+    #[test]
+    fn tiny_images_use_the_sequence_directly() {
+        let mut s = SobolSampler::new(4, Point2i::new(1, 1), RandomizeStrategy::None, 0);
+        s.start_pixel_sample(Point2i::new(0, 0), 3, 0);
+        assert_eq!(s.sobol_index, 3);
+        assert_eq!(s.get_1d(), sobol_sample(3, 2, SobolScrambler::None));
+    }
+}
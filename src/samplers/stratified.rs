@@ -0,0 +1,123 @@
+use crate::samplers::Sampler;
+use crate::samplers::independent::seek_pixel_sample;
+use crate::util::Float;
+use crate::util::hash::{hash, permutation_element};
+use crate::util::rng::Rng;
+use crate::util::vector::{Point2f, Point2i};
+
+/// Divides each dimension of a pixel's samples into as many strata as there
+/// are samples, or a 2D dimension into an `x_samples` by `y_samples` grid,
+/// and places one sample in each, jittered within its stratum.
+///
+/// Which sample gets which stratum is a random permutation that differs per
+/// pixel and dimension, so the dimensions are not correlated.
+#[derive(Debug, Clone)]
+pub struct StratifiedSampler {
+    x_samples: usize,
+    y_samples: usize,
+    jitter: bool,
+    seed: u64,
+    rng: Rng,
+    pixel: Point2i,
+    sample_index: usize,
+    dimension: usize,
+}
+
+impl StratifiedSampler {
+    /// Without `jitter` samples sit at the centers of their strata.
+    pub fn new(x_samples: usize, y_samples: usize, jitter: bool, seed: u64) -> Self {
+        assert!(x_samples > 0 && y_samples > 0);
+        Self {
+            x_samples,
+            y_samples,
+            jitter,
+            seed,
+            rng: Rng::default(),
+            pixel: Point2i::default(),
+            sample_index: 0,
+            dimension: 0,
+        }
+    }
+
+    /// The stratum of the current sample in the next dimension.
+    fn next_stratum(&mut self, n_dimensions: usize) -> u32 {
+        let h = hash(&[
+            self.pixel.x as u64,
+            self.pixel.y as u64,
+            self.dimension as u64,
+            self.seed,
+        ]);
+        self.dimension += n_dimensions;
+        permutation_element(
+            self.sample_index as u32,
+            self.samples_per_pixel() as u32,
+            h as u32,
+        )
+    }
+
+    fn offset(&mut self) -> Float {
+        if self.jitter {
+            self.rng.uniform_float()
+        } else {
+            0.5
+        }
+    }
+}
+
+impl Sampler for StratifiedSampler {
+    fn samples_per_pixel(&self) -> usize {
+        self.x_samples * self.y_samples
+    }
+
+    fn start_pixel_sample(&mut self, p: Point2i, sample_index: usize, dimension: usize) {
+        self.pixel = p;
+        self.sample_index = sample_index;
+        self.dimension = dimension;
+        seek_pixel_sample(&mut self.rng, p, self.seed, sample_index, dimension);
+    }
+
+    fn get_1d(&mut self) -> Float {
+        let stratum = self.next_stratum(1);
+        (stratum as Float + self.offset()) / self.samples_per_pixel() as Float
+    }
+
+    fn get_2d(&mut self) -> Point2f {
+        let stratum = self.next_stratum(2) as usize;
+        let (x, y) = (stratum % self.x_samples, stratum / self.x_samples);
+        let (dx, dy) = (self.offset(), self.offset());
+        Point2f::new(
+            (x as Float + dx) / self.x_samples as Float,
+            (y as Float + dy) / self.y_samples as Float,
+        )
+    }
+
+    fn get_pixel_2d(&mut self) -> Point2f {
+        self.get_2d()
+    }
+
+    fn clone_box(&self) -> Box<dyn Sampler> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::samplers::sampler::{assert_deterministic, assert_stratified_2d};
+
+    #[test]
+    fn one_sample_per_stratum() {
+        assert_deterministic(&mut StratifiedSampler::new(4, 2, true, 0));
+        assert_stratified_2d(&mut StratifiedSampler::new(4, 4, true, 3), 4);
+        let mut s = StratifiedSampler::new(3, 2, false, 0);
+        let mut strata = [0; 6];
+        for i in 0..6 {
+            s.start_pixel_sample(Point2i::new(1, 1), i, 0);
+            let u = s.get_1d();
+            strata[(u * 6.0) as usize] += 1;
+            // Unjittered samples sit at the stratum centers.
+            assert!(((u * 6.0).fract() - 0.5).abs() < 1e-5);
+        }
+        assert_eq!(strata, [1; 6]);
+    }
+}
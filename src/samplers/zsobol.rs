@@ -0,0 +1,185 @@
+use crate::samplers::{RandomizeStrategy, Sampler};
+use crate::util::Float;
+use crate::util::hash::{hash, mix_bits};
+use crate::util::low_discrepancy::{encode_morton2, sobol_sample};
+use crate::util::vector::{Point2f, Point2i};
+
+/// The 24 permutations of four elements.
+const PERMUTATIONS: [[u8; 4]; 24] = [
+    [0, 1, 2, 3],
+    [0, 1, 3, 2],
+    [0, 2, 1, 3],
+    [0, 2, 3, 1],
+    [0, 3, 2, 1],
+    [0, 3, 1, 2],
+    [1, 0, 2, 3],
+    [1, 0, 3, 2],
+    [1, 2, 0, 3],
+    [1, 2, 3, 0],
+    [1, 3, 2, 0],
+    [1, 3, 0, 2],
+    [2, 1, 0, 3],
+    [2, 1, 3, 0],
+    [2, 0, 1, 3],
+    [2, 0, 3, 1],
+    [2, 3, 0, 1],
+    [2, 3, 1, 0],
+    [3, 1, 2, 0],
+    [3, 1, 0, 2],
+    [3, 2, 1, 0],
+    [3, 2, 0, 1],
+    [3, 0, 2, 1],
+    [3, 0, 1, 2],
+];
+
+/// Ahmed and Wonka's screen-space blue-noise Sobol sampler: pixels are
+/// visited along a Morton curve and given consecutive blocks of the first
+/// two Sobol dimensions, with the base-4 digits of the index randomly
+/// permuted per dimension.
+///
+/// Like `PaddedSobolSampler` each pixel's samples are well stratified, but
+/// neighbouring pixels also get complementary points, so that the error
+/// is distributed as blue noise. The number of samples per pixel should be
+/// a power of two.
+#[derive(Debug, Clone)]
+pub struct ZSobolSampler {
+    randomize: RandomizeStrategy,
+    seed: u64,
+    log2_samples_per_pixel: u32,
+    n_base4_digits: u32,
+    morton_index: u64,
+    dimension: usize,
+}
+
+impl ZSobolSampler {
+    pub fn new(
+        samples_per_pixel: usize,
+        full_resolution: Point2i,
+        randomize: RandomizeStrategy,
+        seed: u64,
+    ) -> Self {
+        assert!(
+            samples_per_pixel.is_power_of_two(),
+            "ZSobolSampler needs a power of two samples per pixel"
+        );
+        let log2_samples_per_pixel = samples_per_pixel.trailing_zeros();
+        let res = (full_resolution.x.max(full_resolution.y).max(1) as u32).next_power_of_two();
+        // Each level of the Morton curve adds a base-4 digit.
+        let log2_resolution = res.trailing_zeros();
+        Self {
+            randomize,
+            seed,
+            log2_samples_per_pixel,
+            n_base4_digits: log2_resolution + log2_samples_per_pixel.div_ceil(2),
+            morton_index: 0,
+            dimension: 0,
+        }
+    }
+
+    /// The Sobol index of the current sample in the current dimension: the
+    /// Morton index with each base-4 digit permuted depending on the
+    /// digits above it. An odd power of two samples leaves a base-2 digit at
+    /// the bottom, which is flipped or not instead.
+    fn sample_index(&self) -> u64 {
+        let odd = self.log2_samples_per_pixel & 1 == 1;
+        let dimension_bits = 0x5555_5555u64.wrapping_mul(self.dimension as u64);
+        let mut sample_index = 0;
+        let last_digit = if odd { 1 } else { 0 };
+        for i in (last_digit..self.n_base4_digits).rev() {
+            let digit_shift = 2 * i - last_digit;
+            let digit = (self.morton_index >> digit_shift) & 3;
+            let higher_digits = self.morton_index >> (digit_shift + 2);
+            let p = (mix_bits(higher_digits ^ dimension_bits) >> 24) % 24;
+            let digit = PERMUTATIONS[p as usize][digit as usize] as u64;
+            sample_index |= digit << digit_shift;
+        }
+        if odd {
+            let digit = self.morton_index & 1;
+            sample_index |= digit ^ (mix_bits((self.morton_index >> 1) ^ dimension_bits) & 1);
+        }
+        sample_index
+    }
+}
+
+impl Sampler for ZSobolSampler {
+    fn samples_per_pixel(&self) -> usize {
+        1 << self.log2_samples_per_pixel
+    }
+
+    fn start_pixel_sample(&mut self, p: Point2i, sample_index: usize, dimension: usize) {
+        self.dimension = dimension;
+        self.morton_index = (encode_morton2(p.x as u32, p.y as u32) << self.log2_samples_per_pixel)
+            | sample_index as u64;
+    }
+
+    fn get_1d(&mut self) -> Float {
+        let index = self.sample_index();
+        self.dimension += 1;
+        let h = hash(&[self.dimension as u64, self.seed]);
+        sobol_sample(index, 0, self.randomize.sobol_scrambler(h as u32))
+    }
+
+    fn get_2d(&mut self) -> Point2f {
+        let index = self.sample_index();
+        self.dimension += 2;
+        let h = hash(&[self.dimension as u64, self.seed]);
+        Point2f::new(
+            sobol_sample(index, 0, self.randomize.sobol_scrambler(h as u32)),
+            sobol_sample(index, 1, self.randomize.sobol_scrambler((h >> 32) as u32)),
+        )
+    }
+
+    fn get_pixel_2d(&mut self) -> Point2f {
+        self.get_2d()
+    }
+
+    fn clone_box(&self) -> Box<dyn Sampler> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::samplers::sampler::{assert_deterministic, assert_stratified_2d};
+
+    #[test]
+    fn pixels_get_whole_blocks_of_the_sequence() {
+        let res = Point2i::new(16, 16);
+        for randomize in [RandomizeStrategy::None, RandomizeStrategy::FastOwen] {
+            assert_deterministic(&mut ZSobolSampler::new(16, res, randomize, 0));
+            assert_stratified_2d(&mut ZSobolSampler::new(16, res, randomize, 2), 4);
+        }
+        for spp in [1, 2, 8, 16] {
+            let mut s = ZSobolSampler::new(spp, res, RandomizeStrategy::None, 0);
+            let mut indices: Vec<u64> = (0..spp)
+                .map(|i| {
+                    s.start_pixel_sample(Point2i::new(5, 11), i, 3);
+                    s.sample_index()
+                })
+                .collect();
+            indices.sort_unstable();
+            let first = indices[0];
+            assert_eq!(first % spp as u64, 0);
+            assert!(
+                indices.iter().copied().eq(first..first + spp as u64),
+                "{spp}"
+            );
+        }
+    }
+
+    #[test]
+    fn every_index_is_used_once_across_the_image() {
+        let res = Point2i::new(4, 4);
+        let mut s = ZSobolSampler::new(2, res, RandomizeStrategy::None, 0);
+        let mut indices = Vec::new();
+        for p in &crate::util::bounds::Bounds2i::from_points(Point2i::new(0, 0), res) {
+            for i in 0..2 {
+                s.start_pixel_sample(p, i, 1);
+                indices.push(s.sample_index());
+            }
+        }
+        indices.sort_unstable();
+        assert!(indices.into_iter().eq(0..32));
+    }
+}
@@ -0,0 +1,592 @@
+use std::io;
+use std::sync::Arc;
+
+use crate::accel::{Bvh, SplitMethod};
+use crate::cameras::{
+    Camera, CameraBase, OrthographicCamera, PerspectiveCamera, RealisticCamera, SphericalCamera,
+    SphericalMapping,
+};
+use crate::film::RGBFilm;
+use crate::filters::{
+    BoxFilter, Filter, GaussianFilter, LanczosSincFilter, MitchellFilter, TriangleFilter,
+};
+use crate::samplers::{
+    HaltonSampler, IndependentSampler, PaddedSobolSampler, RandomizeStrategy, Sampler,
+    SobolSampler, StratifiedSampler, ZSobolSampler,
+};
+use crate::scene::params::{ParamValues, Parameter};
+use crate::scene::parser::{CameraEntity, SceneDescription, SceneEntity, ShapeEntity};
+use crate::shapes::{Cone, Cylinder, Disk, Hyperboloid, Paraboloid, Shape, Sphere, TriangleMesh};
+use crate::util::Float;
+use crate::util::bounds::{Bounds2f, Bounds2i};
+use crate::util::math::AnimatedTransform;
+use crate::util::vector::{Point2f, Point2i, Point3f, Vector2f};
+
+const DEFAULT_SAMPLER: &str = "zsobol";
+
+/// A filter chosen by the scene file.
+pub type SceneFilter = Box<dyn Filter + Send + Sync>;
+
+/// Everything needed to render a scene, created from its description.
+pub struct Scene {
+    pub camera: Box<dyn Camera + Send + Sync>,
+    pub film: RGBFilm<SceneFilter>,
+    pub aggregate: Bvh<Shape>,
+    pub sampler: Box<dyn Sampler>,
+    /// Where the film asks for the image to be written.
+    pub filename: String,
+    /// Problems that did not stop the scene from being created, such as
+    /// unsupported directives or parameters nothing used.
+    pub warnings: Vec<String>,
+}
+
+impl Scene {
+    /// Creates the camera, film and shapes of `desc`, using pbrt's defaults
+    /// for whatever it leaves out.
+    pub fn new(desc: SceneDescription) -> io::Result<Self> {
+        let mut warnings = desc.warnings;
+        let filter_entity = desc
+            .filter
+            .unwrap_or_else(|| SceneEntity::unspecified("gaussian"));
+        let filter = make_filter(&filter_entity)?;
+        let film_entity = desc.film.unwrap_or_else(|| SceneEntity::unspecified("rgb"));
+        let (film, filename) = make_film(&film_entity, filter)?;
+        let camera_entity = desc.camera.unwrap_or_else(|| CameraEntity {
+            entity: SceneEntity::unspecified("perspective"),
+            world_from_camera: Default::default(),
+        });
+        let camera = make_camera(&camera_entity, &film_entity, film.full_resolution())?;
+        let sampler_entity = desc
+            .sampler
+            .unwrap_or_else(|| SceneEntity::unspecified(DEFAULT_SAMPLER));
+        let sampler = make_sampler(&sampler_entity, film.full_resolution(), &mut warnings)?;
+
+        let mut shapes = Vec::new();
+        let mut used_shapes = Vec::new();
+        for entity in &desc.shapes {
+            match make_shapes(entity)? {
+                Some(s) => {
+                    shapes.extend(s);
+                    used_shapes.push(&entity.entity);
+                }
+                None => warnings.push(format!(
+                    "{}: shape \"{}\" is not supported yet and was ignored",
+                    entity.entity.location, entity.entity.name
+                )),
+            }
+        }
+
+        let entities = [
+            &filter_entity,
+            &film_entity,
+            &camera_entity.entity,
+            &sampler_entity,
+        ];
+        for entity in entities.into_iter().chain(used_shapes) {
+            warnings.extend(entity.params.unused().map(|p| {
+                format!(
+                    "{}: parameter \"{}\" of \"{}\" was not used",
+                    entity.location, p.name, entity.name
+                )
+            }));
+        }
+
+        Ok(Self {
+            camera,
+            film,
+            aggregate: Bvh::new(shapes, 4, SplitMethod::Sah),
+            sampler,
+            filename,
+            warnings,
+        })
+    }
+}
+
+impl SceneDescription {
+    /// Makes the sampler take `spp` samples per pixel, whatever the scene
+    /// file asks for.
+    pub fn override_pixel_samples(&mut self, spp: usize) {
+        let sampler = self
+            .sampler
+            .get_or_insert_with(|| SceneEntity::unspecified(DEFAULT_SAMPLER));
+        sampler.params.push(Parameter::new(
+            "integer",
+            "pixelsamples",
+            ParamValues::Numbers(vec![spp as f64]),
+            0,
+        ));
+    }
+}
+
+fn invalid(entity: &SceneEntity, msg: impl std::fmt::Display) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("{}: {msg}", entity.location),
+    )
+}
+
+fn make_filter(entity: &SceneEntity) -> io::Result<SceneFilter> {
+    let p = &entity.params;
+    let radius = |default: Float| {
+        Vector2f::new(
+            p.get_float("xradius", default),
+            p.get_float("yradius", default),
+        )
+    };
+    Ok(match entity.name.as_str() {
+        "box" => Box::new(BoxFilter::new(radius(0.5))),
+        "gaussian" => Box::new(GaussianFilter::new(radius(1.5), p.get_float("sigma", 0.5))),
+        "mitchell" => Box::new(MitchellFilter::new(
+            radius(2.0),
+            p.get_float("B", 1.0 / 3.0),
+            p.get_float("C", 1.0 / 3.0),
+        )),
+        "sinc" => Box::new(LanczosSincFilter::new(radius(4.0), p.get_float("tau", 3.0))),
+        "triangle" => Box::new(TriangleFilter::new(radius(2.0))),
+        name => return Err(invalid(entity, format!("unknown filter \"{name}\""))),
+    })
+}
+
+fn make_sampler(
+    entity: &SceneEntity,
+    full_resolution: Point2i,
+    warnings: &mut Vec<String>,
+) -> io::Result<Box<dyn Sampler>> {
+    let p = &entity.params;
+    let seed = p.get_int("seed", 0) as u64;
+    let mut spp = p.get_int("pixelsamples", 16);
+    if spp <= 0 {
+        return Err(invalid(entity, "\"pixelsamples\" must be positive"));
+    }
+    let power_of_two = matches!(entity.name.as_str(), "sobol" | "paddedsobol" | "zsobol");
+    if power_of_two && !(spp as u32).is_power_of_two() {
+        spp = (spp as u32).next_power_of_two() as i32;
+        warnings.push(format!(
+            "{}: \"{}\" sampler needs a power of two samples per pixel, using {spp}",
+            entity.location, entity.name
+        ));
+    }
+    let spp = spp as usize;
+    let randomize = |default: &str| match p.get_string("randomization", default).as_str() {
+        "none" => Ok(RandomizeStrategy::None),
+        "permutedigits" => Ok(RandomizeStrategy::PermuteDigits),
+        "fastowen" => Ok(RandomizeStrategy::FastOwen),
+        "owen" => Ok(RandomizeStrategy::Owen),
+        r => Err(invalid(entity, format!("unknown randomization \"{r}\""))),
+    };
+    Ok(match entity.name.as_str() {
+        "independent" => Box::new(IndependentSampler::new(spp, seed)),
+        "stratified" => {
+            // An explicit sample count is split as evenly as possible.
+            let (x, y) = match p.get_ints("pixelsamples").first() {
+                Some(_) => {
+                    let mut y = (spp as f64).sqrt() as usize;
+                    while !spp.is_multiple_of(y) {
+                        y -= 1;
+                    }
+                    (spp / y, y)
+                }
+                None => (
+                    p.get_int("xsamples", 4).max(1) as usize,
+                    p.get_int("ysamples", 4).max(1) as usize,
+                ),
+            };
+            Box::new(StratifiedSampler::new(
+                x,
+                y,
+                p.get_bool("jitter", true),
+                seed,
+            ))
+        }
+        "halton" => Box::new(HaltonSampler::new(
+            spp,
+            full_resolution,
+            randomize("permutedigits")?,
+            seed,
+        )),
+        "sobol" => Box::new(SobolSampler::new(
+            spp,
+            full_resolution,
+            randomize("fastowen")?,
+            seed,
+        )),
+        "paddedsobol" => Box::new(PaddedSobolSampler::new(spp, randomize("fastowen")?, seed)),
+        "zsobol" => Box::new(ZSobolSampler::new(
+            spp,
+            full_resolution,
+            randomize("fastowen")?,
+            seed,
+        )),
+        name => return Err(invalid(entity, format!("unknown sampler \"{name}\""))),
+    })
+}
+
+fn make_film(
+    entity: &SceneEntity,
+    filter: SceneFilter,
+) -> io::Result<(RGBFilm<SceneFilter>, String)> {
+    if !matches!(entity.name.as_str(), "rgb" | "gbuffer") {
+        return Err(invalid(entity, format!("unknown film \"{}\"", entity.name)));
+    }
+    let p = &entity.params;
+    let resolution = Point2i::new(
+        p.get_int("xresolution", 1280),
+        p.get_int("yresolution", 720),
+    );
+    if resolution.x <= 0 || resolution.y <= 0 {
+        return Err(invalid(entity, "film resolution must be positive"));
+    }
+    let pixel_bounds = match (
+        &p.get_ints("pixelbounds")[..],
+        &p.get_floats("cropwindow")[..],
+    ) {
+        (&[x0, x1, y0, y1], _) => Bounds2i::from_points(Point2i::new(x0, y0), Point2i::new(x1, y1)),
+        (_, &[x0, x1, y0, y1]) => {
+            // Round so that adjacent crop windows share no pixels.
+            let corner = |x: Float, y: Float| {
+                Point2i::new(
+                    (resolution.x as Float * x.clamp(0.0, 1.0)).ceil() as i32,
+                    (resolution.y as Float * y.clamp(0.0, 1.0)).ceil() as i32,
+                )
+            };
+            Bounds2i::from_points(corner(x0, y0), corner(x1, y1))
+        }
+        _ => Bounds2i::from_points(Point2i::new(0, 0), resolution),
+    };
+    let full = Bounds2i::from_points(Point2i::new(0, 0), resolution);
+    if Bounds2i::intersect(pixel_bounds, full).is_degenerate() {
+        return Err(invalid(entity, "crop window does not overlap the image"));
+    }
+    let film = RGBFilm::new(resolution, pixel_bounds, filter)
+        .with_max_component_value(p.get_float("maxcomponentvalue", Float::INFINITY));
+    let filename = p.get_string("filename", "pbrt.exr");
+    Ok((film, filename))
+}
+
+fn make_camera(
+    camera: &CameraEntity,
+    film: &SceneEntity,
+    resolution: Point2i,
+) -> io::Result<Box<dyn Camera + Send + Sync>> {
+    let entity = &camera.entity;
+    let p = &entity.params;
+    let base = CameraBase::new(
+        AnimatedTransform::fixed(camera.world_from_camera),
+        p.get_float("shutteropen", 0.0),
+        p.get_float("shutterclose", 1.0),
+        resolution,
+    );
+    let screen_window = match p.get_floats("screenwindow")[..] {
+        [x0, x1, y0, y1] => Some(Bounds2f::from_points(
+            Point2f::new(x0, y0),
+            Point2f::new(x1, y1),
+        )),
+        _ => None,
+    };
+    let lens_radius = p.get_float("lensradius", 0.0);
+    let focal_distance = p.get_float("focaldistance", 1e6);
+    Ok(match entity.name.as_str() {
+        "perspective" => Box::new(PerspectiveCamera::new(
+            base,
+            p.get_float("fov", 90.0),
+            screen_window,
+            lens_radius,
+            focal_distance,
+        )),
+        "orthographic" => Box::new(OrthographicCamera::new(
+            base,
+            screen_window,
+            lens_radius,
+            focal_distance,
+        )),
+        "spherical" => {
+            let mapping = match p.get_string("mapping", "equalarea").as_str() {
+                "equalarea" => SphericalMapping::EqualArea,
+                "equirectangular" => SphericalMapping::EquiRectangular,
+                m => {
+                    return Err(invalid(
+                        entity,
+                        format!("unknown spherical mapping \"{m}\""),
+                    ));
+                }
+            };
+            Box::new(SphericalCamera::new(base, mapping))
+        }
+        "realistic" => {
+            let lens_file = p.get_string("lensfile", "");
+            if lens_file.is_empty() {
+                return Err(invalid(entity, "realistic camera needs a \"lensfile\""));
+            }
+            let elements = RealisticCamera::load_lens_file(entity.directory.join(&lens_file))
+                .map_err(|e| invalid(entity, format!("{lens_file}: {e}")))?;
+            // The film diagonal and aperture are given in millimeters.
+            let diagonal = film.params.get_float("diagonal", 35.0) * 0.001;
+            RealisticCamera::new(
+                base,
+                elements,
+                p.get_float("aperturediameter", 1.0) * 0.001,
+                p.get_float("focusdistance", 10.0),
+                diagonal,
+            )
+            .map(|c| Box::new(c) as Box<dyn Camera + Send + Sync>)
+            .ok_or_else(|| invalid(entity, "lens system cannot focus at \"focusdistance\""))?
+        }
+        name => return Err(invalid(entity, format!("unknown camera \"{name}\""))),
+    })
+}
+
+/// The shapes for one `Shape` directive, or `None` for kinds that are
+/// parsed but not supported yet.
+fn make_shapes(shape: &ShapeEntity) -> io::Result<Option<Vec<Shape>>> {
+    let entity = &shape.entity;
+    let p = &entity.params;
+    let (t, ro) = (shape.render_from_object, shape.reverse_orientation);
+    let phi_max = p.get_float("phimax", 360.0);
+    // Constructors return `None` for shapes that would have no surface.
+    let single = |s: Option<Shape>| {
+        s.map(|s| Some(vec![s])).ok_or_else(|| {
+            invalid(
+                entity,
+                format!(
+                    "{} has an empty radius, height, z or phi range",
+                    entity.name
+                ),
+            )
+        })
+    };
+    match entity.name.as_str() {
+        "sphere" => {
+            let radius = p.get_float("radius", 1.0);
+            let z_min = p.get_float("zmin", -radius);
+            let z_max = p.get_float("zmax", radius);
+            single(Sphere::new(t, ro, radius, z_min, z_max, phi_max).map(Shape::from))
+        }
+        "disk" => single(
+            Disk::new(
+                t,
+                ro,
+                p.get_float("height", 0.0),
+                p.get_float("radius", 1.0),
+                p.get_float("innerradius", 0.0),
+                phi_max,
+            )
+            .map(Shape::from),
+        ),
+        "cylinder" => single(
+            Cylinder::new(
+                t,
+                ro,
+                p.get_float("radius", 1.0),
+                p.get_float("zmin", -1.0),
+                p.get_float("zmax", 1.0),
+                phi_max,
+            )
+            .map(Shape::from),
+        ),
+        "cone" => single(
+            Cone::new(
+                t,
+                ro,
+                p.get_float("height", 1.0),
+                p.get_float("radius", 1.0),
+                phi_max,
+            )
+            .map(Shape::from),
+        ),
+        "paraboloid" => single(
+            Paraboloid::new(
+                t,
+                ro,
+                p.get_float("radius", 1.0),
+                p.get_float("zmin", 0.0),
+                p.get_float("zmax", 1.0),
+                phi_max,
+            )
+            .map(Shape::from),
+        ),
+        "hyperboloid" => {
+            let p1 = p.get_point3("p1", Point3f::new(0.0, 0.0, 0.0));
+            let p2 = p.get_point3("p2", Point3f::new(1.0, 1.0, 1.0));
+            single(Hyperboloid::new(t, ro, p1, p2, phi_max).map(Shape::from))
+        }
+        "trianglemesh" => make_triangle_mesh(shape).map(Some),
+        _ => Ok(None),
+    }
+}
+
+fn make_triangle_mesh(shape: &ShapeEntity) -> io::Result<Vec<Shape>> {
+    let entity = &shape.entity;
+    let p = &entity.params;
+    let positions = p.get_point3s("P");
+    let mut indices = p.get_ints("indices");
+    // A lone triangle may leave out its indices.
+    if indices.is_empty() && positions.len() == 3 {
+        indices = vec![0, 1, 2];
+    }
+    if indices.is_empty() || !indices.len().is_multiple_of(3) {
+        return Err(invalid(entity, "\"indices\" must list whole triangles"));
+    }
+    if indices
+        .iter()
+        .any(|&i| i < 0 || i as usize >= positions.len())
+    {
+        return Err(invalid(entity, "\"indices\" refers past the end of \"P\""));
+    }
+    let per_vertex = |len: usize, name: &str| {
+        if len == 0 || len == positions.len() {
+            Ok(len > 0)
+        } else {
+            Err(invalid(
+                entity,
+                format!("\"{name}\" needs one value per vertex"),
+            ))
+        }
+    };
+    let normals = p.get_normals("N");
+    let uvs = p.get_point2s("uv");
+    let tangents = p.get_vector3s("S");
+    let has_normals = per_vertex(normals.len(), "N")?;
+    let has_uvs = per_vertex(uvs.len(), "uv")?;
+    let has_tangents = per_vertex(tangents.len(), "S")?;
+
+    let mesh = Arc::new(TriangleMesh::new(
+        &shape.render_from_object,
+        shape.reverse_orientation,
+        indices
+            .chunks_exact(3)
+            .map(|v| [v[0] as usize, v[1] as usize, v[2] as usize])
+            .collect(),
+        positions,
+        has_normals.then_some(normals),
+        has_uvs.then_some(uvs),
+        has_tangents.then_some(tangents),
+    ));
+    Ok(TriangleMesh::triangles(&mesh)
+        .into_iter()
+        .map(Shape::from)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scene::parse_str;
+    use std::path::Path;
+
+    fn build(source: &str) -> io::Result<Scene> {
+        Scene::new(parse_str(source, Path::new("."))?)
+    }
+
+    #[test]
+    fn creates_film_camera_and_shapes() {
+        let scene = build(
+            r#"
+            Camera "orthographic"
+            Film "rgb" "integer xresolution" 100 "integer yresolution" 50
+                "float cropwindow" [0.5 1 0 0.5] "string filename" "out.png"
+            PixelFilter "mitchell" "float xradius" 1 "float yradius" 1
+            Sampler "halton" "integer pixelsamples" 8
+            WorldBegin
+            Shape "sphere" "float radius" 2 "float blah" 1
+            Shape "trianglemesh" "point3 P" [0 0 0 1 0 0 0 1 0 1 1 0]
+                "integer indices" [0 1 2 2 1 3]
+            Shape "bilinearmesh"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(scene.film.full_resolution(), Point2i::new(100, 50));
+        assert_eq!(
+            scene.film.pixel_bounds(),
+            Bounds2i::from_points(Point2i::new(50, 0), Point2i::new(100, 25))
+        );
+        assert_eq!(scene.film.filter().radius(), Vector2f::new(1.0, 1.0));
+        assert_eq!(scene.camera.base().resolution, Point2i::new(100, 50));
+        assert_eq!(scene.sampler.samples_per_pixel(), 8);
+        assert_eq!(scene.filename, "out.png");
+        assert_eq!(scene.aggregate.primitives().len(), 3);
+        assert_eq!(scene.warnings.len(), 2, "{:?}", scene.warnings);
+        assert!(scene.warnings[0].contains("bilinearmesh"));
+        assert!(scene.warnings[1].contains("\"blah\""));
+    }
+
+    #[test]
+    fn reversed_meshes_keep_their_winding() {
+        let scene = build(
+            r#"
+            WorldBegin
+            ReverseOrientation
+            Shape "trianglemesh" "point3 P" [0 0 0 1 0 0 0 1 0]
+            "#,
+        )
+        .unwrap();
+        let Shape::Triangle(triangle) = &scene.aggregate.primitives()[0] else {
+            panic!("expected a triangle");
+        };
+        assert_eq!(triangle.vertex_indices(), [0, 1, 2]);
+        assert!(triangle.mesh().reverse_orientation);
+    }
+
+    #[test]
+    fn missing_directives_get_defaults() {
+        let scene = build("WorldBegin").unwrap();
+        assert_eq!(scene.film.full_resolution(), Point2i::new(1280, 720));
+        assert_eq!(scene.sampler.samples_per_pixel(), 16);
+        assert_eq!(scene.filename, "pbrt.exr");
+        assert!(scene.warnings.is_empty());
+    }
+
+    #[test]
+    fn samplers_take_their_sample_count_from_the_file_or_the_override() {
+        let scene = build("Sampler \"zsobol\" \"integer pixelsamples\" 6").unwrap();
+        assert_eq!(scene.sampler.samples_per_pixel(), 8);
+        assert!(
+            scene.warnings[0].contains("power of two"),
+            "{:?}",
+            scene.warnings
+        );
+
+        let scene =
+            build("Sampler \"stratified\" \"integer xsamples\" 3 \"bool jitter\" false").unwrap();
+        assert_eq!(scene.sampler.samples_per_pixel(), 12);
+
+        let mut desc = parse_str("Sampler \"stratified\"", Path::new(".")).unwrap();
+        desc.override_pixel_samples(6);
+        assert_eq!(Scene::new(desc).unwrap().sampler.samples_per_pixel(), 6);
+        let mut desc = parse_str("WorldBegin", Path::new(".")).unwrap();
+        desc.override_pixel_samples(32);
+        assert_eq!(Scene::new(desc).unwrap().sampler.samples_per_pixel(), 32);
+    }
+
+    #[test]
+    fn rejects_unknown_or_invalid_entities() {
+        for (source, expected) in [
+            ("Camera \"pinhole\"", "unknown camera"),
+            ("PixelFilter \"blur\"", "unknown filter"),
+            ("Sampler \"random\"", "unknown sampler"),
+            (
+                "Sampler \"sobol\" \"string randomization\" \"xor\"",
+                "unknown randomization",
+            ),
+            ("Film \"rgb\" \"integer xresolution\" 0", "must be positive"),
+            (
+                "Shape \"trianglemesh\" \"point3 P\" [0 0 0 1 0 0 0 1 0] \"integer indices\" [0 1 3]",
+                "past the end",
+            ),
+            ("Shape \"sphere\" \"float radius\" 0", "empty radius"),
+            (
+                "Shape \"cylinder\" \"float zmin\" 1 \"float zmax\" 1",
+                "empty radius",
+            ),
+            ("Shape \"disk\" \"float innerradius\" 2", "empty radius"),
+            ("Shape \"cone\" \"float phimax\" 0", "phi range"),
+            ("Shape \"paraboloid\" \"float zmax\" -1", "z or phi"),
+            (
+                "Shape \"hyperboloid\" \"point3 p2\" [1 1 0]",
+                "hyperboloid has",
+            ),
+        ] {
+            let err = build(source).err().unwrap();
+            assert!(err.to_string().contains(expected), "{err}");
+        }
+    }
+}
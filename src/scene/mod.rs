@@ -0,0 +1,8 @@
+mod builder;
+mod params;
+mod parser;
+mod tokenizer;
+pub use builder::{Scene, SceneFilter};
+pub use params::{ParamValues, Parameter, ParameterList};
+pub use parser::{CameraEntity, SceneDescription, SceneEntity, ShapeEntity, parse_file, parse_str};
+pub use tokenizer::{Token, TokenKind, tokenize};
@@ -0,0 +1,202 @@
+use std::cell::Cell;
+
+use crate::util::Float;
+use crate::util::vector::{Normal3f, Point2f, Point3f, Vector3f};
+
+/// The values of a parameter, in whichever form its type calls for.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParamValues {
+    Numbers(Vec<f64>),
+    Strings(Vec<String>),
+    Bools(Vec<bool>),
+}
+
+/// A parameter such as `"float radius" [2]`.
+#[derive(Debug, Clone)]
+pub struct Parameter {
+    pub ty: String,
+    pub name: String,
+    pub values: ParamValues,
+    /// Line of the scene file the parameter was declared on.
+    pub line: usize,
+    /// Set once a lookup has consumed the parameter, so that misspelled or
+    /// unsupported ones can be reported.
+    looked_up: Cell<bool>,
+}
+
+impl Parameter {
+    pub fn new(ty: &str, name: &str, values: ParamValues, line: usize) -> Self {
+        Self {
+            ty: ty.to_string(),
+            name: name.to_string(),
+            values,
+            line,
+            looked_up: Cell::new(false),
+        }
+    }
+
+    /// Number of values that make up one element of the parameter's type,
+    /// e.g. three for a `point3`.
+    pub fn arity(ty: &str) -> usize {
+        match ty {
+            "point2" | "vector2" => 2,
+            "point3" | "point" | "vector3" | "vector" | "normal3" | "normal" | "rgb" => 3,
+            _ => 1,
+        }
+    }
+}
+
+/// The parameters that follow a directive, with typed lookups. Lookups only
+/// match parameters declared with the type they ask for.
+#[derive(Debug, Clone, Default)]
+pub struct ParameterList {
+    params: Vec<Parameter>,
+}
+
+impl ParameterList {
+    pub fn new(params: Vec<Parameter>) -> Self {
+        Self { params }
+    }
+
+    /// Adds a parameter, which overrides any earlier one with the same name
+    /// and type.
+    pub fn push(&mut self, param: Parameter) {
+        self.params.push(param);
+    }
+
+    pub fn get_float(&self, name: &str, default: Float) -> Float {
+        self.get_floats(name).first().copied().unwrap_or(default)
+    }
+
+    pub fn get_int(&self, name: &str, default: i32) -> i32 {
+        self.get_ints(name).first().copied().unwrap_or(default)
+    }
+
+    pub fn get_bool(&self, name: &str, default: bool) -> bool {
+        match self.find(name, &["bool"]) {
+            Some(ParamValues::Bools(v)) => v.first().copied().unwrap_or(default),
+            _ => default,
+        }
+    }
+
+    pub fn get_string(&self, name: &str, default: &str) -> String {
+        match self.find(name, &["string"]) {
+            Some(ParamValues::Strings(v)) if !v.is_empty() => v[0].clone(),
+            _ => default.to_string(),
+        }
+    }
+
+    pub fn get_floats(&self, name: &str) -> Vec<Float> {
+        self.numbers(name, &["float"])
+            .iter()
+            .map(|&v| v as Float)
+            .collect()
+    }
+
+    pub fn get_ints(&self, name: &str) -> Vec<i32> {
+        self.numbers(name, &["integer"])
+            .iter()
+            .map(|&v| v as i32)
+            .collect()
+    }
+
+    pub fn get_point2s(&self, name: &str) -> Vec<Point2f> {
+        self.numbers(name, &["point2"])
+            .chunks_exact(2)
+            .map(|v| Point2f::new(v[0] as Float, v[1] as Float))
+            .collect()
+    }
+
+    pub fn get_point3s(&self, name: &str) -> Vec<Point3f> {
+        self.triples(name, &["point3", "point"])
+            .map(|[x, y, z]| Point3f::new(x, y, z))
+            .collect()
+    }
+
+    pub fn get_point3(&self, name: &str, default: Point3f) -> Point3f {
+        self.get_point3s(name).first().copied().unwrap_or(default)
+    }
+
+    pub fn get_vector3s(&self, name: &str) -> Vec<Vector3f> {
+        self.triples(name, &["vector3", "vector"])
+            .map(|[x, y, z]| Vector3f::new(x, y, z))
+            .collect()
+    }
+
+    pub fn get_normals(&self, name: &str) -> Vec<Normal3f> {
+        self.triples(name, &["normal3", "normal"])
+            .map(|[x, y, z]| Normal3f::new(x, y, z))
+            .collect()
+    }
+
+    /// Parameters no lookup has asked for so far.
+    pub fn unused(&self) -> impl Iterator<Item = &Parameter> {
+        self.params.iter().filter(|p| !p.looked_up.get())
+    }
+
+    fn find(&self, name: &str, types: &[&str]) -> Option<&ParamValues> {
+        // Later declarations override earlier ones.
+        let p = self
+            .params
+            .iter()
+            .rev()
+            .find(|p| p.name == name && types.contains(&p.ty.as_str()))?;
+        p.looked_up.set(true);
+        Some(&p.values)
+    }
+
+    fn numbers(&self, name: &str, types: &[&str]) -> &[f64] {
+        match self.find(name, types) {
+            Some(ParamValues::Numbers(v)) => v,
+            _ => &[],
+        }
+    }
+
+    fn triples(&self, name: &str, types: &[&str]) -> impl Iterator<Item = [Float; 3]> {
+        self.numbers(name, types)
+            .chunks_exact(3)
+            .map(|v| [v[0] as Float, v[1] as Float, v[2] as Float])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lookups_match_name_and_type() {
+        let params = ParameterList::new(vec![
+            Parameter::new("float", "radius", ParamValues::Numbers(vec![2.0]), 1),
+            Parameter::new(
+                "integer",
+                "indices",
+                ParamValues::Numbers(vec![0.0, 1.0, 2.0]),
+                1,
+            ),
+            Parameter::new(
+                "point3",
+                "P",
+                ParamValues::Numbers(vec![0.0, 0.0, 0.0, 1.0, 2.0, 3.0]),
+                2,
+            ),
+            Parameter::new(
+                "string",
+                "filename",
+                ParamValues::Strings(vec!["a.exr".into()]),
+                3,
+            ),
+            Parameter::new("bool", "flag", ParamValues::Bools(vec![true]), 4),
+            Parameter::new("float", "radius", ParamValues::Numbers(vec![3.0]), 5),
+            Parameter::new("float", "typo", ParamValues::Numbers(vec![1.0]), 6),
+        ]);
+        assert_eq!(params.get_float("radius", 1.0), 3.0);
+        assert_eq!(params.get_int("radius", 7), 7);
+        assert_eq!(params.get_ints("indices"), [0, 1, 2]);
+        assert_eq!(params.get_point3s("P")[1], Point3f::new(1.0, 2.0, 3.0));
+        assert_eq!(params.get_string("filename", "pbrt.exr"), "a.exr");
+        assert!(params.get_bool("flag", false));
+        assert_eq!(params.get_float("missing", 0.5), 0.5);
+        let unused: Vec<_> = params.unused().map(|p| (p.name.as_str(), p.line)).collect();
+        assert_eq!(unused, [("radius", 1), ("typo", 6)]);
+    }
+}
@@ -0,0 +1,613 @@
+use std::collections::{HashMap, HashSet};
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::scene::params::{ParamValues, Parameter, ParameterList};
+use crate::scene::tokenizer::{Token, TokenKind, tokenize};
+use crate::util::Float;
+use crate::util::math::SquareMatrix;
+use crate::util::math::transformations::Transform;
+use crate::util::vector::{Point3f, Vector3f};
+
+/// A named object of the scene with its parameters, such as a camera or a
+/// shape, as it appeared in the scene file.
+#[derive(Debug, Clone)]
+pub struct SceneEntity {
+    pub name: String,
+    pub params: ParameterList,
+    /// `file:line` of the directive, for messages.
+    pub location: String,
+    /// Directory of the main scene file, which relative paths in its
+    /// parameters are resolved against, as for `Include`.
+    pub directory: PathBuf,
+}
+
+impl SceneEntity {
+    /// Stands in for a directive the scene file left out, so that every
+    /// parameter takes its default.
+    pub fn unspecified(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            params: ParameterList::default(),
+            location: "<default>".to_string(),
+            directory: PathBuf::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CameraEntity {
+    pub entity: SceneEntity,
+    pub world_from_camera: Transform,
+}
+
+#[derive(Debug, Clone)]
+pub struct ShapeEntity {
+    pub entity: SceneEntity,
+    pub render_from_object: Transform,
+    pub reverse_orientation: bool,
+}
+
+/// Everything a scene file describes, before any of it is created. Options
+/// that were not given are `None`.
+#[derive(Debug, Clone, Default)]
+pub struct SceneDescription {
+    pub camera: Option<CameraEntity>,
+    pub film: Option<SceneEntity>,
+    pub filter: Option<SceneEntity>,
+    pub sampler: Option<SceneEntity>,
+    pub shapes: Vec<ShapeEntity>,
+    /// Directives that were understood but are not supported yet.
+    pub warnings: Vec<String>,
+}
+
+/// Parses the scene file at `path` and the files it includes.
+pub fn parse_file(path: impl AsRef<Path>) -> io::Result<SceneDescription> {
+    let path = path.as_ref();
+    let mut parser = Parser::default();
+    parser.parse_file(path, path.parent().unwrap_or(Path::new("")))?;
+    parser.finish()
+}
+
+/// Parses scene text, resolving relative paths against `directory`.
+pub fn parse_str(source: &str, directory: &Path) -> io::Result<SceneDescription> {
+    let mut parser = Parser::default();
+    parser.parse_tokens(source, Path::new("<string>"), directory)?;
+    parser.finish()
+}
+
+/// What applies to the shapes declared at a given point of the file.
+#[derive(Debug, Clone, Copy, Default)]
+struct GraphicsState {
+    ctm: Transform,
+    reverse_orientation: bool,
+}
+
+/// Which directive pushed an entry of the graphics state stack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Block {
+    Attribute,
+    Transform,
+    Object,
+}
+
+#[derive(Default)]
+struct Parser {
+    desc: SceneDescription,
+    state: GraphicsState,
+    stack: Vec<(Block, GraphicsState)>,
+    named_coordinate_systems: HashMap<String, Transform>,
+    /// Directives already warned about, so each is reported once.
+    warned: HashSet<String>,
+    /// Files being parsed, to catch recursive includes.
+    include_stack: Vec<PathBuf>,
+}
+
+/// Reads the tokens of one file.
+struct Cursor<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+    file: &'a Path,
+}
+
+impl Cursor<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<&Token> {
+        let t = self.tokens.get(self.pos);
+        self.pos += 1;
+        t
+    }
+
+    /// Line of the last token read, or of the end of the file.
+    fn line(&self) -> usize {
+        self.tokens
+            .get(self.pos.saturating_sub(1))
+            .or(self.tokens.last())
+            .map_or(1, |t| t.line)
+    }
+
+    fn location(&self) -> String {
+        format!("{}:{}", self.file.display(), self.line())
+    }
+
+    fn error(&self, msg: impl std::fmt::Display) -> io::Error {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{}: {msg}", self.location()),
+        )
+    }
+
+    fn number(&mut self) -> io::Result<Float> {
+        match self.next().map(|t| &t.kind) {
+            Some(TokenKind::Number(v)) => Ok(*v as Float),
+            _ => Err(self.error("expected a number")),
+        }
+    }
+
+    fn numbers<const N: usize>(&mut self) -> io::Result<[Float; N]> {
+        let mut values = [0.0; N];
+        for v in &mut values {
+            *v = self.number()?;
+        }
+        Ok(values)
+    }
+
+    /// `N` numbers, optionally enclosed in brackets.
+    fn bracketed_numbers<const N: usize>(&mut self) -> io::Result<[Float; N]> {
+        let bracketed = matches!(self.peek().map(|t| &t.kind), Some(TokenKind::OpenBracket));
+        if bracketed {
+            self.next();
+        }
+        let values = self.numbers()?;
+        if bracketed && !matches!(self.next().map(|t| &t.kind), Some(TokenKind::CloseBracket)) {
+            return Err(self.error(format!("expected ']' after {N} numbers")));
+        }
+        Ok(values)
+    }
+
+    fn string(&mut self) -> io::Result<String> {
+        match self.next().map(|t| &t.kind) {
+            Some(TokenKind::String(s)) => Ok(s.clone()),
+            _ => Err(self.error("expected a quoted string")),
+        }
+    }
+
+    /// The parameter list following a directive: pairs of a `"type name"`
+    /// string and a value or bracketed list of values.
+    fn params(&mut self) -> io::Result<ParameterList> {
+        let mut params = Vec::new();
+        while let Some(Token {
+            kind: TokenKind::String(decl),
+            line,
+        }) = self.peek().cloned()
+        {
+            self.next();
+            let [ty, name] = decl.split_whitespace().collect::<Vec<_>>()[..] else {
+                return Err(self.error(format!("malformed parameter declaration \"{decl}\"")));
+            };
+            let mut raw = Vec::new();
+            if matches!(self.peek().map(|t| &t.kind), Some(TokenKind::OpenBracket)) {
+                self.next();
+                loop {
+                    match self.next().map(|t| &t.kind) {
+                        Some(TokenKind::CloseBracket) => break,
+                        Some(TokenKind::OpenBracket) | None => {
+                            return Err(self.error(format!("unterminated values of \"{name}\"")));
+                        }
+                        Some(kind) => raw.push(kind.clone()),
+                    }
+                }
+            } else {
+                match self.next() {
+                    Some(t) if !t.is_directive() => raw.push(t.kind.clone()),
+                    _ => return Err(self.error(format!("missing value for \"{name}\""))),
+                }
+            }
+            let values =
+                param_values(ty, &raw).map_err(|msg| self.error(format!("\"{name}\": {msg}")))?;
+            params.push(Parameter::new(ty, name, values, line));
+        }
+        Ok(ParameterList::new(params))
+    }
+
+    /// Skips the arguments of a directive that is being ignored.
+    fn skip_arguments(&mut self) {
+        while self.peek().is_some_and(|t| !t.is_directive()) {
+            self.next();
+        }
+    }
+}
+
+/// Converts the raw tokens of a parameter to values of its type.
+fn param_values(ty: &str, raw: &[TokenKind]) -> Result<ParamValues, String> {
+    let numbers = || {
+        raw.iter()
+            .map(|k| match k {
+                TokenKind::Number(v) => Ok(*v),
+                _ => Err(format!("expected numbers for a {ty} parameter")),
+            })
+            .collect::<Result<Vec<_>, _>>()
+    };
+    let strings = || {
+        raw.iter()
+            .map(|k| match k {
+                TokenKind::String(s) => Ok(s.clone()),
+                _ => Err(format!("expected strings for a {ty} parameter")),
+            })
+            .collect::<Result<Vec<_>, _>>()
+    };
+    match ty {
+        "bool" => raw
+            .iter()
+            .map(|k| match k {
+                TokenKind::Identifier(s) | TokenKind::String(s) if s == "true" => Ok(true),
+                TokenKind::Identifier(s) | TokenKind::String(s) if s == "false" => Ok(false),
+                _ => Err("expected true or false".to_string()),
+            })
+            .collect::<Result<_, _>>()
+            .map(ParamValues::Bools),
+        "string" | "texture" => strings().map(ParamValues::Strings),
+        // Named spectra are strings, the others sampled values.
+        "spectrum" if matches!(raw.first(), Some(TokenKind::String(_))) => {
+            strings().map(ParamValues::Strings)
+        }
+        "integer" | "float" | "point2" | "vector2" | "point3" | "vector3" | "normal3"
+        | "normal" | "point" | "vector" | "rgb" | "blackbody" | "spectrum" => {
+            let v = numbers()?;
+            if ty == "integer" && v.iter().any(|x| x.fract() != 0.0) {
+                return Err("expected integers".to_string());
+            }
+            let arity = Parameter::arity(ty);
+            if v.len() % arity != 0 {
+                return Err(format!(
+                    "{} values is not a multiple of {arity} for a {ty} parameter",
+                    v.len()
+                ));
+            }
+            Ok(ParamValues::Numbers(v))
+        }
+        _ => Err(format!("unknown parameter type \"{ty}\"")),
+    }
+}
+
+/// Directives of the pbrt-v4 format that are accepted but not acted on yet.
+const IGNORED_DIRECTIVES: &[&str] = &[
+    "Accelerator",
+    "AreaLightSource",
+    "Attribute",
+    "ColorSpace",
+    "Integrator",
+    "LightSource",
+    "MakeNamedMaterial",
+    "MakeNamedMedium",
+    "Material",
+    "MediumInterface",
+    "NamedMaterial",
+    "ObjectInstance",
+    "Option",
+    "Texture",
+    "TransformTimes",
+];
+
+impl Parser {
+    /// Parses the file at `path`, resolving relative paths in it against the
+    /// scene's `directory` rather than the file's own, as pbrt-v4 does.
+    fn parse_file(&mut self, path: &Path, directory: &Path) -> io::Result<()> {
+        let source = std::fs::read_to_string(path)
+            .map_err(|e| io::Error::new(e.kind(), format!("{}: {e}", path.display())))?;
+        let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        if self.include_stack.contains(&canonical) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}: included recursively", path.display()),
+            ));
+        }
+        self.include_stack.push(canonical);
+        let result = self.parse_tokens(&source, path, directory);
+        self.include_stack.pop();
+        result
+    }
+
+    fn parse_tokens(&mut self, source: &str, file: &Path, directory: &Path) -> io::Result<()> {
+        let tokens = tokenize(source)
+            .map_err(|e| io::Error::new(e.kind(), format!("{}: {e}", file.display())))?;
+        let mut c = Cursor {
+            tokens: &tokens,
+            pos: 0,
+            file,
+        };
+        while let Some(token) = c.next() {
+            let TokenKind::Identifier(directive) = &token.kind else {
+                return Err(c.error("expected a directive"));
+            };
+            let directive = directive.clone();
+            let entity = |c: &mut Cursor| -> io::Result<SceneEntity> {
+                let location = c.location();
+                Ok(SceneEntity {
+                    name: c.string()?,
+                    params: c.params()?,
+                    location,
+                    directory: directory.to_path_buf(),
+                })
+            };
+            match directive.as_str() {
+                "Identity" => self.state.ctm = Transform::identity(),
+                "Translate" => {
+                    let [x, y, z] = c.numbers()?;
+                    self.concat(Transform::translate(Vector3f::new(x, y, z)));
+                }
+                "Scale" => {
+                    let [x, y, z] = c.numbers()?;
+                    self.concat(Transform::scale(x, y, z));
+                }
+                "Rotate" => {
+                    let [angle, x, y, z] = c.numbers()?;
+                    self.concat(Transform::rotate(angle, Vector3f::new(x, y, z)));
+                }
+                "LookAt" => {
+                    let [ex, ey, ez, lx, ly, lz, ux, uy, uz] = c.numbers()?;
+                    let look_at = Transform::look_at(
+                        Point3f::new(ex, ey, ez),
+                        Point3f::new(lx, ly, lz),
+                        Vector3f::new(ux, uy, uz),
+                    )
+                    .ok_or_else(|| c.error("LookAt up vector is parallel to the view direction"))?;
+                    self.concat(look_at);
+                }
+                "Transform" | "ConcatTransform" => {
+                    let m: [Float; 16] = c.bracketed_numbers()?;
+                    // The matrix is given column by column.
+                    let matrix = SquareMatrix {
+                        matrix: std::array::from_fn(|i| std::array::from_fn(|j| m[j * 4 + i])),
+                    };
+                    let t = Transform::from_matrix(matrix)
+                        .ok_or_else(|| c.error(format!("{directive} matrix is singular")))?;
+                    if directive == "Transform" {
+                        self.state.ctm = t;
+                    } else {
+                        self.concat(t);
+                    }
+                }
+                "CoordinateSystem" => {
+                    let name = c.string()?;
+                    self.named_coordinate_systems.insert(name, self.state.ctm);
+                }
+                "CoordSysTransform" => {
+                    let name = c.string()?;
+                    match self.named_coordinate_systems.get(&name) {
+                        Some(t) => self.state.ctm = *t,
+                        None => self.warn(&c, format!("unknown coordinate system \"{name}\"")),
+                    }
+                }
+                "ActiveTransform" => {
+                    // Takes a bare word, which `skip_arguments` would not.
+                    c.next();
+                    self.warn_once(&c, "ActiveTransform");
+                }
+                "ReverseOrientation" => {
+                    self.state.reverse_orientation = !self.state.reverse_orientation;
+                }
+                "Camera" => {
+                    let camera = entity(&mut c)?;
+                    let world_from_camera = self.state.ctm.inverse();
+                    self.named_coordinate_systems
+                        .insert("camera".to_string(), world_from_camera);
+                    self.desc.camera = Some(CameraEntity {
+                        entity: camera,
+                        world_from_camera,
+                    });
+                }
+                "Film" => self.desc.film = Some(entity(&mut c)?),
+                "PixelFilter" => self.desc.filter = Some(entity(&mut c)?),
+                "Sampler" => self.desc.sampler = Some(entity(&mut c)?),
+                "WorldBegin" => {
+                    self.state.ctm = Transform::identity();
+                    self.named_coordinate_systems
+                        .insert("world".to_string(), self.state.ctm);
+                }
+                "AttributeBegin" => self.push(Block::Attribute),
+                "AttributeEnd" => self.pop(&c, Block::Attribute)?,
+                "TransformBegin" => self.push(Block::Transform),
+                "TransformEnd" => self.pop(&c, Block::Transform)?,
+                "ObjectBegin" => {
+                    c.string()?;
+                    self.warn_once(&c, "ObjectBegin");
+                    self.push(Block::Object);
+                }
+                "ObjectEnd" => self.pop(&c, Block::Object)?,
+                "Shape" => {
+                    let shape = entity(&mut c)?;
+                    // Instanced objects are not supported, and their shapes
+                    // must not show up at the origin.
+                    if !self.stack.iter().any(|(b, _)| *b == Block::Object) {
+                        self.desc.shapes.push(ShapeEntity {
+                            entity: shape,
+                            render_from_object: self.state.ctm,
+                            reverse_orientation: self.state.reverse_orientation,
+                        });
+                    }
+                }
+                "Include" | "Import" => {
+                    let file = c.string()?;
+                    self.parse_file(&directory.join(file), directory)?;
+                }
+                d if IGNORED_DIRECTIVES.contains(&d) => {
+                    self.warn_once(&c, d);
+                    c.skip_arguments();
+                }
+                _ => return Err(c.error(format!("unknown directive \"{directive}\""))),
+            }
+        }
+        Ok(())
+    }
+
+    fn finish(self) -> io::Result<SceneDescription> {
+        if let Some((block, _)) = self.stack.last() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{block:?}Begin without a matching {block:?}End"),
+            ));
+        }
+        Ok(self.desc)
+    }
+
+    fn concat(&mut self, t: Transform) {
+        self.state.ctm = self.state.ctm * t;
+    }
+
+    fn push(&mut self, block: Block) {
+        self.stack.push((block, self.state));
+    }
+
+    fn pop(&mut self, c: &Cursor, block: Block) -> io::Result<()> {
+        match self.stack.pop() {
+            Some((b, state)) if b == block => {
+                if block == Block::Transform {
+                    self.state.ctm = state.ctm;
+                } else {
+                    self.state = state;
+                }
+                Ok(())
+            }
+            _ => Err(c.error(format!("unmatched {block:?}End"))),
+        }
+    }
+
+    fn warn(&mut self, c: &Cursor, msg: impl std::fmt::Display) {
+        self.desc.warnings.push(format!("{}: {msg}", c.location()));
+    }
+
+    fn warn_once(&mut self, c: &Cursor, directive: &str) {
+        if self.warned.insert(directive.to_string()) {
+            self.warn(
+                c,
+                format!("{directive} is not supported yet and was ignored"),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(source: &str) -> io::Result<SceneDescription> {
+        parse_str(source, Path::new("."))
+    }
+
+    #[test]
+    fn records_entities_with_their_transforms() {
+        let desc = parse(
+            r#"
+            LookAt 0 0 -5  0 0 0  0 1 0
+            Camera "perspective" "float fov" 45
+            Film "rgb" "integer xresolution" [64] "integer yresolution" [32]
+            Sampler "independent" "integer pixelsamples" 4
+            WorldBegin
+            LightSource "infinite"
+            AttributeBegin
+                Translate 1 0 0
+                ReverseOrientation
+                Shape "sphere" "float radius" 0.5
+            AttributeEnd
+            Shape "trianglemesh" "point3 P" [0 0 0 1 0 0 0 1 0] "integer indices" [0 1 2]
+            "#,
+        )
+        .unwrap();
+        let camera = desc.camera.unwrap();
+        assert_eq!(camera.entity.name, "perspective");
+        assert_eq!(camera.entity.params.get_float("fov", 90.0), 45.0);
+        let eye = camera
+            .world_from_camera
+            .transform_point(&Point3f::new(0.0, 0.0, 0.0));
+        assert_eq!(eye, Point3f::new(0.0, 0.0, -5.0));
+        assert_eq!(desc.film.unwrap().params.get_int("xresolution", 0), 64);
+        assert_eq!(desc.shapes.len(), 2);
+        let sphere = &desc.shapes[0];
+        assert!(sphere.reverse_orientation);
+        assert_eq!(
+            sphere
+                .render_from_object
+                .transform_point(&Point3f::new(0.0, 0.0, 0.0)),
+            Point3f::new(1.0, 0.0, 0.0)
+        );
+        let mesh = &desc.shapes[1];
+        assert!(!mesh.reverse_orientation && mesh.render_from_object.is_identity());
+        assert_eq!(desc.warnings.len(), 1);
+        assert!(
+            desc.warnings[0].contains("LightSource"),
+            "{:?}",
+            desc.warnings
+        );
+    }
+
+    #[test]
+    fn transform_matrices_are_column_major() {
+        let desc = parse("Transform [1 0 0 0  0 1 0 0  0 0 1 0  3 4 5 1]\nCamera \"orthographic\"")
+            .unwrap();
+        let p = desc
+            .camera
+            .unwrap()
+            .world_from_camera
+            .transform_point(&Point3f::new(0.0, 0.0, 0.0));
+        assert_eq!(p, Point3f::new(-3.0, -4.0, -5.0));
+    }
+
+    #[test]
+    fn includes_are_relative_to_the_scene_file() {
+        let dir = std::env::temp_dir().join(format!("pbrt-parser-test-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("geometry")).unwrap();
+        std::fs::write(
+            dir.join("scene.pbrt"),
+            "WorldBegin\nInclude \"geometry/shapes.pbrt\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("geometry/shapes.pbrt"),
+            "Shape \"disk\"\nInclude \"geometry/more.pbrt\"\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join("geometry/more.pbrt"), "Shape \"cone\"\n").unwrap();
+        let desc = parse_file(dir.join("scene.pbrt")).unwrap();
+        let names: Vec<_> = desc.shapes.iter().map(|s| s.entity.name.as_str()).collect();
+        assert_eq!(names, ["disk", "cone"]);
+        assert!(desc.shapes[1].entity.location.ends_with("more.pbrt:1"));
+        assert!(desc.shapes.iter().all(|s| s.entity.directory == dir));
+
+        std::fs::write(
+            dir.join("geometry/more.pbrt"),
+            "Include \"geometry/shapes.pbrt\"\n",
+        )
+        .unwrap();
+        let err = parse_file(dir.join("scene.pbrt")).unwrap_err();
+        assert!(err.to_string().contains("recursively"), "{err}");
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn errors_name_the_line() {
+        for (source, expected) in [
+            (
+                "WorldBegin\nShape \"sphere\" \"float radius\" \"big\"",
+                ":2: \"radius\"",
+            ),
+            ("WorldBegin\n\nFrobnicate 1", ":3: unknown directive"),
+            (
+                "AttributeBegin\nAttributeEnd\nAttributeEnd",
+                ":3: unmatched Attribute",
+            ),
+            (
+                "Shape \"trianglemesh\" \"point3 P\" [0 0 0 1]",
+                ":1: \"P\": 4 values",
+            ),
+            ("Translate 1 2", ":1: expected a number"),
+        ] {
+            let err = parse(source).unwrap_err();
+            assert!(err.to_string().contains(expected), "{err}");
+        }
+        assert!(parse("AttributeBegin").is_err());
+    }
+}
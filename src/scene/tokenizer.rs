@@ -0,0 +1,162 @@
+use std::io;
+
+/// A lexical element of a pbrt scene file.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TokenKind {
+    /// A bare word: a directive name, or `true` / `false`.
+    Identifier(String),
+    /// A double-quoted string with its escapes resolved.
+    String(String),
+    /// Kept in double precision so large integers such as mesh indices
+    /// survive until they are converted.
+    Number(f64),
+    OpenBracket,
+    CloseBracket,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Token {
+    pub kind: TokenKind,
+    /// One-based line the token starts on.
+    pub line: usize,
+}
+
+impl Token {
+    /// Whether the token starts a new directive rather than being an
+    /// argument of the current one.
+    pub fn is_directive(&self) -> bool {
+        matches!(&self.kind, TokenKind::Identifier(s) if s != "true" && s != "false")
+    }
+}
+
+/// Splits a scene file into tokens. Whitespace and `#` comments separate
+/// tokens and are otherwise ignored.
+pub fn tokenize(source: &str) -> io::Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = source.char_indices().peekable();
+    let mut line = 1;
+    while let Some(&(start, c)) = chars.peek() {
+        match c {
+            '\n' => {
+                line += 1;
+                chars.next();
+            }
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '#' => while chars.next_if(|&(_, c)| c != '\n').is_some() {},
+            '[' | ']' => {
+                chars.next();
+                let kind = if c == '[' {
+                    TokenKind::OpenBracket
+                } else {
+                    TokenKind::CloseBracket
+                };
+                tokens.push(Token { kind, line });
+            }
+            '"' => {
+                chars.next();
+                let first_line = line;
+                let mut s = String::new();
+                loop {
+                    match chars.next() {
+                        None => return Err(syntax_error(first_line, "unterminated string")),
+                        Some((_, '"')) => break,
+                        Some((_, '\n')) => {
+                            return Err(syntax_error(first_line, "newline in string"));
+                        }
+                        Some((_, '\\')) => match chars.next() {
+                            Some((_, 'n')) => s.push('\n'),
+                            Some((_, 't')) => s.push('\t'),
+                            Some((_, c @ ('\\' | '"' | '\''))) => s.push(c),
+                            _ => return Err(syntax_error(line, "unknown escape in string")),
+                        },
+                        Some((_, c)) => s.push(c),
+                    }
+                }
+                tokens.push(Token {
+                    kind: TokenKind::String(s),
+                    line: first_line,
+                });
+            }
+            _ => {
+                let mut end = start;
+                while let Some((i, c)) = chars
+                    .next_if(|&(_, c)| !c.is_whitespace() && !matches!(c, '"' | '[' | ']' | '#'))
+                {
+                    end = i + c.len_utf8();
+                }
+                let word = &source[start..end];
+                let starts_like_number =
+                    word.starts_with(|c: char| c.is_ascii_digit() || matches!(c, '-' | '+' | '.'));
+                let kind = if starts_like_number {
+                    let v = word
+                        .parse()
+                        .map_err(|_| syntax_error(line, format!("malformed number \"{word}\"")))?;
+                    TokenKind::Number(v)
+                } else {
+                    TokenKind::Identifier(word.to_string())
+                };
+                tokens.push(Token { kind, line });
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+fn syntax_error(line: usize, msg: impl std::fmt::Display) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("line {line}: {msg}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(source: &str) -> Vec<TokenKind> {
+        tokenize(source)
+            .unwrap()
+            .into_iter()
+            .map(|t| t.kind)
+            .collect()
+    }
+
+    #[test]
+    fn splits_directives_strings_numbers_and_brackets() {
+        use TokenKind::*;
+        assert_eq!(
+            kinds("Shape \"sphere\" \"float radius\" [2.5] # a comment\nWorldBegin"),
+            [
+                Identifier("Shape".into()),
+                String("sphere".into()),
+                String("float radius".into()),
+                OpenBracket,
+                Number(2.5),
+                CloseBracket,
+                Identifier("WorldBegin".into()),
+            ]
+        );
+        assert_eq!(
+            kinds("[-1 .5 1e3]\"a\\\"b\"true"),
+            [
+                OpenBracket,
+                Number(-1.0),
+                Number(0.5),
+                Number(1000.0),
+                CloseBracket,
+                String("a\"b".into()),
+                Identifier("true".into()),
+            ]
+        );
+        let tokens = tokenize("A\n\n# skipped\n  B").unwrap();
+        assert_eq!((tokens[0].line, tokens[1].line), (1, 4));
+        assert!(tokens[0].is_directive());
+    }
+
+    #[test]
+    fn reports_the_line_of_malformed_input() {
+        let err = tokenize("WorldBegin\nTranslate 1 2x 3").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(err.to_string().starts_with("line 2:"), "{err}");
+        assert!(tokenize("Shape \"sphere").is_err());
+    }
+}
@@ -0,0 +1,147 @@
+use crate::shapes::quadric::{
+    Quadric, QuadricGeometry, ShapeTransform, first_root, phi_max_radians, phi_of, ray_intervals,
+    swept_d2pduu, swept_dpdu,
+};
+use crate::util::Float;
+use crate::util::bounds::Bounds3;
+use crate::util::math::transformations::Transform;
+use crate::util::math::{Interval, gamma};
+use crate::util::rays::Ray;
+use crate::util::vector::{Point2f, Point3f, Vector3f};
+
+/// A cone with its base of `radius` at `z = 0` and its apex at
+/// `z = height`.
+#[derive(Debug, Clone)]
+pub struct Cone {
+    transform: ShapeTransform,
+    radius: Float,
+    height: Float,
+    phi_max: Float,
+}
+
+impl Cone {
+    /// Returns `None` if the height, radius or `phi_max` is not positive.
+    pub fn new(
+        render_from_object: Transform,
+        reverse_orientation: bool,
+        height: Float,
+        radius: Float,
+        phi_max: Float,
+    ) -> Option<Self> {
+        if height.is_nan() || height <= 0.0 || radius.is_nan() || radius <= 0.0 {
+            return None;
+        }
+        Some(Self {
+            transform: ShapeTransform::new(render_from_object, reverse_orientation),
+            radius,
+            height,
+            phi_max: phi_max_radians(phi_max)?,
+        })
+    }
+
+    fn geometry(&self, p: Point3f, phi: Float) -> QuadricGeometry {
+        let v = p.z / self.height;
+        let s = 1.0 / (1.0 - v);
+        QuadricGeometry {
+            p,
+            p_error: Vector3f::from(p).abs() * gamma(7),
+            uv: Point2f::new(phi / self.phi_max, v),
+            dpdu: swept_dpdu(&p, self.phi_max),
+            dpdv: Vector3f::new(-p.x * s, -p.y * s, self.height),
+            d2pduu: swept_d2pduu(&p, self.phi_max),
+            d2pduv: Vector3f::new(p.y, -p.x, 0.0) * (self.phi_max * s),
+            d2pdvv: Vector3f::default(),
+        }
+    }
+}
+
+impl Quadric for Cone {
+    fn shape_transform(&self) -> &ShapeTransform {
+        &self.transform
+    }
+
+    fn object_bound(&self) -> Bounds3 {
+        Bounds3::from_points(
+            Point3f::new(-self.radius, -self.radius, 0.0),
+            Point3f::new(self.radius, self.radius, self.height),
+        )
+    }
+
+    fn area(&self) -> Float {
+        self.radius * (self.height * self.height + self.radius * self.radius).sqrt() * self.phi_max
+            / 2.0
+    }
+
+    fn intersect_object(&self, ray: &Ray) -> Option<(Float, QuadricGeometry)> {
+        let ([ox, oy, oz], [dx, dy, dz]) = ray_intervals(ray);
+        let k = (Interval::new(self.radius) / self.height).sqr();
+        let oz = oz - self.height;
+        let a = dx.sqr() + dy.sqr() - k * dz.sqr();
+        let b = (dx * ox + dy * oy - k * dz * oz) * 2.0;
+        let c = ox.sqr() + oy.sqr() - k * oz.sqr();
+        let roots = Interval::quadratic(a, b, c)?;
+        first_root(roots, ray.t_max(), |t| {
+            let p = ray.get(t);
+            let phi = phi_of(&p);
+            let clipped = p.z < 0.0 || p.z > self.height || phi > self.phi_max;
+            // The apex itself has no well-defined derivatives.
+            (!clipped && p.z < self.height).then(|| self.geometry(p, phi))
+        })
+    }
+
+    fn sample_object(&self, u: Point2f) -> QuadricGeometry {
+        // Area grows with the square of the distance from the apex.
+        let from_apex = u.x.sqrt();
+        let z = self.height * (1.0 - from_apex);
+        let r = self.radius * from_apex;
+        let phi = u.y * self.phi_max;
+        self.geometry(Point3f::new(r * phi.cos(), r * phi.sin(), z), phi)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hit_on_side_has_outward_normal() {
+        let c = Cone::new(Transform::identity(), false, 1.0, 1.0, 360.0).unwrap();
+        let ray = Ray::new(
+            Point3f::new(-5.0, 0.0, 0.5),
+            Vector3f::new(1.0, 0.0, 0.0),
+            0.0,
+        );
+        let hit = c.intersect_hit(&ray).unwrap();
+        assert!((hit.p.x + 0.5).abs() < 1e-5);
+        assert!(hit.n.x < 0.0 && hit.n.z > 0.0);
+        assert!((hit.uv.y - 0.5).abs() < 1e-5);
+        let above = Ray::new(
+            Point3f::new(-5.0, 0.0, 1.5),
+            Vector3f::new(1.0, 0.0, 0.0),
+            0.0,
+        );
+        assert!(c.intersect_hit(&above).is_none());
+    }
+
+    #[test]
+    fn samples_lie_on_the_surface() {
+        let c = Cone::new(Transform::identity(), false, 2.0, 1.0, 360.0).unwrap();
+        for i in 1..8 {
+            let s = c.sample(Point2f::new(i as Float / 8.0, 0.3));
+            let r = (s.p.x * s.p.x + s.p.y * s.p.y).sqrt();
+            assert!((r - (1.0 - s.p.z / 2.0)).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn degenerate_cones_are_rejected() {
+        let cone = |height, radius, phi_max| {
+            Cone::new(Transform::identity(), false, height, radius, phi_max)
+        };
+        assert!(cone(0.0, 1.0, 360.0).is_none());
+        assert!(cone(1.0, 0.0, 360.0).is_none());
+        assert!(cone(1.0, 1.0, 0.0).is_none());
+        assert!(cone(1.0, 1.0, Float::NAN).is_none());
+        assert!(cone(2.0, 0.5, 720.0).is_some());
+    }
+}
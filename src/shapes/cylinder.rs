@@ -0,0 +1,162 @@
+use crate::shapes::quadric::{
+    Quadric, QuadricGeometry, ShapeTransform, first_root, phi_max_radians, phi_of, ray_intervals,
+    swept_d2pduu, swept_dpdu,
+};
+use crate::util::Float;
+use crate::util::bounds::Bounds3;
+use crate::util::math::transformations::Transform;
+use crate::util::math::{Interval, gamma};
+use crate::util::rays::Ray;
+use crate::util::vector::{Point2f, Point3f, Vector3f};
+
+/// An open cylinder around the z axis between `z_min` and `z_max`.
+#[derive(Debug, Clone)]
+pub struct Cylinder {
+    transform: ShapeTransform,
+    radius: Float,
+    z_min: Float,
+    z_max: Float,
+    phi_max: Float,
+}
+
+impl Cylinder {
+    /// Returns `None` if the radius or `phi_max` is not positive or the z
+    /// range is empty.
+    pub fn new(
+        render_from_object: Transform,
+        reverse_orientation: bool,
+        radius: Float,
+        z_min: Float,
+        z_max: Float,
+        phi_max: Float,
+    ) -> Option<Self> {
+        let (z_lo, z_hi) = (z_min.min(z_max), z_min.max(z_max));
+        if radius.is_nan() || radius <= 0.0 || z_lo.is_nan() || z_lo >= z_hi {
+            return None;
+        }
+        Some(Self {
+            transform: ShapeTransform::new(render_from_object, reverse_orientation),
+            radius,
+            z_min: z_lo,
+            z_max: z_hi,
+            phi_max: phi_max_radians(phi_max)?,
+        })
+    }
+
+    fn geometry(&self, p: Point3f, phi: Float) -> QuadricGeometry {
+        let zero = Vector3f::default();
+        QuadricGeometry {
+            p,
+            p_error: Vector3f::new(p.x, p.y, 0.0).abs() * gamma(3),
+            uv: Point2f::new(
+                phi / self.phi_max,
+                (p.z - self.z_min) / (self.z_max - self.z_min),
+            ),
+            dpdu: swept_dpdu(&p, self.phi_max),
+            dpdv: Vector3f::new(0.0, 0.0, self.z_max - self.z_min),
+            d2pduu: swept_d2pduu(&p, self.phi_max),
+            d2pduv: zero,
+            d2pdvv: zero,
+        }
+    }
+}
+
+impl Quadric for Cylinder {
+    fn shape_transform(&self) -> &ShapeTransform {
+        &self.transform
+    }
+
+    fn object_bound(&self) -> Bounds3 {
+        Bounds3::from_points(
+            Point3f::new(-self.radius, -self.radius, self.z_min),
+            Point3f::new(self.radius, self.radius, self.z_max),
+        )
+    }
+
+    fn area(&self) -> Float {
+        (self.z_max - self.z_min) * self.radius * self.phi_max
+    }
+
+    fn intersect_object(&self, ray: &Ray) -> Option<(Float, QuadricGeometry)> {
+        let ([ox, oy, _], [dx, dy, _]) = ray_intervals(ray);
+        let a = dx.sqr() + dy.sqr();
+        // A ray parallel to the axis never crosses the side.
+        if a.upper_bound() == 0.0 {
+            return None;
+        }
+        let b = (dx * ox + dy * oy) * 2.0;
+        let c = ox.sqr() + oy.sqr() - Interval::new(self.radius).sqr();
+        let roots = Interval::quadratic(a, b, c)?;
+        first_root(roots, ray.t_max(), |t| {
+            let mut p = ray.get(t);
+            let hit_radius = (p.x * p.x + p.y * p.y).sqrt();
+            p.x *= self.radius / hit_radius;
+            p.y *= self.radius / hit_radius;
+            let phi = phi_of(&p);
+            let clipped = p.z < self.z_min || p.z > self.z_max || phi > self.phi_max;
+            (!clipped).then(|| self.geometry(p, phi))
+        })
+    }
+
+    fn sample_object(&self, u: Point2f) -> QuadricGeometry {
+        let z = self.z_min + u.x * (self.z_max - self.z_min);
+        let phi = u.y * self.phi_max;
+        let p = Point3f::new(self.radius * phi.cos(), self.radius * phi.sin(), z);
+        self.geometry(p, phi)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::math::PI;
+
+    #[test]
+    fn hits_outside_then_inside() {
+        let c = Cylinder::new(Transform::identity(), false, 1.0, -1.0, 1.0, 360.0).unwrap();
+        let ray = Ray::new(
+            Point3f::new(-5.0, 0.0, 0.5),
+            Vector3f::new(1.0, 0.0, 0.0),
+            0.0,
+        );
+        let hit = c.intersect_hit(&ray).unwrap();
+        assert!((hit.t - 4.0).abs() < 1e-5);
+        assert!((hit.n.x + 1.0).abs() < 1e-5);
+        assert!((hit.uv.y - 0.75).abs() < 1e-5);
+
+        let inside = Ray::new(
+            Point3f::new(0.0, 0.0, 0.0),
+            Vector3f::new(0.0, 1.0, 0.0),
+            0.0,
+        );
+        let hit = c.intersect_hit(&inside).unwrap();
+        assert!((hit.t - 1.0).abs() < 1e-5);
+
+        let axial = Ray::new(
+            Point3f::new(0.0, 0.0, -5.0),
+            Vector3f::new(0.0, 0.0, 1.0),
+            0.0,
+        );
+        assert!(c.intersect_hit(&axial).is_none());
+    }
+
+    #[test]
+    fn area_and_samples() {
+        let c = Cylinder::new(Transform::identity(), false, 2.0, 0.0, 3.0, 180.0).unwrap();
+        assert!((c.area() - 6.0 * PI).abs() < 1e-4);
+        let s = c.sample(Point2f::new(0.5, 0.5));
+        assert!((s.p.x).abs() < 1e-5 && (s.p.y - 2.0).abs() < 1e-5 && (s.p.z - 1.5).abs() < 1e-5);
+        assert!((s.n.y - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn degenerate_cylinders_are_rejected() {
+        let cylinder = |radius, z_min, z_max, phi_max| {
+            Cylinder::new(Transform::identity(), false, radius, z_min, z_max, phi_max)
+        };
+        assert!(cylinder(0.0, -1.0, 1.0, 360.0).is_none());
+        assert!(cylinder(1.0, 0.5, 0.5, 360.0).is_none());
+        assert!(cylinder(1.0, -1.0, 1.0, 0.0).is_none());
+        assert!(cylinder(1.0, 1.0, -1.0, 90.0).is_some());
+    }
+}
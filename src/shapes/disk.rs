@@ -0,0 +1,167 @@
+use crate::shapes::quadric::{
+    Quadric, QuadricGeometry, ShapeTransform, phi_max_radians, phi_of, swept_dpdu,
+};
+use crate::util::Float;
+use crate::util::bounds::Bounds3;
+use crate::util::math::transformations::Transform;
+use crate::util::rays::Ray;
+use crate::util::vector::{Point2f, Point3f, Vector3f};
+
+/// A disk, or annulus if `inner_radius` is positive, in the plane
+/// `z = height` facing +z.
+///
+/// `u` runs with `phi` and `v` runs from the outer edge to the inner one.
+#[derive(Debug, Clone)]
+pub struct Disk {
+    transform: ShapeTransform,
+    height: Float,
+    radius: Float,
+    inner_radius: Float,
+    phi_max: Float,
+}
+
+impl Disk {
+    /// Returns `None` if `phi_max` is not positive or `inner_radius` is not
+    /// in `[0, radius)`.
+    pub fn new(
+        render_from_object: Transform,
+        reverse_orientation: bool,
+        height: Float,
+        radius: Float,
+        inner_radius: Float,
+        phi_max: Float,
+    ) -> Option<Self> {
+        if inner_radius.is_nan() || inner_radius < 0.0 || radius.is_nan() || inner_radius >= radius
+        {
+            return None;
+        }
+        Some(Self {
+            transform: ShapeTransform::new(render_from_object, reverse_orientation),
+            height,
+            radius,
+            inner_radius,
+            phi_max: phi_max_radians(phi_max)?,
+        })
+    }
+
+    fn geometry(&self, p: Point3f, phi: Float) -> QuadricGeometry {
+        let r_hit = (p.x * p.x + p.y * p.y).sqrt();
+        let zero = Vector3f::default();
+        QuadricGeometry {
+            p,
+            p_error: zero,
+            uv: Point2f::new(
+                phi / self.phi_max,
+                (self.radius - r_hit) / (self.radius - self.inner_radius),
+            ),
+            dpdu: swept_dpdu(&p, self.phi_max),
+            dpdv: Vector3f::new(p.x, p.y, 0.0) * ((self.inner_radius - self.radius) / r_hit),
+            d2pduu: zero,
+            d2pduv: zero,
+            d2pdvv: zero,
+        }
+    }
+}
+
+impl Quadric for Disk {
+    fn shape_transform(&self) -> &ShapeTransform {
+        &self.transform
+    }
+
+    fn object_bound(&self) -> Bounds3 {
+        Bounds3::from_points(
+            Point3f::new(-self.radius, -self.radius, self.height),
+            Point3f::new(self.radius, self.radius, self.height),
+        )
+    }
+
+    fn area(&self) -> Float {
+        self.phi_max * 0.5 * (self.radius * self.radius - self.inner_radius * self.inner_radius)
+    }
+
+    fn intersect_object(&self, ray: &Ray) -> Option<(Float, QuadricGeometry)> {
+        let o = ray.origin();
+        let d = ray.direction();
+        if d.z == 0.0 {
+            return None;
+        }
+        let t = (self.height - o.z) / d.z;
+        if t <= 0.0 || t >= ray.t_max() {
+            return None;
+        }
+        let mut p = ray.get(t);
+        let dist2 = p.x * p.x + p.y * p.y;
+        if dist2 > self.radius * self.radius || dist2 < self.inner_radius * self.inner_radius {
+            return None;
+        }
+        let phi = phi_of(&p);
+        if phi > self.phi_max {
+            return None;
+        }
+        // The plane is known exactly, so snap to it.
+        p.z = self.height;
+        Some((t, self.geometry(p, phi)))
+    }
+
+    fn sample_object(&self, u: Point2f) -> QuadricGeometry {
+        let ri2 = self.inner_radius * self.inner_radius;
+        let r = (ri2 + u.x * (self.radius * self.radius - ri2)).sqrt();
+        let phi = u.y * self.phi_max;
+        let p = Point3f::new(r * phi.cos(), r * phi.sin(), self.height);
+        self.geometry(p, phi)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::vector::Normal3f;
+
+    #[test]
+    fn annulus_hits_between_radii() {
+        let d = Disk::new(Transform::identity(), false, 1.0, 2.0, 0.5, 360.0).unwrap();
+        let down = |x: Float| {
+            Ray::new(
+                Point3f::new(x, 0.0, 3.0),
+                Vector3f::new(0.0, 0.0, -1.0),
+                0.0,
+            )
+        };
+        let hit = d.intersect_hit(&down(1.0)).unwrap();
+        assert!((hit.t - 2.0).abs() < 1e-5);
+        assert_eq!(hit.n, Normal3f::new(0.0, 0.0, 1.0));
+        assert!((hit.uv.y - 2.0 / 3.0).abs() < 1e-5);
+        assert!(d.intersect_hit(&down(0.25)).is_none());
+        assert!(d.intersect_hit(&down(2.5)).is_none());
+    }
+
+    #[test]
+    fn samples_cover_the_annulus() {
+        let d = Disk::new(Transform::identity(), false, 0.0, 1.0, 0.5, 90.0).unwrap();
+        for i in 0..8 {
+            let s = d.sample(Point2f::new(i as Float / 8.0, 1.0 - i as Float / 8.0));
+            let r = (s.p.x * s.p.x + s.p.y * s.p.y).sqrt();
+            assert!((0.5 - 1e-5..=1.0 + 1e-5).contains(&r));
+            assert!(s.p.x >= -1e-5 && s.p.y >= -1e-5);
+        }
+    }
+
+    #[test]
+    fn degenerate_disks_are_rejected() {
+        let disk = |radius, inner_radius, phi_max| {
+            Disk::new(
+                Transform::identity(),
+                false,
+                0.0,
+                radius,
+                inner_radius,
+                phi_max,
+            )
+        };
+        assert!(disk(0.0, 0.0, 360.0).is_none());
+        assert!(disk(1.0, 1.0, 360.0).is_none());
+        assert!(disk(1.0, -0.5, 360.0).is_none());
+        assert!(disk(1.0, 0.0, -10.0).is_none());
+        assert!(disk(1.0, 0.5, 360.0).is_some());
+    }
+}
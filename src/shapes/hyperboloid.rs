@@ -0,0 +1,281 @@
+use crate::shapes::quadric::{
+    Quadric, QuadricGeometry, ShapeTransform, first_root, phi_max_radians, ray_intervals,
+    swept_d2pduu, swept_dpdu,
+};
+use crate::util::Float;
+use crate::util::bounds::Bounds3;
+use crate::util::math::transformations::Transform;
+use crate::util::math::{Interval, PI, gamma};
+use crate::util::rays::Ray;
+use crate::util::vector::{Point2f, Point3f, Vector3f};
+
+/// The surface swept by rotating the segment from `p1` to `p2` around the
+/// z axis: a hyperboloid of one sheet, or a cone or cylinder in the
+/// degenerate cases.
+///
+/// `u` runs with the rotation and `v` along the segment.
+#[derive(Debug, Clone)]
+pub struct Hyperboloid {
+    transform: ShapeTransform,
+    p1: Point3f,
+    p2: Point3f,
+    z_min: Float,
+    z_max: Float,
+    r_max: Float,
+    phi_max: Float,
+    /// Coefficients of the implicit form `ah * (x^2 + y^2) - ch * z^2 = 1`.
+    ah: Float,
+    ch: Float,
+}
+
+impl Hyperboloid {
+    /// Returns `None` if the segment is horizontal, which sweeps a disk
+    /// rather than a hyperboloid, or if `phi_max` is not positive.
+    pub fn new(
+        render_from_object: Transform,
+        reverse_orientation: bool,
+        p1: Point3f,
+        p2: Point3f,
+        phi_max: Float,
+    ) -> Option<Self> {
+        if p1.z == p2.z {
+            return None;
+        }
+        let radius = |p: &Point3f| (p.x * p.x + p.y * p.y).sqrt();
+
+        // Solve for ah and ch using p2 and another point on the line. If
+        // that point happens to make the system singular, move further
+        // along the line.
+        let (mut ah, mut ch) = (Float::INFINITY, Float::INFINITY);
+        let mut pp = p1;
+        for _ in 0..32 {
+            if ah.is_finite() && ch.is_finite() {
+                break;
+            }
+            pp += (p2 - p1) * 2.0;
+            let xy1 = pp.x * pp.x + pp.y * pp.y;
+            let xy2 = p2.x * p2.x + p2.y * p2.y;
+            ah = (1.0 / xy1 - (pp.z * pp.z) / (xy1 * p2.z * p2.z))
+                / (1.0 - (xy2 * pp.z * pp.z) / (xy1 * p2.z * p2.z));
+            ch = (ah * xy2 - 1.0) / (p2.z * p2.z);
+        }
+
+        Some(Self {
+            transform: ShapeTransform::new(render_from_object, reverse_orientation),
+            p1,
+            p2,
+            z_min: p1.z.min(p2.z),
+            z_max: p1.z.max(p2.z),
+            r_max: radius(&p1).max(radius(&p2)),
+            phi_max: phi_max_radians(phi_max)?,
+            ah,
+            ch,
+        })
+    }
+
+    /// Point on the generating segment at parameter `v`.
+    fn segment_point(&self, v: Float) -> Point3f {
+        self.p1 + (self.p2 - self.p1) * v
+    }
+
+    fn geometry(&self, p: Point3f, phi: Float, v: Float) -> QuadricGeometry {
+        let (sin_phi, cos_phi) = phi.sin_cos();
+        let d = self.p2 - self.p1;
+        let dpdv = Vector3f::new(
+            d.x * cos_phi - d.y * sin_phi,
+            d.x * sin_phi + d.y * cos_phi,
+            d.z,
+        );
+        QuadricGeometry {
+            p,
+            p_error: Vector3f::from(p).abs() * gamma(10),
+            uv: Point2f::new(phi / self.phi_max, v),
+            dpdu: swept_dpdu(&p, self.phi_max),
+            dpdv,
+            d2pduu: swept_d2pduu(&p, self.phi_max),
+            d2pduv: Vector3f::new(-dpdv.y, dpdv.x, 0.0) * self.phi_max,
+            d2pdvv: Vector3f::default(),
+        }
+    }
+
+    /// Antiderivative in `v` of the area swept per unit angle.
+    ///
+    /// Rotating the segment point `q(v)` sweeps `|dq/dphi x dq/dv|`, which is
+    /// `sqrt(a v^2 + b v + c)` with `a >= 0`; the integral has a closed form.
+    fn swept_area_antiderivative(&self, v: Float) -> Float {
+        let d = self.p2 - self.p1;
+        let dxy2 = d.x * d.x + d.y * d.y;
+        let d2 = d.length_squared();
+        // The z component of p1 x d is constant along the segment.
+        let cross = self.p1.x * d.y - self.p1.y * d.x;
+        let a = dxy2 * d2;
+        let b = 2.0 * (self.p1.x * d.x + self.p1.y * d.y) * d2;
+        let c = (self.p1.x * self.p1.x + self.p1.y * self.p1.y) * d2 - cross * cross;
+        if a == 0.0 {
+            return c.max(0.0).sqrt() * v;
+        }
+        // With w = v + b / 2a, the integrand is sqrt(a) * sqrt(w^2 + k).
+        let h = b / (2.0 * a);
+        let w = v + h;
+        let k = (c / a - h * h).max(0.0);
+        let s = (w * w + k).sqrt();
+        let tail = if k > 0.0 {
+            k * (w / k.sqrt()).asinh()
+        } else {
+            0.0
+        };
+        a.sqrt() * 0.5 * (w * s + tail)
+    }
+}
+
+impl Quadric for Hyperboloid {
+    fn shape_transform(&self) -> &ShapeTransform {
+        &self.transform
+    }
+
+    fn object_bound(&self) -> Bounds3 {
+        Bounds3::from_points(
+            Point3f::new(-self.r_max, -self.r_max, self.z_min),
+            Point3f::new(self.r_max, self.r_max, self.z_max),
+        )
+    }
+
+    fn area(&self) -> Float {
+        self.phi_max * (self.swept_area_antiderivative(1.0) - self.swept_area_antiderivative(0.0))
+    }
+
+    fn intersect_object(&self, ray: &Ray) -> Option<(Float, QuadricGeometry)> {
+        let ([ox, oy, oz], [dx, dy, dz]) = ray_intervals(ray);
+        let (ah, ch) = (Interval::new(self.ah), Interval::new(self.ch));
+        let a = ah * (dx.sqr() + dy.sqr()) - ch * dz.sqr();
+        let b = (ah * (dx * ox + dy * oy) - ch * dz * oz) * 2.0;
+        let c = ah * (ox.sqr() + oy.sqr()) - ch * oz.sqr() - 1.0;
+        let roots = Interval::quadratic(a, b, c)?;
+        first_root(roots, ray.t_max(), |t| {
+            let p = ray.get(t);
+            let v = (p.z - self.p1.z) / (self.p2.z - self.p1.z);
+            // phi is measured from the segment point at the same height.
+            let pr = self.segment_point(v);
+            let mut phi = (pr.x * p.y - p.x * pr.y).atan2(p.x * pr.x + p.y * pr.y);
+            if phi < 0.0 {
+                phi += 2.0 * PI;
+            }
+            let clipped = p.z < self.z_min || p.z > self.z_max || phi > self.phi_max;
+            (!clipped).then(|| self.geometry(p, phi, v))
+        })
+    }
+
+    fn sample_object(&self, u: Point2f) -> QuadricGeometry {
+        // The swept area is monotonic in v, so invert it by bisection.
+        let f0 = self.swept_area_antiderivative(0.0);
+        let target = f0 + u.x * (self.swept_area_antiderivative(1.0) - f0);
+        let (mut lo, mut hi) = (0.0, 1.0);
+        for _ in 0..40 {
+            let mid = 0.5 * (lo + hi);
+            if self.swept_area_antiderivative(mid) < target {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        let v = 0.5 * (lo + hi);
+        let phi = u.y * self.phi_max;
+        let (sin_phi, cos_phi) = phi.sin_cos();
+        let q = self.segment_point(v);
+        let p = Point3f::new(
+            q.x * cos_phi - q.y * sin_phi,
+            q.x * sin_phi + q.y * cos_phi,
+            q.z,
+        );
+        self.geometry(p, phi, v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn twisted() -> Hyperboloid {
+        Hyperboloid::new(
+            Transform::identity(),
+            false,
+            Point3f::new(1.0, -1.0, -1.0),
+            Point3f::new(1.0, 1.0, 1.0),
+            360.0,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn horizontal_segment_is_rejected() {
+        let h = Hyperboloid::new(
+            Transform::identity(),
+            false,
+            Point3f::new(1.0, 0.0, 0.0),
+            Point3f::new(0.0, 1.0, 0.0),
+            360.0,
+        );
+        assert!(h.is_none());
+        let h = Hyperboloid::new(
+            Transform::identity(),
+            false,
+            Point3f::new(1.0, 0.0, 0.0),
+            Point3f::new(0.0, 1.0, 1.0),
+            0.0,
+        );
+        assert!(h.is_none());
+    }
+
+    #[test]
+    fn waist_is_hit_at_minimum_radius() {
+        // The segment passes closest to the axis at (1, 0, 0).
+        let h = twisted();
+        let ray = Ray::new(
+            Point3f::new(-5.0, 0.0, 0.0),
+            Vector3f::new(1.0, 0.0, 0.0),
+            0.0,
+        );
+        let hit = h.intersect_hit(&ray).unwrap();
+        assert!((hit.p.x + 1.0).abs() < 1e-4);
+        assert!((hit.uv.y - 0.5).abs() < 1e-4);
+        let n = hit.dpdu.cross(&hit.dpdv).normalize();
+        assert!((n - Vector3f::from(hit.n)).length() < 1e-4);
+    }
+
+    #[test]
+    fn closed_form_area_matches_numeric_integration() {
+        let h = twisted();
+        let steps = 2000;
+        let d = h.p2 - h.p1;
+        let numeric: Float = (0..steps)
+            .map(|i| {
+                let v = (i as Float + 0.5) / steps as Float;
+                let q = h.segment_point(v);
+                let dq_dphi = Vector3f::new(-q.y, q.x, 0.0);
+                dq_dphi.cross(&d).length() / steps as Float
+            })
+            .sum::<Float>()
+            * h.phi_max;
+        assert!((h.area() - numeric).abs() < 1e-3 * numeric);
+
+        let cylinder = Hyperboloid::new(
+            Transform::identity(),
+            false,
+            Point3f::new(2.0, 0.0, 0.0),
+            Point3f::new(2.0, 0.0, 3.0),
+            360.0,
+        )
+        .unwrap();
+        assert!((cylinder.area() - 2.0 * PI * 2.0 * 3.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn samples_satisfy_implicit_equation() {
+        let h = twisted();
+        for i in 0..8 {
+            let s = h.sample(Point2f::new((i as Float + 0.5) / 8.0, 0.7));
+            let implicit = h.ah * (s.p.x * s.p.x + s.p.y * s.p.y) - h.ch * s.p.z * s.p.z;
+            assert!((implicit - 1.0).abs() < 1e-3);
+        }
+    }
+}
@@ -0,0 +1,18 @@
+mod cone;
+mod cylinder;
+mod disk;
+mod hyperboloid;
+mod paraboloid;
+mod quadric;
+mod shape;
+mod sphere;
+mod triangle;
+pub use cone::Cone;
+pub use cylinder::Cylinder;
+pub use disk::Disk;
+pub use hyperboloid::Hyperboloid;
+pub use paraboloid::Paraboloid;
+pub use quadric::{Quadric, QuadricGeometry, QuadricHit, ShapeSample, ShapeTransform};
+pub use shape::Shape;
+pub use sphere::Sphere;
+pub use triangle::{Triangle, TriangleHit, TriangleIntersection, TriangleMesh, intersect_triangle};
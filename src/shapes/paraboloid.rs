@@ -0,0 +1,158 @@
+use crate::shapes::quadric::{
+    Quadric, QuadricGeometry, ShapeTransform, first_root, phi_max_radians, phi_of, ray_intervals,
+    swept_d2pduu, swept_dpdu,
+};
+use crate::util::Float;
+use crate::util::bounds::Bounds3;
+use crate::util::math::transformations::Transform;
+use crate::util::math::{Interval, gamma};
+use crate::util::rays::Ray;
+use crate::util::vector::{Point2f, Point3f, Vector3f};
+
+/// The paraboloid `z = z_max * (x^2 + y^2) / radius^2`, clipped to
+/// `z_min..z_max`.
+#[derive(Debug, Clone)]
+pub struct Paraboloid {
+    transform: ShapeTransform,
+    radius: Float,
+    z_min: Float,
+    z_max: Float,
+    phi_max: Float,
+}
+
+impl Paraboloid {
+    /// Returns `None` if the radius or `phi_max` is not positive or the z
+    /// range, once clamped to `z >= 0`, is empty.
+    pub fn new(
+        render_from_object: Transform,
+        reverse_orientation: bool,
+        radius: Float,
+        z_min: Float,
+        z_max: Float,
+        phi_max: Float,
+    ) -> Option<Self> {
+        let (z_lo, z_hi) = (z_min.min(z_max).max(0.0), z_min.max(z_max));
+        if radius.is_nan() || radius <= 0.0 || z_hi.is_nan() || z_lo >= z_hi {
+            return None;
+        }
+        Some(Self {
+            transform: ShapeTransform::new(render_from_object, reverse_orientation),
+            radius,
+            z_min: z_lo,
+            z_max: z_hi,
+            phi_max: phi_max_radians(phi_max)?,
+        })
+    }
+
+    /// `k` such that the area up to height `z` is proportional to
+    /// `(k * z + 1)^(3/2)`.
+    fn area_k(&self) -> Float {
+        4.0 * self.z_max / (self.radius * self.radius)
+    }
+
+    fn geometry(&self, p: Point3f, phi: Float) -> QuadricGeometry {
+        let dz = self.z_max - self.z_min;
+        let two_z = 2.0 * p.z;
+        QuadricGeometry {
+            p,
+            p_error: Vector3f::from(p).abs() * gamma(7),
+            uv: Point2f::new(phi / self.phi_max, (p.z - self.z_min) / dz),
+            dpdu: swept_dpdu(&p, self.phi_max),
+            dpdv: Vector3f::new(p.x / two_z, p.y / two_z, 1.0) * dz,
+            d2pduu: swept_d2pduu(&p, self.phi_max),
+            d2pduv: Vector3f::new(-p.y / two_z, p.x / two_z, 0.0) * (dz * self.phi_max),
+            d2pdvv: Vector3f::new(p.x, p.y, 0.0) * (-dz * dz / (two_z * two_z)),
+        }
+    }
+}
+
+impl Quadric for Paraboloid {
+    fn shape_transform(&self) -> &ShapeTransform {
+        &self.transform
+    }
+
+    fn object_bound(&self) -> Bounds3 {
+        Bounds3::from_points(
+            Point3f::new(-self.radius, -self.radius, self.z_min),
+            Point3f::new(self.radius, self.radius, self.z_max),
+        )
+    }
+
+    fn area(&self) -> Float {
+        let r2 = self.radius * self.radius;
+        let k = self.area_k();
+        (r2 * r2 * self.phi_max / (12.0 * self.z_max * self.z_max))
+            * ((k * self.z_max + 1.0).powf(1.5) - (k * self.z_min + 1.0).powf(1.5))
+    }
+
+    fn intersect_object(&self, ray: &Ray) -> Option<(Float, QuadricGeometry)> {
+        let ([ox, oy, oz], [dx, dy, dz]) = ray_intervals(ray);
+        let k = Interval::new(self.z_max) / Interval::new(self.radius).sqr();
+        let a = k * (dx.sqr() + dy.sqr());
+        let b = k * (dx * ox + dy * oy) * 2.0 - dz;
+        let c = k * (ox.sqr() + oy.sqr()) - oz;
+        let roots = Interval::quadratic(a, b, c)?;
+        first_root(roots, ray.t_max(), |t| {
+            let p = ray.get(t);
+            let phi = phi_of(&p);
+            let clipped = p.z < self.z_min || p.z > self.z_max || phi > self.phi_max;
+            // The vertex has no well-defined derivatives.
+            (!clipped && p.z > 0.0).then(|| self.geometry(p, phi))
+        })
+    }
+
+    fn sample_object(&self, u: Point2f) -> QuadricGeometry {
+        // Invert the area as a function of z, which has a closed form.
+        let k = self.area_k();
+        let a0 = (k * self.z_min + 1.0).powf(1.5);
+        let a1 = (k * self.z_max + 1.0).powf(1.5);
+        let w = a0 + u.x * (a1 - a0);
+        let z = (w.powf(2.0 / 3.0) - 1.0) / k;
+        let r = self.radius * (z / self.z_max).max(0.0).sqrt();
+        let phi = u.y * self.phi_max;
+        self.geometry(Point3f::new(r * phi.cos(), r * phi.sin(), z), phi)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hit_from_above_lands_on_surface() {
+        let p = Paraboloid::new(Transform::identity(), false, 1.0, 0.0, 1.0, 360.0).unwrap();
+        let ray = Ray::new(
+            Point3f::new(0.5, 0.0, 5.0),
+            Vector3f::new(0.0, 0.0, -1.0),
+            0.0,
+        );
+        let hit = p.intersect_hit(&ray).unwrap();
+        assert!((hit.p.z - 0.25).abs() < 1e-5);
+        let n = hit.dpdu.cross(&hit.dpdv).normalize();
+        assert!((n - Vector3f::from(hit.n)).length() < 1e-4);
+    }
+
+    #[test]
+    fn sampled_area_is_uniform_in_z() {
+        // Half of the samples must fall below the height that splits the
+        // area in two.
+        let p = Paraboloid::new(Transform::identity(), false, 1.0, 0.0, 1.0, 360.0).unwrap();
+        let z_half = p.sample(Point2f::new(0.5, 0.0)).p.z;
+        let mut upper = p.clone();
+        upper.z_min = z_half;
+        assert!((upper.area() - p.area() / 2.0).abs() < 1e-3 * p.area());
+        let s = p.sample(Point2f::new(0.25, 0.5));
+        assert!((s.p.x * s.p.x + s.p.y * s.p.y - s.p.z).abs() < 1e-5);
+    }
+
+    #[test]
+    fn degenerate_paraboloids_are_rejected() {
+        let paraboloid = |radius, z_min, z_max| {
+            Paraboloid::new(Transform::identity(), false, radius, z_min, z_max, 360.0)
+        };
+        assert!(paraboloid(0.0, 0.0, 1.0).is_none());
+        assert!(paraboloid(1.0, 0.5, 0.5).is_none());
+        assert!(paraboloid(1.0, -2.0, -1.0).is_none());
+        assert!(paraboloid(1.0, -1.0, 1.0).is_some());
+    }
+}
@@ -0,0 +1,236 @@
+use crate::accel::Primitive;
+use crate::util::Float;
+use crate::util::bounds::Bounds3;
+use crate::util::interactions::SurfaceInteraction;
+use crate::util::math::transformations::Transform;
+use crate::util::math::{Interval, PI, difference_of_products};
+use crate::util::rays::Ray;
+use crate::util::vector::{Normal3f, Point2f, Point3f, Vector3f};
+
+/// Placement of a shape in the scene: the transforms in both directions and
+/// whether its normals should point inward.
+#[derive(Debug, Clone, Copy)]
+pub struct ShapeTransform {
+    pub render_from_object: Transform,
+    pub object_from_render: Transform,
+    pub reverse_orientation: bool,
+    transform_swaps_handedness: bool,
+}
+
+impl ShapeTransform {
+    pub fn new(render_from_object: Transform, reverse_orientation: bool) -> Self {
+        Self {
+            render_from_object,
+            object_from_render: render_from_object.inverse(),
+            reverse_orientation,
+            transform_swaps_handedness: render_from_object.swaps_handedness(),
+        }
+    }
+
+    /// Whether the normal computed as `dpdu x dpdv` has to be negated.
+    fn flip_normal(&self) -> bool {
+        self.reverse_orientation ^ self.transform_swaps_handedness
+    }
+
+    /// Finishes an object-space hit: derives the normal and its derivatives
+    /// and moves everything to render space.
+    pub fn hit(&self, t: Float, g: &QuadricGeometry) -> QuadricHit {
+        // Weingarten equations give the change in normal from the first and
+        // second fundamental forms.
+        let e1 = g.dpdu.dot(&g.dpdu);
+        let f1 = g.dpdu.dot(&g.dpdv);
+        let g1 = g.dpdv.dot(&g.dpdv);
+        let n = g.dpdu.cross(&g.dpdv).normalize();
+        let e2 = n.dot(&g.d2pduu);
+        let f2 = n.dot(&g.d2pduv);
+        let g2 = n.dot(&g.d2pdvv);
+        let egf2 = difference_of_products(e1, g1, f1, f1);
+        let inv_egf2 = if egf2 == 0.0 { 0.0 } else { 1.0 / egf2 };
+        let dndu =
+            g.dpdu * ((f2 * f1 - e2 * g1) * inv_egf2) + g.dpdv * ((e2 * f1 - f2 * e1) * inv_egf2);
+        let dndv =
+            g.dpdu * ((g2 * f1 - f2 * g1) * inv_egf2) + g.dpdv * ((f2 * f1 - g2 * e1) * inv_egf2);
+
+        let n = if self.flip_normal() { -n } else { n };
+        let to_render = &self.render_from_object;
+        let (p, p_error) = to_render.transform_point_with_error(&g.p, &g.p_error);
+        QuadricHit {
+            t,
+            p,
+            p_error,
+            uv: g.uv,
+            n: to_render.transform_normal(&Normal3f::from(n)).normalize(),
+            dpdu: to_render.transform_vector(&g.dpdu),
+            dpdv: to_render.transform_vector(&g.dpdv),
+            dndu: to_render.transform_normal(&Normal3f::from(dndu)),
+            dndv: to_render.transform_normal(&Normal3f::from(dndv)),
+        }
+    }
+}
+
+/// Local geometry at a point of a quadric, in the shape's object space.
+#[derive(Debug, Clone, Copy)]
+pub struct QuadricGeometry {
+    pub p: Point3f,
+    pub p_error: Vector3f,
+    pub uv: Point2f,
+    pub dpdu: Vector3f,
+    pub dpdv: Vector3f,
+    pub d2pduu: Vector3f,
+    pub d2pduv: Vector3f,
+    pub d2pdvv: Vector3f,
+}
+
+/// A ray hit on a quadric, in render space.
+#[derive(Debug, Clone, Copy)]
+pub struct QuadricHit {
+    pub t: Float,
+    pub p: Point3f,
+    /// Conservative bound on the absolute error in `p`.
+    pub p_error: Vector3f,
+    pub uv: Point2f,
+    pub n: Normal3f,
+    pub dpdu: Vector3f,
+    pub dpdv: Vector3f,
+    pub dndu: Normal3f,
+    pub dndv: Normal3f,
+}
+
+impl QuadricHit {
+    /// The full surface record for this hit of `ray`.
+    pub fn interaction(&self, ray: &Ray) -> SurfaceInteraction {
+        // `n` already accounts for orientation; recover the flag that makes
+        // `dpdu x dpdv` agree with it.
+        let flip = self.n.dot_vector(&self.dpdu.cross(&self.dpdv)) < 0.0;
+        SurfaceInteraction::new(
+            self.p,
+            self.p_error,
+            self.uv,
+            -ray.direction().normalize(),
+            self.dpdu,
+            self.dpdv,
+            self.dndu,
+            self.dndv,
+            ray.time(),
+            flip,
+        )
+    }
+}
+
+/// A point sampled on the surface of a shape.
+#[derive(Debug, Clone, Copy)]
+pub struct ShapeSample {
+    pub p: Point3f,
+    pub n: Normal3f,
+    /// Density with respect to surface area.
+    pub pdf: Float,
+}
+
+/// Shapes defined implicitly by a quadratic equation and swept around the
+/// object-space z axis.
+///
+/// Implementors work entirely in object space; the provided methods handle
+/// moving rays in and results out. Areas and sample densities are measured
+/// in object space.
+pub trait Quadric {
+    fn shape_transform(&self) -> &ShapeTransform;
+    fn object_bound(&self) -> Bounds3;
+    fn area(&self) -> Float;
+    /// Closest hit of an object-space ray in `(0, ray.t_max())`.
+    fn intersect_object(&self, ray: &Ray) -> Option<(Float, QuadricGeometry)>;
+    /// Point on the surface for a uniform sample `u` in `[0, 1)^2`,
+    /// distributed uniformly by area.
+    fn sample_object(&self, u: Point2f) -> QuadricGeometry;
+
+    fn intersect_hit(&self, ray: &Ray) -> Option<QuadricHit> {
+        let st = self.shape_transform();
+        // Moving the origin into object space shortens the valid range, so
+        // the limit has to travel with the ray.
+        let ray = st.object_from_render.transform_ray(ray);
+        let (t, g) = self.intersect_object(&ray)?;
+        Some(st.hit(t, &g))
+    }
+
+    fn sample(&self, u: Point2f) -> ShapeSample {
+        let st = self.shape_transform();
+        let hit = st.hit(0.0, &self.sample_object(u));
+        ShapeSample {
+            p: hit.p,
+            n: hit.n,
+            pdf: 1.0 / self.area(),
+        }
+    }
+}
+
+impl<Q: Quadric> Primitive for Q {
+    fn bounds(&self) -> Bounds3 {
+        self.shape_transform()
+            .render_from_object
+            .transform_bounds(&self.object_bound())
+    }
+    fn intersect(&self, ray: &Ray) -> Option<Float> {
+        let ray = self.shape_transform().object_from_render.transform_ray(ray);
+        self.intersect_object(&ray).map(|(t, _)| t)
+    }
+}
+
+/// Tries the two roots of a quadric in order and returns the first one that
+/// certainly lies in `(0, t_max)` and that `hit_at` accepts, which is where
+/// the clipping of partial shapes happens.
+///
+/// The roots are intervals so that a hit whose error bounds reach back to
+/// the ray origin, such as the surface a secondary ray was just spawned
+/// from, is rejected rather than reported at a tiny `t`.
+pub(crate) fn first_root<R>(
+    (t0, t1): (Interval, Interval),
+    t_max: Float,
+    mut hit_at: impl FnMut(Float) -> Option<R>,
+) -> Option<(Float, R)> {
+    if t0.upper_bound() > t_max || t1.lower_bound() <= 0.0 {
+        return None;
+    }
+    for t in [t0, t1] {
+        if t.lower_bound() > 0.0
+            && t.upper_bound() < t_max
+            && let Some(r) = hit_at(t.midpoint())
+        {
+            return Some((t.midpoint(), r));
+        }
+    }
+    None
+}
+
+/// Origin and direction of an object-space ray as exact intervals, to seed
+/// the error-tracking root computations.
+pub(crate) fn ray_intervals(ray: &Ray) -> ([Interval; 3], [Interval; 3]) {
+    let (o, d) = (ray.origin(), ray.direction());
+    (
+        [o.x, o.y, o.z].map(Interval::new),
+        [d.x, d.y, d.z].map(Interval::new),
+    )
+}
+
+/// Angle of `p` around the z axis in `[0, 2pi)`.
+pub(crate) fn phi_of(p: &Point3f) -> Float {
+    let phi = p.y.atan2(p.x);
+    if phi < 0.0 { phi + 2.0 * PI } else { phi }
+}
+
+/// Sweep angle in radians from one in degrees, clamped to a full turn, or
+/// `None` if it sweeps nothing.
+pub(crate) fn phi_max_radians(degrees: Float) -> Option<Float> {
+    if degrees.is_nan() || degrees <= 0.0 {
+        return None;
+    }
+    Some(degrees.min(360.0).to_radians())
+}
+
+/// `d2p/du2` of any surface swept around z by `u * phi_max`.
+pub(crate) fn swept_d2pduu(p: &Point3f, phi_max: Float) -> Vector3f {
+    Vector3f::new(p.x, p.y, 0.0) * (-phi_max * phi_max)
+}
+
+/// `dp/du` of any surface swept around z by `u * phi_max`.
+pub(crate) fn swept_dpdu(p: &Point3f, phi_max: Float) -> Vector3f {
+    Vector3f::new(-phi_max * p.y, phi_max * p.x, 0.0)
+}
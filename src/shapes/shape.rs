@@ -0,0 +1,172 @@
+use crate::accel::Primitive;
+use crate::shapes::{Cone, Cylinder, Disk, Hyperboloid, Paraboloid, Quadric, Sphere, Triangle};
+use crate::util::Float;
+use crate::util::bounds::Bounds3;
+use crate::util::interactions::SurfaceInteraction;
+use crate::util::rays::Ray;
+
+/// Any of the shapes a scene can be made of, so that a single acceleration
+/// structure can hold all of them.
+#[derive(Debug, Clone)]
+pub enum Shape {
+    Sphere(Sphere),
+    Cylinder(Cylinder),
+    Disk(Disk),
+    Cone(Cone),
+    Paraboloid(Paraboloid),
+    Hyperboloid(Hyperboloid),
+    Triangle(Triangle),
+}
+
+impl Shape {
+    /// Distance to the closest hit with `ray` in `(0, ray.t_max())` and the
+    /// surface record there.
+    pub fn intersect_interaction(&self, ray: &Ray) -> Option<(Float, SurfaceInteraction)> {
+        match self {
+            Shape::Sphere(s) => s.intersect_hit(ray).map(|h| (h.t, h.interaction(ray))),
+            Shape::Cylinder(s) => s.intersect_hit(ray).map(|h| (h.t, h.interaction(ray))),
+            Shape::Disk(s) => s.intersect_hit(ray).map(|h| (h.t, h.interaction(ray))),
+            Shape::Cone(s) => s.intersect_hit(ray).map(|h| (h.t, h.interaction(ray))),
+            Shape::Paraboloid(s) => s.intersect_hit(ray).map(|h| (h.t, h.interaction(ray))),
+            Shape::Hyperboloid(s) => s.intersect_hit(ray).map(|h| (h.t, h.interaction(ray))),
+            Shape::Triangle(s) => s.intersect_hit(ray).map(|h| (h.t, h.interaction(ray))),
+        }
+    }
+
+    fn as_primitive(&self) -> &dyn Primitive {
+        match self {
+            Shape::Sphere(s) => s,
+            Shape::Cylinder(s) => s,
+            Shape::Disk(s) => s,
+            Shape::Cone(s) => s,
+            Shape::Paraboloid(s) => s,
+            Shape::Hyperboloid(s) => s,
+            Shape::Triangle(s) => s,
+        }
+    }
+}
+
+impl Primitive for Shape {
+    fn bounds(&self) -> Bounds3 {
+        self.as_primitive().bounds()
+    }
+    fn intersect(&self, ray: &Ray) -> Option<Float> {
+        self.as_primitive().intersect(ray)
+    }
+    fn intersect_p(&self, ray: &Ray) -> bool {
+        self.as_primitive().intersect_p(ray)
+    }
+}
+
+macro_rules! impl_from_shape {
+    ($($kind:ident),*) => {
+        $(impl From<$kind> for Shape {
+            fn from(s: $kind) -> Self {
+                Shape::$kind(s)
+            }
+        })*
+    };
+}
+impl_from_shape!(
+    Sphere,
+    Cylinder,
+    Disk,
+    Cone,
+    Paraboloid,
+    Hyperboloid,
+    Triangle
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::accel::{Bvh, SplitMethod};
+    use crate::shapes::TriangleMesh;
+    use crate::util::interactions::Interaction;
+    use crate::util::math::transformations::Transform;
+    use crate::util::vector::{Normal3f, Point2f, Point3f, Vector3f};
+    use std::sync::Arc;
+
+    #[test]
+    fn mixed_shapes_share_one_bvh() {
+        let sphere = Sphere::full(
+            Transform::translate(Vector3f::new(0.0, 0.0, 5.0)),
+            false,
+            1.0,
+        );
+        let mesh = Arc::new(TriangleMesh::new(
+            &Transform::identity(),
+            false,
+            vec![[0, 1, 2]],
+            vec![
+                Point3f::new(-1.0, -1.0, 2.0),
+                Point3f::new(1.0, -1.0, 2.0),
+                Point3f::new(0.0, 1.0, 2.0),
+            ],
+            None,
+            None,
+            None,
+        ));
+        let mut shapes: Vec<Shape> = vec![sphere.into()];
+        shapes.extend(TriangleMesh::triangles(&mesh).into_iter().map(Shape::from));
+        let bvh = Bvh::new(shapes, 1, SplitMethod::Sah);
+
+        let ray = Ray::new(
+            Point3f::new(0.0, 0.0, 0.0),
+            Vector3f::new(0.0, 0.0, 1.0),
+            0.0,
+        );
+        let hit = bvh.intersect(&ray).unwrap();
+        assert!(matches!(
+            bvh.primitives()[hit.primitive],
+            Shape::Triangle(_)
+        ));
+        assert!((hit.t - 2.0).abs() < 1e-5);
+        let (with, si) = bvh
+            .intersect_with(&ray, |s, r| s.intersect_interaction(r))
+            .unwrap();
+        assert_eq!(with, hit);
+        assert!((si.p.z - 2.0).abs() < 1e-5 && si.n.z.abs() > 0.999);
+
+        let ray = Ray::new(
+            Point3f::new(3.0, 0.0, 0.0),
+            Vector3f::new(0.0, 0.0, 1.0),
+            0.0,
+        );
+        assert!(bvh.intersect(&ray).is_none());
+        let ray = Ray::new(
+            Point3f::new(0.0, 0.0, 3.0),
+            Vector3f::new(0.0, 0.0, 1.0),
+            0.0,
+        );
+        let hit = bvh.intersect(&ray).unwrap();
+        assert!((hit.t - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn shadow_rays_ignore_surfaces_beyond_the_target() {
+        let sphere = Sphere::full(
+            Transform::translate(Vector3f::new(0.0, 0.0, 5.0)),
+            false,
+            1.0,
+        );
+        let bvh = Bvh::new(vec![Shape::from(sphere)], 1, SplitMethod::Sah);
+        let it = Interaction::new(
+            Point3f::new(0.0, 0.0, 0.0),
+            Vector3f::default(),
+            Normal3f::new(0.0, 0.0, 1.0),
+            Point2f::default(),
+            Vector3f::new(0.0, 0.0, 1.0),
+            0.0,
+        );
+
+        // The sphere's near side is at z = 4, past the target.
+        let r = it.spawn_ray_to_point(Point3f::new(0.0, 0.0, 3.0));
+        assert!(bvh.intersect(&r).is_none());
+        assert!(!bvh.intersect_p(&r));
+        assert!(bvh.intersect_p(&r.with_t_max(Float::INFINITY)));
+
+        let r = it.spawn_ray_to_point(Point3f::new(0.0, 0.0, 4.5));
+        assert!(bvh.intersect_p(&r));
+    }
+}
@@ -0,0 +1,281 @@
+use crate::shapes::quadric::{
+    Quadric, QuadricGeometry, ShapeTransform, first_root, phi_max_radians, phi_of, ray_intervals,
+    swept_d2pduu, swept_dpdu,
+};
+use crate::util::Float;
+use crate::util::bounds::Bounds3;
+use crate::util::math::transformations::Transform;
+use crate::util::math::{Interval, gamma, spherical_direction};
+use crate::util::rays::Ray;
+use crate::util::vector::{Point2f, Point3f, Vector3f};
+
+/// A sphere centered at the object-space origin, optionally clipped to
+/// `z_min..z_max` and swept only through `phi_max` degrees.
+///
+/// `u` runs with `phi` and `v` runs with `theta` from `z_min` to `z_max`.
+#[derive(Debug, Clone)]
+pub struct Sphere {
+    transform: ShapeTransform,
+    radius: Float,
+    z_min: Float,
+    z_max: Float,
+    theta_z_min: Float,
+    theta_z_max: Float,
+    phi_max: Float,
+}
+
+impl Sphere {
+    /// Returns `None` if the radius or `phi_max` is not positive or the z
+    /// range, once clamped to the sphere, is empty, which leaves no `theta`
+    /// range to parameterize `v` over.
+    pub fn new(
+        render_from_object: Transform,
+        reverse_orientation: bool,
+        radius: Float,
+        z_min: Float,
+        z_max: Float,
+        phi_max: Float,
+    ) -> Option<Self> {
+        if radius.is_nan() || radius <= 0.0 {
+            return None;
+        }
+        let z_lo = z_min.min(z_max).clamp(-radius, radius);
+        let z_hi = z_min.max(z_max).clamp(-radius, radius);
+        if z_lo.is_nan() || z_lo >= z_hi {
+            return None;
+        }
+        Some(Self {
+            transform: ShapeTransform::new(render_from_object, reverse_orientation),
+            radius,
+            z_min: z_lo,
+            z_max: z_hi,
+            theta_z_min: (z_lo / radius).clamp(-1.0, 1.0).acos(),
+            theta_z_max: (z_hi / radius).clamp(-1.0, 1.0).acos(),
+            phi_max: phi_max_radians(phi_max)?,
+        })
+    }
+
+    /// A complete sphere.
+    ///
+    /// Panics if `radius` is not positive.
+    pub fn full(render_from_object: Transform, reverse_orientation: bool, radius: Float) -> Self {
+        Self::new(
+            render_from_object,
+            reverse_orientation,
+            radius,
+            -radius,
+            radius,
+            360.0,
+        )
+        .expect("sphere radius must be positive")
+    }
+
+    pub fn radius(&self) -> Float {
+        self.radius
+    }
+
+    fn geometry(&self, p: Point3f, phi: Float) -> QuadricGeometry {
+        let r = self.radius;
+        let u = phi / self.phi_max;
+        let cos_theta = (p.z / r).clamp(-1.0, 1.0);
+        let theta = cos_theta.acos();
+        let theta_range = self.theta_z_max - self.theta_z_min;
+        let v = (theta - self.theta_z_min) / theta_range;
+
+        let z_radius = (p.x * p.x + p.y * p.y).sqrt();
+        let cos_phi = p.x / z_radius;
+        let sin_phi = p.y / z_radius;
+        let sin_theta = (1.0 - cos_theta * cos_theta).max(0.0).sqrt();
+        QuadricGeometry {
+            p,
+            p_error: Vector3f::from(p).abs() * gamma(5),
+            uv: Point2f::new(u, v),
+            dpdu: swept_dpdu(&p, self.phi_max),
+            dpdv: Vector3f::new(p.z * cos_phi, p.z * sin_phi, -r * sin_theta) * theta_range,
+            d2pduu: swept_d2pduu(&p, self.phi_max),
+            d2pduv: Vector3f::new(-sin_phi, cos_phi, 0.0) * (theta_range * p.z * self.phi_max),
+            d2pdvv: Vector3f::from(p) * (-theta_range * theta_range),
+        }
+    }
+}
+
+impl Quadric for Sphere {
+    fn shape_transform(&self) -> &ShapeTransform {
+        &self.transform
+    }
+
+    fn object_bound(&self) -> Bounds3 {
+        Bounds3::from_points(
+            Point3f::new(-self.radius, -self.radius, self.z_min),
+            Point3f::new(self.radius, self.radius, self.z_max),
+        )
+    }
+
+    fn area(&self) -> Float {
+        self.phi_max * self.radius * (self.z_max - self.z_min)
+    }
+
+    fn intersect_object(&self, ray: &Ray) -> Option<(Float, QuadricGeometry)> {
+        let r = self.radius;
+        let ([ox, oy, oz], [dx, dy, dz]) = ray_intervals(ray);
+        let roots = Interval::quadratic(
+            dx.sqr() + dy.sqr() + dz.sqr(),
+            (dx * ox + dy * oy + dz * oz) * 2.0,
+            ox.sqr() + oy.sqr() + oz.sqr() - Interval::new(r).sqr(),
+        )?;
+        first_root(roots, ray.t_max(), |t| {
+            // Reproject onto the surface to undo the error in t.
+            let v = Vector3f::from(ray.get(t));
+            let mut p = Point3f::from(v * (r / v.length()));
+            if p.x == 0.0 && p.y == 0.0 {
+                p.x = 1e-5 * r;
+            }
+            let phi = phi_of(&p);
+            let clipped = (self.z_min > -r && p.z < self.z_min)
+                || (self.z_max < r && p.z > self.z_max)
+                || phi > self.phi_max;
+            (!clipped).then(|| self.geometry(p, phi))
+        })
+    }
+
+    fn sample_object(&self, u: Point2f) -> QuadricGeometry {
+        // Archimedes: a uniform z gives a uniform distribution over the
+        // area of a spherical zone.
+        let z = self.z_min + u.x * (self.z_max - self.z_min);
+        let cos_theta = z / self.radius;
+        let sin_theta = (1.0 - cos_theta * cos_theta).max(0.0).sqrt();
+        let phi = u.y * self.phi_max;
+        let mut p = Point3f::from(spherical_direction(sin_theta, cos_theta, phi) * self.radius);
+        if p.x == 0.0 && p.y == 0.0 {
+            p.x = 1e-5 * self.radius;
+        }
+        self.geometry(p, phi_of(&p))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::accel::Primitive;
+    use crate::util::math::PI;
+
+    #[test]
+    fn hits_front_of_translated_sphere() {
+        let s = Sphere::full(
+            Transform::translate(Vector3f::new(0.0, 0.0, 5.0)),
+            false,
+            1.0,
+        );
+        let ray = Ray::new(
+            Point3f::new(0.0, 0.0, 0.0),
+            Vector3f::new(0.0, 0.0, 1.0),
+            0.0,
+        );
+        let hit = s.intersect_hit(&ray).unwrap();
+        assert!((hit.t - 4.0).abs() < 1e-5);
+        assert!((hit.p.z - 4.0).abs() < 1e-5);
+        assert!((hit.n.z + 1.0).abs() < 1e-5);
+        assert!(s.intersect(&ray.with_t_max(3.0)).is_none());
+        assert_eq!(s.bounds().pMin.z, 4.0);
+
+        let si = hit.interaction(&ray);
+        assert_eq!(si.n, hit.n);
+        assert_eq!(si.wo, Vector3f::new(0.0, 0.0, -1.0));
+    }
+
+    #[test]
+    fn spawned_rays_do_not_rehit_the_surface() {
+        let s = Sphere::full(
+            Transform::translate(Vector3f::new(0.3, -7.1, 2.9)) * Transform::scale(1.7, 1.7, 1.7),
+            false,
+            1.3,
+        );
+        for i in 0..32 {
+            let a = i as Float * 0.19;
+            let ray = Ray::new(
+                Point3f::new(a.cos() * 10.0, -7.1 + a.sin() * 10.0, 3.3),
+                Vector3f::new(-a.cos(), -a.sin(), -0.02),
+                0.0,
+            );
+            let Some(hit) = s.intersect_hit(&ray) else {
+                continue;
+            };
+            let si = hit.interaction(&ray);
+            let out = si.spawn_ray(Vector3f::from(si.n));
+            assert!(s.intersect(&out).is_none());
+            // Going inward, the only hit is the far side.
+            let inward = si.spawn_ray(-Vector3f::from(si.n));
+            let t = s.intersect(&inward).unwrap();
+            assert!(t > 1.0);
+        }
+    }
+
+    #[test]
+    fn reverse_orientation_flips_normal() {
+        let s = Sphere::full(Transform::identity(), true, 2.0);
+        let ray = Ray::new(
+            Point3f::new(5.0, 0.0, 0.0),
+            Vector3f::new(-1.0, 0.0, 0.0),
+            0.0,
+        );
+        let hit = s.intersect_hit(&ray).unwrap();
+        assert!((hit.n.x + 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn clipped_sphere_is_hit_from_inside() {
+        // Upper hemisphere only: a ray through the missing bottom hits the
+        // inside of the top.
+        let s = Sphere::new(Transform::identity(), false, 1.0, 0.0, 1.0, 360.0).unwrap();
+        let ray = Ray::new(
+            Point3f::new(0.1, 0.1, -5.0),
+            Vector3f::new(0.0, 0.0, 1.0),
+            0.0,
+        );
+        let hit = s.intersect_hit(&ray).unwrap();
+        assert!(hit.p.z > 0.9);
+        assert!((s.area() - 2.0 * PI).abs() < 1e-5);
+    }
+
+    #[test]
+    fn degenerate_spheres_are_rejected() {
+        let sphere = |radius, z_min, z_max| {
+            Sphere::new(Transform::identity(), false, radius, z_min, z_max, 360.0)
+        };
+        assert!(Sphere::new(Transform::identity(), false, 1.0, -1.0, 1.0, 0.0).is_none());
+        assert!(sphere(1.0, Float::NAN, Float::NAN).is_none());
+        assert!(sphere(1.0, 0.5, 0.5).is_none());
+        assert!(sphere(1.0, 2.0, 3.0).is_none());
+        assert!(sphere(0.0, -1.0, 1.0).is_none());
+        assert!(sphere(-1.0, -1.0, 1.0).is_none());
+        assert!(sphere(1.0, 0.5, 0.25).is_some());
+    }
+
+    #[test]
+    fn partial_derivatives_match_normal() {
+        let s = Sphere::new(Transform::identity(), false, 1.0, -1.0, 1.0, 270.0).unwrap();
+        let ray = Ray::new(
+            Point3f::new(3.0, 0.5, 0.2),
+            Vector3f::new(-1.0, 0.0, 0.0),
+            0.0,
+        );
+        let hit = s.intersect_hit(&ray).unwrap();
+        let n = hit.dpdu.cross(&hit.dpdv).normalize();
+        assert!((n - Vector3f::from(hit.n)).length() < 1e-4);
+        // On a unit sphere the normal derivatives equal the position ones.
+        assert!((Vector3f::from(hit.dndu) - hit.dpdu).length() < 1e-4);
+    }
+
+    #[test]
+    fn samples_lie_on_the_surface() {
+        let s = Sphere::new(Transform::identity(), false, 2.0, -1.0, 1.5, 180.0).unwrap();
+        for i in 0..16 {
+            let u = Point2f::new((i as Float + 0.5) / 16.0, (i * 7 % 16) as Float / 16.0);
+            let ss = s.sample(u);
+            assert!((Vector3f::from(ss.p).length() - 2.0).abs() < 1e-4);
+            assert!(ss.p.z >= -1.0 - 1e-4 && ss.p.z <= 1.5 + 1e-4 && ss.p.y >= -1e-4);
+            assert!((Vector3f::from(ss.n) - Vector3f::from(ss.p) / 2.0).length() < 1e-4);
+            assert!((ss.pdf - 1.0 / s.area()).abs() < 1e-6);
+        }
+    }
+}
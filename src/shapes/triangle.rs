@@ -0,0 +1,579 @@
+use std::sync::Arc;
+
+use crate::accel::Primitive;
+use crate::util::Float;
+use crate::util::bounds::Bounds3;
+use crate::util::interactions::SurfaceInteraction;
+use crate::util::math::transformations::Transform;
+use crate::util::math::{difference_of_products, gamma};
+use crate::util::rays::Ray;
+use crate::util::vector::{Normal3f, Point2f, Point3f, Vector3f};
+
+/// Vertex data shared by every triangle of a mesh, stored in render space.
+///
+/// Per-vertex normals, UVs and tangents are optional, but when present there
+/// must be one for each position.
+#[derive(Debug, Clone)]
+pub struct TriangleMesh {
+    pub indices: Vec<[usize; 3]>,
+    pub positions: Vec<Point3f>,
+    pub normals: Option<Vec<Normal3f>>,
+    pub uvs: Option<Vec<Point2f>>,
+    pub tangents: Option<Vec<Vector3f>>,
+    pub reverse_orientation: bool,
+    pub transform_swaps_handedness: bool,
+}
+
+impl TriangleMesh {
+    /// Builds a mesh from object-space vertex data, transforming positions,
+    /// normals and tangents by `render_from_object`. As for the quadrics,
+    /// `reverse_orientation` flips the geometric normals.
+    ///
+    /// Panics if an index is out of range or an attribute array does not
+    /// match the number of positions.
+    pub fn new(
+        render_from_object: &Transform,
+        reverse_orientation: bool,
+        indices: Vec<[usize; 3]>,
+        positions: Vec<Point3f>,
+        normals: Option<Vec<Normal3f>>,
+        uvs: Option<Vec<Point2f>>,
+        tangents: Option<Vec<Vector3f>>,
+    ) -> Self {
+        let n = positions.len();
+        assert!(
+            indices.iter().flatten().all(|&i| i < n),
+            "triangle index out of range"
+        );
+        assert!(
+            normals.as_ref().is_none_or(|v| v.len() == n),
+            "one normal per vertex"
+        );
+        assert!(
+            uvs.as_ref().is_none_or(|v| v.len() == n),
+            "one uv per vertex"
+        );
+        assert!(
+            tangents.as_ref().is_none_or(|v| v.len() == n),
+            "one tangent per vertex"
+        );
+
+        Self {
+            indices,
+            positions: positions
+                .iter()
+                .map(|p| render_from_object.transform_point(p))
+                .collect(),
+            normals: normals.map(|ns| {
+                ns.iter()
+                    .map(|n| render_from_object.transform_normal(n))
+                    .collect()
+            }),
+            uvs,
+            tangents: tangents.map(|ts| {
+                ts.iter()
+                    .map(|t| render_from_object.transform_vector(t))
+                    .collect()
+            }),
+            reverse_orientation,
+            transform_swaps_handedness: render_from_object.swaps_handedness(),
+        }
+    }
+
+    pub fn triangle_count(&self) -> usize {
+        self.indices.len()
+    }
+
+    /// One `Triangle` per face, ready to hand to an acceleration structure.
+    pub fn triangles(mesh: &Arc<TriangleMesh>) -> Vec<Triangle> {
+        (0..mesh.triangle_count())
+            .map(|index| Triangle {
+                mesh: Arc::clone(mesh),
+                index,
+            })
+            .collect()
+    }
+}
+
+/// Barycentric coordinates and distance of a ray-triangle hit.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TriangleIntersection {
+    pub b0: Float,
+    pub b1: Float,
+    pub b2: Float,
+    pub t: Float,
+}
+
+/// Full local geometry at a point on a triangle.
+///
+/// The geometric normal `n` follows the winding of the face, flipped if the
+/// mesh has reversed orientation or a handedness-swapping transform, and
+/// flipped onto the side of the shading normal when the mesh has per-vertex
+/// normals.
+/// The shading frame is interpolated from the vertex data where it exists and
+/// falls back to the geometric frame where it does not.
+#[derive(Debug, Clone, Copy)]
+pub struct TriangleHit {
+    pub t: Float,
+    pub barycentrics: [Float; 3],
+    pub p: Point3f,
+    /// Conservative bound on the absolute error in `p`.
+    pub p_error: Vector3f,
+    pub uv: Point2f,
+    pub n: Normal3f,
+    pub dpdu: Vector3f,
+    pub dpdv: Vector3f,
+    pub shading_n: Normal3f,
+    pub shading_dpdu: Vector3f,
+    pub shading_dpdv: Vector3f,
+}
+
+impl TriangleHit {
+    /// The full surface record for this hit of `ray`. Triangles are flat, so
+    /// the geometric normal derivatives are zero.
+    pub fn interaction(&self, ray: &Ray) -> SurfaceInteraction {
+        let zero = Normal3f::default();
+        let mut si = SurfaceInteraction::new(
+            self.p,
+            self.p_error,
+            self.uv,
+            -ray.direction().normalize(),
+            self.dpdu,
+            self.dpdv,
+            zero,
+            zero,
+            ray.time(),
+            false,
+        );
+        // The face normal has already been oriented against the shading
+        // normal, so it wins over the parameterization.
+        si.interaction.n = self.n;
+        si.shading.n = self.n;
+        si.set_shading_geometry(
+            self.shading_n,
+            self.shading_dpdu,
+            self.shading_dpdv,
+            zero,
+            zero,
+            true,
+        );
+        si
+    }
+}
+
+/// Watertight ray-triangle intersection (Woop et al., "Watertight
+/// Ray/Triangle Intersection").
+///
+/// The vertices are moved into a space where the ray starts at the origin
+/// and points down +z, which reduces the test to 2D edge functions. Unlike
+/// Möller-Trumbore, a ray crossing an edge shared by two triangles is
+/// guaranteed to hit at least one of them, so meshes never leak. Hits at
+/// `t` not safely greater than zero, given the rounding error in `t`, are
+/// rejected, as are hits at or beyond `ray.t_max()`.
+pub fn intersect_triangle(
+    ray: &Ray,
+    p0: Point3f,
+    p1: Point3f,
+    p2: Point3f,
+) -> Option<TriangleIntersection> {
+    // Degenerate triangles have no surface to hit.
+    if (p2 - p0).cross(&(p1 - p0)).length_squared() == 0.0 {
+        return None;
+    }
+
+    let o = Vector3f::from(ray.origin());
+    let mut p0t = Vector3f::from(p0) - o;
+    let mut p1t = Vector3f::from(p1) - o;
+    let mut p2t = Vector3f::from(p2) - o;
+
+    // Make the dominant direction axis z.
+    let kz = ray.direction().abs().max_component_index();
+    let kx = if kz + 1 == 3 { 0 } else { kz + 1 };
+    let ky = if kx + 1 == 3 { 0 } else { kx + 1 };
+    let d = ray.direction().permute([kx, ky, kz]);
+    p0t = p0t.permute([kx, ky, kz]);
+    p1t = p1t.permute([kx, ky, kz]);
+    p2t = p2t.permute([kx, ky, kz]);
+
+    // Shear so the ray direction becomes +z. The z shear is deferred until
+    // the ray is known to pass through the triangle.
+    let sx = -d.x / d.z;
+    let sy = -d.y / d.z;
+    let sz = 1.0 / d.z;
+    for p in [&mut p0t, &mut p1t, &mut p2t] {
+        p.x += sx * p.z;
+        p.y += sy * p.z;
+    }
+
+    let e0 = difference_of_products(p1t.x, p2t.y, p1t.y, p2t.x);
+    let e1 = difference_of_products(p2t.x, p0t.y, p2t.y, p0t.x);
+    let e2 = difference_of_products(p0t.x, p1t.y, p0t.y, p1t.x);
+
+    // An edge function of exactly zero may be a rounding artifact; settle it
+    // in double precision so rays through shared edges stay consistent. With
+    // `Float = f64` there is no higher precision to fall back to.
+    #[cfg(not(feature = "float-as-double"))]
+    let (e0, e1, e2) = if e0 == 0.0 || e1 == 0.0 || e2 == 0.0 {
+        let edge = |a: &Vector3f, b: &Vector3f| {
+            (a.x as f64 * b.y as f64 - a.y as f64 * b.x as f64) as Float
+        };
+        (edge(&p1t, &p2t), edge(&p2t, &p0t), edge(&p0t, &p1t))
+    } else {
+        (e0, e1, e2)
+    };
+
+    if (e0 < 0.0 || e1 < 0.0 || e2 < 0.0) && (e0 > 0.0 || e1 > 0.0 || e2 > 0.0) {
+        return None;
+    }
+    let det = e0 + e1 + e2;
+    if det == 0.0 {
+        return None;
+    }
+
+    // Compare the scaled distance against the range before dividing.
+    p0t.z *= sz;
+    p1t.z *= sz;
+    p2t.z *= sz;
+    let t_scaled = e0 * p0t.z + e1 * p1t.z + e2 * p2t.z;
+    let t_max = ray.t_max();
+    if det < 0.0 && (t_scaled >= 0.0 || t_scaled < t_max * det) {
+        return None;
+    }
+    if det > 0.0 && (t_scaled <= 0.0 || t_scaled > t_max * det) {
+        return None;
+    }
+
+    let inv_det = 1.0 / det;
+    let b0 = e0 * inv_det;
+    let b1 = e1 * inv_det;
+    let b2 = e2 * inv_det;
+    let t = t_scaled * inv_det;
+
+    // Reject hits whose t could be non-positive once rounding is accounted
+    // for.
+    let max_zt = Vector3f::new(p0t.z, p1t.z, p2t.z)
+        .abs()
+        .max_component_value();
+    let delta_z = gamma(3) * max_zt;
+    let max_xt = Vector3f::new(p0t.x, p1t.x, p2t.x)
+        .abs()
+        .max_component_value();
+    let max_yt = Vector3f::new(p0t.y, p1t.y, p2t.y)
+        .abs()
+        .max_component_value();
+    let delta_x = gamma(5) * (max_xt + max_zt);
+    let delta_y = gamma(5) * (max_yt + max_zt);
+    let delta_e = 2.0 * (gamma(2) * max_xt * max_yt + delta_y * max_xt + delta_x * max_yt);
+    let max_e = Vector3f::new(e0, e1, e2).abs().max_component_value();
+    let delta_t =
+        3.0 * (gamma(3) * max_e * max_zt + delta_e * max_zt + delta_z * max_e) * inv_det.abs();
+    if t <= delta_t {
+        return None;
+    }
+
+    Some(TriangleIntersection { b0, b1, b2, t })
+}
+
+/// A single face of a `TriangleMesh`.
+#[derive(Debug, Clone)]
+pub struct Triangle {
+    mesh: Arc<TriangleMesh>,
+    index: usize,
+}
+
+impl Triangle {
+    pub fn mesh(&self) -> &Arc<TriangleMesh> {
+        &self.mesh
+    }
+
+    pub fn vertex_indices(&self) -> [usize; 3] {
+        self.mesh.indices[self.index]
+    }
+
+    pub fn vertices(&self) -> [Point3f; 3] {
+        self.vertex_indices().map(|v| self.mesh.positions[v])
+    }
+
+    pub fn area(&self) -> Float {
+        let [p0, p1, p2] = self.vertices();
+        0.5 * (p1 - p0).cross(&(p2 - p0)).length()
+    }
+
+    /// Per-vertex UVs, defaulting to a fixed parameterization when the mesh
+    /// has none.
+    fn uvs(&self) -> [Point2f; 3] {
+        match &self.mesh.uvs {
+            Some(uv) => self.vertex_indices().map(|v| uv[v]),
+            None => [
+                Point2f::new(0.0, 0.0),
+                Point2f::new(1.0, 0.0),
+                Point2f::new(1.0, 1.0),
+            ],
+        }
+    }
+
+    pub fn intersect_triangle(&self, ray: &Ray) -> Option<TriangleIntersection> {
+        let [p0, p1, p2] = self.vertices();
+        intersect_triangle(ray, p0, p1, p2)
+    }
+
+    /// Closest hit with `ray` in `(0, ray.t_max())` along with the surface
+    /// geometry there.
+    pub fn intersect_hit(&self, ray: &Ray) -> Option<TriangleHit> {
+        let ti = self.intersect_triangle(ray)?;
+        Some(self.hit(&ti))
+    }
+
+    /// Interpolates the surface geometry at a hit found by
+    /// `intersect_triangle`.
+    pub fn hit(&self, ti: &TriangleIntersection) -> TriangleHit {
+        let v = self.vertex_indices();
+        let [p0, p1, p2] = self.vertices();
+        let [uv0, uv1, uv2] = self.uvs();
+        let b = [ti.b0, ti.b1, ti.b2];
+
+        // Partial derivatives from the UV parameterization, falling back to
+        // an arbitrary frame around the face normal if it is degenerate.
+        let duv02 = uv0 - uv2;
+        let duv12 = uv1 - uv2;
+        let dp02 = p0 - p2;
+        let dp12 = p1 - p2;
+        let determinant = difference_of_products(duv02.x, duv12.y, duv02.y, duv12.x);
+        let mut dpdu = Vector3f::default();
+        let mut dpdv = Vector3f::default();
+        let degenerate_uv = determinant.abs() < 1e-9;
+        if !degenerate_uv {
+            let inv_det = 1.0 / determinant;
+            dpdu = (dp02 * duv12.y - dp12 * duv02.y) * inv_det;
+            dpdv = (dp12 * duv02.x - dp02 * duv12.x) * inv_det;
+        }
+        if degenerate_uv || dpdu.cross(&dpdv).length_squared() == 0.0 {
+            let ng = (p2 - p0).cross(&(p1 - p0)).normalize();
+            (dpdu, dpdv) = Vector3f::coordinate_system(&ng);
+        }
+
+        let lerp3 = |a: Vector3f, b1: Vector3f, c: Vector3f| a * b[0] + b1 * b[1] + c * b[2];
+        let p_abs_sum = lerp3(
+            Vector3f::from(p0).abs(),
+            Vector3f::from(p1).abs(),
+            Vector3f::from(p2).abs(),
+        );
+        let p = Point3f::from(lerp3(p0.into(), p1.into(), p2.into()));
+        let uv = Point2f::new(
+            b[0] * uv0.x + b[1] * uv1.x + b[2] * uv2.x,
+            b[0] * uv0.y + b[1] * uv1.y + b[2] * uv2.y,
+        );
+
+        let mut n = Normal3f::from(dp02.cross(&dp12).normalize());
+        if self.mesh.reverse_orientation ^ self.mesh.transform_swaps_handedness {
+            n = -n;
+        }
+
+        let (shading_n, shading_dpdu, shading_dpdv) = match &self.mesh.normals {
+            None => (n, dpdu, dpdv),
+            Some(normals) => {
+                let ns = lerp3(
+                    normals[v[0]].into(),
+                    normals[v[1]].into(),
+                    normals[v[2]].into(),
+                );
+                let ns = if ns.length_squared() > 0.0 {
+                    Normal3f::from(ns.normalize())
+                } else {
+                    n
+                };
+                // The interpolated normal is the better hint of which side
+                // is outside.
+                n = n.face_forward(&Vector3f::from(ns));
+
+                let mut ss = match &self.mesh.tangents {
+                    Some(t) => lerp3(t[v[0]], t[v[1]], t[v[2]]),
+                    None => dpdu,
+                };
+                if ss.length_squared() == 0.0 {
+                    ss = dpdu;
+                }
+                let nsv = Vector3f::from(ns);
+                let mut ts = nsv.cross(&ss);
+                if ts.length_squared() > 0.0 {
+                    ss = ts.cross(&nsv);
+                } else {
+                    (ss, ts) = Vector3f::coordinate_system(&nsv);
+                }
+                (ns, ss, ts)
+            }
+        };
+
+        TriangleHit {
+            t: ti.t,
+            barycentrics: b,
+            p,
+            p_error: p_abs_sum * gamma(7),
+            uv,
+            n,
+            dpdu,
+            dpdv,
+            shading_n,
+            shading_dpdu,
+            shading_dpdv,
+        }
+    }
+}
+
+impl Primitive for Triangle {
+    fn bounds(&self) -> Bounds3 {
+        let [p0, p1, p2] = self.vertices();
+        Bounds3::from_points(p0, p1).union_point(&p2)
+    }
+    fn intersect(&self, ray: &Ray) -> Option<Float> {
+        self.intersect_triangle(ray).map(|ti| ti.t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::accel::{Bvh, SplitMethod};
+
+    /// Two triangles forming the unit square in the z = 0 plane.
+    fn quad(normals: Option<Vec<Normal3f>>) -> Arc<TriangleMesh> {
+        Arc::new(TriangleMesh::new(
+            &Transform::identity(),
+            false,
+            vec![[0, 1, 2], [0, 2, 3]],
+            vec![
+                Point3f::new(0.0, 0.0, 0.0),
+                Point3f::new(1.0, 0.0, 0.0),
+                Point3f::new(1.0, 1.0, 0.0),
+                Point3f::new(0.0, 1.0, 0.0),
+            ],
+            normals,
+            Some(vec![
+                Point2f::new(0.0, 0.0),
+                Point2f::new(1.0, 0.0),
+                Point2f::new(1.0, 1.0),
+                Point2f::new(0.0, 1.0),
+            ]),
+            None,
+        ))
+    }
+
+    fn down_ray(x: Float, y: Float) -> Ray {
+        Ray::new(Point3f::new(x, y, 2.0), Vector3f::new(0.0, 0.0, -1.0), 0.0)
+    }
+
+    #[test]
+    fn hit_reports_distance_barycentrics_and_uv() {
+        let tris = TriangleMesh::triangles(&quad(None));
+        let hit = tris[0].intersect_hit(&down_ray(0.75, 0.25)).unwrap();
+        assert_eq!(hit.t, 2.0);
+        assert!((hit.barycentrics.iter().sum::<Float>() - 1.0).abs() < 1e-6);
+        assert!((hit.p.x - 0.75).abs() < 1e-6 && (hit.p.y - 0.25).abs() < 1e-6);
+        assert!((hit.uv.x - 0.75).abs() < 1e-6 && (hit.uv.y - 0.25).abs() < 1e-6);
+        assert!((hit.dpdu - Vector3f::new(1.0, 0.0, 0.0)).length() < 1e-6);
+        assert!((hit.dpdv - Vector3f::new(0.0, 1.0, 0.0)).length() < 1e-6);
+        assert_eq!(hit.n.z.abs(), 1.0);
+        assert!(tris[1].intersect_hit(&down_ray(0.75, 0.25)).is_none());
+
+        let si = hit.interaction(&down_ray(0.75, 0.25));
+        assert_eq!(si.wo, Vector3f::new(0.0, 0.0, 1.0));
+        assert_eq!(si.n, hit.n);
+        assert_eq!(si.shading.n, hit.n);
+    }
+
+    #[test]
+    fn misses_outside_and_beyond_t_max() {
+        let tris = TriangleMesh::triangles(&quad(None));
+        assert!(tris[0].intersect(&down_ray(1.5, 0.5)).is_none());
+        assert!(
+            tris[0]
+                .intersect(&down_ray(0.75, 0.25).with_t_max(1.5))
+                .is_none()
+        );
+        let away = Ray::new(
+            Point3f::new(0.5, 0.2, 2.0),
+            Vector3f::new(0.0, 0.0, 1.0),
+            0.0,
+        );
+        assert!(tris[0].intersect(&away).is_none());
+    }
+
+    #[test]
+    fn shared_edge_is_watertight() {
+        // Rays exactly along the diagonal shared by both triangles must hit
+        // at least one of them.
+        let tris = TriangleMesh::triangles(&quad(None));
+        for i in 1..100 {
+            let s = i as Float / 100.0;
+            let ray = down_ray(s, s);
+            assert!(tris.iter().any(|t| t.intersect(&ray).is_some()));
+        }
+    }
+
+    #[test]
+    fn shading_normal_flips_geometric_normal() {
+        let up = Normal3f::new(0.0, 0.0, 1.0);
+        let tris = TriangleMesh::triangles(&quad(Some(vec![up; 4])));
+        let hit = tris[0].intersect_hit(&down_ray(0.6, 0.3)).unwrap();
+        assert_eq!(hit.shading_n, up);
+        assert_eq!(hit.n, up);
+        assert!(hit.shading_dpdu.dot(&Vector3f::from(up)).abs() < 1e-6);
+        assert!(hit.shading_dpdv.dot(&hit.shading_dpdu).abs() < 1e-6);
+    }
+
+    #[test]
+    fn orientation_and_mirroring_flip_the_geometric_normal() {
+        let up = Normal3f::new(0.0, 0.0, 1.0);
+        // The mirror moves the triangle to negative x and reverses its
+        // winding there.
+        let mirror = Transform::scale(-1.0, 1.0, 1.0);
+        for (render_from_object, x, reverse_orientation, n) in [
+            (Transform::identity(), 0.6, false, up),
+            (Transform::identity(), 0.6, true, -up),
+            (mirror, -0.6, false, up),
+            (mirror, -0.6, true, -up),
+        ] {
+            let mesh = Arc::new(TriangleMesh::new(
+                &render_from_object,
+                reverse_orientation,
+                vec![[0, 1, 2]],
+                vec![
+                    Point3f::new(0.0, 0.0, 0.0),
+                    Point3f::new(1.0, 0.0, 0.0),
+                    Point3f::new(1.0, 1.0, 0.0),
+                ],
+                None,
+                None,
+                None,
+            ));
+            let ray = down_ray(x, 0.3);
+            let hit = TriangleMesh::triangles(&mesh)[0]
+                .intersect_hit(&ray)
+                .unwrap();
+            assert_eq!(hit.n, n, "{x} {reverse_orientation}");
+            assert_eq!(hit.interaction(&ray).interaction.n, n);
+        }
+    }
+
+    #[test]
+    fn mesh_is_transformed_and_plugs_into_bvh() {
+        let mesh = Arc::new(TriangleMesh::new(
+            &Transform::translate(Vector3f::new(0.0, 0.0, -3.0)),
+            false,
+            vec![[0, 1, 2]],
+            vec![
+                Point3f::new(-1.0, -1.0, 0.0),
+                Point3f::new(1.0, -1.0, 0.0),
+                Point3f::new(0.0, 1.0, 0.0),
+            ],
+            None,
+            None,
+            None,
+        ));
+        let bvh = Bvh::new(TriangleMesh::triangles(&mesh), 4, SplitMethod::Sah);
+        assert_eq!(bvh.bounds().pMin.z, -3.0);
+        let hit = bvh.intersect(&down_ray(0.0, 0.0)).unwrap();
+        assert_eq!(hit.t, 5.0);
+        assert!((bvh.primitives()[0].area() - 2.0).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,285 @@
+use num_traits::{Bounded, Float as FloatOps};
+use std::ops::{Add, Index, Mul, Sub};
+
+use crate::util::vector::{Point2, Point2i, Vector2};
+use crate::util::{Float, Int};
+
+/// An axis-aligned rectangle, used for image-space regions such as film
+/// tiles and crop windows.
+///
+/// Integer bounds treat `pMax` as exclusive when iterated, so a
+/// `Bounds2i` from `(0, 0)` to `(w, h)` covers exactly the pixels of a
+/// `w` x `h` image.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bounds2<T> {
+    pub pMin: Point2<T>,
+    pub pMax: Point2<T>,
+}
+
+pub type Bounds2f = Bounds2<Float>;
+pub type Bounds2i = Bounds2<Int>;
+
+impl<T: Bounded + Copy> Bounds2<T> {
+    /// An empty rectangle that any union will replace.
+    pub fn new() -> Self {
+        Self {
+            pMin: Point2::new(T::max_value(), T::max_value()),
+            pMax: Point2::new(T::min_value(), T::min_value()),
+        }
+    }
+}
+
+impl<T: Bounded + Copy> Default for Bounds2<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: PartialOrd + Copy> Bounds2<T> {
+    /// The rectangle spanned by two opposite corners, given in any order.
+    pub fn from_points(p1: Point2<T>, p2: Point2<T>) -> Self {
+        Self {
+            pMin: p1.min(&p2),
+            pMax: p1.max(&p2),
+        }
+    }
+    pub fn union_point(&self, p: &Point2<T>) -> Self {
+        Self {
+            pMin: self.pMin.min(p),
+            pMax: self.pMax.max(p),
+        }
+    }
+    pub fn union_bounds(&self, b: &Bounds2<T>) -> Self {
+        Self {
+            pMin: self.pMin.min(&b.pMin),
+            pMax: self.pMax.max(&b.pMax),
+        }
+    }
+    /// The region shared by both rectangles; empty if they do not overlap.
+    pub fn intersect(b1: Bounds2<T>, b2: Bounds2<T>) -> Self {
+        Self {
+            pMin: b1.pMin.max(&b2.pMin),
+            pMax: b1.pMax.min(&b2.pMax),
+        }
+    }
+    pub fn overlaps(b1: Bounds2<T>, b2: Bounds2<T>) -> bool {
+        let x = b1.pMax.x >= b2.pMin.x && b1.pMin.x <= b2.pMax.x;
+        let y = b1.pMax.y >= b2.pMin.y && b1.pMin.y <= b2.pMax.y;
+        x && y
+    }
+    pub fn is_point_inside(&self, p: &Point2<T>) -> bool {
+        p.x >= self.pMin.x && p.x <= self.pMax.x && p.y >= self.pMin.y && p.y <= self.pMax.y
+    }
+    /// Like `is_point_inside`, but points on the upper edges are outside.
+    /// This is the test that matches pixel iteration.
+    pub fn is_point_inside_exclusive(&self, p: &Point2<T>) -> bool {
+        p.x >= self.pMin.x && p.x < self.pMax.x && p.y >= self.pMin.y && p.y < self.pMax.y
+    }
+    /// True if the rectangle covers no area.
+    pub fn is_empty(&self) -> bool {
+        self.pMin.x >= self.pMax.x || self.pMin.y >= self.pMax.y
+    }
+    /// True if the rectangle is inverted along some axis, as `new` returns.
+    pub fn is_degenerate(&self) -> bool {
+        self.pMin.x > self.pMax.x || self.pMin.y > self.pMax.y
+    }
+    /// Corner `i`: bit 0 selects x from `pMax` and bit 1 selects y.
+    pub fn corner(&self, i: usize) -> Point2<T> {
+        Point2::new(self[i & 1].x, self[(i >> 1) & 1].y)
+    }
+}
+
+impl<T> Bounds2<T>
+where
+    T: PartialOrd + Copy + Sub<Output = T> + Mul<Output = T>,
+{
+    pub fn diagonal(&self) -> Vector2<T> {
+        self.pMax - self.pMin
+    }
+    pub fn area(&self) -> T {
+        let d = self.diagonal();
+        d.x * d.y
+    }
+    /// Index of the longer axis.
+    pub fn maximum_extent(&self) -> usize {
+        let d = self.diagonal();
+        if d.x > d.y { 0 } else { 1 }
+    }
+}
+
+impl<T> Bounds2<T>
+where
+    T: Copy + Sub<Output = T> + Add<Output = T>,
+{
+    pub fn expand(b: Bounds2<T>, delta: T) -> Self {
+        let d = Vector2::new(delta, delta);
+        Self {
+            pMin: b.pMin - d,
+            pMax: b.pMax + d,
+        }
+    }
+}
+
+impl<T: FloatOps> Bounds2<T> {
+    /// Point at the relative position `t`, so `(0, 0)` is `pMin` and `(1, 1)`
+    /// is `pMax`.
+    pub fn lerp(&self, t: Point2<T>) -> Point2<T> {
+        let one = T::one();
+        Point2::new(
+            (one - t.x) * self.pMin.x + t.x * self.pMax.x,
+            (one - t.y) * self.pMin.y + t.y * self.pMax.y,
+        )
+    }
+    /// Inverse of `lerp`: where `p` lies relative to the corners.
+    pub fn offset(&self, p: &Point2<T>) -> Vector2<T> {
+        let mut o = *p - self.pMin;
+        if self.pMax.x > self.pMin.x {
+            o.x = o.x / (self.pMax.x - self.pMin.x);
+        }
+        if self.pMax.y > self.pMin.y {
+            o.y = o.y / (self.pMax.y - self.pMin.y);
+        }
+        o
+    }
+}
+
+/// `bounds[0]` is `pMin` and `bounds[1]` is `pMax`.
+impl<T> Index<usize> for Bounds2<T> {
+    type Output = Point2<T>;
+    fn index(&self, i: usize) -> &Self::Output {
+        match i {
+            0 => &self.pMin,
+            1 => &self.pMax,
+            _ => panic!("index out of bounds"),
+        }
+    }
+}
+
+impl From<Bounds2i> for Bounds2f {
+    fn from(b: Bounds2i) -> Self {
+        Self {
+            pMin: b.pMin.map(|c| c as Float),
+            pMax: b.pMax.map(|c| c as Float),
+        }
+    }
+}
+
+impl Bounds2i {
+    /// Iterates over the pixels covered by the bounds in scanline order,
+    /// with `pMax` exclusive.
+    pub fn iter(&self) -> Bounds2iIterator {
+        Bounds2iIterator {
+            bounds: *self,
+            next: self.pMin,
+        }
+    }
+}
+
+impl IntoIterator for &Bounds2i {
+    type Item = Point2i;
+    type IntoIter = Bounds2iIterator;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Scanline iterator over the pixels of a `Bounds2i`.
+#[derive(Debug, Clone)]
+pub struct Bounds2iIterator {
+    bounds: Bounds2i,
+    next: Point2i,
+}
+
+impl Iterator for Bounds2iIterator {
+    type Item = Point2i;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.bounds.is_empty() || self.next.y >= self.bounds.pMax.y {
+            return None;
+        }
+        let p = self.next;
+        self.next.x += 1;
+        if self.next.x == self.bounds.pMax.x {
+            self.next.x = self.bounds.pMin.x;
+            self.next.y += 1;
+        }
+        Some(p)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.bounds.is_empty() || self.next.y >= self.bounds.pMax.y {
+            return (0, Some(0));
+        }
+        let width = (self.bounds.pMax.x - self.bounds.pMin.x) as usize;
+        let rows_left = (self.bounds.pMax.y - self.next.y) as usize;
+        let done_in_row = (self.next.x - self.bounds.pMin.x) as usize;
+        let n = rows_left * width - done_in_row;
+        (n, Some(n))
+    }
+}
+
+impl ExactSizeIterator for Bounds2iIterator {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::vector::Point2f;
+
+    #[test]
+    fn test_pixel_iteration_is_scanline_and_exclusive() {
+        let b = Bounds2i::from_points(Point2i::new(1, 2), Point2i::new(3, 4));
+        let pixels: Vec<Point2i> = b.iter().collect();
+        assert_eq!(
+            pixels,
+            vec![
+                Point2i::new(1, 2),
+                Point2i::new(2, 2),
+                Point2i::new(1, 3),
+                Point2i::new(2, 3),
+            ]
+        );
+        assert_eq!(b.iter().len(), 4);
+        assert_eq!(b.area(), 4);
+        assert!(pixels.iter().all(|p| b.is_point_inside_exclusive(p)));
+    }
+
+    #[test]
+    fn test_empty_bounds_yield_no_pixels() {
+        let b = Bounds2i::from_points(Point2i::new(0, 0), Point2i::new(5, 0));
+        assert!(b.is_empty());
+        assert_eq!(b.iter().count(), 0);
+        assert_eq!((&Bounds2i::new()).into_iter().count(), 0);
+    }
+
+    #[test]
+    fn test_union_intersect_overlap() {
+        let a = Bounds2i::from_points(Point2i::new(0, 0), Point2i::new(4, 4));
+        let b = Bounds2i::from_points(Point2i::new(2, 3), Point2i::new(6, 8));
+        let i = Bounds2i::intersect(a, b);
+        assert_eq!(
+            i,
+            Bounds2i::from_points(Point2i::new(2, 3), Point2i::new(4, 4))
+        );
+        assert!(Bounds2i::overlaps(a, b));
+        let u = Bounds2i::new()
+            .union_bounds(&a)
+            .union_point(&Point2i::new(-1, 9));
+        assert_eq!(
+            u,
+            Bounds2i::from_points(Point2i::new(-1, 0), Point2i::new(4, 9))
+        );
+        assert_eq!(u.maximum_extent(), 1);
+    }
+
+    #[test]
+    fn test_float_lerp_offset() {
+        let b = Bounds2f::from(Bounds2i::from_points(
+            Point2i::new(0, 0),
+            Point2i::new(4, 2),
+        ));
+        let p = b.lerp(Point2f::new(0.5, 0.5));
+        assert_eq!(p, Point2f::new(2.0, 1.0));
+        let o = b.offset(&p);
+        assert_eq!((o.x, o.y), (0.5, 0.5));
+        assert_eq!(b.corner(1), Point2f::new(4.0, 0.0));
+    }
+}
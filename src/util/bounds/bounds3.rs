@@ -1,72 +1,251 @@
+use std::ops::Index;
+
 use crate::util::Float;
-use crate::util::vector::{Point3, Vector3};
+use crate::util::math::{Interval, gamma};
+use crate::util::rays::Ray;
+use crate::util::vector::{Point3f, Vector3f};
+
+/// An axis-aligned bounding box.
+///
+/// A box with `pMin > pMax` along any axis is empty; `new` returns such a box
+/// so that it can be grown with `union_point`/`union_bounds`.
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Bounds3 {
-    pMin: Point3,
-    pMax: Point3,
+    pub pMin: Point3f,
+    pub pMax: Point3f,
+}
+
+impl Default for Bounds3 {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Bounds3 {
-    fn new() -> Self {
+    /// An empty box that any union will replace.
+    pub fn new() -> Self {
         let minNum = Float::MIN;
         let maxNum = Float::MAX;
         Self {
-            pMin: Point3::new(maxNum, maxNum, maxNum),
-            pMax: Point3::new(minNum, minNum, minNum),
+            pMin: Point3f::new(maxNum, maxNum, maxNum),
+            pMax: Point3f::new(minNum, minNum, minNum),
         }
     }
-    fn union_point(&self, point: &Point3) -> Bounds3 {
-        let pMin = Point3::new(
-            self.pMin.get_x().min(point.get_x()),
-            self.pMin.get_y().min(point.get_y()),
-            self.pMin.get_z().min(point.get_z()),
-        );
-        let pMax = Point3::new(
-            self.pMax.get_x().max(point.get_x()),
-            self.pMax.get_y().max(point.get_y()),
-            self.pMax.get_z().max(point.get_z()),
-        );
-        Bounds3 { pMin, pMax }
+    /// A degenerate box containing a single point.
+    pub fn from_point(p: Point3f) -> Self {
+        Self { pMin: p, pMax: p }
+    }
+    /// The box spanned by two opposite corners, given in any order.
+    pub fn from_points(p1: Point3f, p2: Point3f) -> Self {
+        Self {
+            pMin: p1.min(&p2),
+            pMax: p1.max(&p2),
+        }
+    }
+
+    pub fn union_point(&self, point: &Point3f) -> Bounds3 {
+        Bounds3 {
+            pMin: self.pMin.min(point),
+            pMax: self.pMax.max(point),
+        }
+    }
+    pub fn union_bounds(&self, bounds: &Bounds3) -> Bounds3 {
+        Bounds3 {
+            pMin: self.pMin.min(&bounds.pMin),
+            pMax: self.pMax.max(&bounds.pMax),
+        }
     }
-    fn union_bounds(&self, bounds: &Bounds3) -> Bounds3 {
-        let mut bounds = self.union_point(&bounds.pMin);
-        bounds = bounds.union_point(&bounds.pMax);
-        bounds
+    /// The region shared by both boxes; empty if they do not overlap.
+    pub fn intersect(b1: Bounds3, b2: Bounds3) -> Bounds3 {
+        Bounds3 {
+            pMin: b1.pMin.max(&b2.pMin),
+            pMax: b1.pMax.min(&b2.pMax),
+        }
     }
     #[inline]
-    fn overlaps(b1: Bounds3, b2: Bounds3) -> bool {
-        let x = (b1.pMax.get_x() >= b2.pMin.get_x()) && (b1.pMin.get_x() <= b2.pMax.get_x());
-        let y = (b1.pMax.get_y() >= b2.pMin.get_y()) && (b1.pMin.get_y() <= b2.pMax.get_y());
-        let z = (b1.pMax.get_z() >= b2.pMin.get_z()) && (b1.pMin.get_z() <= b2.pMax.get_z());
+    pub fn overlaps(b1: Bounds3, b2: Bounds3) -> bool {
+        let x = (b1.pMax.x >= b2.pMin.x) && (b1.pMin.x <= b2.pMax.x);
+        let y = (b1.pMax.y >= b2.pMin.y) && (b1.pMin.y <= b2.pMax.y);
+        let z = (b1.pMax.z >= b2.pMin.z) && (b1.pMin.z <= b2.pMax.z);
         x && y && z
     }
     #[inline]
-    fn is_point_inside(&self, point: &Point3) -> bool {
-        (point.get_x() >= self.pMin.get_x()
-            && point.get_x() <= self.pMax.get_x()
-            && point.get_y() >= self.pMin.get_y()
-            && point.get_y() <= self.pMax.get_y()
-            && point.get_z() >= self.pMin.get_z()
-            && point.get_z() <= self.pMax.get_z())
-    }
-    fn diagonal(&self) -> Vector3 {
-        &self.pMax - &self.pMin
-    }
-    fn surface_area(&self) -> Float {
+    pub fn is_point_inside(&self, point: &Point3f) -> bool {
+        point.x >= self.pMin.x
+            && point.x <= self.pMax.x
+            && point.y >= self.pMin.y
+            && point.y <= self.pMax.y
+            && point.z >= self.pMin.z
+            && point.z <= self.pMax.z
+    }
+    /// Like `is_point_inside`, but points on the upper faces are outside.
+    #[inline]
+    pub fn is_point_inside_exclusive(&self, point: &Point3f) -> bool {
+        point.x >= self.pMin.x
+            && point.x < self.pMax.x
+            && point.y >= self.pMin.y
+            && point.y < self.pMax.y
+            && point.z >= self.pMin.z
+            && point.z < self.pMax.z
+    }
+    /// True if the box encloses no volume, including boxes that are flat
+    /// along some axis.
+    pub fn is_empty(&self) -> bool {
+        self.pMin.x >= self.pMax.x || self.pMin.y >= self.pMax.y || self.pMin.z >= self.pMax.z
+    }
+    /// True if the box is inverted along some axis, as `new` returns.
+    pub fn is_degenerate(&self) -> bool {
+        self.pMin.x > self.pMax.x || self.pMin.y > self.pMax.y || self.pMin.z > self.pMax.z
+    }
+
+    /// Corner `i` of the box: bit 0 selects x from `pMax`, bit 1 y and bit 2 z.
+    pub fn corner(&self, i: usize) -> Point3f {
+        Point3f::new(self[i & 1].x, self[(i >> 1) & 1].y, self[(i >> 2) & 1].z)
+    }
+    pub fn diagonal(&self) -> Vector3f {
+        self.pMax - self.pMin
+    }
+    pub fn surface_area(&self) -> Float {
         let dg = self.diagonal();
-        2.0 * (dg.get_x() * dg.get_y() + dg.get_y() * dg.get_z() + dg.get_z() * dg.get_x())
+        2.0 * (dg.x * dg.y + dg.y * dg.z + dg.z * dg.x)
     }
-    fn expand(b1: Bounds3, delta: Float) -> Bounds3 {
-        let pMin = Vector3::new(
-            b1.pMin.get_x() - delta,
-            b1.pMin.get_y() - delta,
-            b1.pMin.get_z() - delta,
-        );
-        let pMax = Vector3::new(
-            b1.pMin.get_x() + delta,
-            b1.pMin.get_y() + delta,
-            b1.pMin.get_z() + delta,
-        );
-        Bounds3 { pMin, pMax }
+    pub fn volume(&self) -> Float {
+        let dg = self.diagonal();
+        dg.x * dg.y * dg.z
+    }
+    /// Index of the longest axis.
+    pub fn maximum_extent(&self) -> usize {
+        self.diagonal().max_component_index()
+    }
+    /// Point at the relative position `t` inside the box, so `(0, 0, 0)` is
+    /// `pMin` and `(1, 1, 1)` is `pMax`.
+    pub fn lerp(&self, t: Point3f) -> Point3f {
+        Point3f::new(
+            (1.0 - t.x) * self.pMin.x + t.x * self.pMax.x,
+            (1.0 - t.y) * self.pMin.y + t.y * self.pMax.y,
+            (1.0 - t.z) * self.pMin.z + t.z * self.pMax.z,
+        )
+    }
+    /// Inverse of `lerp`: where `p` lies relative to the corners.
+    pub fn offset(&self, p: &Point3f) -> Vector3f {
+        let mut o = *p - self.pMin;
+        for i in 0..3 {
+            if self.pMax[i] > self.pMin[i] {
+                o[i] /= self.pMax[i] - self.pMin[i];
+            }
+        }
+        o
+    }
+    /// Center and radius of a sphere enclosing the box.
+    pub fn bounding_sphere(&self) -> (Point3f, Float) {
+        let center = Point3f::lerp(0.5, &self.pMin, &self.pMax);
+        let radius = if self.is_point_inside(&center) {
+            Point3f::distance(&center, &self.pMax)
+        } else {
+            0.0
+        };
+        (center, radius)
+    }
+    pub fn expand(b1: Bounds3, delta: Float) -> Bounds3 {
+        let d = Vector3f::new(delta, delta, delta);
+        Bounds3 {
+            pMin: b1.pMin - d,
+            pMax: b1.pMax + d,
+        }
+    }
+
+    /// Slab test against the parametric range `[0, ray.t_max()]` of `ray`.
+    ///
+    /// Returns the entry and exit distances of the overlap. The slab
+    /// distances are computed as intervals and the overlap is taken between
+    /// their outer bounds, so rounding can never make a ray that grazes the
+    /// box report a miss. A ray parallel to a slab is inside it everywhere or
+    /// nowhere, including when it lies exactly in one of its planes.
+    pub fn intersect_p(&self, ray: &Ray) -> Option<(Float, Float)> {
+        let o = ray.origin();
+        let d = ray.direction();
+        let mut t0: Float = 0.0;
+        let mut t1 = ray.t_max();
+        for i in 0..3 {
+            if d[i] == 0.0 {
+                if o[i] < self.pMin[i] || o[i] > self.pMax[i] {
+                    return None;
+                }
+                continue;
+            }
+            let t_lo = (Interval::new(self.pMin[i]) - o[i]) / d[i];
+            let t_hi = (Interval::new(self.pMax[i]) - o[i]) / d[i];
+            let (t_near, t_far) = if d[i] > 0.0 {
+                (t_lo, t_hi)
+            } else {
+                (t_hi, t_lo)
+            };
+            t0 = t0.max(t_near.lower_bound());
+            t1 = t1.min(t_far.upper_bound());
+            if t0 > t1 {
+                return None;
+            }
+        }
+        Some((t0, t1))
+    }
+
+    /// Hit-only slab test for traversal loops that test many boxes against
+    /// the same ray.
+    ///
+    /// `ray_t_max` stands in for `ray.t_max()` so that a traversal can
+    /// shrink it as closer hits are found without copying the ray.
+    ///
+    /// `inv_dir` holds the reciprocal ray direction and `dir_is_neg[i]` is `1`
+    /// when the direction is negative along axis `i`, both computed once per
+    /// ray. The sign array selects the near and far planes directly, so no
+    /// swaps are needed. Instead of interval arithmetic, every far distance is
+    /// enlarged by `1 + 2 * gamma(3)` to cover the rounding; the running interval
+    /// is narrowed with `max`/`min`, which discard a NaN operand, so an axis
+    /// the ray lies in the plane of cannot poison the result.
+    #[inline]
+    pub fn intersect_p_fast(
+        &self,
+        ray: &Ray,
+        ray_t_max: Float,
+        inv_dir: &Vector3f,
+        dir_is_neg: [usize; 3],
+    ) -> bool {
+        let o = ray.origin();
+        let mut t_min = (self[dir_is_neg[0]].x - o.x) * inv_dir.x;
+        let mut t_max = (self[1 - dir_is_neg[0]].x - o.x) * inv_dir.x;
+        let ty_min = (self[dir_is_neg[1]].y - o.y) * inv_dir.y;
+        let mut ty_max = (self[1 - dir_is_neg[1]].y - o.y) * inv_dir.y;
+        t_max *= 1.0 + 2.0 * gamma(3);
+        ty_max *= 1.0 + 2.0 * gamma(3);
+
+        if t_min > ty_max || ty_min > t_max {
+            return false;
+        }
+        t_min = t_min.max(ty_min);
+        t_max = t_max.min(ty_max);
+
+        let tz_min = (self[dir_is_neg[2]].z - o.z) * inv_dir.z;
+        let mut tz_max = (self[1 - dir_is_neg[2]].z - o.z) * inv_dir.z;
+        tz_max *= 1.0 + 2.0 * gamma(3);
+
+        if t_min > tz_max || tz_min > t_max {
+            return false;
+        }
+        t_min = t_min.max(tz_min);
+        t_max = t_max.min(tz_max);
+        t_min < ray_t_max && t_max > 0.0
+    }
+}
+
+/// `bounds[0]` is `pMin` and `bounds[1]` is `pMax`.
+impl Index<usize> for Bounds3 {
+    type Output = Point3f;
+    fn index(&self, i: usize) -> &Self::Output {
+        match i {
+            0 => &self.pMin,
+            1 => &self.pMax,
+            _ => panic!("index out of bounds"),
+        }
     }
 }
 
@@ -77,59 +256,197 @@ mod tests {
     #[test]
     fn test_new_bounds3() {
         let b = Bounds3::new();
-        assert_eq!(b.pMin.get_x(), Float::MAX);
-        assert_eq!(b.pMin.get_y(), Float::MAX);
-        assert_eq!(b.pMin.get_z(), Float::MAX);
-        assert_eq!(b.pMax.get_x(), Float::MIN);
-        assert_eq!(b.pMax.get_y(), Float::MIN);
-        assert_eq!(b.pMax.get_z(), Float::MIN);
+        assert_eq!(b.pMin.x, Float::MAX);
+        assert_eq!(b.pMin.y, Float::MAX);
+        assert_eq!(b.pMin.z, Float::MAX);
+        assert_eq!(b.pMax.x, Float::MIN);
+        assert_eq!(b.pMax.y, Float::MIN);
+        assert_eq!(b.pMax.z, Float::MIN);
     }
 
     #[test]
     fn test_union_point() {
         let b = Bounds3::new();
-        let p = Point3::new(1.0, 2.0, 3.0);
+        let p = Point3f::new(1.0, 2.0, 3.0);
         let b2 = b.union_point(&p);
-        assert_eq!(b2.pMin.get_x(), 1.0);
-        assert_eq!(b2.pMin.get_y(), 2.0);
-        assert_eq!(b2.pMin.get_z(), 3.0);
-        assert_eq!(b2.pMax.get_x(), 1.0);
-        assert_eq!(b2.pMax.get_y(), 2.0);
-        assert_eq!(b2.pMax.get_z(), 3.0);
+        assert_eq!(b2.pMin.x, 1.0);
+        assert_eq!(b2.pMin.y, 2.0);
+        assert_eq!(b2.pMin.z, 3.0);
+        assert_eq!(b2.pMax.x, 1.0);
+        assert_eq!(b2.pMax.y, 2.0);
+        assert_eq!(b2.pMax.z, 3.0);
     }
 
     #[test]
     fn test_union_bounds() {
-        let b1 = Bounds3::new().union_point(&Point3::new(0.0, 0.0, 0.0));
-        let b2 = Bounds3::new().union_point(&Point3::new(1.0, 2.0, 3.0));
+        let b1 = Bounds3::new().union_point(&Point3f::new(0.0, 0.0, 0.0));
+        let b2 = Bounds3::new().union_point(&Point3f::new(1.0, 2.0, 3.0));
         let b3 = b1.union_bounds(&b2);
-        assert_eq!(b3.pMin.get_x(), 0.0);
-        assert_eq!(b3.pMin.get_y(), 0.0);
-        assert_eq!(b3.pMin.get_z(), 0.0);
-        assert_eq!(b3.pMax.get_x(), 1.0);
-        assert_eq!(b3.pMax.get_y(), 2.0);
-        assert_eq!(b3.pMax.get_z(), 3.0);
+        assert_eq!(b3.pMin.x, 0.0);
+        assert_eq!(b3.pMin.y, 0.0);
+        assert_eq!(b3.pMin.z, 0.0);
+        assert_eq!(b3.pMax.x, 1.0);
+        assert_eq!(b3.pMax.y, 2.0);
+        assert_eq!(b3.pMax.z, 3.0);
     }
 
     #[test]
     fn test_overlaps_true() {
         let b1 = Bounds3::new()
-            .union_point(&Point3::new(0.0, 0.0, 0.0))
-            .union_point(&Point3::new(2.0, 2.0, 2.0));
+            .union_point(&Point3f::new(0.0, 0.0, 0.0))
+            .union_point(&Point3f::new(2.0, 2.0, 2.0));
         let b2 = Bounds3::new()
-            .union_point(&Point3::new(1.0, 1.0, 1.0))
-            .union_point(&Point3::new(3.0, 3.0, 3.0));
+            .union_point(&Point3f::new(1.0, 1.0, 1.0))
+            .union_point(&Point3f::new(3.0, 3.0, 3.0));
         assert!(Bounds3::overlaps(b1, b2));
     }
 
     #[test]
     fn test_overlaps_false() {
         let b1 = Bounds3::new()
-            .union_point(&Point3::new(0.0, 0.0, 0.0))
-            .union_point(&Point3::new(1.0, 1.0, 1.0));
+            .union_point(&Point3f::new(0.0, 0.0, 0.0))
+            .union_point(&Point3f::new(1.0, 1.0, 1.0));
         let b2 = Bounds3::new()
-            .union_point(&Point3::new(2.0, 2.0, 2.0))
-            .union_point(&Point3::new(3.0, 3.0, 3.0));
+            .union_point(&Point3f::new(2.0, 2.0, 2.0))
+            .union_point(&Point3f::new(3.0, 3.0, 3.0));
         assert!(!Bounds3::overlaps(b1, b2));
     }
+
+    fn unit_box() -> Bounds3 {
+        Bounds3::new()
+            .union_point(&Point3f::new(0.0, 0.0, 0.0))
+            .union_point(&Point3f::new(1.0, 1.0, 1.0))
+    }
+
+    fn fast_hit(b: &Bounds3, ray: &Ray) -> bool {
+        let d = ray.direction();
+        let inv_dir = Vector3f::new(1.0 / d.x, 1.0 / d.y, 1.0 / d.z);
+        let dir_is_neg = [
+            (inv_dir.x < 0.0) as usize,
+            (inv_dir.y < 0.0) as usize,
+            (inv_dir.z < 0.0) as usize,
+        ];
+        b.intersect_p_fast(ray, ray.t_max(), &inv_dir, dir_is_neg)
+    }
+
+    #[test]
+    fn test_intersect_p_hit_and_miss() {
+        let b = unit_box();
+        let ray = Ray::new(
+            Point3f::new(-1.0, 0.5, 0.5),
+            Vector3f::new(1.0, 0.0, 0.0),
+            0.0,
+        );
+        let (t0, t1) = b.intersect_p(&ray).unwrap();
+        assert_eq!(t0, 1.0);
+        assert!((2.0..2.0 + 1e-5).contains(&t1));
+        assert!(fast_hit(&b, &ray));
+
+        // Box lies beyond t_max.
+        assert!(b.intersect_p(&ray.with_t_max(0.5)).is_none());
+        assert!(!fast_hit(&b, &ray.with_t_max(0.5)));
+
+        let miss = Ray::new(
+            Point3f::new(-1.0, 2.0, 0.5),
+            Vector3f::new(1.0, 0.0, 0.0),
+            0.0,
+        );
+        assert!(b.intersect_p(&miss).is_none());
+        assert!(!fast_hit(&b, &miss));
+    }
+
+    #[test]
+    fn test_intersect_p_origin_inside() {
+        let b = unit_box();
+        let ray = Ray::new(
+            Point3f::new(0.5, 0.5, 0.5),
+            Vector3f::new(0.0, -1.0, 0.0),
+            0.0,
+        );
+        let (t0, t1) = b.intersect_p(&ray).unwrap();
+        assert_eq!(t0, 0.0);
+        assert!(t1 >= 0.5);
+        assert!(fast_hit(&b, &ray));
+    }
+
+    #[test]
+    fn test_intersect_p_ray_in_slab_plane() {
+        // The origin lies on the x = 0 face and the ray has no x motion, so
+        // (pMin.x - o.x) * (1 / d.x) is 0 * inf = NaN.
+        let b = unit_box();
+        let ray = Ray::new(
+            Point3f::new(0.0, -1.0, 0.5),
+            Vector3f::new(0.0, 1.0, 0.0),
+            0.0,
+        );
+        let (t0, t1) = b.intersect_p(&ray).unwrap();
+        assert!(!t0.is_nan() && !t1.is_nan());
+        assert_eq!(t0, 1.0);
+        assert!(fast_hit(&b, &ray));
+    }
+
+    #[test]
+    fn test_intersect_p_fast_agrees() {
+        let b = unit_box();
+        for i in 0..64 {
+            let angle = i as Float * 0.1;
+            let o = Point3f::new(0.5 + 2.0 * angle.cos(), 0.5 + 2.0 * angle.sin(), 0.3);
+            let target = Point3f::new(0.5, 0.5 + (i % 5) as Float * 0.3 - 0.6, 0.5);
+            let ray = Ray::new(o, target - o, 0.0);
+            assert_eq!(
+                b.intersect_p(&ray).is_some(),
+                fast_hit(&b, &ray),
+                "ray {}",
+                i
+            );
+        }
+    }
+
+    #[test]
+    fn test_intersect_and_empty() {
+        let a = Bounds3::from_points(Point3f::new(0.0, 0.0, 0.0), Point3f::new(2.0, 2.0, 2.0));
+        let b = Bounds3::from_points(Point3f::new(3.0, 3.0, 3.0), Point3f::new(1.0, 1.0, 1.0));
+        let i = Bounds3::intersect(a, b);
+        assert_eq!(i.pMin, Point3f::new(1.0, 1.0, 1.0));
+        assert_eq!(i.pMax, Point3f::new(2.0, 2.0, 2.0));
+        assert!(!i.is_empty());
+
+        let far = Bounds3::from_point(Point3f::new(5.0, 5.0, 5.0));
+        assert!(far.is_empty());
+        assert!(!far.is_degenerate());
+        assert!(Bounds3::intersect(a, far).is_degenerate());
+        assert!(Bounds3::new().is_degenerate());
+    }
+
+    #[test]
+    fn test_extent_lerp_offset() {
+        let b = Bounds3::from_points(Point3f::new(0.0, 0.0, 0.0), Point3f::new(1.0, 4.0, 2.0));
+        assert_eq!(b.maximum_extent(), 1);
+        assert_eq!(b.volume(), 8.0);
+        assert_eq!(b.surface_area(), 28.0);
+        let p = b.lerp(Point3f::new(0.5, 0.25, 1.0));
+        assert_eq!(p, Point3f::new(0.5, 1.0, 2.0));
+        assert_eq!(b.offset(&p), Vector3f::new(0.5, 0.25, 1.0));
+    }
+
+    #[test]
+    fn test_corners_and_index() {
+        let b = Bounds3::from_points(Point3f::new(0.0, 0.0, 0.0), Point3f::new(1.0, 2.0, 3.0));
+        assert_eq!(b.corner(0), b[0]);
+        assert_eq!(b.corner(7), b[1]);
+        assert_eq!(b.corner(5), Point3f::new(1.0, 0.0, 3.0));
+    }
+
+    #[test]
+    fn test_bounding_sphere_and_expand() {
+        let b = Bounds3::from_points(Point3f::new(-1.0, -1.0, -1.0), Point3f::new(1.0, 1.0, 1.0));
+        let (center, radius) = b.bounding_sphere();
+        assert_eq!(center, Point3f::new(0.0, 0.0, 0.0));
+        assert_eq!(radius, (3.0 as Float).sqrt());
+        let e = Bounds3::expand(b, 1.0);
+        assert_eq!(e.pMin, Point3f::new(-2.0, -2.0, -2.0));
+        assert_eq!(e.pMax, Point3f::new(2.0, 2.0, 2.0));
+        assert!(e.is_point_inside(&Point3f::new(2.0, 0.0, 0.0)));
+        assert!(!e.is_point_inside_exclusive(&Point3f::new(2.0, 0.0, 0.0)));
+    }
 }
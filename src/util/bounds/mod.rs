@@ -1,2 +1,4 @@
+mod bounds2;
 mod bounds3;
-pub use bounds3::Bounds3;
\ No newline at end of file
+pub use bounds2::{Bounds2, Bounds2f, Bounds2i, Bounds2iIterator};
+pub use bounds3::Bounds3;
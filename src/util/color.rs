@@ -0,0 +1,121 @@
+use std::ops::{Add, AddAssign, Div, Index, Mul, MulAssign, Sub};
+
+use crate::util::Float;
+
+/// A linear RGB triple, used for radiance estimates and image pixels.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RGB {
+    pub r: Float,
+    pub g: Float,
+    pub b: Float,
+}
+
+impl RGB {
+    pub const fn new(r: Float, g: Float, b: Float) -> Self {
+        Self { r, g, b }
+    }
+
+    pub fn max_component_value(&self) -> Float {
+        self.r.max(self.g).max(self.b)
+    }
+
+    /// Luminance `Y` for linear sRGB primaries.
+    pub fn luminance(&self) -> Float {
+        0.2126 * self.r + 0.7152 * self.g + 0.0722 * self.b
+    }
+
+    pub fn is_black(&self) -> bool {
+        self.r == 0.0 && self.g == 0.0 && self.b == 0.0
+    }
+
+    pub fn has_nans(&self) -> bool {
+        self.r.is_nan() || self.g.is_nan() || self.b.is_nan()
+    }
+}
+
+/// Encodes a linear value in `[0, 1]` with the sRGB transfer curve.
+pub fn linear_to_srgb(v: Float) -> Float {
+    if v <= 0.003_130_8 {
+        12.92 * v
+    } else {
+        1.055 * v.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// Inverse of `linear_to_srgb`.
+pub fn srgb_to_linear(v: Float) -> Float {
+    if v <= 0.040_45 {
+        v / 12.92
+    } else {
+        ((v + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+impl Index<usize> for RGB {
+    type Output = Float;
+    fn index(&self, i: usize) -> &Float {
+        match i {
+            0 => &self.r,
+            1 => &self.g,
+            2 => &self.b,
+            _ => panic!("index out of bounds"),
+        }
+    }
+}
+
+impl Add for RGB {
+    type Output = RGB;
+    fn add(self, rhs: RGB) -> RGB {
+        RGB::new(self.r + rhs.r, self.g + rhs.g, self.b + rhs.b)
+    }
+}
+impl AddAssign for RGB {
+    fn add_assign(&mut self, rhs: RGB) {
+        *self = *self + rhs;
+    }
+}
+impl Sub for RGB {
+    type Output = RGB;
+    fn sub(self, rhs: RGB) -> RGB {
+        RGB::new(self.r - rhs.r, self.g - rhs.g, self.b - rhs.b)
+    }
+}
+impl Mul for RGB {
+    type Output = RGB;
+    fn mul(self, rhs: RGB) -> RGB {
+        RGB::new(self.r * rhs.r, self.g * rhs.g, self.b * rhs.b)
+    }
+}
+impl Mul<Float> for RGB {
+    type Output = RGB;
+    fn mul(self, s: Float) -> RGB {
+        RGB::new(self.r * s, self.g * s, self.b * s)
+    }
+}
+impl MulAssign<Float> for RGB {
+    fn mul_assign(&mut self, s: Float) {
+        *self = *self * s;
+    }
+}
+impl Div<Float> for RGB {
+    type Output = RGB;
+    fn div(self, s: Float) -> RGB {
+        RGB::new(self.r / s, self.g / s, self.b / s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn srgb_curve_round_trips() {
+        assert_eq!(linear_to_srgb(0.0), 0.0);
+        assert!((linear_to_srgb(1.0) - 1.0).abs() < 1e-6);
+        assert!((linear_to_srgb(0.214_041) - 0.5).abs() < 1e-5);
+        for i in 0..=20 {
+            let v = i as Float / 20.0;
+            assert!((srgb_to_linear(linear_to_srgb(v)) - v).abs() < 1e-5);
+        }
+    }
+}
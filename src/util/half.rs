@@ -0,0 +1,109 @@
+/// An IEEE 754 half-precision float, used for compact HDR image storage.
+///
+/// Only conversions are provided; arithmetic should happen in `f32`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Half(u16);
+
+impl Half {
+    pub const fn from_bits(bits: u16) -> Self {
+        Self(bits)
+    }
+
+    pub const fn to_bits(self) -> u16 {
+        self.0
+    }
+
+    /// The nearest half to `f`, rounding ties to even. Values beyond the
+    /// half range become infinities and NaNs stay NaNs.
+    pub fn from_f32(f: f32) -> Self {
+        let bits = f.to_bits();
+        let sign = ((bits >> 16) & 0x8000) as u16;
+        let exp = ((bits >> 23) & 0xff) as i32;
+        let mant = bits & 0x7f_ffff;
+        if exp == 0xff {
+            let nan = if mant != 0 {
+                0x200 | (mant >> 13) as u16
+            } else {
+                0
+            };
+            return Self(sign | 0x7c00 | nan);
+        }
+        let e = exp - 127 + 15;
+        if e >= 0x1f {
+            return Self(sign | 0x7c00);
+        }
+        // Shift the mantissa, with the implicit one for subnormal results,
+        // down to 10 bits and round the dropped bits to nearest even. A
+        // carry out of the mantissa correctly bumps the exponent.
+        let (m, shift, base) = if e <= 0 {
+            if e < -10 {
+                return Self(sign);
+            }
+            (mant | 0x80_0000, (14 - e) as u32, 0)
+        } else {
+            (mant, 13, (e as u32) << 10)
+        };
+        let mut h = m >> shift;
+        let rem = m & ((1 << shift) - 1);
+        let halfway = 1 << (shift - 1);
+        if rem > halfway || (rem == halfway && h & 1 == 1) {
+            h += 1;
+        }
+        Self(sign | (base + h) as u16)
+    }
+
+    /// The exact `f32` value of the half.
+    pub fn to_f32(self) -> f32 {
+        let h = self.0 as u32;
+        let sign = (h & 0x8000) << 16;
+        let exp = (h >> 10) & 0x1f;
+        let mant = h & 0x3ff;
+        let bits = match exp {
+            0 if mant == 0 => sign,
+            0 => {
+                // Subnormal: renormalize the mantissa.
+                let shift = mant.leading_zeros() - 21;
+                sign | ((113 - shift) << 23) | (((mant << shift) & 0x3ff) << 13)
+            }
+            0x1f => sign | 0x7f80_0000 | (mant << 13),
+            _ => sign | ((exp + 112) << 23) | (mant << 13),
+        };
+        f32::from_bits(bits)
+    }
+}
+
+impl From<f32> for Half {
+    fn from(f: f32) -> Self {
+        Self::from_f32(f)
+    }
+}
+
+impl From<Half> for f32 {
+    fn from(h: Half) -> Self {
+        h.to_f32()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conversions_round_trip_and_round_to_even() {
+        for bits in (0..=0xffffu16).filter(|b| b & 0x7c00 != 0x7c00) {
+            let h = Half::from_bits(bits);
+            assert_eq!(Half::from_f32(h.to_f32()), h, "{bits:#x}");
+        }
+        assert_eq!(Half::from_f32(1.0).to_bits(), 0x3c00);
+        assert_eq!(Half::from_f32(-2.0).to_bits(), 0xc000);
+        assert_eq!(Half::from_f32(65504.0).to_bits(), 0x7bff);
+        assert_eq!(Half::from_f32(1e6).to_bits(), 0x7c00);
+        assert!(Half::from_f32(f32::NAN).to_f32().is_nan());
+        // Halfway between 1 and the next half rounds down to the even 1.
+        assert_eq!(Half::from_f32(1.0 + 1.0 / 2048.0).to_bits(), 0x3c00);
+        assert_eq!(Half::from_f32(1.0 + 3.0 / 2048.0).to_bits(), 0x3c02);
+        // Smallest subnormal, and values below half of it.
+        assert_eq!(Half::from_bits(1).to_f32(), 2.0f32.powi(-24));
+        assert_eq!(Half::from_f32(2.0f32.powi(-26)).to_bits(), 0);
+    }
+}
@@ -0,0 +1,110 @@
+/// Scrambles the bits of `v` so that nearby inputs give unrelated outputs
+/// (the finalizer of Stafford's "Mix13" variant of MurmurHash3).
+#[inline]
+pub fn mix_bits(mut v: u64) -> u64 {
+    v ^= v >> 31;
+    v = v.wrapping_mul(0x7fb5_d329_728e_a185);
+    v ^= v >> 27;
+    v = v.wrapping_mul(0x81da_def4_bc2d_d44d);
+    v ^= v >> 33;
+    v
+}
+
+/// MurmurHash64A of `key`.
+pub fn murmur_hash64a(key: &[u8], seed: u64) -> u64 {
+    const M: u64 = 0xc6a4_a793_5bd1_e995;
+    const R: u32 = 47;
+    let mut h = seed ^ (key.len() as u64).wrapping_mul(M);
+    let mut chunks = key.chunks_exact(8);
+    for chunk in &mut chunks {
+        let mut k = u64::from_le_bytes(chunk.try_into().unwrap());
+        k = k.wrapping_mul(M);
+        k ^= k >> R;
+        k = k.wrapping_mul(M);
+        h ^= k;
+        h = h.wrapping_mul(M);
+    }
+    let tail = chunks.remainder();
+    if !tail.is_empty() {
+        for (i, &b) in tail.iter().enumerate() {
+            h ^= (b as u64) << (8 * i);
+        }
+        h = h.wrapping_mul(M);
+    }
+    h ^= h >> R;
+    h = h.wrapping_mul(M);
+    h ^= h >> R;
+    h
+}
+
+/// A hash of a sequence of values, e.g. a pixel, a dimension and a seed.
+pub fn hash(values: &[u64]) -> u64 {
+    let bytes: Vec<u8> = values.iter().flat_map(|v| v.to_le_bytes()).collect();
+    murmur_hash64a(&bytes, 0)
+}
+
+/// Element `i` of a random permutation of `0..n` chosen by `seed`, without
+/// building the permutation (Kensler's "Correlated Multi-Jittered
+/// Sampling").
+pub fn permutation_element(mut i: u32, n: u32, seed: u32) -> u32 {
+    debug_assert!(i < n);
+    let p = seed;
+    let mut w = n - 1;
+    w |= w >> 1;
+    w |= w >> 2;
+    w |= w >> 4;
+    w |= w >> 8;
+    w |= w >> 16;
+    // Cycle walk until the hashed value is in range.
+    loop {
+        i ^= p;
+        i = i.wrapping_mul(0xe170_893d);
+        i ^= p >> 16;
+        i ^= (i & w) >> 4;
+        i ^= p >> 8;
+        i = i.wrapping_mul(0x0929_eb3f);
+        i ^= p >> 23;
+        i ^= (i & w) >> 1;
+        i = i.wrapping_mul(1 | p >> 27);
+        i = i.wrapping_mul(0x6935_fa69);
+        i ^= (i & w) >> 11;
+        i = i.wrapping_mul(0x74dc_b303);
+        i ^= (i & w) >> 2;
+        i = i.wrapping_mul(0x9e50_1cc3);
+        i ^= (i & w) >> 2;
+        i = i.wrapping_mul(0xc860_a3df);
+        i &= w;
+        i ^= i >> 5;
+        if i < n {
+            break;
+        }
+    }
+    ((i as u64 + p as u64) % n as u64) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hashes_depend_on_every_value() {
+        assert_eq!(hash(&[1, 2, 3]), hash(&[1, 2, 3]));
+        assert_ne!(hash(&[1, 2, 3]), hash(&[1, 3, 2]));
+        assert_ne!(hash(&[1, 2]), hash(&[1, 2, 0]));
+        assert_ne!(murmur_hash64a(b"abc", 0), murmur_hash64a(b"abc", 1));
+    }
+
+    #[test]
+    fn permutation_elements_form_a_permutation() {
+        for n in [1, 2, 7, 16, 100] {
+            for seed in [0, 1, 0xdead_beef] {
+                let mut seen: Vec<u32> = (0..n).map(|i| permutation_element(i, n, seed)).collect();
+                seen.sort_unstable();
+                assert!(seen.iter().copied().eq(0..n), "n = {n}, seed = {seed}");
+            }
+        }
+        let a: Vec<_> = (0..16).map(|i| permutation_element(i, 16, 1)).collect();
+        let b: Vec<_> = (0..16).map(|i| permutation_element(i, 16, 2)).collect();
+        assert_ne!(a, b);
+    }
+}
@@ -1,3 +1,178 @@
+use crate::util::Float;
+use crate::util::math::{next_float_down, next_float_up};
+use crate::util::rays::{MediumHandle, MediumInterface, Ray};
+use crate::util::vector::{Normal3f, Point2f, Point3f, Vector3f};
+
+/// Fraction of a shadow ray left short of its target so that it does not
+/// report the target surface itself as an occluder.
+pub const SHADOW_EPSILON: Float = 0.0001;
+
+/// The data common to every point where light can scatter: where it is, how
+/// precisely that is known, and the direction the query came from.
+///
+/// `n` is zero for points that are not on a surface, such as scattering
+/// events inside a participating medium.
+#[derive(Debug, Clone, Copy, Default)]
 pub struct Interaction {
-    
+    pub p: Point3f,
+    /// Conservative bound on the absolute error in each component of `p`.
+    pub p_error: Vector3f,
+    pub time: Float,
+    /// Normalized direction back along the incoming ray, or zero if there is
+    /// none.
+    pub wo: Vector3f,
+    pub n: Normal3f,
+    pub uv: Point2f,
+    /// Media on either side of the surface, if it bounds any.
+    pub medium_interface: Option<MediumInterface>,
+    /// Medium the point lies in, for points without an interface.
+    pub medium: Option<MediumHandle>,
+}
+
+impl Interaction {
+    pub fn new(
+        p: Point3f,
+        p_error: Vector3f,
+        n: Normal3f,
+        uv: Point2f,
+        wo: Vector3f,
+        time: Float,
+    ) -> Self {
+        Self {
+            p,
+            p_error,
+            time,
+            wo,
+            n,
+            uv,
+            ..Self::default()
+        }
+    }
+
+    /// A point with no surface or incoming direction.
+    pub fn from_point(p: Point3f, time: Float) -> Self {
+        Self {
+            p,
+            time,
+            ..Self::default()
+        }
+    }
+
+    pub fn is_surface_interaction(&self) -> bool {
+        self.n != Normal3f::default()
+    }
+
+    /// Medium that a ray leaving in direction `w` starts in.
+    pub fn get_medium(&self, w: &Vector3f) -> Option<MediumHandle> {
+        match self.medium_interface {
+            Some(mi) if self.n.dot_vector(w) > 0.0 => mi.outside,
+            Some(mi) => mi.inside,
+            None => self.medium,
+        }
+    }
+
+    /// `p` pushed along the normal, to the side `w` points to, far enough
+    /// that the error bounds on `p` cannot put it back on the surface.
+    pub fn offset_ray_origin(&self, w: &Vector3f) -> Point3f {
+        let n = Vector3f::from(self.n);
+        let d = n.abs().dot(&self.p_error);
+        let mut offset = n * d;
+        if w.dot(&n) < 0.0 {
+            offset = -offset;
+        }
+        let mut po = self.p + offset;
+        // Round away from p so the offset is not lost to rounding.
+        for i in 0..3 {
+            if offset[i] > 0.0 {
+                po[i] = next_float_up(po[i]);
+            } else if offset[i] < 0.0 {
+                po[i] = next_float_down(po[i]);
+            }
+        }
+        po
+    }
+
+    /// A ray leaving the point in direction `d`.
+    pub fn spawn_ray(&self, d: Vector3f) -> Ray {
+        Ray::new(self.offset_ray_origin(&d), d, self.time).with_medium(self.get_medium(&d))
+    }
+
+    /// A ray toward `p2` that stops just short of it at `t = 1`.
+    pub fn spawn_ray_to_point(&self, p2: Point3f) -> Ray {
+        let origin = self.offset_ray_origin(&(p2 - self.p));
+        let d = p2 - origin;
+        Ray::new(origin, d, self.time)
+            .with_t_max(1.0 - SHADOW_EPSILON)
+            .with_medium(self.get_medium(&d))
+    }
+
+    /// A ray between two interactions, offset at both ends so that neither
+    /// surface occludes it.
+    pub fn spawn_ray_to(&self, it: &Interaction) -> Ray {
+        let origin = self.offset_ray_origin(&(it.p - self.p));
+        let target = it.offset_ray_origin(&(origin - it.p));
+        let d = target - origin;
+        Ray::new(origin, d, self.time)
+            .with_t_max(1.0 - SHADOW_EPSILON)
+            .with_medium(self.get_medium(&d))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn surface_point() -> Interaction {
+        Interaction::new(
+            Point3f::new(0.0, 0.0, 1.0),
+            Vector3f::new(1e-4, 1e-4, 1e-4),
+            Normal3f::new(0.0, 0.0, 1.0),
+            Point2f::default(),
+            Vector3f::new(0.0, 0.0, 1.0),
+            0.0,
+        )
+    }
+
+    #[test]
+    fn spawned_rays_start_on_the_correct_side() {
+        let it = surface_point();
+        let up = it.spawn_ray(Vector3f::new(0.3, 0.0, 1.0));
+        assert!(up.origin().z > 1.0 + 1e-4);
+        let down = it.spawn_ray(Vector3f::new(0.0, 0.0, -1.0));
+        assert!(down.origin().z < 1.0 - 1e-4);
+        assert_eq!(up.origin().x, 0.0);
+    }
+
+    #[test]
+    fn shadow_rays_stop_short_of_the_target() {
+        let it = surface_point();
+        let target = Point3f::new(0.0, 0.0, 3.0);
+        let r = it.spawn_ray_to_point(target);
+        assert!(r.t_max() < 1.0);
+        assert!((r.get(1.0) - target).length() < 1e-6);
+
+        let other = Interaction::new(
+            target,
+            Vector3f::new(1e-4, 1e-4, 1e-4),
+            Normal3f::new(0.0, 0.0, -1.0),
+            Point2f::default(),
+            Vector3f::default(),
+            0.0,
+        );
+        let r = it.spawn_ray_to(&other);
+        assert!(r.get(1.0).z < 3.0 - 1e-4);
+    }
+
+    #[test]
+    fn medium_follows_the_side_of_the_interface() {
+        let mut it = surface_point();
+        let (inside, outside) = (MediumHandle::new(0), MediumHandle::new(1));
+        it.medium = Some(MediumHandle::new(7));
+        assert_eq!(it.get_medium(&Vector3f::new(0.0, 0.0, 1.0)), it.medium);
+        it.medium_interface = Some(MediumInterface::new(Some(inside), Some(outside)));
+        let r = it.spawn_ray(Vector3f::new(0.0, 0.0, 1.0));
+        assert_eq!(r.medium(), Some(outside));
+        let r = it.spawn_ray(Vector3f::new(0.0, 0.0, -1.0));
+        assert_eq!(r.medium(), Some(inside));
+    }
 }
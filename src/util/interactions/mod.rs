@@ -1,2 +1,4 @@
 mod interaction;
-pub use interaction::Interaction;
+mod surface_interaction;
+pub use interaction::{Interaction, SHADOW_EPSILON};
+pub use surface_interaction::{Shading, SurfaceInteraction};
@@ -0,0 +1,393 @@
+use std::ops::{Deref, DerefMut};
+
+use crate::util::Float;
+use crate::util::interactions::Interaction;
+use crate::util::math::difference_of_products;
+use crate::util::rays::{Ray, RayDifferential};
+use crate::util::vector::{Normal3f, Point2f, Point3f, Vector3f};
+
+/// The shading frame, which may differ from the true surface geometry when
+/// normals are interpolated or perturbed by bump or normal mapping.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Shading {
+    pub n: Normal3f,
+    pub dpdu: Vector3f,
+    pub dpdv: Vector3f,
+    pub dndu: Normal3f,
+    pub dndv: Normal3f,
+}
+
+/// An `Interaction` on a surface, with the local differential geometry of
+/// the `(u, v)` parameterization and, once `compute_differentials` has run,
+/// the screen-space rates of change used to filter textures.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SurfaceInteraction {
+    pub interaction: Interaction,
+    pub dpdu: Vector3f,
+    pub dpdv: Vector3f,
+    pub dndu: Normal3f,
+    pub dndv: Normal3f,
+    pub shading: Shading,
+    pub dpdx: Vector3f,
+    pub dpdy: Vector3f,
+    pub dudx: Float,
+    pub dvdx: Float,
+    pub dudy: Float,
+    pub dvdy: Float,
+}
+
+impl SurfaceInteraction {
+    /// The geometric normal is `dpdu x dpdv`, negated when `flip_normal` is
+    /// set, which shapes use for reversed orientation or a handedness
+    /// swapping transform. The shading frame starts out equal to the
+    /// geometric one.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        p: Point3f,
+        p_error: Vector3f,
+        uv: Point2f,
+        wo: Vector3f,
+        dpdu: Vector3f,
+        dpdv: Vector3f,
+        dndu: Normal3f,
+        dndv: Normal3f,
+        time: Float,
+        flip_normal: bool,
+    ) -> Self {
+        let mut n = Normal3f::from(dpdu.cross(&dpdv).normalize());
+        if flip_normal {
+            n = -n;
+        }
+        Self {
+            interaction: Interaction::new(p, p_error, n, uv, wo, time),
+            dpdu,
+            dpdv,
+            dndu,
+            dndv,
+            shading: Shading {
+                n,
+                dpdu,
+                dpdv,
+                dndu,
+                dndv,
+            },
+            ..Self::default()
+        }
+    }
+
+    /// Replaces the shading frame. The geometric and shading normals are then
+    /// made to lie in the same hemisphere: if `orientation_is_authoritative`
+    /// the geometric normal is flipped to match, otherwise the shading one.
+    pub fn set_shading_geometry(
+        &mut self,
+        ns: Normal3f,
+        dpdus: Vector3f,
+        dpdvs: Vector3f,
+        dndus: Normal3f,
+        dndvs: Normal3f,
+        orientation_is_authoritative: bool,
+    ) {
+        self.shading = Shading {
+            n: ns,
+            dpdu: dpdus,
+            dpdv: dpdvs,
+            dndu: dndus,
+            dndv: dndvs,
+        };
+        if orientation_is_authoritative {
+            self.interaction.n = self.interaction.n.face_forward(&Vector3f::from(ns));
+        } else {
+            self.shading.n = ns.face_forward(&Vector3f::from(self.interaction.n));
+        }
+        // Degenerate parameterizations can produce huge tangents whose
+        // products overflow later on; scale them down.
+        while self.shading.dpdu.length_squared() > 1e16 || self.shading.dpdv.length_squared() > 1e16
+        {
+            self.shading.dpdu *= 1e-8;
+            self.shading.dpdv *= 1e-8;
+        }
+    }
+
+    /// Estimates how `p`, `u` and `v` change per pixel step by intersecting
+    /// the offset rays of `ray` with the tangent plane.
+    ///
+    /// If `ray` carries no differentials, or either offset ray runs parallel
+    /// to the plane, the differentials are set to zero, which disables
+    /// texture filtering at this point rather than producing an infinite
+    /// footprint.
+    pub fn compute_differentials(&mut self, ray: &RayDifferential) {
+        if !ray.has_differentials {
+            self.clear_differentials();
+            return;
+        }
+        let (rx, ry) = (&ray.rx(), &ray.ry());
+        let n = Vector3f::from(self.interaction.n);
+        let p = Vector3f::from(self.interaction.p);
+        let plane_hit = |r: &Ray| {
+            let t = (p - Vector3f::from(r.origin())).dot(&n) / r.direction().dot(&n);
+            r.get(t)
+        };
+        self.dpdx = plane_hit(rx) - self.interaction.p;
+        self.dpdy = plane_hit(ry) - self.interaction.p;
+        if !is_finite(&self.dpdx) || !is_finite(&self.dpdy) {
+            self.clear_differentials();
+            return;
+        }
+
+        // Least-squares fit of dp/dx = dp/du * du/dx + dp/dv * dv/dx, and the
+        // same in y.
+        let ata00 = self.dpdu.dot(&self.dpdu);
+        let ata01 = self.dpdu.dot(&self.dpdv);
+        let ata11 = self.dpdv.dot(&self.dpdv);
+        let inv_det = 1.0 / difference_of_products(ata00, ata11, ata01, ata01);
+        let inv_det = if inv_det.is_finite() { inv_det } else { 0.0 };
+        let atb0x = self.dpdu.dot(&self.dpdx);
+        let atb1x = self.dpdv.dot(&self.dpdx);
+        let atb0y = self.dpdu.dot(&self.dpdy);
+        let atb1y = self.dpdv.dot(&self.dpdy);
+
+        let clamp = |d: Float| {
+            if d.is_finite() {
+                d.clamp(-1e8, 1e8)
+            } else {
+                0.0
+            }
+        };
+        self.dudx = clamp(difference_of_products(ata11, atb0x, ata01, atb1x) * inv_det);
+        self.dvdx = clamp(difference_of_products(ata00, atb1x, ata01, atb0x) * inv_det);
+        self.dudy = clamp(difference_of_products(ata11, atb0y, ata01, atb1y) * inv_det);
+        self.dvdy = clamp(difference_of_products(ata00, atb1y, ata01, atb0y) * inv_det);
+    }
+
+    /// Differential for the perfect specular reflection of `ray` into `wi`.
+    ///
+    /// Needs `compute_differentials` to have run for this hit. The offset
+    /// rays start where the incoming ones met the tangent plane and are
+    /// mirrored about the shading normal as it varies across the footprint.
+    pub fn reflected_differential(&self, ray: &RayDifferential, wi: Vector3f) -> RayDifferential {
+        let mut rd = RayDifferential::new(self.spawn_ray(wi));
+        if !ray.has_differentials {
+            return rd;
+        }
+        let wo = self.wo;
+        let n = Vector3f::from(self.shading.n);
+        let (dndx, dndy) = self.shading_normal_differentials();
+        let dwodx = -ray.rx_direction - wo;
+        let dwody = -ray.ry_direction - wo;
+        let dwo_dot_n_dx = dwodx.dot(&n) + wo.dot(&dndx);
+        let dwo_dot_n_dy = dwody.dot(&n) + wo.dot(&dndy);
+        let wo_dot_n = wo.dot(&n);
+
+        rd.rx_direction = wi - dwodx + (dndx * wo_dot_n + n * dwo_dot_n_dx) * 2.0;
+        rd.ry_direction = wi - dwody + (dndy * wo_dot_n + n * dwo_dot_n_dy) * 2.0;
+        self.finish_differential(rd)
+    }
+
+    /// Differential for the perfect specular transmission of `ray` into `wi`,
+    /// where `eta` is the relative index of refraction across the boundary in
+    /// the direction of travel.
+    ///
+    /// Needs `compute_differentials` to have run for this hit.
+    pub fn refracted_differential(
+        &self,
+        ray: &RayDifferential,
+        wi: Vector3f,
+        eta: Float,
+    ) -> RayDifferential {
+        let mut rd = RayDifferential::new(self.spawn_ray(wi));
+        if !ray.has_differentials {
+            return rd;
+        }
+        let wo = self.wo;
+        let mut n = Vector3f::from(self.shading.n);
+        let (mut dndx, mut dndy) = self.shading_normal_differentials();
+        // Work with the normal on the side of wo.
+        if wo.dot(&n) < 0.0 {
+            n = -n;
+            dndx = -dndx;
+            dndy = -dndy;
+        }
+        let dwodx = -ray.rx_direction - wo;
+        let dwody = -ray.ry_direction - wo;
+        let dwo_dot_n_dx = dwodx.dot(&n) + wo.dot(&dndx);
+        let dwo_dot_n_dy = dwody.dot(&n) + wo.dot(&dndy);
+
+        let mu = wo.dot(&n) / eta - wi.abs_dot(&n);
+        let dmu = 1.0 / eta + 1.0 / (eta * eta) * wo.dot(&n) / wi.dot(&n);
+        let dmudx = dwo_dot_n_dx * dmu;
+        let dmudy = dwo_dot_n_dy * dmu;
+
+        rd.rx_direction = wi - dwodx * eta + dndx * mu + n * dmudx;
+        rd.ry_direction = wi - dwody * eta + dndy * mu + n * dmudy;
+        self.finish_differential(rd)
+    }
+
+    /// Rate of change of the shading normal per pixel step in x and y.
+    fn shading_normal_differentials(&self) -> (Vector3f, Vector3f) {
+        let dndu = Vector3f::from(self.shading.dndu);
+        let dndv = Vector3f::from(self.shading.dndv);
+        (
+            dndu * self.dudx + dndv * self.dvdx,
+            dndu * self.dudy + dndv * self.dvdy,
+        )
+    }
+
+    /// Sets the offset origins and drops differentials that have blown up,
+    /// as happens at grazing angles.
+    fn finish_differential(&self, mut rd: RayDifferential) -> RayDifferential {
+        rd.rx_origin = self.p + self.dpdx;
+        rd.ry_origin = self.p + self.dpdy;
+        let too_large = |v: Vector3f| v.length_squared() > 1e16;
+        rd.has_differentials = !(too_large(rd.rx_direction)
+            || too_large(rd.ry_direction)
+            || too_large(Vector3f::from(rd.rx_origin))
+            || too_large(Vector3f::from(rd.ry_origin)));
+        rd
+    }
+
+    pub fn clear_differentials(&mut self) {
+        self.dpdx = Vector3f::default();
+        self.dpdy = Vector3f::default();
+        self.dudx = 0.0;
+        self.dvdx = 0.0;
+        self.dudy = 0.0;
+        self.dvdy = 0.0;
+    }
+}
+
+fn is_finite(v: &Vector3f) -> bool {
+    v.x.is_finite() && v.y.is_finite() && v.z.is_finite()
+}
+
+impl Deref for SurfaceInteraction {
+    type Target = Interaction;
+    fn deref(&self) -> &Self::Target {
+        &self.interaction
+    }
+}
+
+impl DerefMut for SurfaceInteraction {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.interaction
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The plane z = 0 with u along x scaled by two and v along y.
+    fn plane_hit() -> SurfaceInteraction {
+        SurfaceInteraction::new(
+            Point3f::new(0.0, 0.0, 0.0),
+            Vector3f::default(),
+            Point2f::new(0.0, 0.0),
+            Vector3f::new(0.0, 0.0, 1.0),
+            Vector3f::new(2.0, 0.0, 0.0),
+            Vector3f::new(0.0, 1.0, 0.0),
+            Normal3f::default(),
+            Normal3f::default(),
+            0.0,
+            false,
+        )
+    }
+
+    #[test]
+    fn normal_follows_derivatives_and_flip() {
+        let si = plane_hit();
+        assert_eq!(si.n, Normal3f::new(0.0, 0.0, 1.0));
+        assert_eq!(si.shading.n, si.n);
+        assert!(si.is_surface_interaction());
+        let flipped = SurfaceInteraction::new(
+            si.p, si.p_error, si.uv, si.wo, si.dpdu, si.dpdv, si.dndu, si.dndv, si.time, true,
+        );
+        assert_eq!(flipped.n, Normal3f::new(0.0, 0.0, -1.0));
+    }
+
+    #[test]
+    fn shading_geometry_orientation() {
+        let down = Normal3f::new(0.0, 0.0, -1.0);
+        let (dpdu, dpdv) = (Vector3f::new(1.0, 0.0, 0.0), Vector3f::new(0.0, -1.0, 0.0));
+        let zero = Normal3f::default();
+
+        let mut si = plane_hit();
+        si.set_shading_geometry(down, dpdu, dpdv, zero, zero, true);
+        assert_eq!(si.n, down);
+        assert_eq!(si.shading.n, down);
+
+        let mut si = plane_hit();
+        si.set_shading_geometry(down, dpdu, dpdv, zero, zero, false);
+        assert_eq!(si.n, Normal3f::new(0.0, 0.0, 1.0));
+        assert_eq!(si.shading.n, Normal3f::new(0.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn differentials_from_offset_rays() {
+        let mut si = plane_hit();
+        let down = Vector3f::new(0.0, 0.0, -1.0);
+        let ray = Ray::new(Point3f::new(0.0, 0.0, 1.0), down, 0.0);
+        let rx = Ray::new(Point3f::new(0.5, 0.0, 1.0), down, 0.0);
+        let ry = Ray::new(Point3f::new(0.0, 0.25, 1.0), down, 0.0);
+        si.compute_differentials(&RayDifferential::with_differentials(ray, &rx, &ry));
+        assert_eq!(si.dpdx, Vector3f::new(0.5, 0.0, 0.0));
+        assert_eq!(si.dpdy, Vector3f::new(0.0, 0.25, 0.0));
+        assert_eq!((si.dudx, si.dvdx), (0.25, 0.0));
+        assert_eq!((si.dudy, si.dvdy), (0.0, 0.25));
+    }
+
+    #[test]
+    fn parallel_offset_ray_clears_differentials() {
+        let mut si = plane_hit();
+        let down = Vector3f::new(0.0, 0.0, -1.0);
+        let ray = Ray::new(Point3f::new(0.0, 0.0, 1.0), down, 0.0);
+        let rx = Ray::new(
+            Point3f::new(0.0, 0.0, 1.0),
+            Vector3f::new(1.0, 0.0, 0.0),
+            0.0,
+        );
+        let ry = Ray::new(Point3f::new(0.0, 0.25, 1.0), down, 0.0);
+        si.compute_differentials(&RayDifferential::with_differentials(ray, &rx, &ry));
+        assert_eq!(si.dpdx, Vector3f::default());
+        assert_eq!(si.dudy, 0.0);
+
+        si.compute_differentials(&RayDifferential::new(ray));
+        assert_eq!(si.dpdy, Vector3f::default());
+    }
+
+    /// A hit on the flat plane from a ray straight down whose x offset ray
+    /// is tilted, with the differentials already computed.
+    fn tilted_camera_hit() -> (SurfaceInteraction, RayDifferential) {
+        let mut si = plane_hit();
+        let down = Vector3f::new(0.0, 0.0, -1.0);
+        let ray = Ray::new(Point3f::new(0.0, 0.0, 1.0), down, 0.0);
+        let rx = Ray::new(
+            Point3f::new(0.0, 0.0, 1.0),
+            Vector3f::new(0.1, 0.0, -1.0),
+            0.0,
+        );
+        let ry = Ray::new(Point3f::new(0.0, 0.1, 1.0), down, 0.0);
+        let rd = RayDifferential::with_differentials(ray, &rx, &ry);
+        si.compute_differentials(&rd);
+        (si, rd)
+    }
+
+    #[test]
+    fn mirror_reflects_offset_directions() {
+        let (si, rd) = tilted_camera_hit();
+        let refl = si.reflected_differential(&rd, Vector3f::new(0.0, 0.0, 1.0));
+        assert!(refl.has_differentials);
+        assert_eq!(refl.origin(), si.p);
+        assert!((refl.rx_direction - Vector3f::new(0.1, 0.0, 1.0)).length() < 1e-6);
+        assert!((refl.rx_origin - Point3f::new(0.1, 0.0, 0.0)).length() < 1e-6);
+        assert!((refl.ry_origin - Point3f::new(0.0, 0.1, 0.0)).length() < 1e-6);
+    }
+
+    #[test]
+    fn index_matched_refraction_passes_straight_through() {
+        let (si, rd) = tilted_camera_hit();
+        let refr = si.refracted_differential(&rd, Vector3f::new(0.0, 0.0, -1.0), 1.0);
+        assert!(refr.has_differentials);
+        assert!((refr.rx_direction - rd.rx_direction).length() < 1e-6);
+        assert!((refr.ry_direction - rd.ry_direction).length() < 1e-6);
+    }
+}
@@ -0,0 +1,472 @@
+use std::sync::OnceLock;
+
+use crate::util::Float;
+use crate::util::hash::{hash, mix_bits, permutation_element};
+use crate::util::math::ONE_MINUS_EPSILON;
+use crate::util::sobol_directions::SOBOL_INITIAL_DIRECTIONS;
+
+/// Number of primes, and so of dimensions, available to the radical
+/// inverse functions.
+pub const PRIME_TABLE_SIZE: usize = 1000;
+
+/// Number of dimensions `sobol_sample` supports.
+pub const N_SOBOL_DIMENSIONS: usize = 1024;
+
+/// Number of generator matrix columns per Sobol dimension, which bounds the
+/// sample index to `2^52`.
+pub const SOBOL_MATRIX_SIZE: usize = 52;
+
+/// The first `PRIME_TABLE_SIZE` primes.
+pub fn primes() -> &'static [u32] {
+    static PRIMES: OnceLock<Vec<u32>> = OnceLock::new();
+    PRIMES.get_or_init(|| {
+        let mut primes = Vec::with_capacity(PRIME_TABLE_SIZE);
+        let mut n = 2;
+        while primes.len() < PRIME_TABLE_SIZE {
+            if primes
+                .iter()
+                .take_while(|&&p| p * p <= n)
+                .all(|&p| n % p != 0)
+            {
+                primes.push(n);
+            }
+            n += 1;
+        }
+        primes
+    })
+}
+
+/// Whether `1 - (base - 1) * inv_base_m` still differs from one, i.e.
+/// whether another digit of the radical inverse can change the result.
+#[inline]
+fn digit_matters(base: u32, inv_base_m: Float) -> bool {
+    1.0 - (base - 1) as Float * inv_base_m < 1.0
+}
+
+/// `reversed_digits` with `digit` appended in base `base`, or `None` if that
+/// overflows. With `Float` as `f64` the digits that matter for large bases
+/// do not all fit, but those that are left change the result by at most a
+/// few ulps.
+#[inline]
+fn append_digit(reversed_digits: u64, base: u32, digit: u32) -> Option<u64> {
+    reversed_digits
+        .checked_mul(base as u64)?
+        .checked_add(digit as u64)
+}
+
+/// Reflects the base-`b` digits of `a` about the radix point, where `b` is
+/// the prime number `base_index`.
+pub fn radical_inverse(base_index: usize, mut a: u64) -> Float {
+    let base = primes()[base_index] as u64;
+    let inv_base = 1.0 / base as Float;
+    let mut inv_base_m = 1.0;
+    let mut reversed_digits = 0u64;
+    while a != 0 {
+        let next = a / base;
+        let digit = a - next * base;
+        reversed_digits = reversed_digits.wrapping_mul(base).wrapping_add(digit);
+        inv_base_m *= inv_base;
+        a = next;
+    }
+    (reversed_digits as Float * inv_base_m).min(ONE_MINUS_EPSILON)
+}
+
+/// The index whose first `n_digits` base-`base` digits, reversed, are the
+/// digits of `inverse`.
+pub fn inverse_radical_inverse(mut inverse: u64, base: u64, n_digits: u32) -> u64 {
+    let mut index = 0;
+    for _ in 0..n_digits {
+        let digit = inverse % base;
+        inverse /= base;
+        index = index * base + digit;
+    }
+    index
+}
+
+/// A random permutation of the digit values of a base for each digit
+/// position that affects a `Float` radical inverse.
+#[derive(Debug, Clone)]
+pub struct DigitPermutation {
+    base: u32,
+    n_digits: usize,
+    permutations: Vec<u16>,
+}
+
+impl DigitPermutation {
+    pub fn new(base: u32, seed: u64) -> Self {
+        assert!(base < 65536, "digit values must fit in 16 bits");
+        let inv_base = 1.0 / base as Float;
+        let mut inv_base_m = 1.0;
+        let mut n_digits = 0;
+        while digit_matters(base, inv_base_m) {
+            n_digits += 1;
+            inv_base_m *= inv_base;
+        }
+        let mut permutations = Vec::with_capacity(n_digits * base as usize);
+        for digit_index in 0..n_digits {
+            let digit_seed = hash(&[base as u64, digit_index as u64, seed]) as u32;
+            permutations
+                .extend((0..base).map(|digit| permutation_element(digit, base, digit_seed) as u16));
+        }
+        Self {
+            base,
+            n_digits,
+            permutations,
+        }
+    }
+
+    pub fn permute(&self, digit_index: usize, digit_value: u32) -> u32 {
+        debug_assert!(digit_index < self.n_digits && digit_value < self.base);
+        self.permutations[digit_index * self.base as usize + digit_value as usize] as u32
+    }
+}
+
+/// Digit permutations for every prime base.
+pub fn compute_radical_inverse_permutations(seed: u64) -> Vec<DigitPermutation> {
+    primes()
+        .iter()
+        .map(|&p| DigitPermutation::new(p, seed))
+        .collect()
+}
+
+/// `radical_inverse` with the digits permuted by `perm`, which must be for
+/// the prime number `base_index`. Unlike `radical_inverse` it permutes the
+/// trailing zero digits too, so values do not cluster near zero.
+pub fn scrambled_radical_inverse(base_index: usize, mut a: u64, perm: &DigitPermutation) -> Float {
+    let base = primes()[base_index];
+    let inv_base = 1.0 / base as Float;
+    let mut inv_base_m = 1.0;
+    let mut reversed_digits = 0u64;
+    let mut digit_index = 0;
+    while digit_matters(base, inv_base_m) {
+        let next = a / base as u64;
+        let digit = perm.permute(digit_index, (a - next * base as u64) as u32);
+        let Some(reversed) = append_digit(reversed_digits, base, digit) else {
+            break;
+        };
+        reversed_digits = reversed;
+        inv_base_m *= inv_base;
+        digit_index += 1;
+        a = next;
+    }
+    (reversed_digits as Float * inv_base_m).min(ONE_MINUS_EPSILON)
+}
+
+/// `radical_inverse` with Owen scrambling: each digit is permuted by a
+/// permutation that depends on all of the digits before it.
+pub fn owen_scrambled_radical_inverse(base_index: usize, mut a: u64, hash: u32) -> Float {
+    let base = primes()[base_index];
+    let inv_base = 1.0 / base as Float;
+    let mut inv_base_m = 1.0;
+    let mut reversed_digits = 0u64;
+    while digit_matters(base, inv_base_m) {
+        let next = a / base as u64;
+        let digit = (a - next * base as u64) as u32;
+        let digit_hash = mix_bits(hash as u64 ^ reversed_digits) as u32;
+        let digit = permutation_element(digit, base, digit_hash);
+        let Some(reversed) = append_digit(reversed_digits, base, digit) else {
+            break;
+        };
+        reversed_digits = reversed;
+        inv_base_m *= inv_base;
+        a = next;
+    }
+    (reversed_digits as Float * inv_base_m).min(ONE_MINUS_EPSILON)
+}
+
+/// A randomization applied to the 32-bit fixed-point value of a Sobol
+/// sample.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SobolScrambler {
+    None,
+    /// XORs with the given bits, which flips whole halves of the interval
+    /// at every level.
+    BinaryPermute(u32),
+    /// Laine and Karras's hash-based approximation of Owen scrambling.
+    FastOwen(u32),
+    /// Owen scrambling: each bit is flipped depending on a hash of the bits
+    /// above it.
+    Owen(u32),
+}
+
+impl SobolScrambler {
+    pub fn scramble(self, mut v: u32) -> u32 {
+        match self {
+            SobolScrambler::None => v,
+            SobolScrambler::BinaryPermute(bits) => v ^ bits,
+            SobolScrambler::FastOwen(seed) => {
+                v = v.reverse_bits();
+                v ^= v.wrapping_mul(0x3d20_adea);
+                v = v.wrapping_add(seed);
+                v = v.wrapping_mul((seed >> 16) | 1);
+                v ^= v.wrapping_mul(0x0552_6c56);
+                v ^= v.wrapping_mul(0x53a2_2864);
+                v.reverse_bits()
+            }
+            SobolScrambler::Owen(seed) => {
+                if seed & 1 != 0 {
+                    v ^= 1 << 31;
+                }
+                for b in 1..32 {
+                    let mask = u32::MAX << (32 - b);
+                    if (mix_bits(((v & mask) ^ seed) as u64) as u32) & (1 << b) != 0 {
+                        v ^= 1 << (31 - b);
+                    }
+                }
+                v
+            }
+        }
+    }
+}
+
+/// Dimension `dimension` of the Sobol point with index `a`, randomized by
+/// `scrambler`.
+pub fn sobol_sample(mut a: u64, dimension: usize, scrambler: SobolScrambler) -> Float {
+    let columns = &sobol_matrices()[dimension];
+    let mut v = 0u32;
+    let mut i = 0;
+    while a != 0 {
+        if a & 1 != 0 {
+            v ^= columns[i];
+        }
+        a >>= 1;
+        i += 1;
+    }
+    let v = scrambler.scramble(v);
+    (v as Float * (1.0 / 4_294_967_296.0)).min(ONE_MINUS_EPSILON)
+}
+
+/// Column `column` of the generator matrix of Sobol dimension `dimension`:
+/// the 32-bit fixed-point value of the sample whose index has only that bit
+/// set.
+pub fn sobol_matrix_column(dimension: usize, column: usize) -> u32 {
+    sobol_matrices()[dimension][column]
+}
+
+/// The generator matrices of every dimension, built on first use from the
+/// primitive polynomials over GF(2) in order of degree.
+fn sobol_matrices() -> &'static [[u32; SOBOL_MATRIX_SIZE]] {
+    static MATRICES: OnceLock<Vec<[u32; SOBOL_MATRIX_SIZE]>> = OnceLock::new();
+    MATRICES.get_or_init(|| {
+        let mut polynomials = (1..).flat_map(|degree: u32| {
+            (0..1u64 << (degree - 1))
+                .map(move |a| (degree, a))
+                .filter(|&(degree, a)| is_primitive((1 << degree) | (a << 1) | 1, degree))
+        });
+        let mut matrices = Vec::with_capacity(N_SOBOL_DIMENSIONS);
+        // The first dimension is the van der Corput sequence.
+        matrices.push(direction_columns(&[1; SOBOL_MATRIX_SIZE]));
+        for dim in 1..N_SOBOL_DIMENSIONS {
+            let (s, a) = polynomials.next().unwrap();
+            let s = s as usize;
+            let mut m = [0u64; SOBOL_MATRIX_SIZE];
+            m[..s].copy_from_slice(SOBOL_INITIAL_DIRECTIONS[dim - 1]);
+            // Bratley and Fox's recurrence for the remaining direction
+            // numbers.
+            for k in s..SOBOL_MATRIX_SIZE {
+                let mut mk = m[k - s] ^ (m[k - s] << s);
+                for j in 1..s {
+                    if (a >> (s - 1 - j)) & 1 != 0 {
+                        mk ^= m[k - j] << j;
+                    }
+                }
+                m[k] = mk;
+            }
+            matrices.push(direction_columns(&m));
+        }
+        matrices
+    })
+}
+
+/// Generator matrix columns from direction numbers `m_k / 2^k`, as 32-bit
+/// fixed-point values. Columns past the 32nd keep the bits of `m_k` that
+/// still fall within those 32 bits.
+fn direction_columns(m: &[u64; SOBOL_MATRIX_SIZE]) -> [u32; SOBOL_MATRIX_SIZE] {
+    std::array::from_fn(|i| ((m[i] << (63 - i)) >> 32) as u32)
+}
+
+/// Whether the GF(2) polynomial with coefficient bits `p` and degree
+/// `degree` is primitive, i.e. `x` generates all `2^degree - 1` nonzero
+/// residues modulo it.
+fn is_primitive(p: u64, degree: u32) -> bool {
+    let order = (1u64 << degree) - 1;
+    let x_pow = |mut e: u64| {
+        // `x` itself, which is one modulo `x + 1`.
+        let (mut result, mut base) = (1u64, if degree == 1 { 1 } else { 2 });
+        while e > 0 {
+            if e & 1 != 0 {
+                result = poly_mul_mod(result, base, p, degree);
+            }
+            base = poly_mul_mod(base, base, p, degree);
+            e >>= 1;
+        }
+        result
+    };
+    if x_pow(order) != 1 {
+        return false;
+    }
+    let mut n = order;
+    let mut q = 2;
+    while n > 1 {
+        if q * q > n {
+            q = n;
+        }
+        if n.is_multiple_of(q) {
+            if x_pow(order / q) == 1 {
+                return false;
+            }
+            while n.is_multiple_of(q) {
+                n /= q;
+            }
+        }
+        q += 1;
+    }
+    true
+}
+
+/// `a * b` modulo `p` over GF(2), for `a` and `b` of degree below `degree`.
+fn poly_mul_mod(mut a: u64, mut b: u64, p: u64, degree: u32) -> u64 {
+    let mut result = 0;
+    while b != 0 {
+        if b & 1 != 0 {
+            result ^= a;
+        }
+        b >>= 1;
+        a <<= 1;
+        if a & (1 << degree) != 0 {
+            a ^= p;
+        }
+    }
+    result
+}
+
+/// Spreads the low 32 bits of `x` out to the even bits of the result.
+#[inline]
+fn left_shift2(mut x: u64) -> u64 {
+    x &= 0xffff_ffff;
+    x = (x ^ (x << 16)) & 0x0000_ffff_0000_ffff;
+    x = (x ^ (x << 8)) & 0x00ff_00ff_00ff_00ff;
+    x = (x ^ (x << 4)) & 0x0f0f_0f0f_0f0f_0f0f;
+    x = (x ^ (x << 2)) & 0x3333_3333_3333_3333;
+    x = (x ^ (x << 1)) & 0x5555_5555_5555_5555;
+    x
+}
+
+/// Interleaves the bits of `x` and `y`, with `x` in the even bits, so that
+/// nearby points get nearby codes.
+pub fn encode_morton2(x: u32, y: u32) -> u64 {
+    (left_shift2(y as u64) << 1) | left_shift2(x as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn primes_and_radical_inverses() {
+        let p = primes();
+        assert_eq!(p.len(), PRIME_TABLE_SIZE);
+        assert_eq!(&p[..6], [2, 3, 5, 7, 11, 13]);
+        assert_eq!(p[PRIME_TABLE_SIZE - 1], 7919);
+        assert_eq!(radical_inverse(0, 0b1101), 0.6875);
+        // 5 is "12" in base 3, and 7 is "21".
+        assert!((radical_inverse(1, 5) - 7.0 / 9.0).abs() < 1e-6);
+        assert_eq!(inverse_radical_inverse(7, 3, 2), 5);
+    }
+
+    #[test]
+    fn scrambled_radical_inverses_stay_stratified() {
+        let perm = DigitPermutation::new(3, 7);
+        let scrambles: [&dyn Fn(u64) -> Float; 2] =
+            [&|i| scrambled_radical_inverse(1, i, &perm), &|i| {
+                owen_scrambled_radical_inverse(1, i, 0x1234_5678)
+            }];
+        for scramble in scrambles {
+            let mut strata = [0; 27];
+            for i in 0..27 {
+                strata[(scramble(i) * 27.0) as usize] += 1;
+            }
+            assert!(strata.iter().all(|&n| n == 1), "{strata:?}");
+        }
+        assert_ne!(
+            scrambled_radical_inverse(1, 4, &perm),
+            scrambled_radical_inverse(1, 4, &DigitPermutation::new(3, 8))
+        );
+    }
+
+    #[test]
+    fn scrambled_radical_inverses_handle_every_base() {
+        let perms = compute_radical_inverse_permutations(3);
+        for (base_index, perm) in perms.iter().enumerate() {
+            for a in [0, 1, 123_456_789, u64::MAX] {
+                for v in [
+                    scrambled_radical_inverse(base_index, a, perm),
+                    owen_scrambled_radical_inverse(base_index, a, 0x1234_5678),
+                ] {
+                    assert!((0.0..1.0).contains(&v), "base index {base_index}: {v}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn sobol_dimensions_are_stratified() {
+        for scrambler in [
+            SobolScrambler::None,
+            SobolScrambler::BinaryPermute(0xabcd_1234),
+            SobolScrambler::FastOwen(0x9e37_79b9),
+            SobolScrambler::Owen(0x9e37_79b9),
+        ] {
+            for dim in 0..N_SOBOL_DIMENSIONS {
+                let mut strata = [0; 64];
+                for i in 0..64 {
+                    strata[(sobol_sample(i, dim, scrambler) * 64.0) as usize] += 1;
+                }
+                assert!(strata.iter().all(|&n| n == 1), "{scrambler:?} {dim}");
+            }
+            // The first two dimensions form a (0, 2)-sequence.
+            let mut cells = [0; 64];
+            for i in 0..64 {
+                let x = (sobol_sample(i, 0, scrambler) * 8.0) as usize;
+                let y = (sobol_sample(i, 1, scrambler) * 8.0) as usize;
+                cells[y * 8 + x] += 1;
+            }
+            assert!(cells.iter().all(|&n| n == 1), "{scrambler:?}");
+        }
+        assert_eq!(sobol_sample(1, 0, SobolScrambler::None), 0.5);
+        assert_eq!(sobol_sample(3, 1, SobolScrambler::None), 0.25);
+    }
+
+    #[test]
+    fn initial_directions_cover_every_dimension() {
+        assert_eq!(SOBOL_INITIAL_DIRECTIONS.len(), N_SOBOL_DIMENSIONS - 1);
+        for m in SOBOL_INITIAL_DIRECTIONS {
+            for (k, &mk) in m.iter().enumerate() {
+                assert!(mk % 2 == 1 && mk < 2 << k, "{m:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn primitive_polynomials_are_found_in_order() {
+        let found: Vec<u64> = (3..64)
+            .step_by(2)
+            .filter(|&p| is_primitive(p, 63 - p.leading_zeros()))
+            .collect();
+        // x + 1, x^2 + x + 1, x^3 + x + 1, x^3 + x^2 + 1, x^4 + x + 1, ...
+        assert_eq!(found[..6], [0b11, 0b111, 0b1011, 0b1101, 0b10011, 0b11001]);
+        assert!(
+            !is_primitive(0b11111, 4),
+            "x^4 + x^3 + x^2 + x + 1 has order 5"
+        );
+    }
+
+    #[test]
+    fn morton_codes_interleave_bits() {
+        assert_eq!(encode_morton2(0, 0), 0);
+        assert_eq!(encode_morton2(1, 0), 1);
+        assert_eq!(encode_morton2(0, 1), 2);
+        assert_eq!(encode_morton2(0b11, 0b10), 0b1101);
+        assert_eq!(encode_morton2(u32::MAX, 0), 0x5555_5555_5555_5555);
+    }
+}
@@ -0,0 +1,355 @@
+use crate::util::Float;
+use crate::util::bounds::Bounds3;
+use crate::util::math::transformations::Transform;
+use crate::util::math::{Quaternion, SquareMatrix};
+use crate::util::rays::{Ray, RayDifferential};
+use crate::util::vector::{Point3f, Vector3f};
+
+/// Number of steps `bound_point_motion` samples a rotating trajectory at.
+const MOTION_BOUND_STEPS: usize = 32;
+
+/// A transform that moves between two keyframes over a time interval, for
+/// motion blur.
+///
+/// Each keyframe is decomposed into translation, rotation and scale, which
+/// are interpolated separately: translation and scale linearly and rotation
+/// by quaternion slerp. Interpolating the matrices directly would shear and
+/// shrink objects partway through a rotation. Times outside the interval
+/// clamp to the nearest keyframe.
+#[derive(Debug, Clone, Copy)]
+pub struct AnimatedTransform {
+    start_transform: Transform,
+    end_transform: Transform,
+    start_time: Float,
+    end_time: Float,
+    actually_animated: bool,
+    t: [Vector3f; 2],
+    r: [Quaternion; 2],
+    s: [SquareMatrix<4>; 2],
+    has_rotation: bool,
+}
+
+impl AnimatedTransform {
+    /// Animates between two keyframes, or `None` if either of them is
+    /// singular or only one of them swaps handedness: the interpolated
+    /// transform would have to pass through a singular one on the way.
+    pub fn new(
+        start_transform: Transform,
+        start_time: Float,
+        end_transform: Transform,
+        end_time: Float,
+    ) -> Option<Self> {
+        let singular = |t: &Transform| t.matrix().inverse().is_none();
+        if singular(&start_transform)
+            || singular(&end_transform)
+            || start_transform.swaps_handedness() != end_transform.swaps_handedness()
+        {
+            return None;
+        }
+        let (t0, r0, s0) = decompose(start_transform.matrix());
+        let (t1, mut r1, s1) = decompose(end_transform.matrix());
+        // q and -q are the same rotation; pick the sign that takes the short
+        // way round.
+        if r0.dot(&r1) < 0.0 {
+            r1 = -r1;
+        }
+        Some(Self {
+            start_transform,
+            end_transform,
+            start_time,
+            end_time,
+            actually_animated: start_transform != end_transform,
+            t: [t0, t1],
+            r: [r0, r1],
+            s: [s0, s1],
+            has_rotation: r0 != r1,
+        })
+    }
+
+    /// A transform that does not move. Unlike `new` it accepts a singular
+    /// transform, which is never interpolated.
+    pub fn fixed(transform: Transform) -> Self {
+        let (t, r, s) = decompose(transform.matrix());
+        Self {
+            start_transform: transform,
+            end_transform: transform,
+            start_time: 0.0,
+            end_time: 1.0,
+            actually_animated: false,
+            t: [t; 2],
+            r: [r; 2],
+            s: [s; 2],
+            has_rotation: false,
+        }
+    }
+
+    pub fn start_transform(&self) -> &Transform {
+        &self.start_transform
+    }
+    pub fn end_transform(&self) -> &Transform {
+        &self.end_transform
+    }
+    pub fn is_animated(&self) -> bool {
+        self.actually_animated
+    }
+
+    /// The transform in effect at `time`.
+    pub fn interpolate(&self, time: Float) -> Transform {
+        if !self.actually_animated || time <= self.start_time {
+            return self.start_transform;
+        }
+        if time >= self.end_time {
+            return self.end_transform;
+        }
+        let dt = (time - self.start_time) / (self.end_time - self.start_time);
+        let translate = self.t[0] * (1.0 - dt) + self.t[1] * dt;
+        let rotate = Quaternion::slerp(dt, &self.r[0], &self.r[1]);
+        let mut scale = SquareMatrix::<4>::identity();
+        for i in 0..3 {
+            for j in 0..3 {
+                scale[(i, j)] = self.s[0][(i, j)] * (1.0 - dt) + self.s[1][(i, j)] * dt;
+            }
+        }
+        // The scales of nonsingular keyframes with the same handedness are
+        // both positive or both negative definite, and so is any blend of
+        // them.
+        let scale = Transform::from_matrix(scale).expect("interpolated scale is singular");
+        Transform::translate(translate) * rotate.to_transform() * scale
+    }
+
+    pub fn transform_point(&self, p: &Point3f, time: Float) -> Point3f {
+        self.interpolate(time).transform_point(p)
+    }
+    pub fn transform_vector(&self, v: &Vector3f, time: Float) -> Vector3f {
+        self.interpolate(time).transform_vector(v)
+    }
+    /// Transforms `r` by the transform in effect at the ray's own time.
+    pub fn transform_ray(&self, r: &Ray) -> Ray {
+        self.interpolate(r.time()).transform_ray(r)
+    }
+    pub fn transform_ray_differential(&self, r: &RayDifferential) -> RayDifferential {
+        self.interpolate(r.time()).transform_ray_differential(r)
+    }
+
+    /// Bounds of `b` over the whole time interval.
+    pub fn motion_bounds(&self, b: &Bounds3) -> Bounds3 {
+        if !self.actually_animated {
+            return self.start_transform.transform_bounds(b);
+        }
+        if !self.has_rotation {
+            // Without rotation every point moves linearly, so the keyframes
+            // bound the motion.
+            return self
+                .start_transform
+                .transform_bounds(b)
+                .union_bounds(&self.end_transform.transform_bounds(b));
+        }
+        // At any instant the transform is affine, so the image of the box is
+        // the hull of its transformed corners.
+        (0..8).fold(Bounds3::new(), |bounds, i| {
+            bounds.union_bounds(&self.bound_point_motion(&b.corner(i)))
+        })
+    }
+
+    /// Bounds of the trajectory of `p` over the whole time interval.
+    ///
+    /// The trajectory is sampled at evenly spaced times and the result padded
+    /// by how far the point can travel between two samples, bounded by the
+    /// speeds of its translation, scaling and rotation.
+    pub fn bound_point_motion(&self, p: &Point3f) -> Bounds3 {
+        if !self.actually_animated {
+            return Bounds3::from_point(self.start_transform.transform_point(p));
+        }
+        let scaled = |s: &SquareMatrix<4>| {
+            let [x, y, z, _] = s * [p.x, p.y, p.z, 1.0];
+            Vector3f::new(x, y, z)
+        };
+        let (p0, p1) = (scaled(&self.s[0]), scaled(&self.s[1]));
+        // Rotation angle between the keyframes, with headroom for the
+        // normalized linear blend slerp switches to for close rotations.
+        let angle = 2.0 * self.r[0].dot(&self.r[1]).clamp(-1.0, 1.0).acos() * 1.01;
+        let speed = (self.t[1] - self.t[0]).length()
+            + (p1 - p0).length()
+            + angle * p0.length().max(p1.length());
+
+        let steps = MOTION_BOUND_STEPS;
+        let duration = self.end_time - self.start_time;
+        let bounds = (0..=steps).fold(Bounds3::new(), |bounds, i| {
+            let time = self.start_time + duration * (i as Float / steps as Float);
+            bounds.union_point(&self.transform_point(p, time))
+        });
+        Bounds3::expand(bounds, speed / (2 * steps) as Float)
+    }
+}
+
+/// Splits the matrix of a transform into translation, rotation and the
+/// remaining scale and shear, such that `m = T * R * S`. The scale of a
+/// transform that swaps handedness takes the mirroring, as a negation.
+fn decompose(m: &SquareMatrix<4>) -> (Vector3f, Quaternion, SquareMatrix<4>) {
+    let t = Vector3f::new(m[(0, 3)], m[(1, 3)], m[(2, 3)]);
+    let mut upper = *m;
+    for i in 0..3 {
+        upper[(i, 3)] = 0.0;
+        upper[(3, i)] = 0.0;
+    }
+    upper[(3, 3)] = 1.0;
+
+    // Polar decomposition: averaging with the inverse transpose converges
+    // to the closest rotation.
+    let mut r = upper;
+    for _ in 0..100 {
+        let Some(r_it) = r.transpose().inverse() else {
+            break;
+        };
+        let mut next = r;
+        let mut norm: Float = 0.0;
+        for i in 0..3 {
+            let mut row = 0.0;
+            for j in 0..3 {
+                next[(i, j)] = 0.5 * (r[(i, j)] + r_it[(i, j)]);
+                row += (r[(i, j)] - next[(i, j)]).abs();
+            }
+            norm = norm.max(row);
+        }
+        r = next;
+        if norm < 0.0001 {
+            break;
+        }
+    }
+
+    // For a mirroring `m` this is a rotation followed by a reflection
+    // through the origin, which moves into the scale.
+    if Transform::new(r, r.transpose()).swaps_handedness() {
+        for i in 0..3 {
+            for j in 0..3 {
+                r[(i, j)] = -r[(i, j)];
+            }
+        }
+    }
+    let rotation = Transform::new(r, r.transpose());
+    let s = &r.transpose() * &upper;
+    (t, Quaternion::from(&rotation), s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: &Transform, b: &Transform) {
+        for i in 0..4 {
+            for j in 0..4 {
+                let (x, y) = (a.matrix()[(i, j)], b.matrix()[(i, j)]);
+                assert!((x - y).abs() < 1e-4, "[{i}][{j}]: {x} vs {y}");
+            }
+        }
+    }
+
+    fn spinning() -> AnimatedTransform {
+        let z = Vector3f::new(0.0, 0.0, 1.0);
+        AnimatedTransform::new(
+            Transform::translate(Vector3f::new(1.0, 0.0, 0.0)) * Transform::rotate(10.0, z),
+            0.0,
+            Transform::translate(Vector3f::new(1.0, 2.0, 0.0))
+                * Transform::rotate(170.0, z)
+                * Transform::scale(2.0, 1.0, 1.0),
+            1.0,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn keyframes_are_reproduced_and_clamped() {
+        let a = spinning();
+        assert!(a.is_animated());
+        assert_close(&a.interpolate(0.0), a.start_transform());
+        assert_close(&a.interpolate(-1.0), a.start_transform());
+        assert_close(&a.interpolate(1.0), a.end_transform());
+        assert_close(&a.interpolate(2.0), a.end_transform());
+        assert!(!AnimatedTransform::fixed(Transform::scale(2.0, 2.0, 2.0)).is_animated());
+    }
+
+    #[test]
+    fn components_interpolate_separately() {
+        let z = Vector3f::new(0.0, 0.0, 1.0);
+        let a = AnimatedTransform::new(
+            Transform::identity(),
+            2.0,
+            Transform::translate(Vector3f::new(4.0, 0.0, 0.0))
+                * Transform::rotate(90.0, z)
+                * Transform::scale(3.0, 3.0, 3.0),
+            4.0,
+        )
+        .unwrap();
+        let expected = Transform::translate(Vector3f::new(2.0, 0.0, 0.0))
+            * Transform::rotate(45.0, z)
+            * Transform::scale(2.0, 2.0, 2.0);
+        assert_close(&a.interpolate(3.0), &expected);
+
+        let ray = Ray::new(Point3f::default(), Vector3f::new(1.0, 0.0, 0.0), 3.0);
+        let r = a.transform_ray(&ray);
+        assert!((r.origin() - Point3f::new(2.0, 0.0, 0.0)).length() < 1e-5);
+        assert!(r.direction().x > 0.0 && (r.direction().x - r.direction().y).abs() < 1e-4);
+    }
+
+    #[test]
+    fn mirrored_keyframes_interpolate_and_mixed_handedness_is_rejected() {
+        let z = Vector3f::new(0.0, 0.0, 1.0);
+        let mirror = Transform::scale(-1.0, 1.0, 1.0);
+        let a = AnimatedTransform::new(
+            mirror,
+            0.0,
+            Transform::rotate(90.0, z) * Transform::scale(-3.0, 3.0, 3.0),
+            1.0,
+        )
+        .unwrap();
+        assert_close(&a.interpolate(0.0), &mirror);
+        let expected = Transform::rotate(45.0, z) * Transform::scale(-2.0, 2.0, 2.0);
+        assert_close(&a.interpolate(0.5), &expected);
+
+        assert!(AnimatedTransform::new(Transform::identity(), 0.0, mirror, 1.0).is_none());
+        let flat = Transform::scale(1.0, 0.0, 1.0);
+        assert!(AnimatedTransform::new(Transform::identity(), 0.0, flat, 1.0).is_none());
+        assert!(AnimatedTransform::new(flat, 0.0, flat, 1.0).is_none());
+        assert!(!AnimatedTransform::fixed(flat).is_animated());
+        assert!(!AnimatedTransform::fixed(mirror).is_animated());
+    }
+
+    #[test]
+    fn motion_bounds_contain_the_trajectory() {
+        let a = spinning();
+        let b = Bounds3::from_points(Point3f::new(-1.0, -0.5, 0.0), Point3f::new(0.5, 1.0, 2.0));
+        let mb = a.motion_bounds(&b);
+        for i in 0..=1000 {
+            let time = i as Float / 1000.0;
+            for c in 0..8 {
+                let p = a.transform_point(&b.corner(c), time);
+                assert!(mb.is_point_inside(&p), "{p:?} at {time} outside {mb:?}");
+            }
+        }
+
+        // Even a slight rotation swings a distant point past both keyframes.
+        let z = Vector3f::new(0.0, 0.0, 1.0);
+        let wobble = AnimatedTransform::new(
+            Transform::rotate(-1.0, z),
+            0.0,
+            Transform::rotate(1.0, z),
+            1.0,
+        )
+        .unwrap();
+        let far = Point3f::new(100.0, 0.0, 0.0);
+        let mb = wobble.motion_bounds(&Bounds3::from_point(far));
+        assert!(mb.is_point_inside(&wobble.transform_point(&far, 0.5)));
+
+        // Pure translation is bounded by the keyframes alone.
+        let slide = AnimatedTransform::new(
+            Transform::identity(),
+            0.0,
+            Transform::translate(Vector3f::new(0.0, 3.0, 0.0)),
+            1.0,
+        )
+        .unwrap();
+        let mb = slide.motion_bounds(&b);
+        assert_eq!(mb.pMin, b.pMin);
+        assert_eq!(mb.pMax, b.pMax + Vector3f::new(0.0, 3.0, 0.0));
+    }
+}
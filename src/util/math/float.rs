@@ -0,0 +1,205 @@
+use crate::util::Float;
+
+pub const PI: Float = std::f64::consts::PI as Float;
+pub const INV_PI: Float = std::f64::consts::FRAC_1_PI as Float;
+
+/// Relative error bound of a single correctly rounded operation: half the
+/// gap between 1 and the next representable float.
+pub const MACHINE_EPSILON: Float = Float::EPSILON * 0.5;
+
+/// The largest float below 1, for clamping samples into `[0, 1)`.
+pub const ONE_MINUS_EPSILON: Float = 1.0 - MACHINE_EPSILON;
+
+/// Conservative bound on the relative error accumulated by `n` successive
+/// floating-point operations (Higham's gamma_n).
+#[inline]
+pub fn gamma(n: i32) -> Float {
+    let n_eps = n as Float * MACHINE_EPSILON;
+    n_eps / (1.0 - n_eps)
+}
+
+/// Computes `a * b - c * d` without the catastrophic cancellation the naive
+/// expression suffers when the two products are close (Kahan's algorithm).
+#[inline]
+pub fn difference_of_products(a: Float, b: Float, c: Float, d: Float) -> Float {
+    let cd = c * d;
+    let error = (-c).mul_add(d, cd);
+    let dop = a.mul_add(b, -cd);
+    dop + error
+}
+
+/// `a * b` together with the rounding error of the product.
+#[inline]
+pub(crate) fn two_prod(a: Float, b: Float) -> (Float, Float) {
+    let ab = a * b;
+    (ab, a.mul_add(b, -ab))
+}
+
+/// `a + b` together with the rounding error of the sum.
+#[inline]
+pub(crate) fn two_sum(a: Float, b: Float) -> (Float, Float) {
+    let s = a + b;
+    let delta = s - a;
+    (s, (a - (s - delta)) + (b - delta))
+}
+
+/// Sum of `a * b` over all pairs, accumulating the rounding error of every
+/// product and partial sum so the result is nearly as accurate as if it had
+/// been computed in twice the precision.
+pub fn inner_product(terms: &[(Float, Float)]) -> Float {
+    let mut sum = 0.0;
+    let mut error = 0.0;
+    for &(a, b) in terms {
+        let (product, product_error) = two_prod(a, b);
+        let (s, sum_error) = two_sum(sum, product);
+        sum = s;
+        error += product_error + sum_error;
+    }
+    sum + error
+}
+
+/// The smallest representable float greater than `v`. Infinity maps to
+/// itself and both zeros step to the smallest positive subnormal.
+#[inline]
+pub fn next_float_up(v: Float) -> Float {
+    if v.is_infinite() && v > 0.0 {
+        return v;
+    }
+    let v = if v == -0.0 { 0.0 } else { v };
+    let bits = v.to_bits();
+    Float::from_bits(if v >= 0.0 { bits + 1 } else { bits - 1 })
+}
+
+/// The largest representable float less than `v`.
+#[inline]
+pub fn next_float_down(v: Float) -> Float {
+    if v.is_infinite() && v < 0.0 {
+        return v;
+    }
+    let v = if v == 0.0 { -0.0 } else { v };
+    let bits = v.to_bits();
+    Float::from_bits(if v > 0.0 { bits - 1 } else { bits + 1 })
+}
+
+/// Square root clamped to zero for slightly negative arguments that are
+/// rounding error rather than a genuine domain error.
+#[inline]
+pub fn safe_sqrt(x: Float) -> Float {
+    x.max(0.0).sqrt()
+}
+
+/// `asin` with its argument clamped to `[-1, 1]`.
+#[inline]
+pub fn safe_asin(x: Float) -> Float {
+    x.clamp(-1.0, 1.0).asin()
+}
+
+/// `acos` with its argument clamped to `[-1, 1]`.
+#[inline]
+pub fn safe_acos(x: Float) -> Float {
+    x.clamp(-1.0, 1.0).acos()
+}
+
+/// The error function, from Abramowitz and Stegun's approximation 7.1.26
+/// (absolute error below `1.5e-7`).
+pub fn erf(x: Float) -> Float {
+    let t = 1.0 / (1.0 + 0.327_591_1 * x.abs());
+    let poly = t
+        * (0.254_829_6
+            + t * (-0.284_496_7 + t * (1.421_413_7 + t * (-1.453_152 + t * 1.061_405_4))));
+    (1.0 - poly * (-x * x).exp()).copysign(x)
+}
+
+/// The normal distribution's density at `x`.
+pub fn gaussian(x: Float, mu: Float, sigma: Float) -> Float {
+    1.0 / (2.0 * PI * sigma * sigma).sqrt() * (-(x - mu) * (x - mu) / (2.0 * sigma * sigma)).exp()
+}
+
+/// Integral of `gaussian(x, mu, sigma)` over `x0..x1`.
+pub fn gaussian_integral(x0: Float, x1: Float, mu: Float, sigma: Float) -> Float {
+    let sigma_root2 = sigma * std::f64::consts::SQRT_2 as Float;
+    0.5 * (erf((mu - x0) / sigma_root2) - erf((mu - x1) / sigma_root2))
+}
+
+/// Real roots of `a * t^2 + b * t + c`, smallest first.
+///
+/// Uses the formulation that avoids cancellation between `-b` and the square
+/// root of the discriminant. A linear equation (`a == 0`) reports its single
+/// root twice.
+pub fn quadratic(a: Float, b: Float, c: Float) -> Option<(Float, Float)> {
+    if a == 0.0 {
+        if b == 0.0 {
+            return None;
+        }
+        let t = -c / b;
+        return Some((t, t));
+    }
+    let discrim = difference_of_products(b, b, 4.0 * a, c);
+    if discrim < 0.0 {
+        return None;
+    }
+    let root_discrim = discrim.sqrt();
+    let q = if b < 0.0 {
+        -0.5 * (b - root_discrim)
+    } else {
+        -0.5 * (b + root_discrim)
+    };
+    if q == 0.0 {
+        return Some((0.0, 0.0));
+    }
+    let (t0, t1) = (q / a, c / q);
+    Some(if t0 > t1 { (t1, t0) } else { (t0, t1) })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gamma_grows_with_operation_count() {
+        assert_eq!(gamma(0), 0.0);
+        assert!(gamma(1) > MACHINE_EPSILON);
+        assert!(gamma(3) > 3.0 * MACHINE_EPSILON && gamma(3) < 4.0 * MACHINE_EPSILON);
+    }
+
+    #[test]
+    fn difference_of_products_is_exact_for_small_integers() {
+        assert_eq!(difference_of_products(3.0, 4.0, 2.0, 5.0), 2.0);
+    }
+
+    #[test]
+    fn next_float_steps_one_ulp() {
+        assert!(next_float_up(1.0) > 1.0);
+        assert_eq!(next_float_up(1.0) - 1.0, Float::EPSILON);
+        assert!(next_float_down(1.0) < 1.0);
+        assert!(next_float_up(0.0) > 0.0 && next_float_up(-0.0) > 0.0);
+        assert!(next_float_down(0.0) < 0.0);
+        assert_eq!(next_float_down(next_float_up(-3.5)), -3.5);
+        assert_eq!(next_float_up(Float::INFINITY), Float::INFINITY);
+    }
+
+    #[test]
+    fn quadratic_roots_are_sorted() {
+        assert_eq!(quadratic(1.0, -3.0, 2.0), Some((1.0, 2.0)));
+        assert_eq!(quadratic(-1.0, 3.0, -2.0), Some((1.0, 2.0)));
+        assert_eq!(quadratic(0.0, 2.0, -4.0), Some((2.0, 2.0)));
+        assert_eq!(quadratic(1.0, 0.0, 1.0), None);
+    }
+
+    #[test]
+    fn gaussian_integrates_to_one() {
+        assert!(erf(0.0).abs() < 1e-6);
+        assert!((erf(1.0) - 0.842_700_8).abs() < 1e-6);
+        assert!((erf(-1.0) + 0.842_700_8).abs() < 1e-6);
+        assert!((gaussian_integral(-10.0, 10.0, 1.0, 2.0) - 1.0).abs() < 1e-5);
+        assert!((gaussian(0.0, 0.0, 1.0) - 0.398_942_3).abs() < 1e-6);
+    }
+
+    #[test]
+    fn inner_product_recovers_cancelled_terms() {
+        // Summed naively in single precision, 1e8 swallows the 1.0.
+        let big = 1.0e8;
+        let result = inner_product(&[(big, 1.0), (1.0, 1.0), (-big, 1.0)]);
+        assert_eq!(result, 1.0);
+    }
+}
@@ -0,0 +1,142 @@
+use crate::util::math::SquareMatrix;
+use crate::util::math::transformations::Transform;
+use crate::util::vector::{Normal3f, Vector3f};
+
+/// An orthonormal basis, such as the local shading frame at a surface point
+/// with the normal along `z`.
+///
+/// Constructors taking two axes trust them to be orthonormal already; use
+/// the single-axis ones to build a basis around an arbitrary direction.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Frame {
+    pub x: Vector3f,
+    pub y: Vector3f,
+    pub z: Vector3f,
+}
+
+impl Default for Frame {
+    /// The standard basis.
+    fn default() -> Self {
+        Self::new(
+            Vector3f::new(1.0, 0.0, 0.0),
+            Vector3f::new(0.0, 1.0, 0.0),
+            Vector3f::new(0.0, 0.0, 1.0),
+        )
+    }
+}
+
+impl Frame {
+    pub fn new(x: Vector3f, y: Vector3f, z: Vector3f) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn from_xz(x: Vector3f, z: Vector3f) -> Self {
+        Self::new(x, z.cross(&x), z)
+    }
+    pub fn from_xy(x: Vector3f, y: Vector3f) -> Self {
+        Self::new(x, y, x.cross(&y))
+    }
+    pub fn from_yz(y: Vector3f, z: Vector3f) -> Self {
+        Self::new(y.cross(&z), y, z)
+    }
+
+    /// A right-handed frame with the unit vector `x` as its first axis.
+    pub fn from_x(x: Vector3f) -> Self {
+        let (y, z) = Vector3f::coordinate_system(&x);
+        Self::new(x, y, z)
+    }
+    /// A right-handed frame with the unit vector `y` as its second axis.
+    pub fn from_y(y: Vector3f) -> Self {
+        let (z, x) = Vector3f::coordinate_system(&y);
+        Self::new(x, y, z)
+    }
+    /// A right-handed frame with the unit vector `z` as its third axis.
+    pub fn from_z(z: Vector3f) -> Self {
+        let (x, y) = Vector3f::coordinate_system(&z);
+        Self::new(x, y, z)
+    }
+
+    /// Expresses the world-space `v` in this frame.
+    pub fn to_local(&self, v: &Vector3f) -> Vector3f {
+        Vector3f::new(v.dot(&self.x), v.dot(&self.y), v.dot(&self.z))
+    }
+    /// Takes `v` expressed in this frame back to world space.
+    pub fn from_local(&self, v: &Vector3f) -> Vector3f {
+        self.x * v.x + self.y * v.y + self.z * v.z
+    }
+
+    // The frame is orthonormal, so normals transform just like vectors.
+
+    pub fn to_local_normal(&self, n: &Normal3f) -> Normal3f {
+        Normal3f::from(self.to_local(&Vector3f::from(*n)))
+    }
+    pub fn from_local_normal(&self, n: &Normal3f) -> Normal3f {
+        Normal3f::from(self.from_local(&Vector3f::from(*n)))
+    }
+}
+
+impl From<Frame> for Transform {
+    /// The world-to-local transform, equivalent to `to_local`.
+    fn from(f: Frame) -> Self {
+        let mut m = SquareMatrix::<4>::identity();
+        for (i, axis) in [f.x, f.y, f.z].iter().enumerate() {
+            m[(i, 0)] = axis.x;
+            m[(i, 1)] = axis.y;
+            m[(i, 2)] = axis.z;
+        }
+        Transform::new(m, m.transpose())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_orthonormal(f: &Frame) {
+        for (a, b) in [(f.x, f.y), (f.y, f.z), (f.z, f.x)] {
+            assert!(a.dot(&b).abs() < 1e-5);
+            assert!((a.length() - 1.0).abs() < 1e-5);
+        }
+        // Right-handed.
+        assert!((f.x.cross(&f.y) - f.z).length() < 1e-5);
+    }
+
+    #[test]
+    fn single_axis_frames_are_orthonormal() {
+        let v = Vector3f::new(0.3, -0.8, 0.2).normalize();
+        for (f, axis) in [
+            (Frame::from_x(v), 0),
+            (Frame::from_y(v), 1),
+            (Frame::from_z(v), 2),
+        ] {
+            assert_orthonormal(&f);
+            assert_eq!([f.x, f.y, f.z][axis], v);
+        }
+        assert_orthonormal(&Frame::from_z(Vector3f::new(0.0, 0.0, -1.0)));
+        assert_eq!(
+            Frame::from_xz(Vector3f::new(1.0, 0.0, 0.0), Vector3f::new(0.0, 0.0, 1.0)),
+            Frame::default()
+        );
+    }
+
+    #[test]
+    fn local_round_trip_matches_transform() {
+        let n = Vector3f::new(1.0, 2.0, 2.0).normalize();
+        let f = Frame::from_z(n);
+        assert!((f.to_local(&n) - Vector3f::new(0.0, 0.0, 1.0)).length() < 1e-5);
+
+        let v = Vector3f::new(-0.5, 4.0, 1.5);
+        let local = f.to_local(&v);
+        assert!((f.from_local(&local) - v).length() < 1e-5);
+        assert!((local.length() - v.length()).abs() < 1e-5);
+        let t = Transform::from(f);
+        assert!((t.transform_vector(&v) - local).length() < 1e-5);
+        assert!((t.inverse().transform_vector(&local) - v).length() < 1e-5);
+
+        let nn = Normal3f::from(n);
+        assert!(
+            (Vector3f::from(f.to_local_normal(&nn)) - Vector3f::new(0.0, 0.0, 1.0)).length() < 1e-5
+        );
+        assert!((Vector3f::from(f.from_local_normal(&f.to_local_normal(&nn))) - n).length() < 1e-5);
+    }
+}
@@ -0,0 +1,308 @@
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use crate::util::Float;
+use crate::util::math::{next_float_down, next_float_up, two_prod, two_sum};
+
+/// A closed range of floats guaranteed to contain the exact value of the
+/// computation that produced it, in the manner of pbrt's `Interval` (the
+/// successor of its `EFloat`).
+///
+/// Every operation rounds its lower bound down and its upper bound up, so
+/// results that are exactly representable stay exact and the rest grow by at
+/// most one ulp per operation.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Interval {
+    low: Float,
+    high: Float,
+}
+
+impl Interval {
+    /// The exact value `v`.
+    pub const fn new(v: Float) -> Self {
+        Self { low: v, high: v }
+    }
+
+    /// The range between `a` and `b`, in either order.
+    pub fn from_bounds(a: Float, b: Float) -> Self {
+        Self {
+            low: a.min(b),
+            high: a.max(b),
+        }
+    }
+
+    /// `v` give or take an absolute error of `err`.
+    pub fn from_value_and_error(v: Float, err: Float) -> Self {
+        if err == 0.0 {
+            return Self::new(v);
+        }
+        Self {
+            low: next_float_down(v - err),
+            high: next_float_up(v + err),
+        }
+    }
+
+    pub fn lower_bound(&self) -> Float {
+        self.low
+    }
+    pub fn upper_bound(&self) -> Float {
+        self.high
+    }
+    pub fn midpoint(&self) -> Float {
+        (self.low + self.high) / 2.0
+    }
+    pub fn width(&self) -> Float {
+        self.high - self.low
+    }
+    pub fn is_exact(&self) -> bool {
+        self.low == self.high
+    }
+    pub fn contains(&self, v: Float) -> bool {
+        v >= self.low && v <= self.high
+    }
+
+    /// `self * self`, which unlike the product is never negative.
+    pub fn sqr(&self) -> Self {
+        let (lo, hi) = (self.low.abs(), self.high.abs());
+        let (lo, hi) = if lo > hi { (hi, lo) } else { (lo, hi) };
+        if self.contains(0.0) {
+            return Self {
+                low: 0.0,
+                high: mul_round(hi, hi).1,
+            };
+        }
+        Self {
+            low: mul_round(lo, lo).0,
+            high: mul_round(hi, hi).1,
+        }
+    }
+
+    /// Square root of the non-negative part of the range.
+    pub fn sqrt(&self) -> Self {
+        Self {
+            low: sqrt_round(self.low.max(0.0)).0,
+            high: sqrt_round(self.high.max(0.0)).1,
+        }
+    }
+
+    pub fn abs(&self) -> Self {
+        if self.low >= 0.0 {
+            *self
+        } else if self.high <= 0.0 {
+            -*self
+        } else {
+            Self {
+                low: 0.0,
+                high: self.high.max(-self.low),
+            }
+        }
+    }
+
+    /// Bounds on the real roots of `a * t^2 + b * t + c`, smallest first,
+    /// using the same formulation as [`quadratic`](crate::util::math::quadratic).
+    ///
+    /// The roots are ordered by their lower bounds. Roots whose bounds are
+    /// infinite could not be pinned down and should be treated as misses.
+    pub fn quadratic(a: Self, b: Self, c: Self) -> Option<(Self, Self)> {
+        if a == Self::new(0.0) {
+            if b == Self::new(0.0) {
+                return None;
+            }
+            let t = -c / b;
+            return Some((t, t));
+        }
+        let discrim = b.sqr() - a * c * 4.0;
+        if discrim.upper_bound() < 0.0 {
+            return None;
+        }
+        let root_discrim = discrim.sqrt();
+        let q = if b.midpoint() < 0.0 {
+            (b - root_discrim) * -0.5
+        } else {
+            (b + root_discrim) * -0.5
+        };
+        let (t0, t1) = (q / a, c / q);
+        Some(if t0.low > t1.low { (t1, t0) } else { (t0, t1) })
+    }
+}
+
+/// The exact sum rounded down and up.
+fn add_round(a: Float, b: Float) -> (Float, Float) {
+    let (s, err) = two_sum(a, b);
+    round(s, err)
+}
+
+/// The exact product rounded down and up.
+fn mul_round(a: Float, b: Float) -> (Float, Float) {
+    let (p, err) = two_prod(a, b);
+    round(p, err)
+}
+
+/// The exact quotient rounded down and up.
+fn div_round(a: Float, b: Float) -> (Float, Float) {
+    let q = a / b;
+    // a = q * b + r exactly, so the true quotient is q + r / b.
+    let r = (-q).mul_add(b, a);
+    round(q, if b < 0.0 { -r } else { r })
+}
+
+/// The exact square root rounded down and up.
+fn sqrt_round(a: Float) -> (Float, Float) {
+    let s = a.sqrt();
+    round(s, (-s).mul_add(s, a))
+}
+
+/// Bounds on a rounded result `v` given the sign of its rounding error.
+fn round(v: Float, err: Float) -> (Float, Float) {
+    if !v.is_finite() || err == 0.0 || err.is_nan() {
+        (v, v)
+    } else if err > 0.0 {
+        (v, next_float_up(v))
+    } else {
+        (next_float_down(v), v)
+    }
+}
+
+impl From<Float> for Interval {
+    fn from(v: Float) -> Self {
+        Self::new(v)
+    }
+}
+
+impl Neg for Interval {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self {
+            low: -self.high,
+            high: -self.low,
+        }
+    }
+}
+
+impl<R: Into<Interval>> Add<R> for Interval {
+    type Output = Self;
+    fn add(self, rhs: R) -> Self {
+        let rhs = rhs.into();
+        Self {
+            low: add_round(self.low, rhs.low).0,
+            high: add_round(self.high, rhs.high).1,
+        }
+    }
+}
+
+impl<R: Into<Interval>> Sub<R> for Interval {
+    type Output = Self;
+    fn sub(self, rhs: R) -> Self {
+        self + -rhs.into()
+    }
+}
+
+impl<R: Into<Interval>> Mul<R> for Interval {
+    type Output = Self;
+    fn mul(self, rhs: R) -> Self {
+        let rhs = rhs.into();
+        let products = [
+            mul_round(self.low, rhs.low),
+            mul_round(self.high, rhs.low),
+            mul_round(self.low, rhs.high),
+            mul_round(self.high, rhs.high),
+        ];
+        Self {
+            low: products
+                .iter()
+                .map(|p| p.0)
+                .fold(Float::INFINITY, Float::min),
+            high: products
+                .iter()
+                .map(|p| p.1)
+                .fold(Float::NEG_INFINITY, Float::max),
+        }
+    }
+}
+
+impl<R: Into<Interval>> Div<R> for Interval {
+    type Output = Self;
+    /// Division by a range containing zero gives the whole real line.
+    fn div(self, rhs: R) -> Self {
+        let rhs = rhs.into();
+        if rhs.contains(0.0) {
+            return Self {
+                low: Float::NEG_INFINITY,
+                high: Float::INFINITY,
+            };
+        }
+        let quotients = [
+            div_round(self.low, rhs.low),
+            div_round(self.high, rhs.low),
+            div_round(self.low, rhs.high),
+            div_round(self.high, rhs.high),
+        ];
+        Self {
+            low: quotients
+                .iter()
+                .map(|q| q.0)
+                .fold(Float::INFINITY, Float::min),
+            high: quotients
+                .iter()
+                .map(|q| q.1)
+                .fold(Float::NEG_INFINITY, Float::max),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exact_results_stay_exact() {
+        let a = Interval::new(1.5);
+        let b = Interval::new(-2.0);
+        assert!((a * b + 3.0).is_exact());
+        assert_eq!((a * b).midpoint(), -3.0);
+        assert!((Interval::new(9.0).sqrt()).is_exact());
+        assert_eq!(Interval::new(0.0) * 7.0, Interval::new(0.0));
+    }
+
+    #[test]
+    fn inexact_results_bracket_the_true_value() {
+        let third = Interval::new(1.0) / 3.0;
+        assert!(!third.is_exact());
+        assert!(third.lower_bound() < third.upper_bound());
+        let x = (third * 3.0) - 1.0;
+        assert!(x.contains(0.0));
+
+        assert!(
+            Interval::new(2.0)
+                .sqrt()
+                .contains(std::f64::consts::SQRT_2 as Float)
+        );
+    }
+
+    #[test]
+    fn squares_and_division_by_zero() {
+        let i = Interval::from_bounds(-2.0, 1.0);
+        assert_eq!(i.sqr(), Interval::from_bounds(0.0, 4.0));
+        assert_eq!(i.abs(), Interval::from_bounds(0.0, 2.0));
+        let q = Interval::new(1.0) / i;
+        assert!(q.lower_bound().is_infinite() && q.upper_bound().is_infinite());
+        let e = Interval::from_value_and_error(1.0, 0.25);
+        assert!(e.lower_bound() < 0.75 && e.upper_bound() > 1.25);
+    }
+
+    #[test]
+    fn quadratic_roots_are_bracketed() {
+        // 4 (t - 1/4)(t - 1), with the coefficients known only approximately.
+        let (t0, t1) = Interval::quadratic(
+            Interval::from_value_and_error(4.0, 1e-6),
+            Interval::new(-5.0),
+            Interval::from_value_and_error(1.0, 1e-6),
+        )
+        .unwrap();
+        assert!(t0.contains(0.25) && t1.contains(1.0));
+        assert!(t0.width() < 1e-5 && t1.width() < 1e-5);
+        assert!(
+            Interval::quadratic(Interval::new(1.0), Interval::new(0.0), Interval::new(1.0))
+                .is_none()
+        );
+    }
+}
@@ -1,3 +1,16 @@
+mod animated_transform;
+pub mod float;
+mod frame;
+mod interval;
+mod quaternion;
 pub mod spherical_geometry;
+mod square_matrix;
+pub mod transformations;
 
+pub use animated_transform::AnimatedTransform;
+pub use float::*;
+pub use frame::Frame;
+pub use interval::Interval;
+pub use quaternion::Quaternion;
 pub use spherical_geometry::*;
+pub use square_matrix::SquareMatrix;
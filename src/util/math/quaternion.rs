@@ -0,0 +1,232 @@
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use crate::util::Float;
+use crate::util::math::SquareMatrix;
+use crate::util::math::transformations::Transform;
+use crate::util::vector::Vector3f;
+
+/// A quaternion `w + v.x i + v.y j + v.z k`.
+///
+/// Unit quaternions represent rotations: turning by `theta` about the unit
+/// axis `a` is `(a * sin(theta / 2), cos(theta / 2))`. Multiplication
+/// composes rotations in the same order as transforms, so `q1 * q2` applies
+/// `q2` first.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quaternion {
+    pub v: Vector3f,
+    pub w: Float,
+}
+
+impl Default for Quaternion {
+    /// The identity rotation.
+    fn default() -> Self {
+        Self::new(Vector3f::default(), 1.0)
+    }
+}
+
+impl Quaternion {
+    pub fn new(v: Vector3f, w: Float) -> Self {
+        Self { v, w }
+    }
+
+    pub fn dot(&self, q: &Quaternion) -> Float {
+        self.v.dot(&q.v) + self.w * q.w
+    }
+    pub fn length(&self) -> Float {
+        self.dot(self).sqrt()
+    }
+    pub fn normalize(&self) -> Self {
+        *self / self.length()
+    }
+
+    /// Spherical linear interpolation between unit quaternions, which turns
+    /// at a constant angular rate.
+    ///
+    /// `q` and `-q` are the same rotation; callers that want the shorter
+    /// path should negate one endpoint when their dot product is negative.
+    pub fn slerp(t: Float, q1: &Quaternion, q2: &Quaternion) -> Self {
+        let cos_theta = q1.dot(q2);
+        // Nearly parallel: the sine below would vanish, and a linear blend
+        // is indistinguishable.
+        if cos_theta > 0.9995 {
+            return (*q1 * (1.0 - t) + *q2 * t).normalize();
+        }
+        let theta = cos_theta.clamp(-1.0, 1.0).acos();
+        let theta_p = theta * t;
+        let q_perp = (*q2 - *q1 * cos_theta).normalize();
+        *q1 * theta_p.cos() + q_perp * theta_p.sin()
+    }
+
+    /// The rotation as a transform.
+    pub fn to_transform(&self) -> Transform {
+        let (x, y, z, w) = (self.v.x, self.v.y, self.v.z, self.w);
+        let (xx, yy, zz) = (x * x, y * y, z * z);
+        let (xy, xz, yz) = (x * y, x * z, y * z);
+        let (wx, wy, wz) = (x * w, y * w, z * w);
+
+        let mut m = SquareMatrix::<4>::identity();
+        m.matrix[0][0] = 1.0 - 2.0 * (yy + zz);
+        m.matrix[0][1] = 2.0 * (xy - wz);
+        m.matrix[0][2] = 2.0 * (xz + wy);
+        m.matrix[1][0] = 2.0 * (xy + wz);
+        m.matrix[1][1] = 1.0 - 2.0 * (xx + zz);
+        m.matrix[1][2] = 2.0 * (yz - wx);
+        m.matrix[2][0] = 2.0 * (xz - wy);
+        m.matrix[2][1] = 2.0 * (yz + wx);
+        m.matrix[2][2] = 1.0 - 2.0 * (xx + yy);
+        // Rotations are orthogonal, so the inverse is the transpose.
+        Transform::new(m, m.transpose())
+    }
+}
+
+impl From<&Transform> for Quaternion {
+    /// The rotation in the upper-left 3x3 block of `t`, which must be a pure
+    /// rotation for the result to be meaningful.
+    fn from(t: &Transform) -> Self {
+        let m = &t.matrix().matrix;
+        let trace = m[0][0] + m[1][1] + m[2][2];
+        if trace > 0.0 {
+            // Compute w from the trace, then the rest from the off-diagonal
+            // differences.
+            let s = (trace + 1.0).sqrt();
+            let w = s / 2.0;
+            let s = 0.5 / s;
+            return Self::new(
+                Vector3f::new(
+                    (m[2][1] - m[1][2]) * s,
+                    (m[0][2] - m[2][0]) * s,
+                    (m[1][0] - m[0][1]) * s,
+                ),
+                w,
+            );
+        }
+        // Start from the largest diagonal element to keep s well away from
+        // zero.
+        let i = if m[1][1] > m[0][0] { 1 } else { 0 };
+        let i = if m[2][2] > m[i][i] { 2 } else { i };
+        let j = (i + 1) % 3;
+        let k = (j + 1) % 3;
+        let mut s = (m[i][i] - (m[j][j] + m[k][k]) + 1.0).sqrt();
+        let mut q = [0.0; 3];
+        q[i] = s * 0.5;
+        if s != 0.0 {
+            s = 0.5 / s;
+        }
+        let w = (m[k][j] - m[j][k]) * s;
+        q[j] = (m[j][i] + m[i][j]) * s;
+        q[k] = (m[k][i] + m[i][k]) * s;
+        Self::new(Vector3f::new(q[0], q[1], q[2]), w)
+    }
+}
+
+impl From<Quaternion> for Transform {
+    fn from(q: Quaternion) -> Self {
+        q.to_transform()
+    }
+}
+
+impl Add for Quaternion {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.v + rhs.v, self.w + rhs.w)
+    }
+}
+
+impl Sub for Quaternion {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.v - rhs.v, self.w - rhs.w)
+    }
+}
+
+impl Neg for Quaternion {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self::new(-self.v, -self.w)
+    }
+}
+
+impl Mul<Float> for Quaternion {
+    type Output = Self;
+    fn mul(self, s: Float) -> Self {
+        Self::new(self.v * s, self.w * s)
+    }
+}
+
+impl Div<Float> for Quaternion {
+    type Output = Self;
+    fn div(self, s: Float) -> Self {
+        Self::new(self.v / s, self.w / s)
+    }
+}
+
+impl Mul for Quaternion {
+    type Output = Self;
+    /// The Hamilton product.
+    fn mul(self, rhs: Self) -> Self {
+        Self::new(
+            rhs.v * self.w + self.v * rhs.w + self.v.cross(&rhs.v),
+            self.w * rhs.w - self.v.dot(&rhs.v),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: &Transform, b: &Transform) {
+        for i in 0..4 {
+            for j in 0..4 {
+                let (x, y) = (a.matrix().matrix[i][j], b.matrix().matrix[i][j]);
+                assert!((x - y).abs() < 1e-5, "[{i}][{j}]: {x} vs {y}");
+            }
+        }
+    }
+
+    #[test]
+    fn round_trips_through_transform() {
+        let axes = [
+            Vector3f::new(1.0, 0.0, 0.0),
+            Vector3f::new(0.0, -1.0, 0.0),
+            Vector3f::new(1.0, 2.0, 3.0),
+        ];
+        // Include angles near a half turn, where the trace is negative.
+        for axis in axes {
+            for theta in [0.0, 30.0, 90.0, 179.0, 250.0] {
+                let t = Transform::rotate(theta, axis);
+                let q = Quaternion::from(&t);
+                assert!((q.length() - 1.0).abs() < 1e-5);
+                assert_close(&q.to_transform(), &t);
+            }
+        }
+        let half = Quaternion::from(&Transform::rotate(90.0, Vector3f::new(0.0, 0.0, 1.0)));
+        let h = (0.5 as Float).sqrt();
+        assert!((half.v.z - h).abs() < 1e-6 && (half.w - h).abs() < 1e-6);
+    }
+
+    #[test]
+    fn product_composes_rotations() {
+        let a = Transform::rotate(40.0, Vector3f::new(0.0, 1.0, 0.0));
+        let b = Transform::rotate(-75.0, Vector3f::new(1.0, 1.0, 0.0));
+        let q = Quaternion::from(&a) * Quaternion::from(&b);
+        assert_close(&q.to_transform(), &(a * b));
+        assert_close(&(Quaternion::default() * q).to_transform(), &(a * b));
+    }
+
+    #[test]
+    fn slerp_turns_at_a_constant_rate() {
+        let z = Vector3f::new(0.0, 0.0, 1.0);
+        let q0 = Quaternion::from(&Transform::rotate(10.0, z));
+        let q1 = Quaternion::from(&Transform::rotate(130.0, z));
+        assert_close(
+            &Quaternion::slerp(0.25, &q0, &q1).to_transform(),
+            &Transform::rotate(40.0, z),
+        );
+        assert_eq!(Quaternion::slerp(0.0, &q0, &q1), q0);
+        assert!((Quaternion::slerp(1.0, &q0, &q1) - q1).length() < 1e-5);
+        // Close endpoints take the linear path and still come out unit length.
+        let q2 = Quaternion::from(&Transform::rotate(10.5, z));
+        assert!((Quaternion::slerp(0.5, &q0, &q2).length() - 1.0).abs() < 1e-6);
+    }
+}
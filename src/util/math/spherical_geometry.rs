@@ -1,22 +1,374 @@
-use std::{f32::consts::PI};
-use num_traits::{clamp, ops::bytes::NumBytes};
-
-use crate::util::{Float, vector::Vector3};
-fn spherical_direction(sin_theta:Float,cos_theta:Float,phi:Float)->Vector3{
-    Vector3::new(
-        clamp(sin_theta, -1.0, 1.0)*phi.cos(),
-        clamp(sin_theta, -1.0, 1.0)*phi.sin(),
-        clamp(cos_theta, -1.0, 1.0)      
+use crate::util::Float;
+use crate::util::bounds::Bounds3;
+use crate::util::math::transformations::Transform;
+use crate::util::math::{PI, safe_acos, safe_sqrt};
+use crate::util::vector::{Point2f, Point2i, Point3f, Vector3f};
+
+/// Unit vector with the given polar angle, by its sine and cosine, and
+/// azimuth `phi` about +z.
+pub fn spherical_direction(sin_theta: Float, cos_theta: Float, phi: Float) -> Vector3f {
+    let sin_theta = sin_theta.clamp(-1.0, 1.0);
+    Vector3f::new(
+        sin_theta * phi.cos(),
+        sin_theta * phi.sin(),
+        cos_theta.clamp(-1.0, 1.0),
     )
 }
-fn spherical_triangle_area(a:Vector3,b:Vector3,c:Vector3)->Float{
-    let numerator = a.dot(&b.cross(&c));    let denominator = 1.0+ a.dot(&b) +a.dot(&c)+b.dot(&c);
-    let val = numerator/denominator;
-    2.0*val.atan()
+
+/// Polar angle of the unit vector `v`, measured from +z.
+pub fn spherical_theta(v: &Vector3f) -> Float {
+    safe_acos(v.z)
+}
+
+/// Azimuth of `v` about +z, in `[0, 2pi)`.
+pub fn spherical_phi(v: &Vector3f) -> Float {
+    let p = v.y.atan2(v.x);
+    if p < 0.0 { p + 2.0 * PI } else { p }
+}
+
+// Trigonometry of a unit direction `w` in a local frame with the normal
+// along +z, as used by reflection models.
+
+pub fn cos_theta(w: &Vector3f) -> Float {
+    w.z
+}
+pub fn cos2_theta(w: &Vector3f) -> Float {
+    w.z * w.z
+}
+pub fn abs_cos_theta(w: &Vector3f) -> Float {
+    w.z.abs()
+}
+pub fn sin2_theta(w: &Vector3f) -> Float {
+    (1.0 - cos2_theta(w)).max(0.0)
+}
+pub fn sin_theta(w: &Vector3f) -> Float {
+    sin2_theta(w).sqrt()
+}
+pub fn tan_theta(w: &Vector3f) -> Float {
+    sin_theta(w) / cos_theta(w)
+}
+pub fn tan2_theta(w: &Vector3f) -> Float {
+    sin2_theta(w) / cos2_theta(w)
+}
+/// Cosine of the azimuth; `1` for a direction along the pole.
+pub fn cos_phi(w: &Vector3f) -> Float {
+    let sin_theta = sin_theta(w);
+    if sin_theta == 0.0 {
+        1.0
+    } else {
+        (w.x / sin_theta).clamp(-1.0, 1.0)
+    }
+}
+/// Sine of the azimuth; `0` for a direction along the pole.
+pub fn sin_phi(w: &Vector3f) -> Float {
+    let sin_theta = sin_theta(w);
+    if sin_theta == 0.0 {
+        0.0
+    } else {
+        (w.y / sin_theta).clamp(-1.0, 1.0)
+    }
+}
+/// Cosine of the difference in azimuth between two directions.
+pub fn cos_dphi(wa: &Vector3f, wb: &Vector3f) -> Float {
+    let wa_xy = wa.x * wa.x + wa.y * wa.y;
+    let wb_xy = wb.x * wb.x + wb.y * wb.y;
+    if wa_xy == 0.0 || wb_xy == 0.0 {
+        return 1.0;
+    }
+    ((wa.x * wb.x + wa.y * wb.y) / (wa_xy * wb_xy).sqrt()).clamp(-1.0, 1.0)
+}
+
+/// Area of the spherical triangle with unit-vector vertices `a`, `b`, `c`,
+/// equivalently the solid angle it subtends.
+pub fn spherical_triangle_area(a: Vector3f, b: Vector3f, c: Vector3f) -> Float {
+    let numerator = a.dot(&b.cross(&c));
+    let denominator = 1.0 + a.dot(&b) + a.dot(&c) + b.dot(&c);
+    (2.0 * numerator.atan2(denominator)).abs()
+}
+
+/// Area of the spherical quadrilateral with unit-vector vertices `a` to `d`
+/// in order, from the sum of its interior angles.
+pub fn spherical_quad_area(a: Vector3f, b: Vector3f, c: Vector3f, d: Vector3f) -> Float {
+    let (axb, bxc, cxd, dxa) = (a.cross(&b), b.cross(&c), c.cross(&d), d.cross(&a));
+    if [axb, bxc, cxd, dxa]
+        .iter()
+        .any(|v| v.length_squared() == 0.0)
+    {
+        return 0.0;
+    }
+    let (axb, bxc, cxd, dxa) = (
+        axb.normalize(),
+        bxc.normalize(),
+        cxd.normalize(),
+        dxa.normalize(),
+    );
+    // Each interior angle is the angle between the planes of adjacent edges.
+    let alpha = Vector3f::angle_between(&dxa, &-axb);
+    let beta = Vector3f::angle_between(&axb, &-bxc);
+    let gamma = Vector3f::angle_between(&bxc, &-cxd);
+    let delta = Vector3f::angle_between(&cxd, &-dxa);
+    (alpha + beta + gamma + delta - 2.0 * PI).abs()
+}
+
+/// Maps the unit square to the unit sphere so that equal areas map to
+/// equal solid angles (Clarberg's octahedral mapping). The center of the
+/// square goes to +z and its corners to -z.
+pub fn equal_area_square_to_sphere(p: Point2f) -> Vector3f {
+    let u = 2.0 * p.x - 1.0;
+    let v = 2.0 * p.y - 1.0;
+    let (up, vp) = (u.abs(), v.abs());
+    // Distance from the diamond |u| + |v| = 1 that maps to the equator.
+    let signed_distance = 1.0 - (up + vp);
+    let r = 1.0 - signed_distance.abs();
+    let phi = if r == 0.0 { 1.0 } else { (vp - up) / r + 1.0 } * PI / 4.0;
+    let z = (1.0 - r * r).copysign(signed_distance);
+    let cos_phi = phi.cos().copysign(u);
+    let sin_phi = phi.sin().copysign(v);
+    let xy = r * safe_sqrt(2.0 - r * r);
+    Vector3f::new(cos_phi * xy, sin_phi * xy, z)
+}
+
+/// Inverse of `equal_area_square_to_sphere` for a unit vector `d`.
+pub fn equal_area_sphere_to_square(d: &Vector3f) -> Point2f {
+    let (x, y, z) = (d.x.abs(), d.y.abs(), d.z.abs());
+    let r = safe_sqrt(1.0 - z);
+    let (a, b) = (x.max(y), x.min(y));
+    let b = if a == 0.0 { 0.0 } else { b / a };
+    let mut phi = b.atan() * 2.0 / PI;
+    if x < y {
+        phi = 1.0 - phi;
+    }
+    let mut v = phi * r;
+    let mut u = r - v;
+    if d.z < 0.0 {
+        // Lower hemisphere: reflect across the diamond.
+        std::mem::swap(&mut u, &mut v);
+        u = 1.0 - u;
+        v = 1.0 - v;
+    }
+    Point2f::new(0.5 * (u.copysign(d.x) + 1.0), 0.5 * (v.copysign(d.y) + 1.0))
+}
+
+/// Wraps a pixel coordinate that has stepped off an equal-area image of
+/// `resolution` back onto it. Crossing an edge of the square mirrors the
+/// position along that edge, which keeps filter footprints continuous on
+/// the sphere.
+pub fn wrap_equal_area_square(mut p: Point2i, resolution: Point2i) -> Point2i {
+    if p.x < 0 {
+        p.x = -p.x;
+        p.y = resolution.y - 1 - p.y;
+    } else if p.x >= resolution.x {
+        p.x = 2 * resolution.x - 1 - p.x;
+        p.y = resolution.y - 1 - p.y;
+    }
+    if p.y < 0 {
+        p.x = resolution.x - 1 - p.x;
+        p.y = -p.y;
+    } else if p.y >= resolution.y {
+        p.x = resolution.x - 1 - p.x;
+        p.y = 2 * resolution.y - 1 - p.y;
+    }
+    p
+}
+
+/// The set of directions within an angle of a central direction `w`, stored
+/// as the cosine of that angle.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DirectionCone {
+    pub w: Vector3f,
+    pub cos_theta: Float,
+}
+
+impl Default for DirectionCone {
+    /// The empty cone.
+    fn default() -> Self {
+        Self {
+            w: Vector3f::default(),
+            cos_theta: Float::INFINITY,
+        }
+    }
+}
+
+impl DirectionCone {
+    pub fn new(w: Vector3f, cos_theta: Float) -> Self {
+        Self {
+            w: w.normalize(),
+            cos_theta,
+        }
+    }
+    /// The single direction `w`.
+    pub fn from_direction(w: Vector3f) -> Self {
+        Self::new(w, 1.0)
+    }
+    pub fn entire_sphere() -> Self {
+        Self::new(Vector3f::new(0.0, 0.0, 1.0), -1.0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cos_theta == Float::INFINITY
+    }
+    pub fn contains(&self, w: &Vector3f) -> bool {
+        !self.is_empty() && self.w.dot(&w.normalize()) >= self.cos_theta
+    }
+
+    /// Directions from `p` toward any point of `b`, bounded through the box's
+    /// bounding sphere.
+    pub fn bound_subtended_directions(b: &Bounds3, p: &Point3f) -> Self {
+        let (center, radius) = b.bounding_sphere();
+        let distance_squared = Point3f::distance_squared(p, &center);
+        if distance_squared < radius * radius {
+            return Self::entire_sphere();
+        }
+        let sin2_theta_max = radius * radius / distance_squared;
+        Self::new(center - *p, safe_sqrt(1.0 - sin2_theta_max))
+    }
+
+    /// The smallest cone containing both `a` and `b`.
+    pub fn union(a: &Self, b: &Self) -> Self {
+        if a.is_empty() {
+            return *b;
+        }
+        if b.is_empty() {
+            return *a;
+        }
+        let theta_a = safe_acos(a.cos_theta);
+        let theta_b = safe_acos(b.cos_theta);
+        let theta_d = Vector3f::angle_between(&a.w, &b.w);
+        // One cone may already contain the other.
+        if (theta_d + theta_b).min(PI) <= theta_a {
+            return *a;
+        }
+        if (theta_d + theta_a).min(PI) <= theta_b {
+            return *b;
+        }
+        let theta_o = (theta_a + theta_d + theta_b) / 2.0;
+        if theta_o >= PI {
+            return Self::entire_sphere();
+        }
+        // Turn a's axis toward b's until the far edges line up.
+        let theta_r = theta_o - theta_a;
+        let wr = a.w.cross(&b.w);
+        if wr.length_squared() == 0.0 {
+            return Self::entire_sphere();
+        }
+        let w = Transform::rotate(theta_r.to_degrees(), wr).transform_vector(&a.w);
+        Self::new(w, theta_o.cos())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-   
+
+    const X: Vector3f = Vector3f::new(1.0, 0.0, 0.0);
+    const Y: Vector3f = Vector3f::new(0.0, 1.0, 0.0);
+    const Z: Vector3f = Vector3f::new(0.0, 0.0, 1.0);
+
+    #[test]
+    fn spherical_coordinates_round_trip() {
+        assert!((spherical_theta(&Z)).abs() < 1e-6);
+        assert!((spherical_theta(&-Z) - PI).abs() < 1e-6);
+        assert!((spherical_phi(&Y) - PI / 2.0).abs() < 1e-6);
+        assert!((spherical_phi(&-Y) - 1.5 * PI).abs() < 1e-6);
+        for (theta, phi) in [(0.3, 0.2), (1.2, 2.5), (2.9, 5.9)] {
+            let (s, c) = (theta as Float).sin_cos();
+            let w = spherical_direction(s, c, phi);
+            assert!((spherical_theta(&w) - theta).abs() < 1e-5);
+            assert!((spherical_phi(&w) - phi).abs() < 1e-5);
+            assert!((cos_phi(&w) - phi.cos()).abs() < 1e-5);
+            assert!((sin_phi(&w) - phi.sin()).abs() < 1e-5);
+            assert!((tan_theta(&w) - theta.tan()).abs() < 1e-4);
+        }
+        assert_eq!(cos_phi(&Z), 1.0);
+        assert!((cos_dphi(&X, &Vector3f::new(-1.0, 0.0, 0.5)) + 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn areas_of_known_regions() {
+        // One octant of the sphere.
+        assert!((spherical_triangle_area(X, Y, Z) - PI / 2.0).abs() < 1e-5);
+        // A cube face seen from the center covers a sixth of the sphere.
+        let corner = |x: Float, y: Float| Vector3f::new(x, y, 1.0).normalize();
+        let face = spherical_quad_area(
+            corner(-1.0, -1.0),
+            corner(1.0, -1.0),
+            corner(1.0, 1.0),
+            corner(-1.0, 1.0),
+        );
+        assert!((face - 4.0 * PI / 6.0).abs() < 1e-4);
+        assert_eq!(spherical_quad_area(X, X, Y, Z), 0.0);
+    }
+
+    #[test]
+    fn equal_area_mapping_round_trips() {
+        assert!((equal_area_square_to_sphere(Point2f::new(0.5, 0.5)) - Z).length() < 1e-6);
+        assert!((equal_area_square_to_sphere(Point2f::new(0.0, 0.0)) + Z).length() < 1e-6);
+        assert!((equal_area_square_to_sphere(Point2f::new(1.0, 0.5)) - X).length() < 1e-6);
+        for i in 0..10 {
+            for j in 0..10 {
+                let p = Point2f::new((i as Float + 0.5) / 10.0, (j as Float + 0.3) / 10.0);
+                let w = equal_area_square_to_sphere(p);
+                assert!((w.length() - 1.0).abs() < 1e-5);
+                let q = equal_area_sphere_to_square(&w);
+                assert!((q.x - p.x).abs() < 1e-4 && (q.y - p.y).abs() < 1e-4);
+            }
+        }
+        // The inner diamond, half the square, is the upper hemisphere.
+        assert!(
+            equal_area_square_to_sphere(Point2f::new(0.3, 0.5)).z > 0.0
+                && equal_area_square_to_sphere(Point2f::new(0.2, 0.2)).z < 0.0
+        );
+        assert!(
+            equal_area_square_to_sphere(Point2f::new(0.25, 0.25))
+                .z
+                .abs()
+                < 1e-6
+        );
+    }
+
+    #[test]
+    fn wrapping_mirrors_across_edges() {
+        let res = Point2i::new(8, 8);
+        assert_eq!(
+            wrap_equal_area_square(Point2i::new(3, 4), res),
+            Point2i::new(3, 4)
+        );
+        assert_eq!(
+            wrap_equal_area_square(Point2i::new(-1, 2), res),
+            Point2i::new(1, 5)
+        );
+        assert_eq!(
+            wrap_equal_area_square(Point2i::new(8, 0), res),
+            Point2i::new(7, 7)
+        );
+        assert_eq!(
+            wrap_equal_area_square(Point2i::new(2, -1), res),
+            Point2i::new(5, 1)
+        );
+    }
+
+    #[test]
+    fn cones_bound_and_union() {
+        let empty = DirectionCone::default();
+        let x = DirectionCone::from_direction(X);
+        assert!(empty.is_empty() && !empty.contains(&X));
+        assert_eq!(DirectionCone::union(&empty, &x), x);
+
+        let xy = DirectionCone::union(&x, &DirectionCone::from_direction(Y));
+        let diagonal = Vector3f::new(1.0, 1.0, 0.0).normalize();
+        assert!((xy.w - diagonal).length() < 1e-5);
+        assert!((xy.cos_theta - (PI / 4.0).cos()).abs() < 1e-5);
+        assert!(xy.contains(&X) && xy.contains(&Y) && !xy.contains(&Z));
+        let opposite = DirectionCone::union(&x, &DirectionCone::from_direction(-X));
+        assert_eq!(opposite.cos_theta, -1.0);
+
+        let b = Bounds3::from_points(Point3f::new(-1.0, -1.0, 9.0), Point3f::new(1.0, 1.0, 11.0));
+        let cone = DirectionCone::bound_subtended_directions(&b, &Point3f::default());
+        assert!((cone.w - Z).length() < 1e-6);
+        for c in 0..8 {
+            assert!(cone.contains(&Vector3f::from(b.corner(c))));
+        }
+        let inside = DirectionCone::bound_subtended_directions(&b, &Point3f::new(0.0, 0.0, 10.0));
+        assert_eq!(inside, DirectionCone::entire_sphere());
+    }
 }
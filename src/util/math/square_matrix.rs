@@ -1,12 +1,19 @@
-use std::ops::{Add, Index, Mul, Sub};
-use std::{convert::identity, vec};
+use std::ops::{Add, Index, IndexMut, Mul, Sub};
 
-use crate::util::types::{Float, Int};
-#[derive(Debug)]
+use crate::util::math::{difference_of_products, inner_product};
+use crate::util::types::Float;
+use crate::util::vector::Vector3f;
+#[derive(Debug, Clone, Copy)]
 pub struct SquareMatrix<const N: usize> {
     pub matrix: [[Float; N]; N],
 }
 
+impl<const N: usize> Default for SquareMatrix<N> {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
 impl<const N: usize> SquareMatrix<N> {
     pub fn new() -> Self {
         SquareMatrix::<N>::identity()
@@ -15,27 +22,216 @@ impl<const N: usize> SquareMatrix<N> {
         if data.len() < N * N {
             panic!("input array is less than size of the matrix")
         }
-        let mut mat = [[0.0; N]; N];
-        let mut count = 0;
-        for i in 0..N {
-            for j in 0..N {
-                mat[i][j] = data[count];
-                count += 1;
-            }
+        Self {
+            matrix: std::array::from_fn(|i| std::array::from_fn(|j| data[i * N + j])),
         }
-        Self { matrix: mat }
     }
     pub fn identity() -> Self {
         let mut mat = [[0.0; N]; N];
-        for i in 0..N {
-            mat[i][i] = 1.0;
+        for (i, row) in mat.iter_mut().enumerate() {
+            row[i] = 1.0;
         }
         Self { matrix: mat }
     }
     pub fn zero() -> Self {
-        let mut mat = [[0.0; N]; N];
+        let mat = [[0.0; N]; N];
         Self { matrix: mat }
     }
+    pub fn transpose(&self) -> Self {
+        let mut result = SquareMatrix::<N>::zero();
+        for i in 0..N {
+            for j in 0..N {
+                result.matrix[i][j] = self.matrix[j][i];
+            }
+        }
+        result
+    }
+    pub fn is_identity(&self) -> bool {
+        for i in 0..N {
+            for j in 0..N {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                if self.matrix[i][j] != expected {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    pub fn determinant(&self) -> Float {
+        let m = &self.matrix;
+        match N {
+            0 => 1.0,
+            1 => m[0][0],
+            2 => difference_of_products(m[0][0], m[1][1], m[0][1], m[1][0]),
+            3 => {
+                let minor12 = difference_of_products(m[1][1], m[2][2], m[1][2], m[2][1]);
+                let minor02 = difference_of_products(m[1][0], m[2][2], m[1][2], m[2][0]);
+                let minor01 = difference_of_products(m[1][0], m[2][1], m[1][1], m[2][0]);
+                m[0][2].mul_add(
+                    minor01,
+                    difference_of_products(m[0][0], minor12, m[0][1], minor02),
+                )
+            }
+            4 => {
+                let (s, c) = self.minors_4x4();
+                inner_product(&[
+                    (s[0], c[5]),
+                    (-s[1], c[4]),
+                    (s[2], c[3]),
+                    (s[3], c[2]),
+                    (s[5], c[0]),
+                    (-s[4], c[1]),
+                ])
+            }
+            _ => self.determinant_lu(),
+        }
+    }
+
+    /// Returns the inverse, or `None` if the matrix is singular.
+    pub fn inverse(&self) -> Option<Self> {
+        match N {
+            3 => self.inverse_3x3(),
+            4 => self.inverse_4x4(),
+            _ => self.inverse_gauss_jordan(),
+        }
+    }
+
+    /// 2x2 minors of the top two rows (`s`) and bottom two rows (`c`) of a
+    /// 4x4 matrix; both the determinant and the inverse are built from them.
+    fn minors_4x4(&self) -> ([Float; 6], [Float; 6]) {
+        let m = &self.matrix;
+        let s = [
+            difference_of_products(m[0][0], m[1][1], m[1][0], m[0][1]),
+            difference_of_products(m[0][0], m[1][2], m[1][0], m[0][2]),
+            difference_of_products(m[0][0], m[1][3], m[1][0], m[0][3]),
+            difference_of_products(m[0][1], m[1][2], m[1][1], m[0][2]),
+            difference_of_products(m[0][1], m[1][3], m[1][1], m[0][3]),
+            difference_of_products(m[0][2], m[1][3], m[1][2], m[0][3]),
+        ];
+        let c = [
+            difference_of_products(m[2][0], m[3][1], m[3][0], m[2][1]),
+            difference_of_products(m[2][0], m[3][2], m[3][0], m[2][2]),
+            difference_of_products(m[2][0], m[3][3], m[3][0], m[2][3]),
+            difference_of_products(m[2][1], m[3][2], m[3][1], m[2][2]),
+            difference_of_products(m[2][1], m[3][3], m[3][1], m[2][3]),
+            difference_of_products(m[2][2], m[3][3], m[3][2], m[2][3]),
+        ];
+        (s, c)
+    }
+
+    fn inverse_3x3(&self) -> Option<Self> {
+        let det = self.determinant();
+        if det == 0.0 {
+            return None;
+        }
+        let inv_det = 1.0 / det;
+        let m = &self.matrix;
+        let mut r = SquareMatrix::<N>::zero();
+        r.matrix[0][0] = inv_det * difference_of_products(m[1][1], m[2][2], m[1][2], m[2][1]);
+        r.matrix[1][0] = inv_det * difference_of_products(m[1][2], m[2][0], m[1][0], m[2][2]);
+        r.matrix[2][0] = inv_det * difference_of_products(m[1][0], m[2][1], m[1][1], m[2][0]);
+        r.matrix[0][1] = inv_det * difference_of_products(m[0][2], m[2][1], m[0][1], m[2][2]);
+        r.matrix[1][1] = inv_det * difference_of_products(m[0][0], m[2][2], m[0][2], m[2][0]);
+        r.matrix[2][1] = inv_det * difference_of_products(m[0][1], m[2][0], m[0][0], m[2][1]);
+        r.matrix[0][2] = inv_det * difference_of_products(m[0][1], m[1][2], m[0][2], m[1][1]);
+        r.matrix[1][2] = inv_det * difference_of_products(m[0][2], m[1][0], m[0][0], m[1][2]);
+        r.matrix[2][2] = inv_det * difference_of_products(m[0][0], m[1][1], m[0][1], m[1][0]);
+        Some(r)
+    }
+
+    fn inverse_4x4(&self) -> Option<Self> {
+        let (s, c) = self.minors_4x4();
+        let det = self.determinant();
+        if det == 0.0 {
+            return None;
+        }
+        let inv_det = 1.0 / det;
+        let m = &self.matrix;
+        let entry = |terms: &[(Float, Float)]| inv_det * inner_product(terms);
+        let mut r = SquareMatrix::<N>::zero();
+        r.matrix[0][0] = entry(&[(m[1][1], c[5]), (m[1][3], c[3]), (-m[1][2], c[4])]);
+        r.matrix[0][1] = entry(&[(-m[0][1], c[5]), (m[0][2], c[4]), (-m[0][3], c[3])]);
+        r.matrix[0][2] = entry(&[(m[3][1], s[5]), (m[3][3], s[3]), (-m[3][2], s[4])]);
+        r.matrix[0][3] = entry(&[(-m[2][1], s[5]), (m[2][2], s[4]), (-m[2][3], s[3])]);
+        r.matrix[1][0] = entry(&[(-m[1][0], c[5]), (m[1][2], c[2]), (-m[1][3], c[1])]);
+        r.matrix[1][1] = entry(&[(m[0][0], c[5]), (m[0][3], c[1]), (-m[0][2], c[2])]);
+        r.matrix[1][2] = entry(&[(-m[3][0], s[5]), (m[3][2], s[2]), (-m[3][3], s[1])]);
+        r.matrix[1][3] = entry(&[(m[2][0], s[5]), (m[2][3], s[1]), (-m[2][2], s[2])]);
+        r.matrix[2][0] = entry(&[(m[1][0], c[4]), (m[1][3], c[0]), (-m[1][1], c[2])]);
+        r.matrix[2][1] = entry(&[(-m[0][0], c[4]), (m[0][1], c[2]), (-m[0][3], c[0])]);
+        r.matrix[2][2] = entry(&[(m[3][0], s[4]), (m[3][3], s[0]), (-m[3][1], s[2])]);
+        r.matrix[2][3] = entry(&[(-m[2][0], s[4]), (m[2][1], s[2]), (-m[2][3], s[0])]);
+        r.matrix[3][0] = entry(&[(-m[1][0], c[3]), (m[1][1], c[1]), (-m[1][2], c[0])]);
+        r.matrix[3][1] = entry(&[(m[0][0], c[3]), (m[0][2], c[0]), (-m[0][1], c[1])]);
+        r.matrix[3][2] = entry(&[(-m[3][0], s[3]), (m[3][1], s[1]), (-m[3][2], s[0])]);
+        r.matrix[3][3] = entry(&[(m[2][0], s[3]), (m[2][2], s[0]), (-m[2][1], s[1])]);
+        Some(r)
+    }
+
+    /// Gaussian elimination with partial pivoting, for sizes without a
+    /// closed-form fast path.
+    fn determinant_lu(&self) -> Float {
+        let mut a = self.matrix;
+        let mut det = 1.0;
+        for col in 0..N {
+            let pivot = (col..N)
+                .max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))
+                .unwrap();
+            if a[pivot][col] == 0.0 {
+                return 0.0;
+            }
+            if pivot != col {
+                a.swap(pivot, col);
+                det = -det;
+            }
+            det *= a[col][col];
+            let (top, bottom) = a.split_at_mut(col + 1);
+            let pivot_row = &top[col];
+            for row in bottom {
+                let factor = row[col] / pivot_row[col];
+                for (v, p) in row[col..].iter_mut().zip(&pivot_row[col..]) {
+                    *v -= factor * p;
+                }
+            }
+        }
+        det
+    }
+
+    /// Gauss-Jordan elimination with partial pivoting, for sizes without a
+    /// closed-form fast path.
+    fn inverse_gauss_jordan(&self) -> Option<Self> {
+        let mut a = self.matrix;
+        let mut inv = Self::identity().matrix;
+        for col in 0..N {
+            let pivot = (col..N)
+                .max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))
+                .unwrap();
+            if a[pivot][col] == 0.0 {
+                return None;
+            }
+            a.swap(pivot, col);
+            inv.swap(pivot, col);
+            let scale = 1.0 / a[col][col];
+            for k in 0..N {
+                a[col][k] *= scale;
+                inv[col][k] *= scale;
+            }
+            for row in 0..N {
+                if row == col {
+                    continue;
+                }
+                let factor = a[row][col];
+                if factor != 0.0 {
+                    for k in 0..N {
+                        a[row][k] -= factor * a[col][k];
+                        inv[row][k] -= factor * inv[col][k];
+                    }
+                }
+            }
+        }
+        Some(Self { matrix: inv })
+    }
 }
 
 impl<const N: usize> Add<&SquareMatrix<N>> for &SquareMatrix<N> {
@@ -62,6 +258,53 @@ impl<const N: usize> Sub<&SquareMatrix<N>> for &SquareMatrix<N> {
         result
     }
 }
+impl<const N: usize> Mul<&SquareMatrix<N>> for &SquareMatrix<N> {
+    type Output = SquareMatrix<N>;
+    fn mul(self, rhs: &SquareMatrix<N>) -> Self::Output {
+        let mut result = SquareMatrix::<N>::zero();
+        for i in 0..N {
+            for j in 0..N {
+                let mut sum = 0.0;
+                for k in 0..N {
+                    sum += self.matrix[i][k] * rhs.matrix[k][j];
+                }
+                result.matrix[i][j] = sum;
+            }
+        }
+        result
+    }
+}
+/// Matrix-vector product, treating `rhs` as a column vector.
+impl<const N: usize> Mul<[Float; N]> for &SquareMatrix<N> {
+    type Output = [Float; N];
+    fn mul(self, rhs: [Float; N]) -> Self::Output {
+        std::array::from_fn(|i| {
+            let terms: [(Float, Float); N] = std::array::from_fn(|j| (self.matrix[i][j], rhs[j]));
+            inner_product(&terms)
+        })
+    }
+}
+impl Mul<Vector3f> for &SquareMatrix<3> {
+    type Output = Vector3f;
+    fn mul(self, rhs: Vector3f) -> Self::Output {
+        let [x, y, z] = self * [rhs.x, rhs.y, rhs.z];
+        Vector3f::new(x, y, z)
+    }
+}
+
+/// Element access by `(row, column)`.
+impl<const N: usize> Index<(usize, usize)> for SquareMatrix<N> {
+    type Output = Float;
+    fn index(&self, (i, j): (usize, usize)) -> &Self::Output {
+        &self.matrix[i][j]
+    }
+}
+impl<const N: usize> IndexMut<(usize, usize)> for SquareMatrix<N> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut Self::Output {
+        &mut self.matrix[i][j]
+    }
+}
+
 impl<const N: usize> PartialEq for SquareMatrix<N> {
     fn eq(&self, other: &Self) -> bool {
         for i in 0..N {
@@ -77,7 +320,9 @@ impl<const N: usize> PartialEq for SquareMatrix<N> {
 
 #[cfg(test)]
 mod tests {
+    use crate::util::Float;
     use crate::util::math::SquareMatrix;
+    use crate::util::vector::Vector3f;
     #[test]
     fn add_2X2_matrix() {
         let mat = SquareMatrix::<2>::fill(vec![0.0, 1.0, 2.0, 3.0]);
@@ -95,12 +340,77 @@ mod tests {
         assert_eq!(result, expected);
     }
     #[test]
+    fn mul_2X2_matrix() {
+        let mat = SquareMatrix::<2>::fill(vec![1.0, 2.0, 3.0, 4.0]);
+        let mat2 = SquareMatrix::<2>::fill(vec![5.0, 6.0, 7.0, 8.0]);
+        let expected = SquareMatrix::<2>::fill(vec![19.0, 22.0, 43.0, 50.0]);
+        assert_eq!(&mat * &mat2, expected);
+        assert_eq!(&mat * &SquareMatrix::<2>::identity(), mat);
+    }
+    #[test]
+    fn transpose_2X2_matrix() {
+        let mat = SquareMatrix::<2>::fill(vec![1.0, 2.0, 3.0, 4.0]);
+        let expected = SquareMatrix::<2>::fill(vec![1.0, 3.0, 2.0, 4.0]);
+        assert_eq!(mat.transpose(), expected);
+    }
+    #[test]
+    fn determinant_fast_paths_match_elimination() {
+        let m3 = SquareMatrix::<3>::fill(vec![2.0, -3.0, 1.0, 2.0, 0.0, -1.0, 1.0, 4.0, 5.0]);
+        assert_eq!(m3.determinant(), 49.0);
+        assert_eq!(m3.determinant_lu(), 49.0);
+        let m4 = SquareMatrix::<4>::fill(vec![
+            1.0, 0.0, 2.0, -1.0, 3.0, 0.0, 0.0, 5.0, 2.0, 1.0, 4.0, -3.0, 1.0, 0.0, 5.0, 0.0,
+        ]);
+        assert_eq!(m4.determinant(), 30.0);
+        assert_eq!(m4.determinant_lu(), 30.0);
+    }
+    #[test]
+    fn inverse_times_matrix_is_identity() {
+        let m4 = SquareMatrix::<4>::fill(vec![
+            1.0, 0.0, 2.0, -1.0, 3.0, 0.0, 0.0, 5.0, 2.0, 1.0, 4.0, -3.0, 1.0, 0.0, 5.0, 0.0,
+        ]);
+        let m3 = SquareMatrix::<3>::fill(vec![2.0, -3.0, 1.0, 2.0, 0.0, -1.0, 1.0, 4.0, 5.0]);
+        let m5 = SquareMatrix::<5>::fill((0..25).map(|i| ((i * 7) % 11) as Float).collect());
+        assert_near_identity(&(&m4 * &m4.inverse().unwrap()));
+        assert_near_identity(&(&m3 * &m3.inverse().unwrap()));
+        assert_near_identity(&(&m5 * &m5.inverse().unwrap()));
+    }
+    #[test]
+    fn singular_matrix_has_no_inverse() {
+        let m = SquareMatrix::<4>::fill(vec![
+            1.0, 2.0, 3.0, 4.0, 2.0, 4.0, 6.0, 8.0, 0.0, 1.0, 0.0, 1.0, 5.0, 1.0, 2.0, 3.0,
+        ]);
+        assert_eq!(m.inverse(), None);
+        assert_eq!(SquareMatrix::<2>::zero().inverse(), None);
+    }
+    #[test]
+    fn matrix_vector_product_and_index() {
+        let mut m = SquareMatrix::<3>::identity();
+        m[(0, 2)] = 2.0;
+        assert_eq!(&m * [1.0, 2.0, 3.0], [7.0, 2.0, 3.0]);
+        assert_eq!(
+            &m * Vector3f::new(1.0, 2.0, 3.0),
+            Vector3f::new(7.0, 2.0, 3.0)
+        );
+        assert!(!m.is_identity());
+        assert!(SquareMatrix::<3>::identity().is_identity());
+    }
+
+    fn assert_near_identity<const N: usize>(m: &SquareMatrix<N>) {
+        for i in 0..N {
+            for j in 0..N {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert!((m[(i, j)] - expected).abs() < 1e-4, "{:?}", m);
+            }
+        }
+    }
+    #[test]
     fn test_4X4_identity() {
         let mat = SquareMatrix::<4>::identity();
         let mut flag = true;
         for i in 0..4 {
             flag = flag && mat.matrix[i][i] == 1.0;
         }
-        assert_eq!(flag, true);
+        assert!(flag);
     }
 }
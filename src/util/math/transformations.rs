@@ -1,32 +1,507 @@
-use crate::util::{math::SquareMatrix, rays::Ray, vector::Vector3};
+use std::ops::Mul;
 
+use crate::util::Float;
+use crate::util::bounds::Bounds3;
+use crate::util::math::{SquareMatrix, gamma};
+use crate::util::rays::{Ray, RayDifferential};
+use crate::util::vector::{Normal3f, Point3f, Vector3f};
+
+/// An affine or projective transformation of 3D space.
+///
+/// The inverse matrix is carried alongside the forward one so that inverting
+/// a transform is free and normals can be transformed by the inverse
+/// transpose without recomputing anything.
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Transform {
     m: SquareMatrix<4>,
     mInv: SquareMatrix<4>,
 }
 
+impl Default for Transform {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
 impl Transform {
-    fn new(matrix: SquareMatrix<4>, inverseMatrix: SquareMatrix<4>) -> Self {
+    /// Builds a transform from a matrix and its inverse. The caller is
+    /// responsible for `inverseMatrix` actually being the inverse.
+    pub fn new(matrix: SquareMatrix<4>, inverseMatrix: SquareMatrix<4>) -> Self {
         Self {
             m: matrix,
             mInv: inverseMatrix,
         }
     }
+    /// Builds a transform from a matrix alone, computing its inverse.
+    /// Returns `None` if the matrix is singular.
+    pub fn from_matrix(matrix: SquareMatrix<4>) -> Option<Self> {
+        matrix.inverse().map(|inverse| Self::new(matrix, inverse))
+    }
+    pub fn identity() -> Self {
+        Self::new(SquareMatrix::identity(), SquareMatrix::identity())
+    }
+
+    pub fn matrix(&self) -> &SquareMatrix<4> {
+        &self.m
+    }
+    pub fn inverse_matrix(&self) -> &SquareMatrix<4> {
+        &self.mInv
+    }
+
+    pub fn inverse(&self) -> Self {
+        Self::new(self.mInv, self.m)
+    }
+    pub fn transpose(&self) -> Self {
+        Self::new(self.m.transpose(), self.mInv.transpose())
+    }
+    pub fn is_identity(&self) -> bool {
+        self.m.is_identity()
+    }
+    /// True if the transform turns a right-handed coordinate system into a
+    /// left-handed one, which flips the orientation of cross products.
+    pub fn swaps_handedness(&self) -> bool {
+        let m = &self.m.matrix;
+        let det = m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0]);
+        det < 0.0
+    }
+
+    pub fn translate(delta: Vector3f) -> Self {
+        let m = SquareMatrix {
+            matrix: [
+                [1.0, 0.0, 0.0, delta.x],
+                [0.0, 1.0, 0.0, delta.y],
+                [0.0, 0.0, 1.0, delta.z],
+                [0.0, 0.0, 0.0, 1.0],
+            ],
+        };
+        let mInv = SquareMatrix {
+            matrix: [
+                [1.0, 0.0, 0.0, -delta.x],
+                [0.0, 1.0, 0.0, -delta.y],
+                [0.0, 0.0, 1.0, -delta.z],
+                [0.0, 0.0, 0.0, 1.0],
+            ],
+        };
+        Self::new(m, mInv)
+    }
+
+    pub fn scale(x: Float, y: Float, z: Float) -> Self {
+        let m = SquareMatrix {
+            matrix: [
+                [x, 0.0, 0.0, 0.0],
+                [0.0, y, 0.0, 0.0],
+                [0.0, 0.0, z, 0.0],
+                [0.0, 0.0, 0.0, 1.0],
+            ],
+        };
+        let mInv = SquareMatrix {
+            matrix: [
+                [1.0 / x, 0.0, 0.0, 0.0],
+                [0.0, 1.0 / y, 0.0, 0.0],
+                [0.0, 0.0, 1.0 / z, 0.0],
+                [0.0, 0.0, 0.0, 1.0],
+            ],
+        };
+        Self::new(m, mInv)
+    }
+
+    /// Rotation about the x axis by `theta` degrees.
+    pub fn rotate_x(theta: Float) -> Self {
+        let (sin_theta, cos_theta) = theta.to_radians().sin_cos();
+        let m = SquareMatrix {
+            matrix: [
+                [1.0, 0.0, 0.0, 0.0],
+                [0.0, cos_theta, -sin_theta, 0.0],
+                [0.0, sin_theta, cos_theta, 0.0],
+                [0.0, 0.0, 0.0, 1.0],
+            ],
+        };
+        Self::new(m, m.transpose())
+    }
+
+    /// Rotation about the y axis by `theta` degrees.
+    pub fn rotate_y(theta: Float) -> Self {
+        let (sin_theta, cos_theta) = theta.to_radians().sin_cos();
+        let m = SquareMatrix {
+            matrix: [
+                [cos_theta, 0.0, sin_theta, 0.0],
+                [0.0, 1.0, 0.0, 0.0],
+                [-sin_theta, 0.0, cos_theta, 0.0],
+                [0.0, 0.0, 0.0, 1.0],
+            ],
+        };
+        Self::new(m, m.transpose())
+    }
 
-    fn transform(&self, v: Vector3) -> Vector3 {
-        Vector3::new(
-            self.m.matrix[0][0] * v.get_x()
-                + self.m.matrix[0][1] * v.get_y()
-                + self.m.matrix[0][2] * v.get_z()
-                + self.m.matrix[0][3],
-            self.m.matrix[1][0] * v.get_x()
-                + self.m.matrix[1][1] * v.get_y()
-                + self.m.matrix[1][2] * v.get_z()
-                + self.m.matrix[1][3],
-            self.m.matrix[2][0] * v.get_x()
-                + self.m.matrix[2][1] * v.get_y()
-                + self.m.matrix[2][2] * v.get_z()
-                + self.m.matrix[2][3],
+    /// Rotation about the z axis by `theta` degrees.
+    pub fn rotate_z(theta: Float) -> Self {
+        let (sin_theta, cos_theta) = theta.to_radians().sin_cos();
+        let m = SquareMatrix {
+            matrix: [
+                [cos_theta, -sin_theta, 0.0, 0.0],
+                [sin_theta, cos_theta, 0.0, 0.0],
+                [0.0, 0.0, 1.0, 0.0],
+                [0.0, 0.0, 0.0, 1.0],
+            ],
+        };
+        Self::new(m, m.transpose())
+    }
+
+    /// Rotation by `theta` degrees about an arbitrary `axis`.
+    pub fn rotate(theta: Float, axis: Vector3f) -> Self {
+        let (sin_theta, cos_theta) = theta.to_radians().sin_cos();
+        Self::rotate_sin_cos(sin_theta, cos_theta, axis)
+    }
+
+    /// Rotation about `axis` given the sine and cosine of the angle directly,
+    /// which avoids a round trip through `acos` when they are already known.
+    pub fn rotate_sin_cos(sin_theta: Float, cos_theta: Float, axis: Vector3f) -> Self {
+        let a = axis.normalize();
+        let mut m = SquareMatrix::<4>::identity();
+        m.matrix[0][0] = a.x * a.x + (1.0 - a.x * a.x) * cos_theta;
+        m.matrix[0][1] = a.x * a.y * (1.0 - cos_theta) - a.z * sin_theta;
+        m.matrix[0][2] = a.x * a.z * (1.0 - cos_theta) + a.y * sin_theta;
+        m.matrix[1][0] = a.x * a.y * (1.0 - cos_theta) + a.z * sin_theta;
+        m.matrix[1][1] = a.y * a.y + (1.0 - a.y * a.y) * cos_theta;
+        m.matrix[1][2] = a.y * a.z * (1.0 - cos_theta) - a.x * sin_theta;
+        m.matrix[2][0] = a.x * a.z * (1.0 - cos_theta) - a.y * sin_theta;
+        m.matrix[2][1] = a.y * a.z * (1.0 - cos_theta) + a.x * sin_theta;
+        m.matrix[2][2] = a.z * a.z + (1.0 - a.z * a.z) * cos_theta;
+        Self::new(m, m.transpose())
+    }
+
+    /// World-to-camera transform for a camera at `pos` looking at `look`.
+    ///
+    /// Returns `None` if `up` is parallel to the viewing direction, since no
+    /// orientation can be derived in that case.
+    pub fn look_at(pos: Point3f, look: Point3f, up: Vector3f) -> Option<Self> {
+        let dir = (look - pos).normalize();
+        let right = up.normalize().cross(&dir);
+        if right.length() == 0.0 {
+            return None;
+        }
+        let right = right.normalize();
+        let new_up = dir.cross(&right);
+
+        // Columns are the camera basis expressed in world space.
+        let world_from_camera = SquareMatrix {
+            matrix: [
+                [right.x, new_up.x, dir.x, pos.x],
+                [right.y, new_up.y, dir.y, pos.y],
+                [right.z, new_up.z, dir.z, pos.z],
+                [0.0, 0.0, 0.0, 1.0],
+            ],
+        };
+        // The inverse of a rigid transform is the transposed rotation
+        // followed by the rotated, negated translation.
+        let p = Vector3f::from(pos);
+        let camera_from_world = SquareMatrix {
+            matrix: [
+                [right.x, right.y, right.z, -right.dot(&p)],
+                [new_up.x, new_up.y, new_up.z, -new_up.dot(&p)],
+                [dir.x, dir.y, dir.z, -dir.dot(&p)],
+                [0.0, 0.0, 0.0, 1.0],
+            ],
+        };
+        Some(Self::new(camera_from_world, world_from_camera))
+    }
+
+    /// Orthographic projection mapping `z` in `[z_near, z_far]` to `[0, 1]`.
+    pub fn orthographic(z_near: Float, z_far: Float) -> Self {
+        Self::scale(1.0, 1.0, 1.0 / (z_far - z_near))
+            * Self::translate(Vector3f::new(0.0, 0.0, -z_near))
+    }
+
+    /// Perspective projection with a field of view of `fov` degrees. Points
+    /// at `n` map to `z = 0` and points at `f` to `z = 1`.
+    pub fn perspective(fov: Float, n: Float, f: Float) -> Self {
+        let a = f / (f - n);
+        let b = -f * n / (f - n);
+        let persp = SquareMatrix {
+            matrix: [
+                [1.0, 0.0, 0.0, 0.0],
+                [0.0, 1.0, 0.0, 0.0],
+                [0.0, 0.0, a, b],
+                [0.0, 0.0, 1.0, 0.0],
+            ],
+        };
+        let persp_inv = SquareMatrix {
+            matrix: [
+                [1.0, 0.0, 0.0, 0.0],
+                [0.0, 1.0, 0.0, 0.0],
+                [0.0, 0.0, 0.0, 1.0],
+                [0.0, 0.0, 1.0 / b, -a / b],
+            ],
+        };
+        let inv_tan_ang = 1.0 / (fov.to_radians() / 2.0).tan();
+        Self::scale(inv_tan_ang, inv_tan_ang, 1.0) * Self::new(persp, persp_inv)
+    }
+
+    /// Applies the full transform, including translation and the projective
+    /// divide when the transform is not affine.
+    pub fn transform_point(&self, p: &Point3f) -> Point3f {
+        let m = &self.m.matrix;
+        let x = m[0][0] * p.x + m[0][1] * p.y + m[0][2] * p.z + m[0][3];
+        let y = m[1][0] * p.x + m[1][1] * p.y + m[1][2] * p.z + m[1][3];
+        let z = m[2][0] * p.x + m[2][1] * p.y + m[2][2] * p.z + m[2][3];
+        let w = m[3][0] * p.x + m[3][1] * p.y + m[3][2] * p.z + m[3][3];
+        if w == 1.0 {
+            Point3f::new(x, y, z)
+        } else {
+            Point3f::new(x, y, z) / w
+        }
+    }
+
+    /// Applies an affine transform to a point that already carries an absolute
+    /// error bound `p_error`, returning the transformed point and a
+    /// conservative bound on its error.
+    pub fn transform_point_with_error(
+        &self,
+        p: &Point3f,
+        p_error: &Vector3f,
+    ) -> (Point3f, Vector3f) {
+        let m = &self.m.matrix;
+        let err = |r: usize| {
+            (gamma(3) + 1.0)
+                * (m[r][0].abs() * p_error.x
+                    + m[r][1].abs() * p_error.y
+                    + m[r][2].abs() * p_error.z)
+                + gamma(3)
+                    * ((m[r][0] * p.x).abs()
+                        + (m[r][1] * p.y).abs()
+                        + (m[r][2] * p.z).abs()
+                        + m[r][3].abs())
+        };
+        (
+            self.transform_point(p),
+            Vector3f::new(err(0), err(1), err(2)),
         )
     }
+
+    /// Directions are unaffected by translation, so only the upper 3x3 is used.
+    pub fn transform_vector(&self, v: &Vector3f) -> Vector3f {
+        let m = &self.m.matrix;
+        Vector3f::new(
+            m[0][0] * v.x + m[0][1] * v.y + m[0][2] * v.z,
+            m[1][0] * v.x + m[1][1] * v.y + m[1][2] * v.z,
+            m[2][0] * v.x + m[2][1] * v.y + m[2][2] * v.z,
+        )
+    }
+
+    /// Normals are transformed by the inverse transpose to stay perpendicular
+    /// to the transformed surface.
+    pub fn transform_normal(&self, n: &Normal3f) -> Normal3f {
+        let mInv = &self.mInv.matrix;
+        Normal3f::new(
+            mInv[0][0] * n.x + mInv[1][0] * n.y + mInv[2][0] * n.z,
+            mInv[0][1] * n.x + mInv[1][1] * n.y + mInv[2][1] * n.z,
+            mInv[0][2] * n.x + mInv[1][2] * n.y + mInv[2][2] * n.z,
+        )
+    }
+
+    /// The transformed origin is advanced along the ray to the far edge of
+    /// its rounding error, so that it cannot land behind a surface the ray
+    /// was spawned from; `t_max` is shortened by the same amount.
+    pub fn transform_ray(&self, r: &Ray) -> Ray {
+        let (mut o, o_error) = self.transform_point_with_error(&r.origin(), &Vector3f::default());
+        let d = self.transform_vector(&r.direction());
+        let mut t_max = r.t_max();
+        let length_squared = d.length_squared();
+        if length_squared > 0.0 {
+            let dt = d.abs().dot(&o_error) / length_squared;
+            o += d * dt;
+            t_max -= dt;
+        }
+        Ray::new(o, d, r.time())
+            .with_t_max(t_max)
+            .with_medium(r.medium())
+    }
+
+    /// Transforms the main ray as `transform_ray` does and the offset rays
+    /// exactly.
+    pub fn transform_ray_differential(&self, r: &RayDifferential) -> RayDifferential {
+        RayDifferential {
+            ray: self.transform_ray(&r.ray),
+            has_differentials: r.has_differentials,
+            rx_origin: self.transform_point(&r.rx_origin),
+            ry_origin: self.transform_point(&r.ry_origin),
+            rx_direction: self.transform_vector(&r.rx_direction),
+            ry_direction: self.transform_vector(&r.ry_direction),
+        }
+    }
+
+    /// Bounds of the transformed box, found by transforming all eight corners.
+    pub fn transform_bounds(&self, b: &Bounds3) -> Bounds3 {
+        (0..8).fold(Bounds3::new(), |bounds, i| {
+            bounds.union_point(&self.transform_point(&b.corner(i)))
+        })
+    }
+}
+
+/// Composition: `(a * b)` applies `b` first, then `a`.
+impl Mul<&Transform> for &Transform {
+    type Output = Transform;
+    fn mul(self, rhs: &Transform) -> Self::Output {
+        Transform::new(&self.m * &rhs.m, &rhs.mInv * &self.mInv)
+    }
+}
+impl Mul for Transform {
+    type Output = Transform;
+    fn mul(self, rhs: Transform) -> Self::Output {
+        Mul::mul(&self, &rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: Float, b: Float) {
+        assert!((a - b).abs() < 1e-5, "{} != {}", a, b);
+    }
+    fn assert_point_close(a: Point3f, b: Point3f) {
+        assert_close(a.x, b.x);
+        assert_close(a.y, b.y);
+        assert_close(a.z, b.z);
+    }
+
+    #[test]
+    fn mirror_swaps_handedness() {
+        assert!(Transform::scale(-1.0, 1.0, 1.0).swaps_handedness());
+        assert!(!Transform::scale(-1.0, -1.0, 1.0).swaps_handedness());
+        assert!(!Transform::rotate_x(30.0).swaps_handedness());
+    }
+
+    #[test]
+    fn point_error_grows_under_transform() {
+        let t = Transform::scale(2.0, 2.0, 2.0);
+        let (p, err) = t.transform_point_with_error(
+            &Point3f::new(1.0, 1.0, 1.0),
+            &Vector3f::new(0.5, 0.0, 0.0),
+        );
+        assert_eq!(p, Point3f::new(2.0, 2.0, 2.0));
+        assert!(err.x >= 1.0 && err.y > 0.0 && err.y < 1e-5);
+    }
+
+    #[test]
+    fn translate_moves_points_not_vectors() {
+        let t = Transform::translate(Vector3f::new(1.0, 2.0, 3.0));
+        let p = t.transform_point(&Point3f::new(1.0, 1.0, 1.0));
+        assert_eq!(p, Point3f::new(2.0, 3.0, 4.0));
+        let v = t.transform_vector(&Vector3f::new(1.0, 1.0, 1.0));
+        assert_eq!(v, Vector3f::new(1.0, 1.0, 1.0));
+        assert_eq!(t.inverse().transform_point(&p), Point3f::new(1.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn normals_stay_perpendicular_under_nonuniform_scale() {
+        let t = Transform::scale(2.0, 1.0, 1.0);
+        // Plane x = y has normal (1, -1, 0) and contains direction (1, 1, 0).
+        let n = t.transform_normal(&Normal3f::new(1.0, -1.0, 0.0));
+        let v = t.transform_vector(&Vector3f::new(1.0, 1.0, 0.0));
+        assert_close(n.dot_vector(&v), 0.0);
+    }
+
+    #[test]
+    fn rotations_agree_with_axis_rotation() {
+        let p = Point3f::new(1.0, 2.0, 3.0);
+        let rz = Transform::rotate_z(90.0);
+        assert_point_close(rz.transform_point(&p), Point3f::new(-2.0, 1.0, 3.0));
+        let axis = Transform::rotate(90.0, Vector3f::new(0.0, 0.0, 1.0));
+        assert_point_close(axis.transform_point(&p), rz.transform_point(&p));
+        let rx = Transform::rotate_x(30.0);
+        let axis = Transform::rotate(30.0, Vector3f::new(1.0, 0.0, 0.0));
+        assert_point_close(axis.transform_point(&p), rx.transform_point(&p));
+    }
+
+    #[test]
+    fn composition_applies_right_to_left() {
+        let t = Transform::translate(Vector3f::new(1.0, 0.0, 0.0));
+        let s = Transform::scale(2.0, 2.0, 2.0);
+        let ts = t * s;
+        assert_eq!(
+            ts.transform_point(&Point3f::new(1.0, 1.0, 1.0)),
+            Point3f::new(3.0, 2.0, 2.0)
+        );
+        let round_trip = ts * ts.inverse();
+        assert!(round_trip.is_identity());
+    }
+
+    #[test]
+    fn from_matrix_computes_inverse() {
+        let composed = Transform::rotate(30.0, Vector3f::new(1.0, 1.0, 0.0))
+            * Transform::translate(Vector3f::new(1.0, -2.0, 3.0))
+            * Transform::scale(2.0, 0.5, 4.0);
+        let t = Transform::from_matrix(*composed.matrix()).unwrap();
+        let p = Point3f::new(0.3, 0.7, -1.1);
+        assert_point_close(
+            t.inverse().transform_point(&composed.transform_point(&p)),
+            p,
+        );
+        assert!(Transform::from_matrix(SquareMatrix::zero()).is_none());
+    }
+
+    #[test]
+    fn look_at_moves_eye_to_origin() {
+        let eye = Point3f::new(0.0, 0.0, -5.0);
+        let t = Transform::look_at(
+            eye,
+            Point3f::new(0.0, 0.0, 0.0),
+            Vector3f::new(0.0, 1.0, 0.0),
+        )
+        .unwrap();
+        assert_point_close(t.transform_point(&eye), Point3f::new(0.0, 0.0, 0.0));
+        assert_point_close(
+            t.transform_point(&Point3f::new(0.0, 0.0, 0.0)),
+            Point3f::new(0.0, 0.0, 5.0),
+        );
+        assert!(
+            Transform::look_at(
+                eye,
+                Point3f::new(0.0, 0.0, 0.0),
+                Vector3f::new(0.0, 0.0, 1.0)
+            )
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn perspective_maps_near_and_far_planes() {
+        let t = Transform::perspective(90.0, 1.0, 10.0);
+        assert_point_close(
+            t.transform_point(&Point3f::new(1.0, 1.0, 1.0)),
+            Point3f::new(1.0, 1.0, 0.0),
+        );
+        assert_point_close(
+            t.transform_point(&Point3f::new(0.0, 0.0, 10.0)),
+            Point3f::new(0.0, 0.0, 1.0),
+        );
+        let p = Point3f::new(0.5, -0.25, 4.0);
+        assert_point_close(t.inverse().transform_point(&t.transform_point(&p)), p);
+    }
+
+    #[test]
+    fn transform_ray_and_bounds() {
+        let t =
+            Transform::translate(Vector3f::new(0.0, 0.0, 1.0)) * Transform::scale(2.0, 2.0, 2.0);
+        let r = t.transform_ray(&Ray::new(
+            Point3f::new(0.0, 0.0, 0.0),
+            Vector3f::new(1.0, 0.0, 0.0),
+            0.5,
+        ));
+        assert_eq!(r.origin(), Point3f::new(0.0, 0.0, 1.0));
+        assert_eq!(r.direction(), Vector3f::new(2.0, 0.0, 0.0));
+        assert_eq!(r.time(), 0.5);
+
+        let b = Bounds3 {
+            pMin: Point3f::new(-1.0, -1.0, -1.0),
+            pMax: Point3f::new(1.0, 1.0, 1.0),
+        };
+        let tb = Transform::rotate_z(45.0).transform_bounds(&b);
+        let s = (2.0 as Float).sqrt();
+        assert_point_close(tb.pMax, Point3f::new(s, s, 1.0));
+        assert_point_close(tb.pMin, Point3f::new(-s, -s, -1.0));
+    }
 }
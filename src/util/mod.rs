@@ -1,13 +1,17 @@
-use crate::util::tuple::{Tuple2, Tuple3};
-use crate::util::vector::{Normal3, Point3, Vector3};
-use crate::util::bounds::Bounds3;
-
 mod types;
 pub use types::*;
 
-mod tuple;
-pub mod vector;
 pub mod rays;
+pub mod tuple;
+pub mod vector;
 
 pub mod bounds;
+pub mod color;
+pub mod half;
+pub mod hash;
+pub mod interactions;
+pub mod low_discrepancy;
 pub mod math;
+pub mod rng;
+pub mod sampling;
+mod sobol_directions;
@@ -0,0 +1,36 @@
+/// Reference to a participating medium, by its index in the scene's list of
+/// media. Rays and interactions carry these rather than the media themselves
+/// so that they stay cheap to copy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MediumHandle(u32);
+
+impl MediumHandle {
+    pub const fn new(index: u32) -> Self {
+        Self(index)
+    }
+    pub const fn index(&self) -> u32 {
+        self.0
+    }
+}
+
+/// The media on either side of a surface, relative to its geometric normal.
+/// A surface that only bounds a medium without scattering light has one of
+/// these but no material.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MediumInterface {
+    pub inside: Option<MediumHandle>,
+    pub outside: Option<MediumHandle>,
+}
+
+impl MediumInterface {
+    pub fn new(inside: Option<MediumHandle>, outside: Option<MediumHandle>) -> Self {
+        Self { inside, outside }
+    }
+    /// The same medium on both sides.
+    pub fn uniform(medium: Option<MediumHandle>) -> Self {
+        Self::new(medium, medium)
+    }
+    pub fn is_transition(&self) -> bool {
+        self.inside != self.outside
+    }
+}
@@ -1,2 +1,6 @@
+mod medium;
 mod ray;
+mod ray_differential;
+pub use medium::{MediumHandle, MediumInterface};
 pub use ray::Ray;
+pub use ray_differential::RayDifferential;
@@ -1,22 +1,72 @@
 use crate::util::Float;
-use crate::util::vector::{Point3, Vector3};
+use crate::util::rays::MediumHandle;
+use crate::util::vector::{Point3f, Vector3f};
 
+/// A semi-infinite line `origin + t * direction` for `t` in `[0, t_max)`.
+///
+/// `medium` is the participating medium the origin lies in, if any.
+#[derive(Debug, Clone, Copy)]
 pub struct Ray {
-    origin: Point3,
-    direction: Vector3,
+    origin: Point3f,
+    direction: Vector3f,
     time: Float,
+    t_max: Float,
+    medium: Option<MediumHandle>,
 }
 
 impl Ray {
-    pub fn new(origin: Point3, direction: Vector3, time: Float) -> Self {
+    /// An unbounded ray in vacuum.
+    pub fn new(origin: Point3f, direction: Vector3f, time: Float) -> Self {
         Self {
             origin,
             direction,
             time,
+            t_max: Float::INFINITY,
+            medium: None,
         }
     }
-    pub fn get(&self, t: Float) -> Point3 {
-        &self.origin + &(&self.direction * t)
+    pub fn with_t_max(mut self, t_max: Float) -> Self {
+        self.t_max = t_max;
+        self
+    }
+    pub fn with_medium(mut self, medium: Option<MediumHandle>) -> Self {
+        self.medium = medium;
+        self
+    }
+
+    pub fn origin(&self) -> Point3f {
+        self.origin
+    }
+    pub fn direction(&self) -> Vector3f {
+        self.direction
+    }
+    pub fn time(&self) -> Float {
+        self.time
+    }
+    pub fn t_max(&self) -> Float {
+        self.t_max
+    }
+    pub fn medium(&self) -> Option<MediumHandle> {
+        self.medium
+    }
+
+    pub fn set_origin(&mut self, origin: Point3f) {
+        self.origin = origin;
+    }
+    pub fn set_direction(&mut self, direction: Vector3f) {
+        self.direction = direction;
+    }
+    /// Shrinks or grows the valid range, typically to the closest hit found
+    /// so far.
+    pub fn set_t_max(&mut self, t_max: Float) {
+        self.t_max = t_max;
+    }
+    pub fn set_medium(&mut self, medium: Option<MediumHandle>) {
+        self.medium = medium;
+    }
+
+    pub fn get(&self, t: Float) -> Point3f {
+        self.origin + self.direction * t
     }
 }
 
@@ -26,14 +76,32 @@ mod tests {
 
     #[test]
     fn test_ray() {
-        let origin = Point3::new(1.0, 2.0, 3.0);
-        let direction = Vector3::new(4.0, 5.0, 6.0);
+        let origin = Point3f::new(1.0, 2.0, 3.0);
+        let direction = Vector3f::new(4.0, 5.0, 6.0);
         let time = 0.5;
         let ray = Ray::new(origin, direction, time);
         let point_at_t = ray.get(2.0);
         println!("Point at t=2.0: {:?}", point_at_t);
-        let expected_point = Point3::new(9.0, 12.0, 15.0);
+        let expected_point = Point3f::new(9.0, 12.0, 15.0);
         // origin + direction * t = (1,2,3) + (4,5,6) * 2 = (1,2,3) + (8,10,12) = (9,12,15)
         assert_eq!(format!("{:?}", point_at_t), format!("{:?}", expected_point));
     }
+
+    #[test]
+    fn defaults_and_setters() {
+        let mut ray = Ray::new(
+            Point3f::new(0.0, 0.0, 0.0),
+            Vector3f::new(1.0, 0.0, 0.0),
+            0.0,
+        );
+        assert_eq!(ray.t_max(), Float::INFINITY);
+        assert!(ray.medium().is_none());
+        ray.set_t_max(2.0);
+        ray.set_medium(Some(MediumHandle::new(3)));
+        assert_eq!(ray.t_max(), 2.0);
+        assert_eq!(ray.medium().map(|m| m.index()), Some(3));
+        let ray = ray.with_t_max(5.0).with_medium(None);
+        assert_eq!(ray.t_max(), 5.0);
+        assert!(ray.medium().is_none());
+    }
 }
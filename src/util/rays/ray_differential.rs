@@ -0,0 +1,139 @@
+use std::ops::{Deref, DerefMut};
+
+use crate::util::Float;
+use crate::util::rays::Ray;
+use crate::util::vector::{Point3f, Vector3f};
+
+/// A ray together with two auxiliary rays offset by one pixel in x and in y
+/// on the film. Where they land on a surface approximates the footprint of
+/// the pixel there, which is what texture lookups filter over.
+#[derive(Debug, Clone, Copy)]
+pub struct RayDifferential {
+    pub ray: Ray,
+    /// False if the offset rays are unknown, e.g. for rays that did not come
+    /// from the camera or after a scattering event that cannot track them.
+    pub has_differentials: bool,
+    pub rx_origin: Point3f,
+    pub ry_origin: Point3f,
+    pub rx_direction: Vector3f,
+    pub ry_direction: Vector3f,
+}
+
+impl RayDifferential {
+    /// A ray with no differential information.
+    pub fn new(ray: Ray) -> Self {
+        Self {
+            ray,
+            has_differentials: false,
+            rx_origin: Point3f::default(),
+            ry_origin: Point3f::default(),
+            rx_direction: Vector3f::default(),
+            ry_direction: Vector3f::default(),
+        }
+    }
+
+    pub fn with_differentials(ray: Ray, rx: &Ray, ry: &Ray) -> Self {
+        Self {
+            ray,
+            has_differentials: true,
+            rx_origin: rx.origin(),
+            ry_origin: ry.origin(),
+            rx_direction: rx.direction(),
+            ry_direction: ry.direction(),
+        }
+    }
+
+    /// The offset ray one pixel over in x.
+    pub fn rx(&self) -> Ray {
+        Ray::new(self.rx_origin, self.rx_direction, self.ray.time())
+    }
+
+    /// The offset ray one pixel over in y.
+    pub fn ry(&self) -> Ray {
+        Ray::new(self.ry_origin, self.ry_direction, self.ray.time())
+    }
+
+    /// Moves the offset rays towards the main ray by the factor `s`, so that
+    /// they describe a footprint `s` times the size of a pixel.
+    pub fn scale_differentials(&mut self, s: Float) {
+        let o = self.ray.origin();
+        let d = self.ray.direction();
+        self.rx_origin = o + (self.rx_origin - o) * s;
+        self.ry_origin = o + (self.ry_origin - o) * s;
+        self.rx_direction = d + (self.rx_direction - d) * s;
+        self.ry_direction = d + (self.ry_direction - d) * s;
+    }
+
+    /// Footprint scale for `samples_per_pixel` camera samples: with more
+    /// samples each one only has to cover part of the pixel, but it never
+    /// shrinks below an eighth to keep some filtering.
+    pub fn spp_scale(samples_per_pixel: u32) -> Float {
+        (1.0 / (samples_per_pixel as Float).sqrt()).max(0.125)
+    }
+}
+
+impl Deref for RayDifferential {
+    type Target = Ray;
+    fn deref(&self) -> &Self::Target {
+        &self.ray
+    }
+}
+
+impl DerefMut for RayDifferential {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.ray
+    }
+}
+
+impl From<Ray> for RayDifferential {
+    fn from(ray: Ray) -> Self {
+        Self::new(ray)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scaling_moves_offsets_toward_main_ray() {
+        let ray = Ray::new(
+            Point3f::new(0.0, 0.0, 0.0),
+            Vector3f::new(0.0, 0.0, 1.0),
+            0.5,
+        );
+        let rx = Ray::new(
+            Point3f::new(1.0, 0.0, 0.0),
+            Vector3f::new(0.2, 0.0, 1.0),
+            0.5,
+        );
+        let ry = Ray::new(
+            Point3f::new(0.0, 2.0, 0.0),
+            Vector3f::new(0.0, 0.4, 1.0),
+            0.5,
+        );
+        let mut rd = RayDifferential::with_differentials(ray, &rx, &ry);
+        rd.scale_differentials(0.5);
+        assert_eq!(rd.rx_origin, Point3f::new(0.5, 0.0, 0.0));
+        assert_eq!(rd.ry_direction, Vector3f::new(0.0, 0.2, 1.0));
+        assert_eq!(rd.rx().time(), 0.5);
+        assert_eq!(rd.direction(), ray.direction());
+    }
+
+    #[test]
+    fn spp_scale_is_clamped() {
+        assert_eq!(RayDifferential::spp_scale(1), 1.0);
+        assert_eq!(RayDifferential::spp_scale(16), 0.25);
+        assert_eq!(RayDifferential::spp_scale(1024), 0.125);
+    }
+
+    #[test]
+    fn plain_ray_has_no_differentials() {
+        let ray = Ray::new(
+            Point3f::new(0.0, 0.0, 0.0),
+            Vector3f::new(1.0, 0.0, 0.0),
+            0.0,
+        );
+        assert!(!RayDifferential::from(ray).has_differentials);
+    }
+}
@@ -0,0 +1,232 @@
+use crate::util::Float;
+use crate::util::bounds::Bounds2f;
+use crate::util::math::{ONE_MINUS_EPSILON, PI};
+use crate::util::vector::{Point2f, Point2i};
+
+/// Maps `u` in `[0, 1)^2` uniformly onto the unit disk with Shirley's
+/// concentric mapping, which keeps strata compact and adjacent samples
+/// adjacent.
+pub fn sample_uniform_disk_concentric(u: Point2f) -> Point2f {
+    let ux = 2.0 * u.x - 1.0;
+    let uy = 2.0 * u.y - 1.0;
+    if ux == 0.0 && uy == 0.0 {
+        return Point2f::default();
+    }
+    let (r, theta) = if ux.abs() > uy.abs() {
+        (ux, PI / 4.0 * (uy / ux))
+    } else {
+        (uy, PI / 2.0 - PI / 4.0 * (ux / uy))
+    };
+    Point2f::new(r * theta.cos(), r * theta.sin())
+}
+
+/// Samples `x` in `[0, 1)` proportionally to the line from `a` at 0 to `b`
+/// at 1.
+pub fn sample_linear(u: Float, a: Float, b: Float) -> Float {
+    if u == 0.0 && a == 0.0 {
+        return 0.0;
+    }
+    let x = u * (a + b) / (a + ((1.0 - u) * a * a + u * b * b).sqrt());
+    x.min(ONE_MINUS_EPSILON)
+}
+
+/// Samples the tent function of radius `r` centered at zero, by picking a
+/// side and sampling the line on it.
+pub fn sample_tent(u: Float, r: Float) -> Float {
+    if u < 0.5 {
+        let u = (u / 0.5).min(ONE_MINUS_EPSILON);
+        -r + r * sample_linear(u, 0.0, 1.0)
+    } else {
+        let u = ((u - 0.5) / 0.5).min(ONE_MINUS_EPSILON);
+        r * sample_linear(u, 1.0, 0.0)
+    }
+}
+
+/// A distribution over `min..max` proportional to a tabulated step
+/// function, sampled by inverting its CDF.
+#[derive(Debug, Clone, Default)]
+pub struct PiecewiseConstant1D {
+    /// Absolute values of the tabulated function.
+    func: Vec<Float>,
+    cdf: Vec<Float>,
+    min: Float,
+    max: Float,
+    func_int: Float,
+}
+
+impl PiecewiseConstant1D {
+    /// The distribution for the steps `f`, which are made positive. An
+    /// all-zero function samples uniformly.
+    pub fn new(f: &[Float], min: Float, max: Float) -> Self {
+        assert!(!f.is_empty(), "empty piecewise-constant function");
+        let n = f.len();
+        let func: Vec<Float> = f.iter().map(|v| v.abs()).collect();
+        let mut cdf = vec![0.0; n + 1];
+        for i in 1..=n {
+            cdf[i] = cdf[i - 1] + func[i - 1] * (max - min) / n as Float;
+        }
+        let func_int = cdf[n];
+        for (i, c) in cdf.iter_mut().enumerate().skip(1) {
+            *c = if func_int == 0.0 {
+                i as Float / n as Float
+            } else {
+                *c / func_int
+            };
+        }
+        Self {
+            func,
+            cdf,
+            min,
+            max,
+            func_int,
+        }
+    }
+
+    /// Integral of the function over its domain.
+    pub fn integral(&self) -> Float {
+        self.func_int
+    }
+
+    pub fn size(&self) -> usize {
+        self.func.len()
+    }
+
+    /// A point distributed like the function, its density, and the index of
+    /// the step it falls in.
+    pub fn sample(&self, u: Float) -> (Float, Float, usize) {
+        // The last entry with `cdf <= u`, skipping empty leading steps.
+        let o = self.cdf.partition_point(|&c| c <= u).clamp(1, self.size()) - 1;
+        let mut du = u - self.cdf[o];
+        if self.cdf[o + 1] - self.cdf[o] > 0.0 {
+            du /= self.cdf[o + 1] - self.cdf[o];
+        }
+        let pdf = if self.func_int > 0.0 {
+            self.func[o] / self.func_int
+        } else {
+            0.0
+        };
+        let t = (o as Float + du) / self.size() as Float;
+        let x = self.min + t * (self.max - self.min);
+        (x, pdf, o)
+    }
+}
+
+/// A distribution over a rectangle proportional to a tabulated step
+/// function, sampled as a marginal distribution in y and conditional ones
+/// in x.
+#[derive(Debug, Clone, Default)]
+pub struct PiecewiseConstant2D {
+    domain: Bounds2f,
+    p_conditional_v: Vec<PiecewiseConstant1D>,
+    p_marginal: PiecewiseConstant1D,
+}
+
+impl PiecewiseConstant2D {
+    /// The distribution for the `nu` x `nv` steps of `func`, stored row by
+    /// row, stretched over `domain`.
+    pub fn new(func: &[Float], nu: usize, nv: usize, domain: Bounds2f) -> Self {
+        assert_eq!(func.len(), nu * nv, "function does not have nu * nv values");
+        let (x0, x1) = (domain.pMin.x, domain.pMax.x);
+        let (y0, y1) = (domain.pMin.y, domain.pMax.y);
+        let p_conditional_v: Vec<_> = func
+            .chunks(nu)
+            .map(|row| PiecewiseConstant1D::new(row, x0, x1))
+            .collect();
+        let marginal: Vec<Float> = p_conditional_v.iter().map(|d| d.integral()).collect();
+        Self {
+            domain,
+            p_conditional_v,
+            p_marginal: PiecewiseConstant1D::new(&marginal, y0, y1),
+        }
+    }
+
+    /// Integral of the function over the domain.
+    pub fn integral(&self) -> Float {
+        self.p_marginal.integral()
+    }
+
+    /// A point distributed like the function, its density, and the step it
+    /// falls in.
+    pub fn sample(&self, u: Point2f) -> (Point2f, Float, Point2i) {
+        let (y, pdf_y, v) = self.p_marginal.sample(u.y);
+        let (x, pdf_x, u_index) = self.p_conditional_v[v].sample(u.x);
+        (
+            Point2f::new(x, y),
+            pdf_x * pdf_y,
+            Point2i::new(u_index as i32, v as i32),
+        )
+    }
+
+    /// Density of sampling `p`, which must be inside the domain.
+    pub fn pdf(&self, p: Point2f) -> Float {
+        let o = self.domain.offset(&p);
+        let nu = self.p_conditional_v[0].size();
+        let nv = self.p_marginal.size();
+        let iu = ((o.x * nu as Float) as usize).min(nu - 1);
+        let iv = ((o.y * nv as Float) as usize).min(nv - 1);
+        let integral = self.integral();
+        if integral == 0.0 {
+            return 0.0;
+        }
+        self.p_conditional_v[iv].func[iu] / integral
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn concentric_disk_covers_the_disk() {
+        assert_eq!(
+            sample_uniform_disk_concentric(Point2f::new(0.5, 0.5)),
+            Point2f::default()
+        );
+        let edge = sample_uniform_disk_concentric(Point2f::new(1.0, 0.5));
+        assert!((edge.x - 1.0).abs() < 1e-6 && edge.y.abs() < 1e-6);
+        // Equal areas of the square map to equal areas of the disk, so a
+        // quarter of a stratified grid lands inside radius 1/2.
+        let n = 64;
+        let inside = (0..n * n)
+            .filter(|i| {
+                let u = Point2f::new(
+                    ((i % n) as Float + 0.5) / n as Float,
+                    ((i / n) as Float + 0.5) / n as Float,
+                );
+                let p = sample_uniform_disk_concentric(u);
+                assert!(p.x * p.x + p.y * p.y <= 1.0 + 1e-5);
+                p.x * p.x + p.y * p.y < 0.25
+            })
+            .count();
+        assert!((inside as Float / (n * n) as Float - 0.25).abs() < 0.01);
+    }
+
+    #[test]
+    fn tent_samples_stay_in_radius_and_favor_the_center() {
+        assert_eq!(sample_tent(0.5, 2.0), 0.0);
+        assert!((sample_tent(0.0, 2.0) + 2.0).abs() < 1e-6);
+        assert!(sample_tent(ONE_MINUS_EPSILON, 2.0) < 2.0);
+        // A quarter of the tent's area lies beyond half its radius.
+        assert!((sample_tent(0.125, 2.0) + 1.0).abs() < 1e-5);
+        assert!((sample_linear(0.5, 1.0, 1.0) - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn piecewise_constant_samples_follow_the_function() {
+        let d = PiecewiseConstant1D::new(&[0.0, 1.0, 3.0, 0.0], 0.0, 2.0);
+        assert_eq!(d.integral(), 2.0);
+        let (x, pdf, o) = d.sample(0.5);
+        assert_eq!((o, pdf), (2, 1.5));
+        assert!((x - 1.0 - 1.0 / 6.0).abs() < 1e-6);
+        assert_eq!(d.sample(0.0).2, 1);
+        assert_eq!(d.sample(ONE_MINUS_EPSILON).2, 2);
+
+        let domain = Bounds2f::from_points(Point2f::new(-1.0, -1.0), Point2f::new(1.0, 1.0));
+        let d2 = PiecewiseConstant2D::new(&[1.0, 1.0, 0.0, 2.0], 2, 2, domain);
+        assert_eq!(d2.integral(), 4.0);
+        let (p, pdf, offset) = d2.sample(Point2f::new(0.25, 0.75));
+        assert_eq!(offset, Point2i::new(1, 1));
+        assert!(p.x > 0.0 && p.y > 0.0);
+        assert_eq!((pdf, d2.pdf(p)), (0.5, 0.5));
+    }
+}
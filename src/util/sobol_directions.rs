@@ -0,0 +1,1036 @@
+//! Initial direction numbers of the Sobol dimensions after the first.
+
+/// Initial direction numbers `m_1..m_s` of each Sobol dimension after the
+/// first, where `s` is the degree of its primitive polynomial, in the order
+/// `sobol_matrices` takes the polynomials.
+///
+/// The first 12 are from Joe and Kuo's table. The rest were searched for
+/// offline with a criterion like theirs: each dimension in turn took, out
+/// of 64 random candidates, the one whose two-dimensional projections with
+/// all earlier dimensions have the smallest total t-value over the nets of
+/// the first `2^1` to `2^12` points.
+pub(crate) const SOBOL_INITIAL_DIRECTIONS: &[&[u64]] = &[
+    &[1],
+    &[1, 3],
+    &[1, 3, 1],
+    &[1, 1, 1],
+    &[1, 1, 3, 3],
+    &[1, 3, 5, 13],
+    &[1, 1, 5, 5, 17],
+    &[1, 1, 5, 5, 5],
+    &[1, 1, 7, 11, 19],
+    &[1, 1, 5, 1, 1],
+    &[1, 1, 1, 3, 11],
+    &[1, 3, 5, 5, 31],
+    &[1, 1, 1, 9, 13, 27],
+    &[1, 1, 1, 9, 1, 37],
+    &[1, 3, 3, 5, 13, 43],
+    &[1, 3, 7, 15, 21, 57],
+    &[1, 1, 7, 9, 1, 29],
+    &[1, 3, 3, 5, 19, 13],
+    &[1, 3, 7, 5, 11, 25, 113],
+    &[1, 3, 3, 5, 11, 45, 125],
+    &[1, 3, 1, 11, 29, 17, 5],
+    &[1, 3, 1, 11, 25, 1, 107],
+    &[1, 1, 3, 7, 3, 49, 115],
+    &[1, 1, 7, 15, 1, 7, 9],
+    &[1, 1, 7, 1, 23, 13, 119],
+    &[1, 1, 1, 5, 27, 61, 51],
+    &[1, 3, 5, 5, 27, 41, 59],
+    &[1, 1, 5, 3, 13, 63, 83],
+    &[1, 3, 7, 5, 11, 15, 99],
+    &[1, 1, 5, 5, 1, 23, 23],
+    &[1, 3, 7, 1, 13, 63, 17],
+    &[1, 3, 7, 9, 27, 49, 123],
+    &[1, 3, 3, 15, 9, 29, 85],
+    &[1, 3, 3, 1, 17, 33, 91],
+    &[1, 1, 1, 11, 1, 63, 113],
+    &[1, 3, 7, 15, 11, 33, 75],
+    &[1, 3, 3, 13, 31, 21, 101, 201],
+    &[1, 3, 1, 13, 17, 59, 121, 211],
+    &[1, 1, 7, 13, 19, 11, 97, 165],
+    &[1, 1, 5, 7, 29, 31, 67, 197],
+    &[1, 3, 1, 15, 9, 17, 85, 143],
+    &[1, 1, 1, 13, 17, 17, 75, 173],
+    &[1, 3, 7, 11, 17, 29, 95, 41],
+    &[1, 1, 7, 11, 23, 29, 13, 229],
+    &[1, 1, 3, 7, 23, 27, 101, 121],
+    &[1, 1, 1, 7, 11, 33, 91, 9],
+    &[1, 1, 7, 11, 3, 19, 119, 155],
+    &[1, 1, 5, 7, 7, 5, 59, 249],
+    &[1, 3, 5, 5, 3, 9, 109, 171],
+    &[1, 3, 1, 15, 25, 37, 103, 35],
+    &[1, 3, 7, 13, 29, 57, 9, 73],
+    &[1, 3, 5, 3, 23, 19, 45, 91],
+    &[1, 1, 7, 1, 13, 11, 127, 21, 217],
+    &[1, 1, 5, 3, 9, 61, 61, 197, 277],
+    &[1, 3, 1, 3, 7, 15, 7, 245, 227],
+    &[1, 1, 7, 3, 25, 37, 23, 55, 327],
+    &[1, 1, 7, 11, 3, 3, 7, 25, 357],
+    &[1, 1, 1, 15, 25, 59, 41, 169, 463],
+    &[1, 3, 5, 3, 21, 25, 59, 57, 173],
+    &[1, 1, 5, 5, 7, 31, 117, 23, 361],
+    &[1, 1, 3, 13, 7, 21, 99, 139, 353],
+    &[1, 3, 1, 1, 25, 39, 71, 23, 297],
+    &[1, 3, 5, 11, 17, 13, 43, 215, 153],
+    &[1, 3, 5, 7, 3, 39, 11, 245, 183],
+    &[1, 1, 1, 1, 7, 13, 103, 153, 323],
+    &[1, 3, 7, 15, 23, 37, 119, 161, 509],
+    &[1, 1, 5, 13, 19, 9, 119, 19, 329],
+    &[1, 1, 3, 9, 9, 27, 99, 21, 435],
+    &[1, 1, 1, 7, 9, 13, 15, 23, 335],
+    &[1, 3, 7, 5, 19, 33, 67, 159, 237],
+    &[1, 3, 1, 13, 31, 9, 1, 179, 75],
+    &[1, 1, 3, 5, 25, 53, 33, 129, 481],
+    &[1, 1, 3, 5, 25, 3, 127, 141, 99],
+    &[1, 3, 7, 11, 9, 5, 101, 197, 503],
+    &[1, 3, 7, 3, 13, 49, 57, 135, 297],
+    &[1, 3, 3, 5, 25, 27, 57, 61, 75],
+    &[1, 3, 1, 7, 3, 55, 27, 235, 267],
+    &[1, 1, 1, 9, 9, 59, 17, 75, 233],
+    &[1, 3, 3, 1, 25, 19, 77, 147, 45],
+    &[1, 3, 3, 7, 9, 27, 63, 23, 129],
+    &[1, 1, 1, 9, 5, 49, 31, 145, 483],
+    &[1, 3, 3, 5, 7, 7, 17, 101, 305],
+    &[1, 1, 3, 3, 29, 13, 77, 147, 203],
+    &[1, 1, 7, 15, 25, 39, 33, 13, 421],
+    &[1, 3, 5, 1, 25, 19, 43, 179, 361],
+    &[1, 3, 5, 13, 13, 7, 63, 85, 71],
+    &[1, 3, 5, 15, 11, 7, 107, 137, 227],
+    &[1, 1, 1, 9, 5, 7, 83, 85, 163],
+    &[1, 1, 3, 1, 13, 17, 1, 65, 261],
+    &[1, 1, 7, 13, 17, 11, 39, 13, 473],
+    &[1, 3, 3, 9, 5, 39, 41, 39, 485],
+    &[1, 1, 3, 9, 23, 45, 61, 91, 301],
+    &[1, 3, 7, 1, 1, 53, 87, 183, 405],
+    &[1, 3, 3, 13, 29, 7, 21, 191, 147],
+    &[1, 1, 5, 13, 15, 21, 31, 199, 171],
+    &[1, 3, 1, 7, 23, 63, 17, 123, 393],
+    &[1, 3, 1, 7, 21, 63, 61, 43, 199],
+    &[1, 3, 3, 7, 17, 3, 47, 195, 323],
+    &[1, 3, 1, 5, 29, 31, 107, 235, 317],
+    &[1, 3, 5, 3, 31, 25, 117, 105, 379],
+    &[1, 1, 5, 13, 27, 37, 79, 193, 109, 739],
+    &[1, 3, 5, 15, 11, 53, 17, 131, 209, 187],
+    &[1, 3, 5, 9, 31, 49, 111, 169, 93, 25],
+    &[1, 1, 3, 7, 31, 33, 125, 141, 195, 949],
+    &[1, 1, 1, 3, 1, 41, 83, 161, 45, 825],
+    &[1, 3, 3, 3, 11, 27, 65, 45, 441, 357],
+    &[1, 3, 7, 1, 31, 9, 87, 253, 333, 713],
+    &[1, 1, 1, 5, 11, 39, 87, 23, 485, 521],
+    &[1, 3, 7, 7, 9, 27, 95, 229, 205, 451],
+    &[1, 1, 7, 9, 11, 29, 19, 31, 271, 743],
+    &[1, 3, 5, 9, 23, 59, 31, 195, 201, 359],
+    &[1, 3, 5, 1, 11, 63, 59, 29, 441, 389],
+    &[1, 1, 3, 9, 11, 51, 19, 119, 191, 735],
+    &[1, 3, 3, 9, 7, 55, 73, 23, 375, 125],
+    &[1, 3, 7, 9, 29, 23, 93, 101, 407, 13],
+    &[1, 1, 1, 9, 5, 25, 119, 101, 227, 523],
+    &[1, 3, 3, 3, 23, 7, 45, 197, 325, 107],
+    &[1, 3, 1, 13, 29, 41, 119, 9, 73, 55],
+    &[1, 1, 5, 7, 13, 53, 99, 61, 221, 835],
+    &[1, 1, 3, 5, 25, 47, 117, 213, 301, 363],
+    &[1, 1, 7, 11, 21, 29, 125, 155, 311, 383],
+    &[1, 1, 7, 13, 31, 49, 73, 9, 155, 729],
+    &[1, 1, 7, 7, 9, 63, 117, 23, 391, 731],
+    &[1, 3, 5, 1, 7, 57, 73, 69, 135, 135],
+    &[1, 3, 1, 11, 7, 7, 41, 187, 87, 335],
+    &[1, 3, 7, 9, 9, 33, 99, 85, 483, 67],
+    &[1, 3, 1, 5, 19, 29, 81, 225, 407, 175],
+    &[1, 1, 5, 3, 3, 23, 37, 197, 69, 351],
+    &[1, 1, 5, 11, 17, 57, 117, 45, 469, 519],
+    &[1, 1, 1, 5, 21, 5, 35, 183, 493, 783],
+    &[1, 3, 7, 7, 21, 17, 61, 243, 335, 941],
+    &[1, 3, 5, 7, 15, 15, 81, 111, 429, 741],
+    &[1, 3, 7, 3, 17, 7, 7, 193, 89, 969],
+    &[1, 3, 1, 9, 7, 23, 55, 15, 423, 859],
+    &[1, 3, 1, 11, 15, 33, 21, 197, 305, 113],
+    &[1, 1, 1, 11, 25, 33, 7, 89, 489, 313],
+    &[1, 3, 5, 13, 5, 61, 37, 225, 151, 457],
+    &[1, 1, 7, 13, 1, 63, 65, 81, 169, 95],
+    &[1, 1, 5, 7, 31, 37, 51, 193, 405, 913],
+    &[1, 1, 1, 11, 11, 23, 29, 131, 65, 151],
+    &[1, 1, 1, 5, 19, 9, 79, 173, 125, 821],
+    &[1, 1, 7, 13, 13, 21, 75, 127, 255, 623],
+    &[1, 1, 7, 11, 1, 43, 105, 209, 5, 949],
+    &[1, 1, 5, 5, 7, 5, 43, 57, 291, 731],
+    &[1, 1, 3, 9, 17, 35, 61, 173, 499, 315],
+    &[1, 1, 1, 7, 15, 25, 57, 91, 183, 793],
+    &[1, 3, 5, 1, 31, 25, 111, 113, 341, 21],
+    &[1, 1, 3, 3, 1, 57, 9, 129, 153, 485],
+    &[1, 3, 3, 9, 5, 37, 63, 87, 299, 795],
+    &[1, 3, 5, 13, 15, 57, 9, 213, 187, 347],
+    &[1, 1, 1, 9, 3, 55, 41, 195, 503, 289],
+    &[1, 1, 7, 11, 5, 17, 15, 23, 85, 317],
+    &[1, 3, 3, 11, 27, 39, 105, 107, 325, 333],
+    &[1, 3, 3, 1, 7, 9, 79, 205, 93, 245],
+    &[1, 3, 3, 1, 15, 27, 71, 39, 491, 493],
+    &[1, 1, 5, 7, 17, 3, 127, 99, 387, 543],
+    &[1, 3, 5, 11, 29, 35, 17, 165, 85, 141],
+    &[1, 1, 3, 1, 21, 45, 73, 43, 79, 621],
+    &[1, 3, 3, 15, 3, 59, 113, 185, 169, 391],
+    &[1, 3, 7, 11, 25, 57, 51, 113, 303, 921],
+    &[1, 1, 1, 5, 21, 23, 29, 117, 139, 651, 177],
+    &[1, 3, 3, 7, 5, 15, 43, 209, 253, 987, 879],
+    &[1, 3, 3, 5, 19, 31, 61, 133, 143, 329, 331],
+    &[1, 1, 5, 5, 5, 13, 93, 69, 343, 453, 869],
+    &[1, 3, 1, 11, 13, 61, 103, 235, 129, 91, 307],
+    &[1, 1, 5, 5, 25, 5, 101, 99, 215, 89, 679],
+    &[1, 1, 1, 13, 25, 29, 51, 163, 101, 505, 1267],
+    &[1, 1, 5, 7, 17, 17, 49, 181, 161, 739, 997],
+    &[1, 3, 3, 9, 13, 31, 123, 245, 329, 215, 1711],
+    &[1, 1, 7, 15, 19, 31, 19, 73, 475, 203, 1957],
+    &[1, 1, 5, 1, 25, 41, 33, 171, 3, 777, 1287],
+    &[1, 3, 1, 11, 5, 19, 57, 207, 61, 855, 395],
+    &[1, 3, 3, 7, 21, 41, 95, 119, 309, 325, 239],
+    &[1, 3, 5, 5, 15, 7, 73, 249, 85, 181, 1449],
+    &[1, 3, 5, 9, 21, 19, 123, 175, 273, 713, 1215],
+    &[1, 1, 7, 3, 31, 41, 77, 21, 211, 281, 1855],
+    &[1, 1, 7, 1, 3, 49, 3, 43, 465, 347, 1477],
+    &[1, 3, 1, 1, 15, 33, 53, 201, 197, 735, 1311],
+    &[1, 3, 7, 7, 7, 23, 17, 39, 499, 599, 405],
+    &[1, 1, 1, 3, 11, 37, 69, 59, 479, 453, 151],
+    &[1, 3, 3, 15, 13, 7, 93, 139, 511, 407, 1181],
+    &[1, 3, 7, 15, 29, 23, 45, 11, 509, 991, 1937],
+    &[1, 1, 7, 13, 3, 25, 23, 85, 305, 353, 1835],
+    &[1, 1, 1, 3, 7, 21, 15, 43, 23, 397, 551],
+    &[1, 3, 5, 1, 29, 31, 71, 17, 113, 691, 1665],
+    &[1, 3, 3, 1, 21, 57, 63, 189, 241, 649, 867],
+    &[1, 3, 5, 15, 5, 25, 89, 251, 41, 243, 1283],
+    &[1, 1, 5, 9, 11, 3, 127, 135, 425, 619, 719],
+    &[1, 3, 7, 7, 7, 35, 59, 45, 477, 497, 907],
+    &[1, 3, 1, 5, 19, 59, 53, 119, 207, 805, 217],
+    &[1, 3, 5, 11, 3, 5, 9, 87, 413, 291, 1835],
+    &[1, 3, 3, 5, 23, 29, 85, 159, 379, 817, 1595],
+    &[1, 3, 7, 11, 11, 35, 47, 243, 485, 347, 1463],
+    &[1, 3, 7, 5, 11, 33, 37, 43, 309, 753, 909],
+    &[1, 3, 5, 7, 9, 61, 105, 165, 95, 513, 1183],
+    &[1, 1, 3, 1, 11, 27, 29, 41, 253, 333, 549],
+    &[1, 1, 3, 3, 9, 23, 101, 83, 375, 183, 1323],
+    &[1, 3, 7, 15, 11, 63, 65, 169, 37, 501, 61],
+    &[1, 1, 1, 13, 23, 53, 21, 31, 175, 9, 25],
+    &[1, 1, 7, 5, 27, 49, 49, 145, 401, 457, 1917],
+    &[1, 1, 7, 15, 29, 5, 77, 15, 407, 891, 553],
+    &[1, 1, 1, 9, 5, 51, 15, 175, 163, 825, 1023],
+    &[1, 1, 5, 5, 7, 27, 3, 233, 265, 55, 911],
+    &[1, 1, 7, 15, 15, 47, 79, 189, 211, 139, 1993],
+    &[1, 1, 1, 7, 13, 21, 119, 217, 431, 515, 683],
+    &[1, 3, 1, 7, 23, 55, 53, 25, 195, 341, 1577],
+    &[1, 1, 3, 15, 17, 13, 123, 231, 77, 575, 729],
+    &[1, 3, 3, 9, 25, 7, 19, 135, 453, 529, 915],
+    &[1, 3, 5, 1, 29, 51, 47, 107, 419, 595, 521],
+    &[1, 1, 1, 1, 5, 37, 77, 41, 353, 977, 37],
+    &[1, 3, 7, 11, 11, 35, 109, 249, 361, 205, 649],
+    &[1, 3, 5, 3, 23, 3, 73, 93, 55, 793, 839],
+    &[1, 3, 5, 11, 17, 13, 41, 99, 323, 717, 899],
+    &[1, 1, 1, 1, 7, 47, 87, 7, 287, 167, 1091],
+    &[1, 1, 1, 13, 17, 47, 103, 237, 277, 739, 803],
+    &[1, 3, 7, 9, 17, 31, 5, 71, 155, 479, 1551],
+    &[1, 1, 3, 15, 11, 13, 85, 145, 343, 999, 557],
+    &[1, 1, 3, 1, 3, 7, 45, 119, 127, 409, 577],
+    &[1, 1, 7, 11, 11, 33, 91, 85, 247, 1, 949],
+    &[1, 3, 3, 5, 17, 13, 47, 225, 381, 259, 1887],
+    &[1, 3, 3, 9, 1, 59, 37, 33, 433, 219, 1539],
+    &[1, 1, 3, 3, 23, 55, 81, 85, 313, 949, 869],
+    &[1, 1, 5, 7, 17, 7, 113, 121, 309, 623, 1377],
+    &[1, 1, 5, 3, 21, 23, 9, 167, 101, 247, 911],
+    &[1, 3, 5, 15, 25, 43, 61, 139, 29, 787, 499],
+    &[1, 3, 1, 11, 3, 39, 49, 171, 251, 457, 839],
+    &[1, 3, 5, 3, 15, 49, 7, 201, 93, 665, 1615],
+    &[1, 3, 1, 11, 3, 31, 59, 115, 341, 343, 511],
+    &[1, 1, 7, 9, 1, 13, 105, 123, 229, 719, 855],
+    &[1, 3, 1, 11, 29, 1, 91, 1, 257, 309, 1865],
+    &[1, 1, 3, 11, 31, 1, 37, 25, 23, 871, 101],
+    &[1, 1, 1, 7, 17, 3, 35, 147, 261, 877, 1947],
+    &[1, 1, 5, 7, 25, 59, 79, 171, 389, 489, 1453],
+    &[1, 3, 5, 3, 13, 17, 115, 29, 85, 941, 909],
+    &[1, 3, 3, 3, 23, 19, 3, 111, 281, 805, 449],
+    &[1, 1, 3, 11, 29, 11, 39, 155, 47, 119, 101],
+    &[1, 3, 7, 15, 11, 33, 105, 181, 129, 29, 333],
+    &[1, 1, 1, 1, 17, 47, 63, 227, 189, 231, 295],
+    &[1, 1, 3, 7, 25, 19, 115, 85, 477, 337, 673],
+    &[1, 1, 3, 7, 13, 51, 11, 49, 215, 831, 909],
+    &[1, 1, 1, 13, 21, 35, 27, 191, 359, 735, 261],
+    &[1, 3, 7, 3, 15, 31, 5, 15, 247, 39, 1763],
+    &[1, 1, 1, 1, 5, 29, 59, 93, 3, 563, 1059],
+    &[1, 3, 1, 15, 29, 53, 83, 169, 9, 371, 1005],
+    &[1, 3, 3, 7, 15, 7, 27, 101, 403, 511, 1933],
+    &[1, 1, 1, 9, 13, 9, 81, 13, 499, 971, 627],
+    &[1, 1, 3, 3, 15, 37, 47, 43, 199, 193, 1905],
+    &[1, 3, 3, 9, 5, 61, 33, 81, 229, 939, 647],
+    &[1, 1, 3, 5, 31, 35, 113, 245, 473, 279, 837],
+    &[1, 1, 1, 9, 11, 55, 21, 3, 13, 391, 1895],
+    &[1, 1, 7, 9, 3, 39, 55, 191, 217, 893, 1129],
+    &[1, 3, 1, 13, 13, 31, 103, 181, 215, 749, 2027],
+    &[1, 1, 3, 13, 15, 17, 1, 251, 15, 53, 1185],
+    &[1, 3, 5, 3, 25, 43, 93, 203, 161, 5, 751],
+    &[1, 3, 5, 1, 29, 51, 67, 69, 365, 791, 1079],
+    &[1, 1, 7, 9, 7, 27, 113, 253, 489, 215, 1449],
+    &[1, 1, 7, 11, 13, 55, 95, 13, 385, 899, 1969],
+    &[1, 1, 1, 11, 19, 23, 81, 203, 199, 647, 1143],
+    &[1, 1, 7, 11, 15, 17, 83, 53, 1, 495, 317],
+    &[1, 3, 1, 9, 1, 63, 13, 165, 383, 849, 1809],
+    &[1, 1, 5, 11, 21, 39, 77, 37, 487, 285, 147],
+    &[1, 1, 1, 9, 17, 41, 41, 249, 325, 991, 1367],
+    &[1, 1, 5, 5, 7, 29, 107, 73, 69, 1019, 273],
+    &[1, 3, 7, 3, 5, 3, 35, 241, 229, 371, 1943],
+    &[1, 1, 5, 5, 29, 27, 11, 7, 439, 409, 1521],
+    &[1, 3, 5, 15, 5, 27, 69, 167, 221, 409, 593],
+    &[1, 3, 3, 1, 17, 43, 67, 255, 325, 313, 1621],
+    &[1, 3, 7, 15, 13, 39, 29, 157, 89, 843, 829],
+    &[1, 1, 7, 13, 5, 21, 57, 217, 239, 575, 5],
+    &[1, 1, 5, 1, 1, 29, 119, 223, 179, 397, 1629],
+    &[1, 3, 5, 7, 15, 59, 113, 155, 489, 807, 1489],
+    &[1, 3, 5, 13, 27, 15, 15, 95, 1, 495, 1155],
+    &[1, 3, 1, 7, 29, 9, 101, 169, 499, 643, 1773],
+    &[1, 3, 1, 13, 29, 57, 75, 73, 131, 595, 1617],
+    &[1, 3, 3, 9, 11, 11, 31, 245, 247, 69, 1833],
+    &[1, 3, 7, 15, 29, 1, 21, 191, 175, 163, 1207],
+    &[1, 1, 3, 11, 17, 19, 83, 143, 253, 329, 1075],
+    &[1, 1, 5, 1, 3, 53, 95, 73, 85, 629, 119],
+    &[1, 3, 1, 1, 15, 17, 89, 71, 99, 569, 1837],
+    &[1, 3, 1, 5, 19, 1, 37, 189, 9, 509, 1095],
+    &[1, 3, 7, 9, 15, 9, 105, 213, 429, 853, 1379],
+    &[1, 3, 3, 5, 9, 57, 61, 69, 237, 275, 1653],
+    &[1, 3, 3, 7, 5, 19, 59, 111, 349, 583, 1811],
+    &[1, 1, 7, 9, 11, 7, 39, 21, 149, 349, 1763],
+    &[1, 1, 1, 5, 25, 59, 45, 243, 261, 689, 1637],
+    &[1, 1, 5, 5, 21, 9, 115, 117, 359, 337, 1573],
+    &[1, 1, 1, 1, 1, 1, 5, 19, 263, 27, 565],
+    &[1, 1, 3, 9, 19, 35, 3, 153, 55, 635, 315],
+    &[1, 3, 5, 13, 5, 61, 75, 247, 257, 873, 1205],
+    &[1, 1, 7, 13, 21, 3, 103, 5, 459, 1007, 1207],
+    &[1, 1, 1, 11, 7, 1, 87, 55, 379, 853, 705],
+    &[1, 1, 7, 13, 21, 49, 123, 207, 323, 465, 1391],
+    &[1, 1, 5, 5, 19, 39, 119, 159, 217, 667, 493],
+    &[1, 1, 7, 11, 13, 19, 11, 63, 439, 893, 1113],
+    &[1, 3, 7, 5, 27, 11, 19, 201, 315, 363, 1725],
+    &[1, 1, 5, 5, 5, 11, 23, 35, 465, 841, 1181],
+    &[1, 3, 1, 3, 11, 37, 1, 125, 491, 343, 1451],
+    &[1, 1, 3, 5, 11, 27, 123, 59, 7, 837, 1003],
+    &[1, 3, 5, 3, 25, 31, 85, 177, 225, 203, 1609],
+    &[1, 1, 5, 15, 31, 45, 27, 53, 473, 707, 1833],
+    &[1, 3, 7, 7, 5, 29, 99, 141, 351, 187, 1837],
+    &[1, 3, 1, 11, 5, 11, 103, 165, 325, 507, 1129],
+    &[1, 1, 3, 7, 21, 31, 61, 71, 255, 885, 1193],
+    &[1, 3, 7, 1, 11, 57, 51, 209, 447, 303, 1011],
+    &[1, 3, 5, 1, 21, 25, 35, 183, 481, 881, 531],
+    &[1, 3, 5, 3, 3, 61, 75, 255, 371, 65, 897],
+    &[1, 1, 3, 13, 19, 63, 15, 179, 143, 469, 1073],
+    &[1, 1, 7, 9, 11, 19, 35, 29, 445, 865, 969],
+    &[1, 3, 1, 5, 31, 27, 105, 145, 117, 441, 1639],
+    &[1, 1, 5, 5, 29, 25, 81, 195, 107, 511, 747],
+    &[1, 3, 1, 11, 1, 31, 3, 37, 181, 329, 1231],
+    &[1, 1, 1, 1, 7, 11, 21, 37, 273, 927, 1531],
+    &[1, 1, 1, 13, 17, 21, 111, 103, 5, 723, 803],
+    &[1, 1, 5, 1, 21, 33, 109, 39, 119, 241, 1909],
+    &[1, 1, 7, 13, 21, 31, 71, 57, 183, 903, 85],
+    &[1, 1, 5, 7, 17, 7, 91, 239, 345, 141, 1431],
+    &[1, 1, 1, 11, 11, 11, 71, 141, 207, 319, 2021],
+    &[1, 3, 5, 1, 31, 31, 91, 41, 61, 327, 749],
+    &[1, 1, 5, 5, 29, 39, 7, 49, 469, 197, 841],
+    &[1, 3, 5, 9, 25, 7, 39, 147, 69, 163, 1051],
+    &[1, 1, 7, 15, 9, 37, 75, 225, 7, 199, 1941],
+    &[1, 3, 7, 13, 15, 9, 93, 133, 437, 595, 991],
+    &[1, 3, 3, 3, 19, 63, 125, 217, 3, 653, 455],
+    &[1, 1, 1, 13, 17, 41, 45, 9, 351, 611, 1333],
+    &[1, 3, 5, 15, 15, 61, 3, 249, 269, 931, 559],
+    &[1, 3, 1, 1, 3, 21, 13, 215, 277, 23, 275],
+    &[1, 3, 7, 5, 1, 17, 5, 87, 493, 945, 1837],
+    &[1, 1, 1, 11, 1, 43, 125, 221, 101, 571, 1007],
+    &[1, 1, 3, 7, 25, 49, 31, 41, 117, 841, 1999],
+    &[1, 3, 1, 13, 29, 37, 41, 73, 59, 895, 637],
+    &[1, 3, 1, 9, 5, 23, 1, 81, 103, 89, 1841],
+    &[1, 1, 1, 7, 9, 9, 35, 119, 355, 91, 1673],
+    &[1, 3, 1, 5, 31, 23, 113, 247, 495, 317, 165],
+    &[1, 1, 1, 9, 29, 25, 47, 75, 295, 29, 1225],
+    &[1, 3, 1, 13, 3, 41, 11, 103, 303, 785, 1459],
+    &[1, 3, 5, 13, 13, 33, 97, 191, 411, 165, 775],
+    &[1, 1, 1, 11, 9, 9, 85, 13, 9, 931, 651, 1361],
+    &[1, 3, 3, 7, 19, 1, 79, 109, 355, 481, 985, 553],
+    &[1, 3, 7, 15, 31, 33, 107, 229, 461, 251, 759, 2439],
+    &[1, 1, 1, 5, 19, 51, 121, 57, 171, 283, 1997, 2395],
+    &[1, 3, 3, 7, 3, 63, 115, 143, 275, 367, 1909, 2545],
+    &[1, 1, 7, 15, 19, 61, 87, 233, 35, 871, 1441, 3599],
+    &[1, 1, 3, 7, 21, 9, 91, 91, 17, 131, 1709, 269],
+    &[1, 1, 1, 15, 19, 35, 7, 231, 11, 173, 1589, 2527],
+    &[1, 3, 5, 13, 27, 35, 89, 113, 293, 761, 207, 1105],
+    &[1, 3, 5, 1, 27, 25, 15, 145, 441, 661, 829, 2525],
+    &[1, 1, 5, 11, 9, 17, 103, 225, 169, 523, 1579, 743],
+    &[1, 1, 7, 15, 29, 29, 59, 39, 261, 265, 1877, 1447],
+    &[1, 1, 5, 1, 5, 21, 89, 233, 505, 911, 221, 1465],
+    &[1, 1, 7, 9, 5, 45, 67, 177, 441, 959, 855, 673],
+    &[1, 3, 5, 15, 5, 37, 85, 127, 63, 205, 1103, 1255],
+    &[1, 3, 5, 3, 29, 41, 105, 67, 269, 793, 899, 845],
+    &[1, 3, 5, 9, 25, 35, 77, 115, 351, 75, 1503, 2435],
+    &[1, 3, 5, 15, 9, 49, 97, 239, 483, 109, 1619, 1059],
+    &[1, 1, 7, 13, 19, 3, 35, 145, 155, 551, 1017, 2463],
+    &[1, 3, 5, 15, 19, 53, 113, 171, 283, 323, 149, 3797],
+    &[1, 3, 1, 11, 31, 19, 47, 249, 171, 693, 651, 41],
+    &[1, 1, 1, 5, 25, 37, 89, 35, 143, 375, 953, 1887],
+    &[1, 1, 1, 7, 31, 23, 35, 5, 433, 791, 1007, 2695],
+    &[1, 1, 1, 11, 3, 45, 113, 95, 381, 675, 935, 1813],
+    &[1, 3, 1, 5, 23, 19, 73, 255, 413, 187, 381, 3239],
+    &[1, 3, 3, 7, 15, 13, 41, 255, 211, 209, 1319, 1805],
+    &[1, 1, 1, 11, 15, 21, 59, 195, 293, 843, 587, 1035],
+    &[1, 1, 5, 7, 15, 11, 29, 213, 197, 495, 787, 561],
+    &[1, 3, 7, 3, 1, 39, 9, 39, 297, 147, 433, 2921],
+    &[1, 3, 3, 7, 7, 53, 33, 215, 413, 59, 909, 1773],
+    &[1, 1, 7, 15, 17, 55, 115, 211, 159, 309, 1693, 1915],
+    &[1, 3, 1, 5, 17, 33, 127, 233, 159, 947, 1101, 1559],
+    &[1, 1, 3, 5, 31, 53, 11, 195, 309, 293, 1119, 3497],
+    &[1, 3, 5, 15, 1, 15, 69, 67, 165, 319, 401, 2175],
+    &[1, 1, 3, 3, 5, 57, 41, 235, 339, 9, 1689, 1765],
+    &[1, 3, 7, 13, 15, 43, 43, 223, 37, 181, 639, 371],
+    &[1, 1, 5, 1, 13, 51, 49, 229, 497, 639, 387, 2653],
+    &[1, 1, 5, 1, 11, 41, 87, 125, 197, 837, 15, 4039],
+    &[1, 1, 5, 1, 13, 13, 109, 17, 493, 667, 331, 1963],
+    &[1, 3, 5, 5, 13, 3, 107, 31, 173, 651, 747, 2785],
+    &[1, 1, 1, 5, 15, 19, 59, 3, 37, 27, 1543, 3813],
+    &[1, 1, 5, 7, 25, 23, 77, 155, 203, 1003, 1297, 2655],
+    &[1, 3, 7, 3, 1, 33, 49, 7, 103, 773, 1589, 3019],
+    &[1, 3, 1, 11, 5, 53, 57, 197, 269, 917, 497, 1585],
+    &[1, 1, 1, 13, 19, 31, 115, 237, 437, 705, 1691, 551],
+    &[1, 3, 5, 3, 31, 39, 27, 31, 331, 371, 1211, 2623],
+    &[1, 3, 3, 11, 29, 41, 123, 247, 281, 853, 1913, 3335],
+    &[1, 1, 7, 9, 5, 19, 57, 45, 69, 725, 223, 999],
+    &[1, 3, 3, 5, 17, 13, 121, 249, 271, 759, 1835, 191],
+    &[1, 3, 5, 9, 27, 23, 95, 11, 165, 907, 161, 1039],
+    &[1, 3, 1, 13, 17, 11, 107, 93, 415, 1013, 1869, 679],
+    &[1, 1, 5, 11, 23, 47, 73, 79, 125, 257, 1279, 2313],
+    &[1, 1, 5, 1, 13, 61, 103, 155, 127, 497, 1267, 1165],
+    &[1, 3, 7, 1, 3, 45, 1, 45, 269, 59, 1803, 85],
+    &[1, 1, 3, 7, 27, 15, 33, 141, 229, 453, 2029, 875],
+    &[1, 1, 7, 15, 13, 29, 35, 3, 169, 455, 1695, 3777],
+    &[1, 3, 3, 7, 11, 33, 9, 197, 25, 757, 753, 2195],
+    &[1, 1, 1, 11, 3, 27, 67, 49, 315, 25, 133, 977],
+    &[1, 3, 5, 15, 13, 49, 39, 219, 271, 451, 1361, 1289],
+    &[1, 1, 5, 1, 15, 49, 51, 193, 333, 5, 767, 3833],
+    &[1, 3, 3, 11, 13, 11, 35, 135, 455, 821, 97, 1311],
+    &[1, 1, 5, 11, 15, 41, 107, 213, 385, 353, 1211, 1415],
+    &[1, 3, 1, 15, 3, 57, 91, 117, 61, 891, 525, 2517],
+    &[1, 1, 7, 13, 27, 23, 73, 187, 465, 119, 373, 65],
+    &[1, 1, 3, 5, 27, 57, 9, 189, 379, 397, 693, 2701],
+    &[1, 3, 3, 5, 19, 51, 87, 57, 427, 983, 919, 1909],
+    &[1, 3, 3, 11, 1, 53, 115, 151, 105, 739, 321, 1671],
+    &[1, 1, 5, 5, 29, 25, 87, 173, 423, 693, 1577, 2911],
+    &[1, 1, 1, 1, 1, 63, 125, 79, 391, 429, 1999, 1131],
+    &[1, 1, 5, 3, 11, 55, 25, 203, 427, 7, 2047, 195],
+    &[1, 3, 5, 3, 31, 43, 21, 19, 479, 441, 1345, 2759],
+    &[1, 1, 3, 9, 31, 57, 31, 241, 307, 371, 995, 1831],
+    &[1, 1, 5, 3, 31, 7, 103, 85, 77, 571, 1237, 3131],
+    &[1, 3, 1, 7, 27, 5, 49, 247, 511, 59, 135, 3061],
+    &[1, 3, 3, 5, 7, 13, 33, 253, 277, 345, 1535, 3683],
+    &[1, 3, 5, 15, 5, 61, 79, 39, 175, 773, 1803, 2755],
+    &[1, 3, 3, 7, 15, 17, 49, 159, 465, 209, 715, 3515],
+    &[1, 1, 7, 1, 25, 13, 73, 141, 311, 467, 623, 3433],
+    &[1, 1, 1, 5, 17, 1, 47, 191, 293, 983, 163, 2833],
+    &[1, 1, 3, 7, 17, 9, 103, 241, 191, 911, 1171, 825],
+    &[1, 1, 5, 1, 19, 45, 119, 5, 209, 751, 619, 1901],
+    &[1, 3, 1, 11, 13, 19, 121, 169, 149, 913, 1663, 2899],
+    &[1, 3, 5, 5, 15, 59, 25, 169, 463, 893, 1825, 463],
+    &[1, 1, 1, 11, 3, 55, 3, 125, 205, 215, 1735, 3463],
+    &[1, 3, 1, 13, 13, 11, 77, 91, 51, 803, 807, 2245],
+    &[1, 1, 1, 3, 7, 15, 45, 205, 141, 325, 261, 783],
+    &[1, 3, 5, 1, 29, 21, 65, 67, 321, 461, 1247, 389],
+    &[1, 3, 3, 7, 13, 37, 7, 247, 103, 361, 1727, 2379],
+    &[1, 3, 3, 5, 17, 41, 37, 1, 201, 589, 1119, 2169],
+    &[1, 1, 1, 3, 25, 41, 59, 151, 327, 859, 489, 2723],
+    &[1, 3, 1, 13, 27, 55, 59, 105, 179, 777, 1415, 3243],
+    &[1, 3, 3, 7, 11, 17, 69, 7, 231, 807, 271, 1015],
+    &[1, 3, 5, 1, 15, 37, 77, 17, 497, 301, 111, 687],
+    &[1, 3, 5, 11, 19, 13, 51, 233, 183, 89, 593, 3223],
+    &[1, 3, 7, 5, 31, 19, 21, 141, 357, 895, 1401, 3275],
+    &[1, 1, 1, 11, 7, 51, 115, 157, 43, 629, 987, 2191],
+    &[1, 1, 7, 13, 17, 15, 17, 213, 163, 821, 107, 437],
+    &[1, 1, 5, 3, 17, 59, 71, 23, 227, 165, 1383, 2469],
+    &[1, 3, 1, 15, 19, 13, 45, 225, 63, 137, 1049, 3157],
+    &[1, 1, 7, 11, 7, 21, 1, 143, 27, 713, 1459, 1661],
+    &[1, 1, 5, 3, 15, 57, 121, 91, 163, 549, 1901, 4079],
+    &[1, 1, 3, 3, 3, 55, 77, 83, 271, 377, 1103, 3421],
+    &[1, 3, 7, 15, 21, 57, 43, 191, 345, 269, 1811, 1873],
+    &[1, 3, 3, 9, 27, 7, 19, 233, 309, 993, 271, 3391],
+    &[1, 1, 3, 1, 7, 21, 25, 245, 239, 909, 1979, 3577],
+    &[1, 1, 1, 11, 3, 59, 57, 197, 151, 971, 1959, 1065],
+    &[1, 3, 3, 11, 31, 43, 73, 151, 509, 937, 1099, 2395],
+    &[1, 1, 7, 15, 25, 1, 81, 47, 395, 1009, 1865, 2135],
+    &[1, 1, 7, 13, 23, 3, 111, 159, 127, 835, 957, 3595],
+    &[1, 1, 5, 3, 9, 41, 21, 59, 485, 299, 87, 3781],
+    &[1, 3, 5, 13, 15, 51, 93, 67, 427, 779, 559, 3689],
+    &[1, 1, 7, 9, 7, 37, 101, 71, 427, 785, 1323, 189],
+    &[1, 1, 5, 5, 19, 61, 71, 27, 201, 947, 1249, 367],
+    &[1, 1, 3, 11, 31, 51, 123, 17, 113, 565, 819, 1113],
+    &[1, 1, 7, 9, 13, 55, 19, 117, 149, 721, 1399, 703],
+    &[1, 1, 3, 3, 5, 39, 109, 201, 487, 193, 829, 1947],
+    &[1, 1, 3, 13, 15, 3, 121, 37, 353, 13, 1399, 1727],
+    &[1, 3, 1, 1, 1, 55, 3, 147, 487, 641, 1301, 2931],
+    &[1, 3, 1, 7, 23, 7, 75, 163, 375, 357, 353, 3249],
+    &[1, 1, 7, 13, 7, 59, 125, 201, 35, 285, 1623, 233],
+    &[1, 3, 1, 13, 13, 27, 97, 109, 89, 1001, 951, 3113],
+    &[1, 1, 1, 13, 19, 37, 47, 135, 471, 189, 1107, 1439],
+    &[1, 3, 5, 13, 15, 53, 79, 159, 349, 253, 385, 3789],
+    &[1, 1, 3, 1, 15, 49, 111, 1, 147, 375, 1855, 2777],
+    &[1, 1, 1, 13, 21, 39, 83, 73, 147, 1007, 1017, 2729],
+    &[1, 1, 7, 13, 1, 13, 87, 107, 225, 521, 1859, 3999],
+    &[1, 1, 3, 5, 27, 7, 89, 195, 229, 683, 1357, 3405],
+    &[1, 3, 5, 3, 29, 33, 61, 115, 433, 845, 817, 1791],
+    &[1, 3, 1, 5, 31, 7, 29, 167, 179, 583, 1423, 3503],
+    &[1, 3, 7, 3, 11, 45, 41, 45, 91, 635, 73, 2135],
+    &[1, 1, 3, 11, 27, 51, 19, 239, 397, 211, 755, 629],
+    &[1, 3, 1, 5, 17, 9, 97, 233, 349, 391, 1423, 1797],
+    &[1, 3, 5, 15, 5, 1, 103, 209, 295, 437, 723, 2219],
+    &[1, 1, 1, 9, 29, 47, 111, 51, 281, 853, 1609, 3859],
+    &[1, 1, 5, 3, 11, 41, 117, 179, 29, 201, 233, 547],
+    &[1, 3, 5, 1, 21, 7, 11, 161, 291, 407, 2035, 293],
+    &[1, 1, 3, 11, 19, 23, 89, 159, 87, 203, 165, 815],
+    &[1, 1, 7, 15, 17, 3, 49, 161, 83, 523, 1557, 2871],
+    &[1, 3, 3, 7, 23, 15, 91, 37, 429, 407, 1251, 3989],
+    &[1, 3, 7, 15, 9, 63, 53, 137, 61, 185, 1207, 2863],
+    &[1, 1, 7, 15, 9, 23, 1, 205, 327, 519, 751, 3229],
+    &[1, 1, 7, 15, 1, 27, 53, 221, 141, 661, 489, 2429],
+    &[1, 1, 7, 13, 17, 3, 35, 221, 131, 345, 1715, 765],
+    &[1, 1, 3, 9, 31, 33, 5, 137, 101, 9, 277, 2021],
+    &[1, 3, 1, 11, 3, 27, 103, 209, 363, 187, 1753, 3955, 1967],
+    &[1, 3, 1, 7, 21, 61, 75, 29, 245, 645, 1313, 2581, 5863],
+    &[1, 3, 5, 3, 25, 61, 67, 87, 435, 305, 1765, 1841, 3027],
+    &[1, 1, 1, 7, 31, 25, 35, 253, 505, 791, 1017, 1743, 6097],
+    &[1, 3, 7, 3, 11, 55, 25, 239, 501, 625, 879, 1983, 2449],
+    &[1, 1, 1, 9, 1, 53, 7, 113, 53, 259, 1669, 2739, 1095],
+    &[1, 3, 7, 15, 17, 57, 39, 239, 333, 719, 443, 3113, 1393],
+    &[1, 1, 1, 9, 7, 39, 77, 15, 367, 973, 675, 279, 7929],
+    &[1, 3, 7, 15, 11, 23, 79, 135, 189, 883, 75, 645, 4161],
+    &[1, 3, 1, 13, 31, 39, 95, 21, 55, 679, 1243, 3879, 3523],
+    &[1, 1, 1, 9, 13, 13, 41, 193, 215, 691, 1071, 3357, 2315],
+    &[1, 3, 7, 7, 29, 17, 119, 131, 423, 525, 501, 3399, 1487],
+    &[1, 3, 1, 11, 27, 53, 69, 11, 23, 855, 1309, 1983, 7079],
+    &[1, 1, 1, 13, 17, 23, 7, 43, 499, 1011, 27, 3623, 2085],
+    &[1, 1, 3, 7, 17, 3, 5, 231, 83, 915, 47, 1623, 3413],
+    &[1, 3, 7, 15, 27, 43, 125, 135, 363, 891, 1967, 3609, 303],
+    &[1, 3, 7, 3, 1, 49, 121, 3, 1, 61, 1531, 4023, 5241],
+    &[1, 3, 5, 13, 31, 33, 11, 1, 467, 949, 2025, 2373, 5527],
+    &[1, 1, 3, 11, 23, 45, 39, 69, 433, 531, 227, 3317, 7951],
+    &[1, 1, 5, 3, 11, 23, 39, 171, 157, 485, 283, 3677, 2987],
+    &[1, 3, 1, 11, 3, 27, 111, 249, 191, 253, 881, 1689, 3703],
+    &[1, 1, 3, 5, 11, 29, 31, 79, 395, 151, 719, 1399, 7181],
+    &[1, 1, 5, 5, 5, 13, 105, 205, 17, 69, 329, 1351, 8101],
+    &[1, 1, 5, 3, 9, 7, 123, 227, 7, 107, 1127, 2673, 7743],
+    &[1, 1, 7, 9, 15, 1, 3, 1, 143, 471, 1201, 2587, 4533],
+    &[1, 1, 1, 3, 5, 27, 39, 47, 5, 421, 1799, 3293, 7969],
+    &[1, 3, 5, 3, 9, 29, 107, 215, 5, 857, 1147, 2437, 9],
+    &[1, 3, 5, 3, 27, 51, 83, 211, 211, 367, 1519, 3435, 3565],
+    &[1, 3, 3, 9, 25, 23, 21, 163, 383, 877, 297, 361, 5559],
+    &[1, 1, 7, 15, 25, 33, 15, 69, 425, 999, 779, 2959, 4155],
+    &[1, 3, 3, 1, 13, 31, 35, 251, 25, 395, 627, 2779, 2485],
+    &[1, 3, 5, 7, 5, 53, 91, 65, 403, 985, 1495, 2127, 1733],
+    &[1, 3, 1, 1, 1, 19, 89, 17, 131, 627, 929, 559, 1777],
+    &[1, 3, 5, 13, 13, 43, 1, 61, 311, 49, 1391, 1607, 79],
+    &[1, 3, 5, 1, 25, 27, 79, 177, 195, 519, 1823, 3691, 2825],
+    &[1, 3, 7, 3, 7, 37, 81, 93, 345, 687, 1947, 663, 4825],
+    &[1, 1, 5, 1, 27, 19, 3, 243, 9, 279, 525, 963, 5463],
+    &[1, 3, 7, 13, 15, 25, 83, 173, 357, 473, 1441, 3141, 4517],
+    &[1, 1, 1, 13, 19, 39, 39, 55, 117, 99, 1701, 3343, 687],
+    &[1, 1, 5, 5, 29, 37, 95, 137, 51, 647, 1321, 2625, 7497],
+    &[1, 3, 1, 5, 17, 35, 123, 113, 471, 495, 1019, 1875, 1745],
+    &[1, 3, 3, 11, 29, 3, 81, 225, 175, 891, 1383, 1065, 4017],
+    &[1, 1, 5, 3, 3, 27, 107, 35, 269, 175, 459, 2219, 2937],
+    &[1, 3, 1, 11, 7, 19, 117, 253, 501, 689, 479, 2643, 3503],
+    &[1, 3, 3, 5, 11, 63, 17, 115, 147, 855, 175, 3009, 3057],
+    &[1, 1, 5, 7, 19, 23, 41, 191, 313, 841, 1043, 1085, 2433],
+    &[1, 1, 5, 3, 1, 13, 9, 215, 217, 589, 1463, 1007, 6701],
+    &[1, 1, 7, 9, 15, 3, 29, 207, 91, 641, 1177, 2215, 2361],
+    &[1, 3, 3, 1, 29, 37, 39, 47, 131, 831, 1695, 1099, 5111],
+    &[1, 3, 7, 13, 17, 49, 105, 47, 409, 153, 871, 165, 1561],
+    &[1, 3, 5, 15, 13, 55, 55, 115, 457, 353, 447, 857, 6133],
+    &[1, 1, 1, 7, 13, 37, 87, 85, 501, 5, 691, 461, 2539],
+    &[1, 1, 7, 9, 15, 57, 69, 173, 283, 411, 191, 1281, 2681],
+    &[1, 3, 3, 3, 9, 53, 77, 169, 351, 243, 323, 793, 3103],
+    &[1, 3, 7, 3, 1, 55, 39, 81, 401, 711, 737, 3365, 2977],
+    &[1, 3, 3, 5, 3, 31, 69, 61, 35, 971, 1155, 2753, 3123],
+    &[1, 1, 1, 9, 3, 49, 3, 113, 121, 787, 1623, 3333, 6621],
+    &[1, 3, 7, 3, 5, 47, 117, 93, 227, 3, 1509, 2883, 3933],
+    &[1, 3, 3, 9, 7, 41, 107, 167, 165, 427, 1697, 365, 6049],
+    &[1, 3, 1, 13, 9, 59, 99, 69, 259, 911, 1445, 3959, 2301],
+    &[1, 3, 3, 11, 3, 41, 47, 213, 307, 237, 1673, 7, 1101],
+    &[1, 1, 5, 1, 1, 57, 89, 131, 463, 919, 1329, 659, 4733],
+    &[1, 1, 5, 5, 25, 39, 41, 193, 317, 235, 231, 3753, 6593],
+    &[1, 3, 3, 7, 21, 5, 125, 171, 201, 411, 751, 837, 2221],
+    &[1, 1, 1, 11, 9, 49, 7, 155, 505, 263, 1193, 3839, 4753],
+    &[1, 1, 3, 7, 27, 33, 125, 183, 481, 659, 1987, 3345, 5133],
+    &[1, 1, 7, 11, 15, 27, 79, 3, 45, 517, 1925, 353, 2957],
+    &[1, 3, 3, 7, 15, 37, 107, 215, 431, 227, 213, 1107, 1211],
+    &[1, 3, 3, 9, 7, 45, 91, 23, 21, 451, 1619, 1287, 7611],
+    &[1, 3, 1, 9, 5, 61, 57, 89, 57, 149, 2001, 2837, 11],
+    &[1, 3, 3, 11, 23, 61, 91, 201, 477, 241, 131, 2701, 6869],
+    &[1, 3, 5, 15, 21, 1, 31, 223, 141, 123, 949, 651, 6713],
+    &[1, 3, 3, 7, 17, 7, 21, 51, 305, 903, 1899, 629, 3259],
+    &[1, 1, 3, 11, 25, 53, 17, 117, 145, 113, 437, 799, 7663],
+    &[1, 1, 3, 7, 15, 29, 57, 3, 249, 11, 369, 1109, 2925],
+    &[1, 1, 1, 11, 7, 15, 87, 61, 267, 1003, 647, 2525, 3961],
+    &[1, 3, 3, 11, 3, 41, 101, 139, 417, 513, 1625, 269, 7793],
+    &[1, 1, 5, 1, 3, 1, 29, 135, 133, 593, 185, 3919, 1283],
+    &[1, 1, 5, 7, 29, 21, 65, 171, 449, 977, 19, 1237, 2871],
+    &[1, 1, 5, 7, 5, 49, 107, 41, 231, 149, 1933, 2491, 2111],
+    &[1, 3, 5, 9, 21, 23, 47, 177, 51, 305, 587, 1465, 5053],
+    &[1, 1, 5, 5, 25, 9, 123, 61, 383, 733, 179, 3683, 6693],
+    &[1, 3, 5, 15, 17, 33, 111, 185, 95, 529, 137, 3677, 7575],
+    &[1, 1, 7, 11, 1, 45, 109, 193, 281, 189, 769, 639, 7529],
+    &[1, 1, 7, 11, 11, 39, 87, 167, 61, 563, 67, 3303, 4721],
+    &[1, 1, 7, 13, 3, 21, 35, 35, 69, 753, 1917, 597, 715],
+    &[1, 1, 7, 9, 1, 11, 65, 49, 445, 219, 867, 1087, 985],
+    &[1, 1, 1, 9, 5, 41, 81, 13, 301, 789, 1927, 1021, 2355],
+    &[1, 1, 5, 5, 29, 21, 67, 197, 157, 627, 601, 3533, 7881],
+    &[1, 3, 7, 3, 5, 19, 123, 157, 473, 611, 71, 395, 1003],
+    &[1, 3, 3, 5, 21, 3, 113, 241, 465, 485, 1401, 11, 1467],
+    &[1, 1, 7, 9, 11, 39, 107, 171, 277, 121, 989, 3657, 7617],
+    &[1, 3, 1, 5, 19, 63, 59, 43, 453, 957, 747, 1353, 7881],
+    &[1, 1, 7, 9, 11, 13, 13, 121, 337, 561, 1981, 1785, 7033],
+    &[1, 3, 1, 11, 5, 57, 7, 5, 483, 67, 1331, 3311, 7977],
+    &[1, 1, 5, 7, 29, 3, 115, 103, 337, 49, 1457, 2457, 7501],
+    &[1, 1, 7, 13, 1, 25, 57, 157, 269, 357, 1519, 1047, 6237],
+    &[1, 1, 3, 11, 23, 13, 23, 139, 289, 357, 1631, 3221, 931],
+    &[1, 1, 7, 9, 7, 59, 85, 251, 465, 827, 65, 71, 1355],
+    &[1, 1, 5, 5, 31, 51, 77, 159, 83, 1003, 1185, 259, 7791],
+    &[1, 1, 5, 3, 13, 55, 69, 165, 197, 677, 1065, 1727, 6801],
+    &[1, 3, 5, 13, 11, 63, 77, 165, 265, 49, 813, 2789, 2749],
+    &[1, 1, 1, 1, 25, 37, 9, 243, 255, 835, 1905, 47, 7237],
+    &[1, 3, 1, 15, 15, 53, 53, 253, 81, 475, 139, 3117, 1379],
+    &[1, 3, 1, 13, 11, 31, 79, 251, 159, 473, 1393, 479, 5779],
+    &[1, 3, 3, 7, 23, 13, 35, 225, 27, 237, 241, 2715, 7149],
+    &[1, 3, 5, 1, 9, 15, 101, 167, 113, 637, 239, 3341, 4789],
+    &[1, 1, 1, 7, 9, 25, 91, 209, 117, 501, 1453, 803, 6431],
+    &[1, 3, 7, 3, 7, 17, 49, 145, 75, 159, 975, 4045, 4661],
+    &[1, 3, 7, 7, 19, 55, 3, 147, 237, 389, 1815, 2913, 3415],
+    &[1, 3, 1, 11, 7, 9, 25, 79, 289, 777, 663, 2721, 279],
+    &[1, 1, 7, 13, 25, 45, 59, 65, 241, 521, 1059, 2111, 1053],
+    &[1, 1, 7, 15, 23, 5, 99, 79, 131, 697, 739, 723, 1111],
+    &[1, 1, 3, 3, 27, 31, 9, 217, 319, 45, 1045, 797, 4893],
+    &[1, 3, 5, 1, 21, 43, 47, 193, 431, 639, 369, 903, 7895],
+    &[1, 3, 5, 3, 17, 25, 99, 183, 121, 583, 869, 809, 353],
+    &[1, 1, 5, 3, 13, 51, 127, 9, 255, 687, 1511, 1297, 6877],
+    &[1, 1, 5, 7, 25, 31, 127, 133, 99, 491, 761, 1219, 3765],
+    &[1, 1, 3, 3, 5, 7, 41, 29, 459, 961, 1279, 4007, 7429],
+    &[1, 3, 1, 13, 9, 31, 103, 219, 367, 593, 1289, 1941, 6459],
+    &[1, 3, 5, 9, 1, 19, 81, 185, 285, 83, 867, 3595, 745],
+    &[1, 1, 1, 5, 21, 9, 29, 55, 5, 789, 989, 991, 2093],
+    &[1, 3, 7, 11, 23, 39, 15, 181, 343, 557, 1813, 2711, 5329],
+    &[1, 3, 7, 15, 17, 31, 109, 143, 453, 991, 1923, 3029, 7417],
+    &[1, 1, 3, 13, 19, 43, 79, 3, 475, 979, 411, 2961, 4843],
+    &[1, 1, 1, 7, 3, 11, 5, 125, 343, 219, 905, 1781, 5333],
+    &[1, 1, 3, 1, 5, 7, 21, 9, 379, 757, 301, 2073, 2949],
+    &[1, 1, 3, 1, 27, 25, 109, 1, 297, 369, 1731, 3929, 6127],
+    &[1, 3, 3, 7, 21, 35, 57, 19, 143, 15, 937, 601, 7995],
+    &[1, 1, 7, 13, 15, 21, 123, 137, 353, 627, 1475, 1379, 5375],
+    &[1, 3, 5, 13, 13, 47, 37, 221, 483, 101, 1715, 2807, 1621],
+    &[1, 3, 1, 13, 23, 5, 119, 129, 453, 177, 55, 497, 3017],
+    &[1, 1, 7, 15, 27, 55, 107, 171, 61, 297, 589, 499, 2303],
+    &[1, 1, 1, 13, 19, 23, 97, 39, 201, 985, 659, 4055, 1423],
+    &[1, 1, 5, 3, 9, 61, 15, 137, 99, 249, 361, 363, 6645],
+    &[1, 1, 1, 9, 11, 49, 29, 243, 491, 883, 1779, 1853, 5047],
+    &[1, 3, 7, 9, 7, 57, 59, 143, 403, 805, 435, 2255, 7579],
+    &[1, 1, 3, 3, 31, 27, 107, 205, 39, 91, 513, 3543, 1657],
+    &[1, 3, 1, 7, 17, 53, 107, 103, 299, 435, 1335, 975, 5703],
+    &[1, 3, 3, 7, 11, 21, 11, 17, 263, 49, 1405, 3393, 5805],
+    &[1, 1, 3, 15, 19, 63, 39, 15, 191, 209, 1663, 1929, 617],
+    &[1, 3, 3, 11, 29, 13, 41, 135, 25, 853, 1801, 3925, 3531],
+    &[1, 1, 5, 3, 9, 53, 19, 201, 435, 235, 157, 1185, 2287],
+    &[1, 3, 7, 15, 21, 29, 87, 1, 427, 387, 195, 1281, 4753],
+    &[1, 3, 5, 7, 7, 31, 93, 125, 1, 253, 1941, 2917, 1981],
+    &[1, 3, 5, 3, 17, 31, 89, 239, 75, 263, 257, 1439, 2839],
+    &[1, 3, 3, 7, 11, 37, 109, 235, 191, 399, 1729, 705, 3873],
+    &[1, 3, 7, 15, 19, 61, 47, 65, 201, 183, 927, 2503, 4779],
+    &[1, 3, 5, 15, 11, 33, 37, 35, 199, 849, 83, 2279, 3473],
+    &[1, 1, 5, 3, 11, 29, 127, 41, 435, 839, 277, 2999, 4297],
+    &[1, 1, 3, 9, 21, 53, 39, 101, 275, 711, 955, 901, 4939],
+    &[1, 1, 3, 1, 7, 61, 117, 187, 353, 299, 1945, 1133, 1785],
+    &[1, 1, 1, 11, 9, 25, 27, 233, 115, 541, 141, 2475, 2363],
+    &[1, 3, 5, 3, 21, 23, 95, 31, 497, 247, 1179, 947, 7295],
+    &[1, 3, 1, 7, 21, 57, 61, 255, 261, 539, 645, 3499, 2213],
+    &[1, 3, 1, 11, 5, 15, 99, 201, 57, 271, 611, 2391, 2857],
+    &[1, 1, 1, 15, 17, 45, 47, 75, 59, 989, 163, 2043, 6101],
+    &[1, 1, 7, 15, 25, 55, 119, 169, 459, 7, 411, 2977, 5165],
+    &[1, 1, 7, 9, 15, 5, 127, 189, 9, 937, 667, 231, 4525],
+    &[1, 1, 1, 11, 29, 35, 125, 101, 91, 737, 2009, 993, 1033],
+    &[1, 1, 3, 1, 3, 7, 121, 127, 215, 123, 1069, 19, 3855],
+    &[1, 1, 1, 15, 19, 35, 39, 235, 151, 501, 1265, 3655, 7259],
+    &[1, 1, 3, 1, 1, 11, 7, 97, 293, 545, 1381, 1375, 6101],
+    &[1, 1, 7, 11, 9, 23, 3, 219, 47, 435, 431, 2749, 2421],
+    &[1, 1, 3, 13, 17, 33, 47, 147, 73, 731, 1903, 751, 1067],
+    &[1, 1, 1, 1, 31, 41, 21, 249, 199, 85, 683, 3899, 1783],
+    &[1, 3, 1, 13, 27, 47, 87, 29, 41, 273, 1231, 3821, 1955],
+    &[1, 3, 3, 5, 17, 3, 125, 139, 249, 781, 21, 641, 5523],
+    &[1, 1, 7, 9, 13, 5, 9, 255, 413, 229, 1733, 2703, 5339],
+    &[1, 1, 5, 3, 11, 47, 61, 243, 257, 59, 1717, 457, 5971],
+    &[1, 3, 1, 7, 23, 61, 7, 103, 133, 107, 1619, 2883, 1845],
+    &[1, 3, 1, 11, 5, 35, 83, 105, 479, 987, 1061, 2341, 6287],
+    &[1, 1, 3, 1, 3, 5, 103, 153, 251, 63, 835, 1907, 2127],
+    &[1, 3, 5, 15, 15, 47, 33, 209, 327, 405, 2021, 2269, 7621],
+    &[1, 1, 5, 3, 9, 29, 91, 107, 473, 117, 729, 3907, 4587],
+    &[1, 1, 5, 3, 11, 1, 127, 75, 421, 387, 363, 4045, 4485],
+    &[1, 1, 7, 11, 11, 43, 53, 69, 135, 845, 617, 3799, 5929],
+    &[1, 1, 1, 15, 17, 9, 59, 201, 189, 75, 1985, 1555, 5029],
+    &[1, 3, 5, 3, 25, 51, 57, 121, 363, 671, 1355, 949, 3609],
+    &[1, 3, 5, 15, 3, 15, 37, 139, 325, 257, 413, 1247, 6589],
+    &[1, 3, 5, 15, 21, 27, 117, 45, 353, 49, 425, 1345, 6013],
+    &[1, 1, 1, 11, 7, 53, 1, 3, 251, 215, 1303, 3037, 7615],
+    &[1, 1, 1, 13, 25, 45, 21, 193, 75, 955, 525, 1469, 3689],
+    &[1, 1, 7, 11, 9, 61, 95, 157, 453, 751, 1561, 1251, 6255],
+    &[1, 1, 5, 5, 25, 29, 95, 185, 301, 183, 1221, 393, 1625],
+    &[1, 3, 5, 3, 25, 49, 91, 209, 427, 129, 1205, 1071, 7343],
+    &[1, 3, 7, 3, 15, 53, 21, 121, 269, 621, 1443, 3749, 535],
+    &[1, 1, 7, 15, 31, 57, 113, 97, 89, 745, 775, 3603, 4813],
+    &[1, 3, 3, 7, 17, 11, 89, 127, 249, 753, 1367, 3255, 5537],
+    &[1, 1, 7, 15, 23, 19, 27, 177, 433, 329, 1729, 3725, 4577],
+    &[1, 1, 5, 7, 29, 29, 51, 119, 345, 465, 1981, 1237, 961],
+    &[1, 1, 7, 13, 31, 63, 81, 23, 251, 25, 371, 3249, 2525],
+    &[1, 3, 1, 11, 1, 55, 59, 219, 343, 971, 593, 739, 7643],
+    &[1, 3, 1, 9, 7, 11, 85, 87, 287, 439, 1717, 3751, 4963],
+    &[1, 1, 3, 7, 27, 37, 117, 109, 483, 391, 1915, 2315, 1329],
+    &[1, 3, 7, 3, 3, 53, 55, 241, 507, 457, 1379, 1885, 3477],
+    &[1, 1, 1, 11, 9, 21, 75, 181, 193, 907, 619, 1385, 7265],
+    &[1, 3, 7, 3, 15, 47, 99, 187, 189, 911, 1491, 3439, 2295],
+    &[1, 1, 1, 15, 19, 19, 15, 219, 145, 749, 711, 2625, 5997],
+    &[1, 1, 1, 11, 1, 5, 23, 157, 477, 673, 815, 143, 6229],
+    &[1, 3, 1, 7, 29, 3, 57, 187, 7, 215, 935, 2585, 3595],
+    &[1, 3, 1, 7, 17, 27, 39, 163, 175, 93, 817, 1883, 3493],
+    &[1, 3, 7, 1, 1, 15, 113, 79, 409, 961, 337, 1519, 8175],
+    &[1, 1, 1, 11, 5, 53, 123, 195, 341, 543, 1429, 1871, 3605],
+    &[1, 3, 3, 7, 1, 61, 99, 37, 241, 199, 609, 1667, 5813],
+    &[1, 3, 7, 5, 19, 7, 117, 77, 131, 245, 907, 1165, 7435],
+    &[1, 3, 5, 13, 13, 47, 53, 187, 171, 633, 161, 2361, 1361],
+    &[1, 1, 7, 15, 31, 37, 27, 113, 481, 199, 825, 3291, 4049],
+    &[1, 3, 1, 9, 7, 23, 39, 191, 291, 791, 949, 2959, 3187],
+    &[1, 3, 1, 11, 3, 55, 59, 201, 443, 97, 477, 1261, 771],
+    &[1, 1, 5, 1, 13, 53, 61, 251, 185, 501, 699, 3083, 3001],
+    &[1, 1, 7, 13, 19, 27, 75, 119, 501, 633, 889, 1303, 5849],
+    &[1, 3, 7, 9, 25, 51, 113, 181, 489, 503, 523, 3711, 5133],
+    &[1, 3, 7, 3, 1, 21, 13, 33, 461, 241, 805, 127, 1507],
+    &[1, 1, 7, 13, 19, 7, 43, 251, 11, 77, 825, 1459, 2855],
+    &[1, 3, 5, 13, 19, 5, 115, 163, 379, 113, 1809, 3503, 3477],
+    &[1, 1, 5, 7, 25, 33, 65, 127, 189, 873, 591, 621, 3235],
+    &[1, 3, 3, 5, 13, 27, 51, 147, 159, 45, 1725, 207, 1765],
+    &[1, 3, 7, 15, 29, 37, 37, 251, 219, 967, 1609, 2357, 7287],
+    &[1, 3, 5, 15, 9, 63, 43, 227, 65, 267, 1803, 3155, 1503],
+    &[1, 3, 3, 5, 19, 13, 101, 229, 471, 981, 1073, 3461, 2521],
+    &[1, 1, 1, 15, 15, 19, 67, 75, 473, 293, 1965, 2961, 7057],
+    &[1, 3, 7, 15, 11, 15, 71, 209, 137, 687, 2005, 2969, 4787],
+    &[1, 3, 3, 11, 3, 59, 63, 251, 423, 19, 537, 47, 441],
+    &[1, 3, 1, 5, 21, 3, 27, 243, 275, 417, 253, 2385, 7437],
+    &[1, 1, 5, 3, 13, 41, 43, 149, 399, 771, 1219, 3879, 2571],
+    &[1, 1, 5, 7, 17, 1, 115, 109, 87, 723, 1513, 2407, 5309],
+    &[1, 3, 1, 7, 21, 17, 1, 225, 377, 943, 571, 3053, 741],
+    &[1, 1, 5, 5, 17, 43, 109, 15, 149, 445, 971, 3411, 2043],
+    &[1, 1, 1, 11, 7, 49, 121, 89, 5, 735, 1303, 1373, 6647],
+    &[1, 1, 3, 5, 31, 57, 81, 31, 147, 53, 185, 1001, 4243],
+    &[1, 1, 5, 5, 9, 31, 17, 159, 163, 629, 95, 1543, 515],
+    &[1, 3, 5, 7, 7, 47, 3, 7, 435, 477, 819, 3695, 5207],
+    &[1, 1, 5, 1, 9, 15, 43, 41, 451, 375, 1449, 4057, 3079],
+    &[1, 3, 7, 15, 11, 9, 75, 135, 173, 555, 1161, 3911, 8153],
+    &[1, 1, 7, 13, 21, 5, 39, 169, 419, 19, 1569, 1639, 3465],
+    &[1, 1, 3, 7, 23, 9, 7, 231, 267, 933, 445, 433, 7797],
+    &[1, 1, 5, 3, 9, 59, 35, 177, 75, 567, 333, 2561, 7885],
+    &[1, 1, 7, 15, 3, 29, 5, 191, 511, 681, 1883, 67, 3689],
+    &[1, 1, 3, 1, 5, 55, 101, 47, 495, 517, 821, 595, 3621],
+    &[1, 3, 1, 13, 9, 19, 127, 147, 137, 543, 237, 597, 1749],
+    &[1, 3, 1, 11, 5, 21, 57, 133, 99, 897, 539, 1175, 7061],
+    &[1, 3, 7, 13, 15, 47, 47, 27, 215, 675, 1421, 873, 7561],
+    &[1, 1, 7, 15, 27, 29, 41, 31, 149, 585, 119, 729, 247],
+    &[1, 3, 5, 5, 13, 5, 99, 39, 387, 645, 905, 4065, 7873],
+    &[1, 1, 3, 1, 7, 33, 123, 111, 15, 31, 1343, 3461, 6493],
+    &[1, 1, 7, 13, 5, 51, 99, 31, 263, 967, 779, 2757, 6155],
+    &[1, 1, 1, 11, 11, 33, 47, 31, 105, 183, 335, 3511, 7815],
+    &[1, 1, 7, 9, 5, 19, 5, 83, 99, 513, 2029, 3927, 839],
+    &[1, 1, 3, 5, 17, 11, 81, 199, 67, 775, 885, 1877, 6171],
+    &[1, 3, 1, 9, 5, 21, 61, 199, 163, 193, 937, 3127, 5515],
+    &[1, 1, 1, 7, 9, 33, 105, 45, 331, 249, 917, 1577, 2979],
+    &[1, 3, 1, 7, 21, 59, 127, 241, 277, 769, 2009, 2577, 3713],
+    &[1, 1, 7, 13, 27, 55, 71, 65, 393, 65, 1147, 875, 7745],
+    &[1, 3, 5, 3, 31, 27, 119, 55, 231, 901, 1005, 3407, 451],
+    &[1, 1, 5, 3, 21, 45, 87, 11, 295, 229, 1853, 363, 613],
+    &[1, 1, 3, 9, 29, 63, 53, 99, 203, 389, 1093, 3211, 7637],
+    &[1, 3, 7, 13, 15, 29, 47, 181, 447, 507, 89, 523, 1719],
+    &[1, 1, 7, 15, 27, 43, 77, 79, 9, 165, 263, 2625, 6345],
+    &[1, 1, 7, 13, 13, 23, 23, 97, 3, 119, 147, 3819, 1953],
+    &[1, 1, 1, 9, 5, 41, 81, 125, 411, 919, 737, 153, 105],
+    &[1, 3, 5, 3, 1, 49, 87, 73, 439, 313, 595, 1681, 2197],
+    &[1, 3, 7, 15, 31, 33, 107, 229, 375, 141, 1265, 1729, 1965],
+    &[1, 3, 5, 1, 31, 27, 103, 225, 19, 1023, 1177, 919, 6691],
+    &[1, 1, 3, 7, 15, 25, 63, 197, 239, 145, 1155, 1587, 4581],
+    &[1, 3, 5, 15, 11, 63, 21, 225, 453, 355, 245, 2559, 7757],
+    &[1, 1, 5, 5, 29, 53, 105, 159, 1, 107, 31, 1851, 179],
+    &[1, 3, 3, 5, 13, 43, 27, 243, 115, 621, 1541, 351, 3223],
+    &[1, 3, 1, 15, 31, 47, 111, 211, 451, 625, 1461, 1183, 5847],
+    &[1, 1, 1, 15, 17, 33, 15, 67, 153, 357, 747, 1191, 2949],
+    &[1, 1, 1, 15, 17, 17, 91, 209, 257, 497, 1799, 2825, 4731],
+    &[1, 3, 3, 5, 17, 11, 3, 151, 37, 377, 167, 1343, 59],
+    &[1, 1, 1, 5, 19, 1, 51, 69, 287, 733, 1195, 2815, 995],
+    &[1, 3, 3, 5, 17, 45, 23, 115, 1, 831, 113, 893, 285],
+    &[1, 3, 7, 13, 19, 57, 61, 231, 235, 571, 61, 3881, 3891],
+    &[1, 3, 5, 13, 9, 45, 15, 29, 315, 267, 1215, 841, 3327],
+    &[1, 3, 3, 11, 29, 9, 65, 117, 291, 511, 869, 3593, 4183],
+    &[1, 1, 1, 5, 23, 53, 105, 75, 399, 929, 1667, 1515, 2603],
+    &[1, 1, 5, 1, 13, 47, 53, 231, 85, 975, 181, 269, 2305],
+    &[1, 1, 5, 3, 1, 15, 51, 195, 229, 915, 1597, 1771, 2361],
+    &[1, 1, 7, 13, 19, 5, 31, 59, 493, 551, 719, 2097, 4209],
+    &[1, 3, 7, 1, 31, 51, 5, 29, 463, 447, 2005, 3209, 7217],
+    &[1, 1, 1, 13, 17, 47, 47, 243, 151, 121, 993, 1865, 7945],
+    &[1, 3, 5, 7, 15, 3, 91, 187, 415, 183, 1935, 1035, 7867],
+    &[1, 1, 7, 15, 31, 49, 107, 173, 15, 21, 1407, 2955, 6415],
+    &[1, 1, 1, 9, 5, 29, 93, 31, 165, 559, 1859, 1237, 2505],
+    &[1, 3, 7, 7, 27, 11, 7, 189, 359, 213, 1203, 1205, 5439],
+    &[1, 3, 5, 15, 19, 1, 109, 165, 23, 807, 1869, 841, 2545],
+    &[1, 3, 3, 7, 15, 33, 35, 135, 417, 27, 1305, 1003, 3913],
+    &[1, 3, 5, 15, 7, 29, 125, 251, 413, 145, 511, 3791, 5315],
+    &[1, 1, 1, 9, 11, 31, 65, 167, 381, 797, 1097, 1077, 4891],
+    &[1, 1, 5, 3, 9, 27, 125, 127, 283, 595, 1193, 1237, 1997],
+    &[1, 1, 1, 11, 3, 49, 11, 11, 417, 733, 1099, 1789, 2977],
+    &[1, 1, 5, 3, 11, 41, 125, 211, 27, 779, 745, 1567, 4737],
+    &[1, 3, 3, 9, 1, 39, 9, 81, 407, 897, 267, 1121, 5301],
+    &[1, 3, 5, 1, 3, 7, 107, 149, 61, 599, 503, 2345, 6045],
+    &[1, 3, 3, 7, 17, 25, 85, 105, 495, 849, 1051, 457, 7689],
+    &[1, 3, 3, 5, 21, 3, 115, 161, 343, 187, 1433, 3921, 6175],
+    &[1, 3, 3, 7, 5, 23, 89, 149, 289, 59, 1687, 1891, 5669],
+    &[1, 1, 1, 15, 21, 5, 111, 181, 229, 315, 27, 1259, 6749],
+    &[1, 3, 1, 11, 3, 31, 25, 29, 117, 359, 1693, 3383, 1311],
+    &[1, 3, 7, 3, 5, 43, 83, 47, 347, 401, 1043, 2773, 3855],
+    &[1, 3, 7, 3, 3, 43, 75, 185, 265, 99, 363, 473, 1337],
+    &[1, 1, 7, 9, 7, 17, 123, 65, 353, 957, 33, 1587, 6497],
+    &[1, 1, 7, 11, 13, 43, 109, 43, 439, 453, 1675, 881, 2605],
+    &[1, 3, 3, 1, 11, 17, 121, 55, 481, 967, 1687, 1431, 1493],
+    &[1, 1, 7, 9, 11, 59, 95, 45, 45, 291, 1823, 1225, 4589],
+    &[1, 3, 3, 9, 1, 35, 109, 99, 381, 231, 1513, 1761, 2775],
+    &[1, 3, 7, 3, 7, 1, 71, 239, 65, 903, 349, 1269, 4409],
+    &[1, 3, 5, 3, 27, 31, 5, 133, 277, 751, 1847, 3485, 7907],
+    &[1, 3, 7, 9, 1, 39, 55, 159, 153, 589, 1229, 2099, 3319],
+    &[1, 3, 1, 11, 3, 49, 57, 123, 407, 125, 1979, 3017, 7551],
+    &[1, 1, 7, 13, 19, 1, 119, 105, 343, 95, 1543, 2725, 3349],
+    &[1, 3, 7, 3, 7, 17, 123, 165, 233, 229, 1119, 1629, 5797],
+    &[1, 1, 7, 11, 11, 7, 89, 23, 235, 273, 859, 1773, 1685],
+    &[1, 1, 1, 15, 21, 19, 117, 57, 311, 747, 441, 3959, 4419],
+    &[1, 3, 5, 3, 1, 3, 11, 107, 451, 813, 1027, 1291, 3715],
+    &[1, 3, 7, 15, 9, 37, 61, 69, 189, 127, 571, 293, 2957],
+    &[1, 3, 3, 7, 13, 41, 111, 251, 491, 319, 149, 2265, 5089],
+    &[1, 1, 1, 7, 13, 47, 99, 43, 167, 255, 1091, 3277, 1161],
+    &[1, 1, 3, 3, 13, 33, 113, 69, 499, 985, 1441, 483, 6851],
+    &[1, 3, 1, 9, 5, 7, 85, 101, 203, 941, 631, 4043, 2395],
+    &[1, 3, 7, 3, 5, 39, 95, 63, 241, 865, 1603, 1695, 5657],
+    &[1, 1, 5, 15, 5, 29, 55, 7, 93, 675, 1011, 2977, 4791],
+    &[1, 3, 1, 11, 15, 17, 83, 211, 97, 805, 1835, 3195, 1923],
+    &[1, 3, 1, 11, 27, 29, 25, 133, 203, 103, 1571, 507, 399],
+    &[1, 3, 5, 1, 29, 27, 107, 207, 17, 85, 537, 1435, 8085],
+    &[1, 1, 7, 9, 9, 27, 75, 21, 161, 317, 379, 3429, 3013],
+    &[1, 1, 1, 15, 23, 29, 109, 233, 161, 653, 1339, 1939, 6293],
+    &[1, 1, 1, 11, 11, 49, 45, 89, 199, 179, 2003, 945, 5829],
+    &[1, 1, 1, 15, 31, 61, 121, 19, 415, 665, 931, 3457, 5737],
+    &[1, 1, 7, 15, 29, 57, 107, 121, 509, 815, 647, 2955, 3977],
+    &[1, 1, 1, 13, 17, 21, 79, 83, 23, 689, 127, 1797, 4887],
+    &[1, 1, 5, 1, 3, 19, 85, 125, 291, 375, 1769, 1531, 4733],
+    &[1, 1, 1, 7, 13, 43, 77, 27, 439, 323, 1927, 3833, 6317],
+    &[1, 1, 1, 11, 29, 47, 123, 167, 75, 311, 21, 429, 4391],
+    &[1, 1, 7, 11, 15, 51, 1, 187, 429, 501, 269, 1895, 5417],
+    &[1, 1, 5, 5, 31, 11, 15, 249, 303, 569, 1725, 3475, 1087],
+    &[1, 1, 1, 11, 19, 39, 55, 227, 291, 831, 1165, 575, 5113],
+    &[1, 1, 3, 7, 19, 21, 103, 143, 233, 1017, 493, 3569, 6895],
+    &[1, 3, 1, 7, 21, 61, 25, 93, 97, 551, 1477, 41, 5221],
+    &[1, 3, 5, 3, 31, 7, 75, 139, 293, 717, 959, 967, 2125],
+    &[1, 1, 3, 7, 27, 49, 59, 147, 277, 681, 1885, 3541, 5381],
+    &[1, 1, 5, 5, 19, 1, 119, 149, 479, 967, 1823, 1523, 7221],
+    &[1, 1, 7, 11, 13, 31, 87, 225, 489, 503, 1783, 1197, 2817],
+    &[1, 1, 3, 3, 5, 11, 7, 51, 21, 9, 1865, 301, 4233],
+    &[1, 1, 1, 11, 3, 61, 15, 249, 225, 937, 1283, 3355, 967],
+    &[1, 1, 1, 15, 23, 29, 13, 171, 447, 527, 253, 3305, 633],
+    &[1, 1, 5, 5, 25, 19, 79, 185, 185, 793, 1037, 537, 2729],
+    &[1, 3, 7, 3, 5, 19, 73, 139, 59, 749, 1255, 669, 7725],
+    &[1, 3, 3, 9, 31, 61, 93, 61, 429, 899, 765, 65, 2753],
+    &[1, 1, 1, 13, 21, 37, 25, 95, 35, 443, 221, 2327, 2515],
+    &[1, 3, 5, 13, 19, 17, 79, 207, 445, 747, 351, 1297, 5429],
+    &[1, 1, 3, 3, 1, 35, 69, 69, 65, 201, 1851, 263, 6825],
+    &[1, 3, 7, 7, 27, 27, 67, 111, 415, 519, 1405, 1233, 6329],
+    &[1, 3, 3, 7, 13, 21, 99, 139, 369, 571, 1565, 3397, 1613],
+    &[1, 1, 1, 11, 5, 45, 83, 51, 467, 919, 2025, 1085, 931],
+    &[1, 3, 1, 11, 27, 57, 107, 165, 135, 315, 1157, 2733, 7687],
+    &[1, 1, 3, 7, 15, 27, 45, 221, 365, 615, 1451, 1781, 4577],
+    &[1, 3, 7, 3, 5, 49, 45, 187, 163, 465, 1439, 3307, 1067],
+    &[1, 1, 1, 15, 19, 7, 33, 29, 199, 987, 209, 3701, 4481],
+    &[1, 3, 7, 15, 31, 63, 117, 167, 171, 939, 659, 951, 8003],
+    &[1, 3, 7, 15, 11, 33, 119, 155, 245, 619, 1347, 2253, 2823],
+    &[1, 1, 5, 7, 25, 11, 99, 103, 411, 755, 973, 843, 6053],
+    &[1, 3, 1, 11, 13, 43, 33, 235, 199, 145, 1545, 537, 4085],
+    &[1, 1, 3, 7, 25, 31, 115, 155, 193, 899, 1315, 783, 2421],
+    &[1, 1, 3, 1, 13, 5, 113, 103, 189, 813, 1101, 1409, 963],
+    &[1, 1, 1, 9, 1, 51, 41, 165, 439, 535, 135, 3317, 1251],
+    &[1, 3, 1, 13, 23, 1, 85, 215, 511, 17, 937, 677, 5223],
+    &[1, 1, 5, 3, 9, 39, 49, 233, 323, 169, 513, 2143, 2073],
+    &[1, 1, 3, 7, 13, 13, 29, 119, 135, 279, 2033, 197, 2515],
+    &[1, 3, 3, 7, 15, 37, 25, 247, 501, 1011, 687, 3655, 4071],
+    &[1, 3, 3, 7, 9, 37, 1, 191, 455, 147, 1779, 15, 7863],
+    &[1, 3, 3, 7, 19, 5, 119, 107, 65, 757, 897, 2019, 1349],
+    &[1, 3, 1, 11, 15, 13, 79, 29, 335, 873, 1053, 545, 6229],
+    &[1, 1, 5, 3, 9, 61, 101, 199, 351, 887, 115, 2761, 6467],
+    &[1, 1, 7, 9, 9, 5, 21, 107, 191, 575, 1491, 3001, 8175],
+    &[1, 3, 5, 13, 13, 59, 37, 115, 251, 637, 817, 2789, 4741],
+    &[1, 3, 5, 3, 23, 23, 123, 219, 453, 547, 1629, 1283, 1609],
+    &[1, 3, 7, 3, 13, 61, 59, 43, 503, 459, 31, 55, 5601],
+    &[1, 1, 7, 11, 13, 19, 65, 71, 251, 593, 1107, 2253, 4957],
+    &[1, 1, 5, 5, 5, 53, 69, 89, 73, 671, 569, 3595, 4019],
+    &[1, 3, 5, 13, 9, 47, 39, 155, 315, 89, 1243, 2307, 5455],
+    &[1, 1, 3, 7, 23, 33, 7, 237, 33, 65, 101, 635, 413],
+    &[1, 3, 1, 11, 3, 29, 107, 249, 335, 235, 109, 2809, 5117],
+    &[1, 1, 1, 9, 31, 63, 3, 71, 327, 151, 965, 2919, 5025],
+    &[1, 3, 1, 11, 27, 17, 29, 29, 61, 571, 661, 2387, 1061],
+    &[1, 1, 5, 3, 1, 25, 7, 173, 87, 957, 1669, 1581, 6647],
+    &[1, 1, 5, 3, 13, 9, 115, 109, 219, 947, 1087, 3455, 1681],
+    &[1, 1, 5, 5, 17, 37, 73, 13, 253, 495, 1767, 3257, 7701],
+    &[1, 1, 7, 13, 31, 39, 51, 9, 265, 109, 7, 2435, 3947],
+    &[1, 3, 5, 15, 5, 11, 107, 143, 3, 321, 1227, 1269, 5403],
+    &[1, 3, 1, 11, 1, 53, 61, 237, 479, 417, 263, 3489, 8089],
+    &[1, 1, 7, 9, 11, 55, 37, 223, 141, 127, 1909, 735, 4883],
+    &[1, 3, 1, 7, 17, 47, 117, 187, 63, 603, 1877, 1291, 6341],
+    &[1, 1, 7, 15, 17, 47, 83, 33, 157, 137, 1, 965, 617],
+    &[1, 1, 3, 1, 3, 7, 39, 79, 149, 683, 1935, 2331, 4619],
+    &[1, 3, 3, 7, 17, 27, 33, 175, 389, 525, 1587, 2557, 377],
+    &[1, 1, 5, 7, 29, 29, 103, 19, 169, 11, 931, 185, 7043],
+    &[1, 1, 7, 15, 19, 1, 67, 147, 239, 71, 369, 1475, 7049],
+    &[1, 1, 7, 9, 3, 25, 47, 129, 319, 801, 219, 3493, 5509],
+    &[1, 3, 7, 15, 13, 9, 91, 251, 379, 325, 1851, 577, 3727],
+    &[1, 1, 5, 5, 27, 35, 37, 133, 461, 409, 395, 509, 5801],
+    &[1, 3, 5, 15, 11, 43, 9, 91, 327, 489, 565, 465, 6747],
+    &[1, 3, 3, 5, 23, 21, 53, 225, 297, 103, 237, 591, 805],
+    &[1, 3, 7, 3, 5, 39, 1, 41, 385, 961, 853, 2265, 1801],
+    &[1, 3, 5, 15, 9, 51, 87, 153, 463, 501, 1509, 2173, 1695],
+    &[1, 1, 5, 5, 19, 13, 111, 193, 457, 499, 557, 563, 4169],
+    &[1, 3, 5, 3, 1, 61, 115, 81, 161, 1015, 173, 3135, 3311],
+    &[1, 1, 7, 9, 11, 17, 53, 71, 225, 97, 1883, 1503, 349],
+    &[1, 3, 3, 7, 23, 15, 105, 175, 23, 21, 651, 1233, 8007],
+    &[1, 3, 5, 15, 11, 53, 43, 11, 59, 339, 1077, 2711, 2187],
+    &[1, 1, 5, 3, 1, 21, 1, 127, 31, 409, 245, 815, 2879],
+    &[1, 1, 7, 11, 15, 23, 71, 127, 117, 939, 1489, 2225, 2647],
+    &[1, 3, 1, 9, 1, 21, 53, 255, 351, 149, 581, 937, 6731],
+    &[1, 1, 5, 5, 27, 43, 55, 85, 465, 89, 1829, 3661, 2771],
+    &[1, 3, 3, 3, 19, 43, 113, 87, 483, 181, 1363, 1151, 5917],
+    &[1, 3, 5, 1, 29, 45, 49, 35, 63, 753, 637, 1903, 7543],
+    &[1, 1, 3, 1, 23, 45, 77, 255, 359, 587, 1603, 3695, 553],
+    &[1, 3, 1, 11, 5, 29, 55, 121, 459, 71, 349, 497, 2149],
+    &[1, 3, 7, 13, 15, 21, 115, 175, 299, 119, 947, 2045, 1069],
+    &[1, 3, 3, 11, 27, 41, 117, 97, 473, 433, 563, 2811, 5565],
+    &[1, 1, 7, 15, 17, 9, 55, 133, 135, 245, 529, 1667, 4667],
+    &[1, 1, 5, 9, 15, 7, 27, 191, 87, 27, 353, 2231, 4185],
+    &[1, 1, 1, 9, 1, 57, 23, 133, 155, 613, 853, 1277, 1891],
+    &[1, 3, 3, 7, 19, 1, 29, 249, 333, 737, 1783, 1681, 459],
+    &[1, 3, 7, 15, 19, 5, 13, 77, 413, 603, 395, 1605, 5003],
+    &[1, 1, 5, 7, 27, 23, 55, 203, 373, 277, 1867, 681, 759],
+    &[1, 3, 3, 5, 23, 3, 113, 65, 443, 269, 1109, 349, 4551],
+    &[1, 3, 1, 15, 21, 49, 121, 171, 131, 115, 765, 3811, 4775],
+    &[1, 3, 5, 15, 17, 47, 59, 237, 77, 567, 1905, 3347, 3571],
+    &[1, 3, 7, 15, 19, 29, 25, 111, 335, 195, 189, 953, 4975],
+    &[1, 1, 7, 11, 9, 19, 53, 255, 77, 829, 1787, 1445, 2309],
+    &[1, 3, 3, 7, 9, 39, 103, 29, 475, 255, 1267, 29, 1447],
+    &[1, 1, 1, 11, 11, 1, 97, 195, 231, 301, 311, 3133, 4167],
+    &[1, 1, 1, 9, 5, 63, 63, 169, 387, 285, 921, 187, 2763],
+    &[1, 1, 7, 11, 11, 11, 35, 139, 95, 607, 1173, 703, 5465],
+    &[1, 3, 1, 13, 29, 41, 115, 93, 101, 775, 1433, 2297, 5819],
+    &[1, 3, 3, 1, 1, 13, 3, 133, 23, 239, 1779, 75, 5039],
+    &[1, 1, 5, 5, 17, 9, 11, 247, 29, 617, 1707, 1413, 2021],
+    &[1, 1, 3, 3, 1, 19, 9, 195, 123, 129, 1145, 1325, 2249],
+    &[1, 1, 1, 11, 9, 25, 105, 85, 391, 1009, 77, 4047, 4711],
+    &[1, 1, 7, 11, 13, 1, 23, 203, 317, 259, 1565, 985, 3163],
+    &[1, 1, 3, 1, 3, 13, 89, 141, 25, 725, 1589, 3455, 5721],
+    &[1, 1, 3, 3, 1, 55, 123, 21, 79, 567, 407, 2621, 1415],
+    &[1, 3, 5, 3, 29, 33, 77, 51, 403, 277, 205, 3183, 3487],
+    &[1, 1, 7, 1, 31, 37, 83, 219, 167, 937, 1539, 3591, 6351],
+    &[1, 1, 5, 7, 27, 19, 115, 105, 59, 29, 613, 2587, 5291],
+    &[1, 3, 3, 7, 17, 1, 1, 61, 333, 565, 1239, 2361, 939],
+    &[1, 3, 1, 1, 3, 5, 39, 143, 495, 275, 1499, 229, 7183],
+    &[1, 3, 5, 13, 11, 33, 101, 99, 247, 99, 1711, 3107, 6815],
+    &[1, 1, 5, 3, 9, 41, 39, 255, 191, 505, 1459, 1149, 583],
+    &[1, 1, 3, 5, 31, 37, 97, 163, 239, 309, 203, 3835, 1739],
+    &[1, 3, 7, 3, 5, 3, 29, 171, 437, 145, 37, 249, 3361],
+    &[1, 3, 7, 9, 21, 33, 31, 233, 19, 841, 997, 1995, 3993],
+    &[1, 1, 5, 5, 29, 21, 13, 73, 11, 371, 1101, 2881, 8037],
+    &[1, 1, 5, 3, 3, 31, 63, 209, 95, 869, 315, 19, 1225],
+    &[1, 1, 5, 5, 17, 55, 93, 9, 199, 893, 1139, 3593, 5407],
+    &[1, 3, 7, 3, 5, 35, 15, 117, 1, 571, 313, 2263, 7409],
+    &[1, 1, 1, 15, 19, 53, 81, 71, 71, 609, 1453, 957, 439],
+    &[1, 1, 5, 5, 29, 51, 25, 207, 201, 273, 383, 1099, 4441],
+    &[1, 1, 7, 9, 15, 47, 69, 171, 441, 779, 1889, 3871, 4537],
+    &[1, 3, 3, 7, 5, 17, 77, 5, 413, 321, 165, 857, 3911],
+    &[1, 1, 7, 11, 1, 1, 39, 107, 475, 3, 163, 3389, 701],
+    &[1, 3, 5, 13, 5, 61, 91, 149, 331, 827, 583, 3629, 4671],
+    &[1, 3, 1, 11, 3, 49, 49, 21, 251, 7, 1853, 2545, 3507],
+    &[1, 3, 1, 11, 25, 11, 121, 73, 295, 451, 1247, 3137, 799],
+    &[1, 3, 1, 7, 21, 53, 113, 229, 23, 603, 1773, 111, 129],
+    &[1, 3, 5, 9, 21, 15, 105, 135, 379, 375, 1857, 3997, 5741],
+    &[1, 3, 1, 11, 5, 19, 109, 15, 225, 179, 773, 177, 7813],
+    &[1, 1, 3, 1, 13, 17, 13, 19, 217, 693, 843, 289, 7607],
+    &[1, 1, 5, 5, 9, 17, 77, 175, 183, 93, 1575, 2101, 387],
+    &[1, 3, 7, 3, 7, 13, 39, 195, 507, 437, 1979, 1101, 7483],
+    &[1, 3, 1, 5, 17, 3, 15, 199, 287, 491, 1735, 3209, 6877],
+    &[1, 3, 3, 7, 17, 11, 111, 243, 51, 107, 1047, 2933, 3105],
+    &[1, 3, 1, 11, 15, 47, 47, 101, 491, 571, 1879, 3157, 5259],
+    &[1, 3, 7, 3, 27, 35, 29, 183, 93, 41, 1577, 2673, 7331],
+    &[1, 1, 3, 13, 15, 3, 115, 113, 351, 21, 1753, 3343, 7325],
+    &[1, 1, 3, 7, 13, 25, 3, 127, 427, 391, 867, 1471, 2135],
+    &[1, 3, 5, 3, 25, 51, 95, 47, 5, 495, 1159, 2521, 2877],
+    &[1, 3, 1, 1, 15, 39, 89, 207, 15, 557, 1873, 3535, 163],
+    &[1, 1, 5, 5, 31, 59, 89, 241, 161, 21, 657, 2723, 2985],
+    &[1, 1, 3, 7, 27, 41, 101, 99, 277, 547, 305, 3739, 943],
+    &[1, 3, 3, 11, 7, 15, 87, 247, 95, 543, 1281, 3109, 6843],
+    &[1, 3, 3, 7, 17, 7, 59, 47, 355, 855, 1079, 227, 5709],
+    &[1, 1, 3, 7, 17, 15, 7, 79, 279, 409, 605, 2711, 5333],
+    &[1, 3, 3, 5, 17, 3, 117, 255, 43, 329, 1343, 1541, 4623],
+    &[1, 3, 7, 3, 15, 23, 117, 215, 159, 723, 525, 675, 39],
+    &[1, 3, 3, 5, 17, 1, 67, 225, 317, 271, 1553, 4077, 5071],
+    &[1, 3, 5, 1, 25, 41, 21, 127, 265, 769, 771, 3337, 1407],
+    &[1, 1, 1, 5, 17, 3, 45, 63, 249, 7, 1987, 2047, 7005],
+    &[1, 1, 7, 15, 19, 7, 5, 169, 37, 381, 1669, 855, 6197],
+    &[1, 3, 5, 15, 9, 63, 29, 53, 51, 433, 1325, 1115, 5861],
+    &[1, 3, 5, 3, 29, 23, 99, 241, 373, 409, 1093, 2713, 6649],
+    &[1, 3, 1, 9, 3, 45, 5, 251, 491, 301, 687, 3663, 7833],
+    &[1, 3, 1, 11, 13, 55, 23, 239, 11, 417, 113, 361, 1535],
+    &[1, 1, 3, 5, 31, 43, 109, 19, 509, 593, 295, 2691, 7177],
+    &[1, 3, 1, 11, 29, 21, 109, 25, 489, 353, 751, 1935, 7065],
+    &[1, 1, 5, 5, 19, 3, 15, 209, 133, 71, 1237, 1725, 6055],
+    &[1, 1, 7, 9, 7, 7, 113, 255, 269, 489, 61, 3657, 3017],
+    &[1, 3, 3, 5, 19, 15, 93, 225, 259, 459, 785, 2275, 4353],
+    &[1, 3, 7, 13, 17, 5, 9, 97, 155, 937, 51, 2331, 3673],
+    &[1, 1, 7, 9, 9, 35, 77, 133, 405, 299, 1467, 3441, 5217],
+    &[1, 1, 1, 9, 5, 51, 127, 237, 263, 793, 1135, 1015, 713],
+    &[1, 3, 5, 15, 9, 9, 1, 189, 487, 571, 2039, 3019, 8163],
+    &[1, 1, 3, 1, 27, 31, 3, 109, 247, 391, 197, 697, 245],
+    &[1, 1, 5, 7, 17, 13, 17, 43, 99, 67, 1691, 1081, 3933],
+    &[1, 3, 3, 7, 23, 11, 97, 129, 135, 447, 927, 1669, 733],
+    &[1, 3, 1, 9, 5, 23, 25, 211, 249, 609, 57, 1879, 4201],
+    &[1, 1, 5, 7, 29, 19, 97, 29, 473, 851, 1671, 929, 899],
+    &[1, 3, 3, 7, 9, 31, 39, 225, 377, 319, 799, 3771, 4451],
+    &[1, 3, 7, 5, 7, 45, 49, 133, 417, 241, 511, 3753, 4057],
+    &[1, 1, 3, 1, 5, 9, 7, 1, 343, 617, 713, 1329, 947],
+    &[1, 3, 1, 7, 17, 47, 27, 27, 379, 927, 1111, 133, 933],
+    &[1, 1, 1, 13, 25, 35, 19, 103, 309, 799, 1955, 2271, 795],
+    &[1, 1, 5, 3, 9, 5, 125, 41, 311, 535, 1171, 251, 2551],
+    &[1, 3, 7, 3, 7, 25, 97, 149, 65, 51, 35, 2889, 1165],
+    &[1, 3, 3, 7, 17, 41, 71, 59, 341, 831, 1369, 2361, 2325],
+    &[1, 1, 3, 5, 17, 55, 9, 203, 241, 813, 153, 731, 2147],
+    &[1, 1, 5, 3, 15, 59, 47, 103, 209, 915, 261, 2481, 6607],
+    &[1, 3, 7, 5, 11, 23, 85, 175, 293, 257, 743, 4093, 481],
+    &[1, 3, 1, 11, 1, 55, 45, 253, 201, 445, 1179, 523, 3009],
+    &[1, 3, 5, 15, 11, 29, 77, 47, 25, 887, 771, 2019, 1619],
+    &[1, 1, 1, 11, 7, 15, 45, 253, 151, 349, 729, 997, 6041],
+    &[1, 1, 1, 9, 11, 59, 51, 107, 51, 209, 223, 65, 2503],
+    &[1, 1, 3, 3, 7, 29, 71, 23, 245, 803, 275, 1943, 2481],
+    &[1, 1, 5, 1, 13, 49, 123, 165, 449, 959, 1011, 1005, 2019],
+    &[1, 3, 1, 11, 1, 49, 59, 221, 93, 871, 1277, 741, 1053],
+    &[1, 3, 7, 9, 25, 13, 49, 165, 499, 683, 1061, 2203, 2991],
+    &[1, 3, 3, 7, 13, 55, 39, 9, 281, 873, 1853, 2987, 3735],
+    &[1, 3, 5, 9, 29, 43, 73, 35, 253, 767, 559, 173, 7649],
+    &[1, 1, 5, 3, 11, 57, 97, 33, 325, 425, 317, 1885, 8105],
+    &[1, 3, 3, 5, 17, 13, 53, 195, 67, 55, 1325, 951, 3401],
+    &[1, 1, 1, 9, 9, 11, 117, 85, 7, 61, 741, 2993, 1569],
+    &[1, 1, 5, 5, 17, 19, 91, 33, 163, 679, 1117, 633, 8119],
+    &[1, 1, 1, 15, 17, 43, 3, 187, 487, 137, 545, 1685, 5791],
+    &[1, 3, 5, 1, 29, 23, 127, 29, 171, 809, 1099, 1653, 5821],
+    &[1, 1, 5, 5, 19, 43, 71, 253, 21, 319, 1537, 4033, 2159],
+    &[1, 3, 5, 3, 29, 45, 121, 171, 155, 995, 573, 1825, 2437],
+    &[1, 3, 7, 1, 3, 57, 69, 63, 157, 103, 11, 93, 6245],
+    &[1, 1, 1, 15, 25, 35, 25, 49, 185, 171, 21, 3939, 5203],
+    &[1, 1, 7, 9, 11, 29, 69, 179, 123, 7, 1751, 1185, 6411],
+    &[1, 1, 1, 15, 19, 23, 65, 101, 461, 933, 1011, 1441, 6951],
+];
@@ -0,0 +1,41 @@
+/// Marker for the geometric meaning of a tuple.
+///
+/// `Tuple2` and `Tuple3` carry a kind parameter so that vectors, points and
+/// normals share one implementation of their component-wise algebra while
+/// remaining distinct types. Operators that only make sense for some kinds
+/// (e.g. adding two tuples together) are gated on the marker traits below.
+pub trait TupleKind {
+    /// Prefix used when formatting the tuple, e.g. `Point` for `Point3`.
+    const NAME: &'static str;
+}
+
+/// Kinds that are closed under addition and subtraction. Points are affine and
+/// deliberately excluded: the difference of two points is a vector, and their
+/// sum has no geometric meaning.
+pub trait Linear: TupleKind {}
+
+/// A bare tuple with no geometric interpretation.
+pub enum Plain {}
+/// A direction or displacement.
+pub enum VectorKind {}
+/// A position.
+pub enum PointKind {}
+/// A surface normal.
+pub enum NormalKind {}
+
+impl TupleKind for Plain {
+    const NAME: &'static str = "Tuple";
+}
+impl TupleKind for VectorKind {
+    const NAME: &'static str = "Vector";
+}
+impl TupleKind for PointKind {
+    const NAME: &'static str = "Point";
+}
+impl TupleKind for NormalKind {
+    const NAME: &'static str = "Normal";
+}
+
+impl Linear for Plain {}
+impl Linear for VectorKind {}
+impl Linear for NormalKind {}
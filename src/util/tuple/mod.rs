@@ -1,5 +1,7 @@
+mod kind;
 mod tuple2;
 mod tuple3;
 
+pub use kind::{Linear, NormalKind, Plain, PointKind, TupleKind, VectorKind};
 pub use tuple2::Tuple2;
 pub use tuple3::Tuple3;
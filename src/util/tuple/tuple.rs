@@ -1,4 +0,0 @@
-pub trait Tuple<T> {
-    const nDimensions: usize;
-    fn Add<T>(&self, other: &self);
-}
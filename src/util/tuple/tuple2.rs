@@ -1,44 +1,147 @@
-use num_traits::Float;
-use std::{
-    f64::consts::SQRT_2,
-    ops::{Add, Mul, Sub},
+use num_traits::{Float, Signed};
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::{
+    Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign,
 };
-#[derive(Debug)]
-pub struct Tuple2<T> {
+
+use crate::util::tuple::kind::{Linear, Plain, TupleKind};
+
+/// A generic 2-dimensional tuple, the two-component counterpart of `Tuple3`.
+pub struct Tuple2<T, K = Plain> {
     pub x: T,
     pub y: T,
+    kind: PhantomData<K>,
+}
+
+impl<T, K> Tuple2<T, K> {
+    /// Creates a new `Tuple2`. As with `Tuple3::new`, the kind comes from the
+    /// surrounding type.
+    pub const fn new(x: T, y: T) -> Self {
+        Self {
+            x,
+            y,
+            kind: PhantomData,
+        }
+    }
+
+    /// Applies `f` to every component, keeping the kind.
+    pub fn map<U>(self, f: impl Fn(T) -> U) -> Tuple2<U, K> {
+        Tuple2::new(f(self.x), f(self.y))
+    }
+}
+
+impl<T: Copy, K> Tuple2<T, K> {
+    pub fn permute(&self, perm: [usize; 2]) -> Self {
+        Self::new(self[perm[0]], self[perm[1]])
+    }
+}
+
+impl<T, K> Tuple2<T, K>
+where
+    T: PartialOrd + Copy,
+{
+    pub fn min(&self, other: &Self) -> Self {
+        Self::new(
+            if self.x < other.x { self.x } else { other.x },
+            if self.y < other.y { self.y } else { other.y },
+        )
+    }
+    pub fn max(&self, other: &Self) -> Self {
+        Self::new(
+            if self.x > other.x { self.x } else { other.x },
+            if self.y > other.y { self.y } else { other.y },
+        )
+    }
+    pub fn min_component_value(&self) -> T {
+        if self.x < self.y { self.x } else { self.y }
+    }
+    pub fn max_component_value(&self) -> T {
+        if self.x > self.y { self.x } else { self.y }
+    }
+    pub fn min_component_index(&self) -> usize {
+        if self.x < self.y { 0 } else { 1 }
+    }
+    pub fn max_component_index(&self) -> usize {
+        if self.x > self.y { 0 } else { 1 }
+    }
 }
 
-impl<T> Tuple2<T> {
-    pub fn new(x: T, y: T) -> Self {
-        Self { x, y }
+impl<T, K> Tuple2<T, K>
+where
+    T: Signed + Copy,
+{
+    pub fn abs(&self) -> Self {
+        Self::new(self.x.abs(), self.y.abs())
     }
 }
 
-impl<T> Tuple2<T>
+impl<T, K> Tuple2<T, K>
 where
     T: Float,
 {
-    pub fn length(&self) -> T {
-        let x = self.x * self.x + self.y * self.y;
-        x.sqrt()
+    pub fn floor(&self) -> Self {
+        Self::new(self.x.floor(), self.y.floor())
+    }
+    pub fn ceil(&self) -> Self {
+        Self::new(self.x.ceil(), self.y.ceil())
+    }
+    pub fn has_nan(&self) -> bool {
+        self.x.is_nan() || self.y.is_nan()
+    }
+    pub fn lerp(t: T, a: &Self, b: &Self) -> Self {
+        let s = T::one() - t;
+        Self::new(s * a.x + t * b.x, s * a.y + t * b.y)
     }
 }
-impl<T> Tuple2<T>
+
+impl<T, K: Linear> Tuple2<T, K>
 where
     T: Add<Output = T> + Mul<Output = T> + Copy,
 {
     pub fn dot(&self, other: &Self) -> T {
         self.x * other.x + self.y * other.y
     }
-    pub fn from(tuple: &Tuple2<T>) -> Self {
-        Self {
-            x: tuple.x,
-            y: tuple.y,
-        }
+    pub fn length_squared(&self) -> T {
+        self.dot(self)
     }
 }
-impl<T> PartialEq for Tuple2<T>
+
+impl<T, K: Linear> Tuple2<T, K>
+where
+    T: Float,
+{
+    pub fn length(&self) -> T {
+        self.length_squared().sqrt()
+    }
+    pub fn normalize(&self) -> Self {
+        *self / self.length()
+    }
+}
+
+impl<T: Clone, K> Clone for Tuple2<T, K> {
+    fn clone(&self) -> Self {
+        Self::new(self.x.clone(), self.y.clone())
+    }
+}
+impl<T: Copy, K> Copy for Tuple2<T, K> {}
+
+impl<T: Default, K> Default for Tuple2<T, K> {
+    fn default() -> Self {
+        Self::new(T::default(), T::default())
+    }
+}
+
+impl<T: fmt::Debug, K: TupleKind> fmt::Debug for Tuple2<T, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(&format!("{}2", K::NAME))
+            .field("x", &self.x)
+            .field("y", &self.y)
+            .finish()
+    }
+}
+
+impl<T, K> PartialEq for Tuple2<T, K>
 where
     T: PartialEq,
 {
@@ -46,51 +149,172 @@ where
         self.x == other.x && self.y == other.y
     }
 }
-impl<T> Add for Tuple2<T>
+
+impl<T, K> From<[T; 2]> for Tuple2<T, K> {
+    fn from([x, y]: [T; 2]) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl<T, K> Index<usize> for Tuple2<T, K> {
+    type Output = T;
+    fn index(&self, i: usize) -> &Self::Output {
+        match i {
+            0 => &self.x,
+            1 => &self.y,
+            _ => panic!("index out of bounds"),
+        }
+    }
+}
+impl<T, K> IndexMut<usize> for Tuple2<T, K> {
+    fn index_mut(&mut self, i: usize) -> &mut Self::Output {
+        match i {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            _ => panic!("index out of bounds"),
+        }
+    }
+}
+
+impl<T, K> Neg for Tuple2<T, K>
+where
+    T: Neg<Output = T>,
+{
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl<T, K: Linear> Add for Tuple2<T, K>
 where
     T: Add<Output = T>,
 {
     type Output = Self;
     fn add(self, other: Self) -> Self {
-        Self {
-            x: self.x + other.x,
-            y: self.y + other.y,
-        }
+        Self::new(self.x + other.x, self.y + other.y)
     }
 }
-impl<T> Sub for Tuple2<T>
+impl<T, K: Linear> AddAssign for Tuple2<T, K>
+where
+    T: AddAssign,
+{
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+impl<T, K: Linear> Sub for Tuple2<T, K>
 where
     T: Sub<Output = T>,
 {
     type Output = Self;
     fn sub(self, other: Self) -> Self {
-        Self {
-            x: self.x - other.x,
-            y: self.y - other.y,
-        }
+        Self::new(self.x - other.x, self.y - other.y)
+    }
+}
+impl<T, K: Linear> SubAssign for Tuple2<T, K>
+where
+    T: SubAssign,
+{
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
     }
 }
 
-impl<T> Mul for Tuple2<T>
+impl<T, K: Linear> Mul for Tuple2<T, K>
 where
-    T: Mul<Output = T> + Copy,
+    T: Mul<Output = T>,
 {
     type Output = Self;
     fn mul(self, other: Self) -> Self {
-        Self {
-            x: self.x * other.x,
-            y: self.y * other.y,
-        }
+        Self::new(self.x * other.x, self.y * other.y)
+    }
+}
+impl<T, K: Linear> MulAssign for Tuple2<T, K>
+where
+    T: MulAssign,
+{
+    fn mul_assign(&mut self, rhs: Self) {
+        self.x *= rhs.x;
+        self.y *= rhs.y;
+    }
+}
+impl<T, K: Linear> Div for Tuple2<T, K>
+where
+    T: Div<Output = T>,
+{
+    type Output = Self;
+    fn div(self, other: Self) -> Self {
+        Self::new(self.x / other.x, self.y / other.y)
+    }
+}
+impl<T, K: Linear> DivAssign for Tuple2<T, K>
+where
+    T: DivAssign,
+{
+    fn div_assign(&mut self, rhs: Self) {
+        self.x /= rhs.x;
+        self.y /= rhs.y;
     }
 }
 
-type Vector2i = Tuple2<i32>;
-type Vector2f = Tuple2<f32>;
+impl<T, K> Mul<T> for Tuple2<T, K>
+where
+    T: Mul<Output = T> + Copy,
+{
+    type Output = Self;
+    fn mul(self, rhs: T) -> Self::Output {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+impl<T, K> MulAssign<T> for Tuple2<T, K>
+where
+    T: MulAssign + Copy,
+{
+    fn mul_assign(&mut self, rhs: T) {
+        self.x *= rhs;
+        self.y *= rhs;
+    }
+}
+impl<T, K> Div<T> for Tuple2<T, K>
+where
+    T: Div<Output = T> + Copy,
+{
+    type Output = Self;
+    fn div(self, rhs: T) -> Self::Output {
+        Self::new(self.x / rhs, self.y / rhs)
+    }
+}
+impl<T, K> DivAssign<T> for Tuple2<T, K>
+where
+    T: DivAssign + Copy,
+{
+    fn div_assign(&mut self, rhs: T) {
+        self.x /= rhs;
+        self.y /= rhs;
+    }
+}
+
+macro_rules! impl_scalar_mul_tuple2 {
+    ($($t:ty),*) => {
+        $(
+            impl<K> Mul<Tuple2<$t, K>> for $t {
+                type Output = Tuple2<$t, K>;
+                fn mul(self, rhs: Tuple2<$t, K>) -> Self::Output {
+                    rhs * self
+                }
+            }
+        )*
+    };
+}
+impl_scalar_mul_tuple2!(f32, f64, i32);
 
 #[test]
 fn test_tuple2_dot() {
-    let t1 = Tuple2::new(1, 2);
-    let t2 = Tuple2::new(3, 4);
+    let t1 = Tuple2::<_>::new(1, 2);
+    let t2 = Tuple2::<_>::new(3, 4);
     let result = t1.dot(&t2);
 
     assert_eq!(result, 11);
@@ -98,27 +322,39 @@ fn test_tuple2_dot() {
 
 #[test]
 fn test_tuple2_add() {
-    let t1 = Tuple2::new(1, 2);
-    let t2 = Tuple2::new(3, 4);
+    let t1 = Tuple2::<_>::new(1, 2);
+    let t2 = Tuple2::<_>::new(3, 4);
     let result = t1 + t2;
 
-    assert_eq!(result, Tuple2::new(4, 6));
+    assert_eq!(result, Tuple2::<_>::new(4, 6));
 }
 
 #[test]
 fn test_tuple2_sub() {
-    let t1 = Tuple2::new(1, 2);
-    let t2 = Tuple2::new(3, 4);
+    let t1 = Tuple2::<_>::new(1, 2);
+    let t2 = Tuple2::<_>::new(3, 4);
     let result = t1 - t2;
 
-    assert_eq!(result, Tuple2::new(-2, -2));
+    assert_eq!(result, Tuple2::<_>::new(-2, -2));
 }
 
 #[test]
 fn test_tuple2_mul() {
-    let t1 = Tuple2::new(1, 2);
-    let t2 = Tuple2::new(3, 4);
+    let t1 = Tuple2::<_>::new(1, 2);
+    let t2 = Tuple2::<_>::new(3, 4);
     let result = t1 * t2;
 
-    assert_eq!(result, Tuple2::new(3, 8));
+    assert_eq!(result, Tuple2::<_>::new(3, 8));
+}
+
+#[test]
+fn test_tuple2_assign_ops() {
+    let mut t = Tuple2::<_>::new(1.0, 2.0);
+    t += Tuple2::<_>::new(1.0, 1.0);
+    t *= 2.0;
+    t /= Tuple2::<_>::new(2.0, 3.0);
+    assert_eq!(t, Tuple2::<_>::new(2.0, 2.0));
+    t[1] = 5.0;
+    assert_eq!(t.permute([1, 0]), Tuple2::<_>::new(5.0, 2.0));
+    assert_eq!(t.max_component_index(), 1);
 }
@@ -1,24 +1,38 @@
-use num_traits::Float;
-use std::ops::{Add, Index, Mul, Sub};
+use num_traits::{Float, Signed};
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::{
+    Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign,
+};
+
+use crate::util::tuple::kind::{Linear, Plain, TupleKind};
 
 /// A generic 3-dimensional tuple structure.
 ///
 /// This structure represents a tuple with three components (x, y, z),
 /// commonly used in 3D graphics for vectors, points, and colors.
 /// It supports generic types allowing flexibility in numeric representations.
-#[derive(Debug)]
-pub struct Tuple3<T> {
+///
+/// The `K` parameter records what the tuple means geometrically (see
+/// [`TupleKind`]). All component-wise algebra is implemented here once;
+/// `Vector3`, `Point3` and `Normal3` are aliases that pick a kind.
+pub struct Tuple3<T, K = Plain> {
     /// The x-component (first element) of the tuple
     pub x: T,
     /// The y-component (second element) of the tuple
     pub y: T,
     /// The z-component (third element) of the tuple
     pub z: T,
+    kind: PhantomData<K>,
 }
 
-impl<T> Tuple3<T> {
+impl<T, K> Tuple3<T, K> {
     /// Creates a new `Tuple3` with the specified x, y, and z components.
     ///
+    /// The kind is taken from the surrounding type, so `Vector3::new` builds a
+    /// vector and `Point3::new` a point. A bare tuple needs the kind pinned
+    /// down, e.g. `Tuple3::<_>::new(1, 2, 3)` uses the `Plain` default.
+    ///
     /// # Arguments
     /// * `x` - The first component
     /// * `y` - The second component
@@ -26,11 +40,111 @@ impl<T> Tuple3<T> {
     ///
     /// # Returns
     /// A new `Tuple3` instance
-    pub fn new(x: T, y: T, z: T) -> Self {
-        Self { x, y, z }
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self {
+            x,
+            y,
+            z,
+            kind: PhantomData,
+        }
+    }
+
+    /// Applies `f` to every component, keeping the kind.
+    ///
+    /// This is how integer tuples are converted to floating point ones and
+    /// vice versa.
+    pub fn map<U>(self, f: impl Fn(T) -> U) -> Tuple3<U, K> {
+        Tuple3::new(f(self.x), f(self.y), f(self.z))
+    }
+}
+
+impl<T: Copy, K> Tuple3<T, K> {
+    /// Reorders the components, e.g. `[1, 2, 0]` yields `(y, z, x)`.
+    pub fn permute(&self, perm: [usize; 3]) -> Self {
+        Self::new(self[perm[0]], self[perm[1]], self[perm[2]])
+    }
+}
+
+impl<T, K> Tuple3<T, K>
+where
+    T: PartialOrd + Copy,
+{
+    /// Component-wise minimum of two tuples.
+    pub fn min(&self, other: &Self) -> Self {
+        Self::new(
+            if self.x < other.x { self.x } else { other.x },
+            if self.y < other.y { self.y } else { other.y },
+            if self.z < other.z { self.z } else { other.z },
+        )
+    }
+    /// Component-wise maximum of two tuples.
+    pub fn max(&self, other: &Self) -> Self {
+        Self::new(
+            if self.x > other.x { self.x } else { other.x },
+            if self.y > other.y { self.y } else { other.y },
+            if self.z > other.z { self.z } else { other.z },
+        )
+    }
+    pub fn min_component_value(&self) -> T {
+        let m = if self.x < self.y { self.x } else { self.y };
+        if m < self.z { m } else { self.z }
+    }
+    pub fn max_component_value(&self) -> T {
+        let m = if self.x > self.y { self.x } else { self.y };
+        if m > self.z { m } else { self.z }
+    }
+    /// Index of the smallest component.
+    pub fn min_component_index(&self) -> usize {
+        if self.x < self.y {
+            if self.x < self.z { 0 } else { 2 }
+        } else if self.y < self.z {
+            1
+        } else {
+            2
+        }
+    }
+    /// Index of the largest component, i.e. the dominant axis.
+    pub fn max_component_index(&self) -> usize {
+        if self.x > self.y {
+            if self.x > self.z { 0 } else { 2 }
+        } else if self.y > self.z {
+            1
+        } else {
+            2
+        }
+    }
+}
+
+impl<T, K> Tuple3<T, K>
+where
+    T: Signed + Copy,
+{
+    pub fn abs(&self) -> Self {
+        Self::new(self.x.abs(), self.y.abs(), self.z.abs())
+    }
+}
+
+impl<T, K> Tuple3<T, K>
+where
+    T: Float,
+{
+    pub fn floor(&self) -> Self {
+        Self::new(self.x.floor(), self.y.floor(), self.z.floor())
+    }
+    pub fn ceil(&self) -> Self {
+        Self::new(self.x.ceil(), self.y.ceil(), self.z.ceil())
+    }
+    pub fn has_nan(&self) -> bool {
+        self.x.is_nan() || self.y.is_nan() || self.z.is_nan()
+    }
+    /// Linear interpolation between `a` (at `t = 0`) and `b` (at `t = 1`).
+    pub fn lerp(t: T, a: &Self, b: &Self) -> Self {
+        let s = T::one() - t;
+        Self::new(s * a.x + t * b.x, s * a.y + t * b.y, s * a.z + t * b.z)
     }
 }
-impl<T> Tuple3<T>
+
+impl<T, K: Linear> Tuple3<T, K>
 where
     T: Add<Output = T> + Mul<Output = T> + Copy,
 {
@@ -44,58 +158,90 @@ where
     /// # Returns
     /// The scalar result of the dot product
     pub fn dot(&self, other: &Self) -> T {
-        self.x * other.x + self.y * other.y + self.z + other.z
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+    pub fn length_squared(&self) -> T {
+        self.dot(self)
     }
 }
-impl<T> Tuple3<T>
+
+impl<T, K: Linear> Tuple3<T, K>
 where
-    T: Copy,
+    T: Signed + Copy,
 {
-    pub fn from(tuple: &Tuple3<T>) -> Self {
-        Self {
-            x: tuple.x,
-            y: tuple.y,
-            z: tuple.z,
-        }
+    pub fn abs_dot(&self, other: &Self) -> T {
+        self.dot(other).abs()
     }
 }
-impl<T> Tuple3<T>
+
+impl<T, K: Linear> Tuple3<T, K>
 where
     T: Float,
 {
     pub fn length(&self) -> T {
-        let x = self.x * self.x + self.y * self.y + self.z * self.z;
-        x.sqrt()
+        self.length_squared().sqrt()
     }
 
     pub fn normalize(&self) -> Self {
-        let len = self.length();
-        Self {
-            x: self.x / len,
-            y: self.y / len,
-            z: self.z / len,
-        }
+        *self / self.length()
     }
 
     pub fn cross(&self, other: &Self) -> Self {
-        Self {
-            x: self.y * other.z - self.z * other.y,
-            y: self.z * other.x - self.x * other.z,
-            z: self.x * other.y - self.y * other.x,
+        Self::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+
+    /// Angle between two tuples of any nonzero length.
+    ///
+    /// Uses the half-angle formulation rather than `acos(dot)` so that nearly
+    /// parallel inputs keep their precision.
+    pub fn angle_between(v1: &Self, v2: &Self) -> T {
+        let (v1, v2) = (&v1.normalize(), &v2.normalize());
+        let two = T::one() + T::one();
+        let safe_asin = |x: T| x.max(-T::one()).min(T::one()).asin();
+        if v1.dot(v2) < T::zero() {
+            let pi = T::one().neg().acos();
+            pi - two * safe_asin((*v1 + *v2).length() / two)
+        } else {
+            two * safe_asin((*v2 - *v1).length() / two)
         }
     }
-    pub fn angle_between(v1: &Tuple3<T>, v2: &Tuple3<T>) -> T {
-        let dot_product = v1.dot(v2);
-        let lengths_product = v1.length() * v2.length();
-        let cos_theta = dot_product / lengths_product;
-        let angle = cos_theta.acos();
-        angle
+}
+
+// Standard traits
+//
+// These are implemented by hand rather than derived so that they do not
+// require anything of the kind marker.
+impl<T: Clone, K> Clone for Tuple3<T, K> {
+    fn clone(&self) -> Self {
+        Self::new(self.x.clone(), self.y.clone(), self.z.clone())
+    }
+}
+impl<T: Copy, K> Copy for Tuple3<T, K> {}
+
+impl<T: Default, K> Default for Tuple3<T, K> {
+    fn default() -> Self {
+        Self::new(T::default(), T::default(), T::default())
+    }
+}
+
+impl<T: fmt::Debug, K: TupleKind> fmt::Debug for Tuple3<T, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(&format!("{}3", K::NAME))
+            .field("x", &self.x)
+            .field("y", &self.y)
+            .field("z", &self.z)
+            .finish()
     }
 }
+
 /// Implementation of equality comparison for `Tuple3`.
 ///
 /// Two tuples are equal if all their corresponding components are equal.
-impl<T> PartialEq for Tuple3<T>
+impl<T, K> PartialEq for Tuple3<T, K>
 where
     T: PartialEq,
 {
@@ -103,13 +249,20 @@ where
         self.x == other.x && self.y == other.y && self.z == other.z
     }
 }
+
+impl<T, K> From<[T; 3]> for Tuple3<T, K> {
+    fn from([x, y, z]: [T; 3]) -> Self {
+        Self::new(x, y, z)
+    }
+}
+
 /// Implementation of indexing for `Tuple3`.
 ///
 /// Allows accessing components by index: 0 for x, 1 for y, 2 for z.
 ///
 /// # Panics
 /// Panics if the index is greater than 2.
-impl<T: Copy> Index<usize> for Tuple3<T> {
+impl<T, K> Index<usize> for Tuple3<T, K> {
     type Output = T;
     fn index(&self, i: usize) -> &Self::Output {
         match i {
@@ -120,21 +273,90 @@ impl<T: Copy> Index<usize> for Tuple3<T> {
         }
     }
 }
+impl<T, K> IndexMut<usize> for Tuple3<T, K> {
+    fn index_mut(&mut self, i: usize) -> &mut Self::Output {
+        match i {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            2 => &mut self.z,
+            _ => panic!("index out of bounds"),
+        }
+    }
+}
+
+impl<T, K> Neg for Tuple3<T, K>
+where
+    T: Neg<Output = T>,
+{
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y, -self.z)
+    }
+}
+
+/// Implementation of addition for `Tuple3`.
+///
+/// Adds each component of the left-hand side tuple with the
+/// corresponding component of the right-hand side tuple.
+impl<T, K: Linear> Add for Tuple3<T, K>
+where
+    T: Add<Output = T>,
+{
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+impl<T, K: Linear> Add for &Tuple3<T, K>
+where
+    T: Add<Output = T> + Copy,
+{
+    type Output = Tuple3<T, K>;
+    fn add(self, other: Self) -> Self::Output {
+        *self + *other
+    }
+}
+impl<T, K: Linear> AddAssign for Tuple3<T, K>
+where
+    T: AddAssign,
+{
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+        self.z += rhs.z;
+    }
+}
+
 /// Implementation of subtraction for `Tuple3`.
 ///
 /// Subtracts each component of the right-hand side tuple from the
 /// corresponding component of the left-hand side tuple.
-impl<T> Sub for Tuple3<T>
+impl<T, K: Linear> Sub for Tuple3<T, K>
 where
     T: Sub<Output = T>,
 {
     type Output = Self;
     fn sub(self, rhs: Self) -> Self::Output {
-        Self {
-            x: self.x - rhs.x,
-            y: self.y - rhs.y,
-            z: self.z - rhs.z,
-        }
+        Self::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+impl<T, K: Linear> Sub for &Tuple3<T, K>
+where
+    T: Sub<Output = T> + Copy,
+{
+    type Output = Tuple3<T, K>;
+    fn sub(self, rhs: Self) -> Self::Output {
+        *self - *rhs
+    }
+}
+impl<T, K: Linear> SubAssign for Tuple3<T, K>
+where
+    T: SubAssign,
+{
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+        self.z -= rhs.z;
     }
 }
 
@@ -142,106 +364,202 @@ where
 ///
 /// Multiplies each component of the left-hand side tuple with the
 /// corresponding component of the right-hand side tuple.
-impl<T> Mul<Tuple3<T>> for Tuple3<T>
+impl<T, K: Linear> Mul<Tuple3<T, K>> for Tuple3<T, K>
 where
     T: Mul<Output = T>,
 {
     type Output = Self;
     fn mul(self, rhs: Self) -> Self::Output {
-        Self {
-            x: self.x * rhs.x,
-            y: self.y * rhs.y,
-            z: self.z * rhs.z,
-        }
+        Self::new(self.x * rhs.x, self.y * rhs.y, self.z * rhs.z)
+    }
+}
+impl<T, K: Linear> MulAssign<Tuple3<T, K>> for Tuple3<T, K>
+where
+    T: MulAssign,
+{
+    fn mul_assign(&mut self, rhs: Self) {
+        self.x *= rhs.x;
+        self.y *= rhs.y;
+        self.z *= rhs.z;
     }
 }
 
-impl<T> Mul<T> for Tuple3<T>
+/// Component-wise division for `Tuple3`.
+impl<T, K: Linear> Div<Tuple3<T, K>> for Tuple3<T, K>
+where
+    T: Div<Output = T>,
+{
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self::Output {
+        Self::new(self.x / rhs.x, self.y / rhs.y, self.z / rhs.z)
+    }
+}
+impl<T, K: Linear> DivAssign<Tuple3<T, K>> for Tuple3<T, K>
+where
+    T: DivAssign,
+{
+    fn div_assign(&mut self, rhs: Self) {
+        self.x /= rhs.x;
+        self.y /= rhs.y;
+        self.z /= rhs.z;
+    }
+}
+
+// Scalar operators
+impl<T, K> Mul<T> for Tuple3<T, K>
 where
     T: Mul<Output = T> + Copy,
 {
     type Output = Self;
     fn mul(self, rhs: T) -> Self::Output {
-        Self {
-            x: self.x * rhs,
-            y: self.y * rhs,
-            z: self.z * rhs,
-        }
+        Self::new(self.x * rhs, self.y * rhs, self.z * rhs)
     }
 }
-/// Implementation of addition for `Tuple3`.
-///
-/// Adds each component of the left-hand side tuple with the
-/// corresponding component of the right-hand side tuple.
-impl<T> Add for Tuple3<T>
+impl<T, K> Mul<T> for &Tuple3<T, K>
 where
-    T: Add<Output = T>,
+    T: Mul<Output = T> + Copy,
+{
+    type Output = Tuple3<T, K>;
+    fn mul(self, rhs: T) -> Self::Output {
+        *self * rhs
+    }
+}
+impl<T, K> MulAssign<T> for Tuple3<T, K>
+where
+    T: MulAssign + Copy,
+{
+    fn mul_assign(&mut self, rhs: T) {
+        self.x *= rhs;
+        self.y *= rhs;
+        self.z *= rhs;
+    }
+}
+impl<T, K> Div<T> for Tuple3<T, K>
+where
+    T: Div<Output = T> + Copy,
 {
     type Output = Self;
-    fn add(self, other: Self) -> Self {
-        Self {
-            x: self.x + other.x,
-            y: self.y + other.y,
-            z: self.z + other.z,
-        }
+    fn div(self, rhs: T) -> Self::Output {
+        Self::new(self.x / rhs, self.y / rhs, self.z / rhs)
+    }
+}
+impl<T, K> DivAssign<T> for Tuple3<T, K>
+where
+    T: DivAssign + Copy,
+{
+    fn div_assign(&mut self, rhs: T) {
+        self.x /= rhs;
+        self.y /= rhs;
+        self.z /= rhs;
     }
 }
 
+// `scalar * tuple` has to be spelled out per scalar type because of the
+// orphan rules.
+macro_rules! impl_scalar_mul_tuple3 {
+    ($($t:ty),*) => {
+        $(
+            impl<K> Mul<Tuple3<$t, K>> for $t {
+                type Output = Tuple3<$t, K>;
+                fn mul(self, rhs: Tuple3<$t, K>) -> Self::Output {
+                    rhs * self
+                }
+            }
+        )*
+    };
+}
+impl_scalar_mul_tuple3!(f32, f64, i32);
+
 /// Test for tuple addition.
 /// Verifies that adding two tuples produces the correct component-wise sum.
 #[test]
 fn add_tupple_3() {
-    let mut tup1 = Tuple3::new(1, 2, 4);
-    let tup2 = Tuple3::new(2, 3, 4);
+    let mut tup1 = Tuple3::<_>::new(1, 2, 4);
+    let tup2 = Tuple3::<_>::new(2, 3, 4);
     tup1 = tup2 + tup1;
-    let result = Tuple3::new(3, 5, 8);
+    let result = Tuple3::<_>::new(3, 5, 8);
     assert_eq!(result, tup1);
 }
-// #[test]
-// fn add_tupple_3_2(){
-//     let  mut tup1 = Tuple3::new(1,2,3);
-//     let tup2 = Tuple3::new(4,5,6);
-//     tup1 += tup2;
-//     let result = Tuple3::new(5,7,9);
-//     assert_eq!(result,tup1)
-// }
+#[test]
+fn add_tupple_3_2() {
+    let mut tup1 = Tuple3::<_>::new(1, 2, 3);
+    let tup2 = Tuple3::<_>::new(4, 5, 6);
+    tup1 += tup2;
+    let result = Tuple3::<_>::new(5, 7, 9);
+    assert_eq!(result, tup1)
+}
 /// Test for tuple multiplication.
 /// Verifies that multiplying two tuples produces the correct component-wise product.
 #[test]
 fn mul_tupple_3() {
-    let mut tup1 = Tuple3::new(1, 2, 4);
-    let tup2 = Tuple3::new(2, 3, 4);
+    let mut tup1 = Tuple3::<_>::new(1, 2, 4);
+    let tup2 = Tuple3::<_>::new(2, 3, 4);
     tup1 = tup2 * tup1;
-    let result = Tuple3::new(2, 6, 16);
+    let result = Tuple3::<_>::new(2, 6, 16);
     assert_eq!(result, tup1);
 }
 /// Test for tuple subtraction.
 /// Verifies that subtracting one tuple from another produces the correct component-wise difference.
 #[test]
+#[allow(clippy::assign_op_pattern)]
 fn sub_tupple_3() {
-    let mut tup1 = Tuple3::new(1, 2, 4);
-    let tup2 = Tuple3::new(2, 3, 4);
+    let mut tup1 = Tuple3::<_>::new(1, 2, 4);
+    let tup2 = Tuple3::<_>::new(2, 3, 4);
     tup1 = tup1 - tup2;
-    let result = Tuple3::new(-1, -1, 0);
+    let result = Tuple3::<_>::new(-1, -1, 0);
     assert_eq!(result, tup1);
 }
 
 /// Test for dot product calculation.
 /// Verifies that the dot product of two tuples is computed correctly.
 /// Expected result: (1*2) + (2*3) + (4*4) = 2 + 6 + 16 = 24
-/// Note: There appears to be a bug in the dot product implementation.
 #[test]
 fn check_dot() {
-    let tup1 = Tuple3::new(1, 2, 4);
-    let tup2 = Tuple3::new(2, 3, 4);
+    let tup1 = Tuple3::<_>::new(1, 2, 4);
+    let tup2 = Tuple3::<_>::new(2, 3, 4);
     let m = tup1.dot(&tup2);
-    assert_eq!(16, m);
+    assert_eq!(24, m);
 }
 
 #[test]
+#[allow(clippy::assign_op_pattern)]
 fn check_scaler_mul() {
-    let mut tup1 = Tuple3::new(1, 2, 4);
-    let result = Tuple3::new(4, 8, 16);
+    let mut tup1 = Tuple3::<_>::new(1, 2, 4);
+    let result = Tuple3::<_>::new(4, 8, 16);
     tup1 = tup1 * 4;
     assert_eq!(tup1, result);
+    assert_eq!(4 * Tuple3::<_>::new(1, 2, 4), result);
+}
+
+#[test]
+fn check_index_mut_and_neg() {
+    let mut tup = Tuple3::<_>::new(1, 2, 3);
+    tup[2] = 7;
+    assert_eq!(-tup, Tuple3::<_>::new(-1, -2, -7));
+}
+
+#[test]
+fn check_min_max_components() {
+    let a = Tuple3::<_>::new(1.0, 5.0, -3.0);
+    let b = Tuple3::<_>::new(2.0, 4.0, -4.0);
+    assert_eq!(a.min(&b), Tuple3::<_>::new(1.0, 4.0, -4.0));
+    assert_eq!(a.max(&b), Tuple3::<_>::new(2.0, 5.0, -3.0));
+    assert_eq!(a.abs(), Tuple3::<_>::new(1.0, 5.0, 3.0));
+    assert_eq!(a.max_component_value(), 5.0);
+    assert_eq!(a.max_component_index(), 1);
+    assert_eq!(a.min_component_index(), 2);
+    assert_eq!(a.permute([2, 0, 1]), Tuple3::<_>::new(-3.0, 1.0, 5.0));
+}
+
+#[test]
+fn check_lerp_floor_ceil() {
+    let a = Tuple3::<_>::new(0.0, 2.0, 4.0);
+    let b = Tuple3::<_>::new(1.0, 4.0, 8.0);
+    assert_eq!(
+        Tuple3::<_>::lerp(0.5, &a, &b),
+        Tuple3::<_>::new(0.5, 3.0, 6.0)
+    );
+    let c = Tuple3::<_>::new(1.5, -1.5, 2.0);
+    assert_eq!(c.floor(), Tuple3::<_>::new(1.0, -2.0, 2.0));
+    assert_eq!(c.ceil(), Tuple3::<_>::new(2.0, -1.0, 2.0));
 }
@@ -1,7 +1,17 @@
 // Common type definitions used throughout the project
 
-/// The floating-point type used for all computations
+/// The floating-point type used for all computations.
+///
+/// Single precision by default; build with the `float-as-double` feature for
+/// double precision.
+#[cfg(not(feature = "float-as-double"))]
 pub type Float = f32;
 
+/// The floating-point type used for all computations.
+///
+/// Double precision, selected by the `float-as-double` feature.
+#[cfg(feature = "float-as-double")]
+pub type Float = f64;
+
 /// Integer type for indexing and counts
 pub type Int = i32;
@@ -1,2 +1,10 @@
+mod normal3;
+mod point2;
+mod point3;
+mod vector2;
 mod vector3;
-pub use vector3::{Normal3, Point3, Vector3};
+pub use normal3::{Normal3, Normal3f};
+pub use point2::{Point2, Point2f, Point2i};
+pub use point3::{Point3, Point3f, Point3i};
+pub use vector2::{Vector2, Vector2f, Vector2i};
+pub use vector3::{Vector3, Vector3f, Vector3i};
@@ -0,0 +1,68 @@
+use num_traits::{Float as FloatOps, Signed};
+use std::ops::{Add, Mul};
+
+use crate::util::Float;
+use crate::util::tuple::{NormalKind, Tuple3};
+use crate::util::vector::Vector3;
+
+/// A surface normal.
+///
+/// Normals share their storage with `Vector3` but are not interchangeable with
+/// it: they transform differently and cannot be added to points. Conversions
+/// between the two must be spelled out with `From`.
+pub type Normal3<T> = Tuple3<T, NormalKind>;
+pub type Normal3f = Normal3<Float>;
+
+impl<T> From<Vector3<T>> for Normal3<T> {
+    fn from(v: Vector3<T>) -> Self {
+        Self::new(v.x, v.y, v.z)
+    }
+}
+
+impl<T> Normal3<T>
+where
+    T: Add<Output = T> + Mul<Output = T> + Copy,
+{
+    pub fn dot_vector(&self, v: &Vector3<T>) -> T {
+        self.x * v.x + self.y * v.y + self.z * v.z
+    }
+}
+
+impl<T: Signed + Copy> Normal3<T> {
+    pub fn abs_dot_vector(&self, v: &Vector3<T>) -> T {
+        self.dot_vector(v).abs()
+    }
+}
+
+impl<T: FloatOps> Normal3<T> {
+    /// Flips the normal so that it lies in the same hemisphere as `v`.
+    pub fn face_forward(&self, v: &Vector3<T>) -> Self {
+        if self.dot_vector(v) < T::zero() {
+            -*self
+        } else {
+            *self
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::vector::Vector3f;
+
+    #[test]
+    fn face_forward_flips_into_hemisphere() {
+        let n = Normal3f::new(0.0, 0.0, 1.0);
+        let v = Vector3f::new(0.0, 0.0, -1.0);
+        assert_eq!(n.face_forward(&v), Normal3f::new(0.0, 0.0, -1.0));
+        let v = Vector3f::new(0.0, 1.0, 1.0);
+        assert_eq!(n.face_forward(&v), n);
+    }
+
+    #[test]
+    fn dot_with_vector_and_normal() {
+        let n = Normal3f::new(1.0, 2.0, 3.0);
+        assert_eq!(n.dot(&Normal3f::new(1.0, 1.0, 1.0)), 6.0);
+        assert_eq!(n.abs_dot_vector(&Vector3f::new(-1.0, -1.0, -1.0)), 6.0);
+    }
+}
@@ -0,0 +1,69 @@
+use num_traits::Float as FloatOps;
+use std::ops::{Add, AddAssign, Sub};
+
+use crate::util::tuple::{PointKind, Tuple2};
+use crate::util::vector::Vector2;
+use crate::util::{Float, Int};
+
+/// A position in 2D space, e.g. a pixel or a sample on the film.
+///
+/// Follows the same rules as `Point3`: point − point is a `Vector2`, and a
+/// point can only be offset by a vector.
+pub type Point2<T> = Tuple2<T, PointKind>;
+pub type Point2f = Point2<Float>;
+pub type Point2i = Point2<Int>;
+
+impl<T> From<Vector2<T>> for Point2<T> {
+    fn from(v: Vector2<T>) -> Self {
+        Self::new(v.x, v.y)
+    }
+}
+
+impl<T: FloatOps> Point2<T> {
+    pub fn distance(p1: &Point2<T>, p2: &Point2<T>) -> T {
+        (*p1 - *p2).length()
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point2<T> {
+    type Output = Vector2<T>;
+    fn sub(self, rhs: Self) -> Self::Output {
+        Vector2::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+impl<T: Add<Output = T>> Add<Vector2<T>> for Point2<T> {
+    type Output = Point2<T>;
+    fn add(self, rhs: Vector2<T>) -> Self::Output {
+        Point2::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+impl<T: AddAssign> AddAssign<Vector2<T>> for Point2<T> {
+    fn add_assign(&mut self, rhs: Vector2<T>) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+impl<T: Sub<Output = T>> Sub<Vector2<T>> for Point2<T> {
+    type Output = Point2<T>;
+    fn sub(self, rhs: Vector2<T>) -> Self::Output {
+        Point2::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::vector::Vector2i;
+
+    #[test]
+    fn point2_algebra() {
+        let p = Point2i::new(4, 5);
+        let q = Point2i::new(1, 1);
+        assert_eq!(p - q, Vector2i::new(3, 4));
+        assert_eq!(q + Vector2i::new(3, 4), p);
+        assert_eq!(
+            Point2f::distance(&Point2f::new(0.0, 0.0), &Point2f::new(3.0, 4.0)),
+            5.0
+        );
+    }
+}
@@ -0,0 +1,135 @@
+use num_traits::Float as FloatOps;
+use std::ops::{Add, AddAssign, Sub, SubAssign};
+
+use crate::util::tuple::{PointKind, Tuple3};
+use crate::util::vector::Vector3;
+use crate::util::{Float, Int};
+
+/// A position in 3D space.
+///
+/// `Point3` shares its storage and component-wise algebra with `Vector3` but
+/// only exposes the operations that make geometric sense: the difference of
+/// two points is a `Vector3`, offsetting a point by a vector yields a point,
+/// and two points can never be added together.
+pub type Point3<T> = Tuple3<T, PointKind>;
+pub type Point3f = Point3<Float>;
+pub type Point3i = Point3<Int>;
+
+impl From<(Float, Float, Float)> for Point3f {
+    fn from(value: (Float, Float, Float)) -> Self {
+        Self::new(value.0, value.1, value.2)
+    }
+}
+
+/// Reinterprets a vector as the point it reaches from the origin.
+impl<T> From<Vector3<T>> for Point3<T> {
+    fn from(v: Vector3<T>) -> Self {
+        Self::new(v.x, v.y, v.z)
+    }
+}
+
+impl<T: FloatOps> Point3<T> {
+    #[inline]
+    pub fn distance(p1: &Point3<T>, p2: &Point3<T>) -> T {
+        (*p1 - *p2).length()
+    }
+
+    #[inline]
+    pub fn distance_squared(p1: &Point3<T>, p2: &Point3<T>) -> T {
+        (*p1 - *p2).length_squared()
+    }
+}
+
+// Point - Point = Vector
+impl<T: Sub<Output = T>> Sub for Point3<T> {
+    type Output = Vector3<T>;
+    fn sub(self, rhs: Self) -> Self::Output {
+        Vector3::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+impl<T: Sub<Output = T> + Copy> Sub for &Point3<T> {
+    type Output = Vector3<T>;
+    fn sub(self, rhs: Self) -> Self::Output {
+        *self - *rhs
+    }
+}
+
+// Point + Vector = Point
+impl<T: Add<Output = T>> Add<Vector3<T>> for Point3<T> {
+    type Output = Point3<T>;
+    fn add(self, rhs: Vector3<T>) -> Self::Output {
+        Point3::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+impl<T: Add<Output = T> + Copy> Add<&Vector3<T>> for &Point3<T> {
+    type Output = Point3<T>;
+    fn add(self, rhs: &Vector3<T>) -> Self::Output {
+        *self + *rhs
+    }
+}
+impl<T: AddAssign> AddAssign<Vector3<T>> for Point3<T> {
+    fn add_assign(&mut self, rhs: Vector3<T>) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+        self.z += rhs.z;
+    }
+}
+
+// Point - Vector = Point
+impl<T: Sub<Output = T>> Sub<Vector3<T>> for Point3<T> {
+    type Output = Point3<T>;
+    fn sub(self, rhs: Vector3<T>) -> Self::Output {
+        Point3::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+impl<T: Sub<Output = T> + Copy> Sub<&Vector3<T>> for &Point3<T> {
+    type Output = Point3<T>;
+    fn sub(self, rhs: &Vector3<T>) -> Self::Output {
+        *self - *rhs
+    }
+}
+impl<T: SubAssign> SubAssign<Vector3<T>> for Point3<T> {
+    fn sub_assign(&mut self, rhs: Vector3<T>) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+        self.z -= rhs.z;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::vector::Vector3f;
+
+    #[test]
+    fn point_minus_point_is_vector() {
+        let p1 = Point3f::new(4.0, 6.0, 8.0);
+        let p2 = Point3f::new(1.0, 2.0, 3.0);
+        let v: Vector3f = p1 - p2;
+        assert_eq!(v, Vector3f::new(3.0, 4.0, 5.0));
+    }
+
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn point_plus_vector_is_point() {
+        let p = Point3f::new(1.0, 2.0, 3.0);
+        let v = Vector3f::new(1.0, 1.0, 1.0);
+        let moved: Point3f = &p + &v;
+        assert_eq!(moved, Point3f::new(2.0, 3.0, 4.0));
+        assert_eq!(moved - v, p);
+    }
+
+    #[test]
+    fn distance_between_points() {
+        let p1 = Point3f::new(0.0, 0.0, 0.0);
+        let p2 = Point3f::new(3.0, 4.0, 0.0);
+        assert_eq!(Point3f::distance(&p1, &p2), 5.0);
+        assert_eq!(Point3f::distance_squared(&p1, &p2), 25.0);
+    }
+
+    #[test]
+    fn integer_points_convert_to_float() {
+        let p = Point3i::new(1, -2, 3);
+        assert_eq!(p.map(|c| c as Float), Point3f::new(1.0, -2.0, 3.0));
+    }
+}
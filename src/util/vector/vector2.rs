@@ -0,0 +1,26 @@
+use crate::util::tuple::{Tuple2, VectorKind};
+use crate::util::vector::Point2;
+use crate::util::{Float, Int};
+
+/// A 2D direction or displacement, e.g. a pixel offset.
+pub type Vector2<T> = Tuple2<T, VectorKind>;
+pub type Vector2f = Vector2<Float>;
+pub type Vector2i = Vector2<Int>;
+
+impl<T> From<Point2<T>> for Vector2<T> {
+    fn from(p: Point2<T>) -> Self {
+        Self::new(p.x, p.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vector2_length() {
+        let v = Vector2f::new(3.0, 4.0);
+        assert_eq!(v.length(), 5.0);
+        assert_eq!(v.normalize(), Vector2f::new(0.6, 0.8));
+    }
+}